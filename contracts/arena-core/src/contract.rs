@@ -1,9 +1,10 @@
 use crate::{
-    execute::{self, COMPETITION_MODULE_REPLY_ID},
+    execute::{self, BATCH_DISTRIBUTE_REPLY_ID, COMPETITION_MODULE_REPLY_ID},
     query,
     state::{
-        competition_modules, CompetitionModule, COMPETITION_CATEGORIES_COUNT,
-        COMPETITION_MODULES_COUNT, KEYS, RULESETS_COUNT,
+        competition_modules, ruleset_hash, rulesets, CompetitionModule,
+        COMPETITION_CATEGORIES_COUNT, COMPETITION_MODULES_COUNT, COMPETITION_MODULES_ENABLED, KEYS,
+        RULESETS_COUNT, RULESETS_ENABLED, RULESET_HASHES,
     },
     ContractError,
 };
@@ -47,11 +48,19 @@ pub fn instantiate_extension(
     COMPETITION_MODULES_COUNT.save(deps.storage, &Uint128::zero())?;
     RULESETS_COUNT.save(deps.storage, &Uint128::zero())?;
     COMPETITION_CATEGORIES_COUNT.save(deps.storage, &Uint128::zero())?;
-    crate::execute::update_tax(deps.branch(), &env, dao.clone(), extension.tax)?;
+    crate::execute::update_tax(deps.branch(), &env, dao.clone(), extension.tax, None)?;
     crate::execute::update_categories(deps.branch(), dao.clone(), extension.categories, vec![])?;
-    crate::execute::update_rulesets(deps.branch(), dao.clone(), extension.rulesets, vec![])?;
+    crate::execute::update_rulesets(
+        deps.branch(),
+        &env,
+        dao.clone(),
+        extension.rulesets,
+        vec![],
+        false,
+    )?;
     let competition_response = crate::execute::update_competition_modules(
         deps.branch(),
+        &env,
         dao.clone(),
         extension.competition_modules_instantiate_info,
         vec![],
@@ -81,15 +90,42 @@ pub fn execute(
         ExecuteMsg::Propose { msg } => Ok(execute::propose(deps, env, info, msg)?),
         ExecuteMsg::Extension { msg } => match msg {
             ExecuteExt::UpdateCompetitionModules { to_add, to_disable } => {
-                execute::update_competition_modules(deps, info.sender, to_add, to_disable)
+                execute::update_competition_modules(deps, &env, info.sender, to_add, to_disable)
             }
-            ExecuteExt::UpdateRulesets { to_add, to_disable } => {
-                execute::update_rulesets(deps, info.sender, to_add, to_disable)
+            ExecuteExt::UpdateRulesets {
+                to_add,
+                to_disable,
+                reject_duplicates,
+            } => execute::update_rulesets(
+                deps,
+                &env,
+                info.sender,
+                to_add,
+                to_disable,
+                reject_duplicates.unwrap_or_default(),
+            ),
+            ExecuteExt::UpdateTax { tax, schedule } => {
+                execute::update_tax(deps, &env, info.sender, tax, schedule)
             }
-            ExecuteExt::UpdateTax { tax } => execute::update_tax(deps, &env, info.sender, tax),
             ExecuteExt::UpdateCategories { to_add, to_edit } => {
                 execute::update_categories(deps, info.sender, to_add, to_edit)
             }
+            ExecuteExt::UpdateTaxExemptions { to_add, to_remove } => {
+                execute::update_tax_exemptions(deps, info.sender, to_add, to_remove)
+            }
+            ExecuteExt::UpdateTaxTiers { asset, tiers } => {
+                execute::update_tax_tiers(deps, info.sender, asset, tiers)
+            }
+            ExecuteExt::BatchDistribute { distributions } => {
+                execute::batch_distribute(deps, info.sender, distributions)
+            }
+            ExecuteExt::SetPayoutAddress { addr } => {
+                execute::set_payout_address(deps, info.sender, addr)
+            }
+            ExecuteExt::SetPaused { value } => execute::set_paused(deps, info.sender, value),
+            ExecuteExt::UpdateBlockedAssets { to_add, to_remove } => {
+                execute::update_blocked_assets(deps, info.sender, to_add, to_remove)
+            }
         },
         // Default pre-propose-base behavior for all other messages
         _ => Ok(PrePropose::default().execute(deps, env, info, msg)?),
@@ -128,6 +164,12 @@ pub fn reply(deps: DepsMut, env: Env, msg: Reply) -> Result<Response, ContractEr
 
             competition_modules().save(deps.storage, module_addr.clone(), &competition_module)?;
             KEYS.save(deps.storage, key.clone(), &module_addr, env.block.height)?;
+            COMPETITION_MODULES_ENABLED.save(
+                deps.storage,
+                module_addr.clone(),
+                &true,
+                env.block.height,
+            )?;
             COMPETITION_MODULES_COUNT.update(deps.storage, |x| -> StdResult<_> {
                 Ok(x.checked_add(Uint128::one())?)
             })?;
@@ -145,6 +187,14 @@ pub fn reply(deps: DepsMut, env: Env, msg: Reply) -> Result<Response, ContractEr
                 .add_attribute("competition_module".to_string(), res.contract_address)
                 .add_messages(callback_msgs))
         }
+        BATCH_DISTRIBUTE_REPLY_ID => {
+            // Only failures are replied to, and their state changes are already reverted
+            let error = msg.result.unwrap_err();
+
+            Ok(Response::default()
+                .add_attribute("action", "batch_distribute_failure")
+                .add_attribute("error", error))
+        }
         _ => Err(ContractError::UnknownReplyId { id: msg.id }),
     }
 }
@@ -176,6 +226,9 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> Result<Binary, ContractErro
                 include_disabled,
             )?),
             QueryExt::Ruleset { id } => to_json_binary(&query::ruleset(deps, id)?),
+            QueryExt::RulesetByHash { hash } => {
+                to_json_binary(&query::ruleset_by_hash(deps, hash)?)
+            }
             QueryExt::Categories {
                 start_after,
                 limit,
@@ -187,11 +240,36 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> Result<Binary, ContractErro
                 include_disabled,
             )?),
             QueryExt::Category { id } => to_json_binary(&query::category(deps, id)?),
-            QueryExt::Tax { height } => to_json_binary(&query::tax(deps, env, height)?),
+            QueryExt::Tax {
+                height,
+                addr,
+                escrow_value,
+            } => to_json_binary(&query::tax(deps, env, height, addr, escrow_value)?),
+            QueryExt::IsTaxExempt { addr } => to_json_binary(&query::is_tax_exempt(deps, addr)?),
             QueryExt::CompetitionModule { query } => {
                 to_json_binary(&query::competition_module(deps, env, query)?)
             }
-            QueryExt::DumpState {} => to_json_binary(&query::dump_state(deps, env)?),
+            QueryExt::DumpState { height } => {
+                to_json_binary(&query::dump_state(deps, env, height)?)
+            }
+            QueryExt::TotalValueLocked {
+                module,
+                start_after,
+                limit,
+            } => to_json_binary(&query::total_value_locked(
+                deps,
+                module,
+                start_after,
+                limit,
+            )?),
+            QueryExt::PayoutAddress { addr } => to_json_binary(&query::payout_address(deps, addr)?),
+            QueryExt::IsPaused {} => to_json_binary(&query::is_paused(deps)?),
+            QueryExt::BlockedAssets { start_after, limit } => {
+                to_json_binary(&query::blocked_assets(deps, start_after, limit)?)
+            }
+            QueryExt::IsAssetBlocked { asset } => {
+                to_json_binary(&query::is_asset_blocked(deps, asset)?)
+            }
             QueryExt::IsValidCategoryAndRulesets {
                 category_id,
                 rulesets,
@@ -208,7 +286,40 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> Result<Binary, ContractErro
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
+pub fn migrate(deps: DepsMut, env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
     set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    // Seed the snapshotted statuses for modules and rulesets created before they were tracked
+    let modules = competition_modules()
+        .range(deps.storage, None, None, cosmwasm_std::Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    for (addr, module) in modules {
+        if COMPETITION_MODULES_ENABLED
+            .may_load(deps.storage, addr.clone())?
+            .is_none()
+        {
+            COMPETITION_MODULES_ENABLED.save(
+                deps.storage,
+                addr,
+                &module.is_enabled,
+                env.block.height,
+            )?;
+        }
+    }
+    let all_rulesets = rulesets()
+        .range(deps.storage, None, None, cosmwasm_std::Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    for (id, ruleset) in all_rulesets {
+        if RULESETS_ENABLED.may_load(deps.storage, id)?.is_none() {
+            RULESETS_ENABLED.save(deps.storage, id, &ruleset.is_enabled, env.block.height)?;
+        }
+
+        // Index the content of rulesets created before hashes were tracked
+        let hash = ruleset_hash(&ruleset.rules, &ruleset.description)?;
+        if !RULESET_HASHES.has(deps.storage, hash.as_slice()) {
+            RULESET_HASHES.save(deps.storage, hash.as_slice(), &id)?;
+        }
+    }
+
     Ok(Response::default())
 }
@@ -41,4 +41,7 @@ pub enum ContractError {
 
     #[error("Unauthorized")]
     Unauthorized {},
+
+    #[error("DuplicateRuleset")]
+    DuplicateRuleset { id: Uint128 },
 }
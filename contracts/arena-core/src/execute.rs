@@ -6,14 +6,18 @@ use cosmwasm_std::{
     to_json_binary, Addr, CosmosMsg, Decimal, Deps, DepsMut, Empty, Env, MessageInfo, Response,
     StdError, StdResult, SubMsg, Uint128, WasmMsg,
 };
+use cw_balance::{Distribution, MemberShare};
+use cw_competition::{escrow::CompetitionEscrowQueryMsg, msg::ExecuteBase};
+use cw_storage_plus::Bound;
 use dao_interface::state::ModuleInstantiateInfo;
 use dao_pre_propose_base::error::PreProposeError;
 use dao_voting::proposal::SingleChoiceProposeMsg;
 
 use crate::{
     state::{
-        competition_categories, competition_modules, rulesets, COMPETITION_CATEGORIES_COUNT,
-        RULESETS_COUNT, TAX,
+        competition_categories, competition_modules, ruleset_hash, rulesets, BLOCKED_ASSETS,
+        COMPETITION_CATEGORIES_COUNT, COMPETITION_MODULES_ENABLED, PAUSED, PAYOUT_PREFERENCE,
+        RULESETS_COUNT, RULESETS_ENABLED, RULESET_HASHES, TAX, TAX_EXEMPT, TAX_SCHEDULE, TAX_TIERS,
     },
     ContractError,
 };
@@ -22,9 +26,11 @@ pub const COMPETITION_MODULE_REPLY_ID: u64 = 1;
 pub const DAO_REPLY_ID: u64 = 2;
 pub const ESCROW_REPLY_ID: u64 = 3;
 pub const COMPETITION_REPLY_ID: u64 = 5;
+pub const BATCH_DISTRIBUTE_REPLY_ID: u64 = 6;
 
 pub fn update_competition_modules(
     deps: DepsMut,
+    env: &Env,
     sender: Addr,
     to_add: Vec<ModuleInstantiateInfo>,
     to_disable: Vec<String>,
@@ -47,6 +53,7 @@ pub fn update_competition_modules(
                 Ok(module)
             },
         )?;
+        COMPETITION_MODULES_ENABLED.save(deps.storage, addr, &false, env.block.height)?;
     }
 
     // Convert new modules into wasm messages and prepare for instantiation
@@ -66,10 +73,30 @@ pub fn update_tax(
     env: &Env,
     sender: Addr,
     tax: Decimal,
+    schedule: Option<Vec<(u64, Decimal)>>,
 ) -> Result<Response, ContractError> {
     if PrePropose::default().dao.load(deps.storage)? != sender {
         return Err(ContractError::Unauthorized {});
     }
+
+    // The update is effective from the next block, matching the snapshot
+    let next_height = env.block.height + 1;
+    let schedule = schedule.unwrap_or_default();
+    let mut last_height = next_height;
+    for (height, rate) in &schedule {
+        if *height <= last_height {
+            return Err(ContractError::StdError(StdError::GenericErr {
+                msg: "Tax schedule heights must be increasing and after the next block."
+                    .to_string(),
+            }));
+        }
+        if *rate > Decimal::one() {
+            return Err(ContractError::StdError(StdError::GenericErr {
+                msg: "The dao tax cannot be greater than 100%.".to_string(),
+            }));
+        }
+        last_height = *height;
+    }
     if tax > Decimal::one() {
         return Err(ContractError::StdError(StdError::GenericErr {
             msg: "The dao tax cannot be greater than 100%.".to_string(),
@@ -78,16 +105,148 @@ pub fn update_tax(
 
     TAX.save(deps.storage, &tax, env.block.height)?;
 
+    // Replace any pending steps with the new schedule
+    let pending = TAX_SCHEDULE
+        .keys(
+            deps.storage,
+            Some(Bound::inclusive(next_height)),
+            None,
+            cosmwasm_std::Order::Ascending,
+        )
+        .collect::<StdResult<Vec<_>>>()?;
+    for height in pending {
+        TAX_SCHEDULE.remove(deps.storage, height);
+    }
+    TAX_SCHEDULE.save(deps.storage, next_height, &tax)?;
+    for (height, rate) in &schedule {
+        TAX_SCHEDULE.save(deps.storage, *height, rate)?;
+    }
+
     Ok(Response::new()
         .add_attribute("action", "update_tax")
-        .add_attribute("tax", tax.to_string()))
+        .add_attribute("tax", tax.to_string())
+        .add_attribute("schedule_steps", schedule.len().to_string()))
+}
+
+pub fn update_tax_exemptions(
+    deps: DepsMut,
+    sender: Addr,
+    to_add: Vec<String>,
+    to_remove: Vec<String>,
+) -> Result<Response, ContractError> {
+    // Ensure sender is authorized
+    if PrePropose::default().dao.load(deps.storage)? != sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    for addr in to_remove {
+        let addr = deps.api.addr_validate(&addr)?;
+        TAX_EXEMPT.remove(deps.storage, addr);
+    }
+
+    for addr in to_add {
+        let addr = deps.api.addr_validate(&addr)?;
+        TAX_EXEMPT.save(deps.storage, addr, &())?;
+    }
+
+    Ok(Response::new().add_attribute("action", "update_tax_exemptions"))
+}
+
+pub fn update_tax_tiers(
+    deps: DepsMut,
+    sender: Addr,
+    asset: String,
+    tiers: Vec<(Uint128, Decimal)>,
+) -> Result<Response, ContractError> {
+    // Ensure sender is authorized
+    if PrePropose::default().dao.load(deps.storage)? != sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    if tiers.iter().any(|(_, rate)| *rate > Decimal::one()) {
+        return Err(ContractError::StdError(StdError::GenericErr {
+            msg: "The dao tax cannot be greater than 100%.".to_string(),
+        }));
+    }
+
+    let min_values = TAX_TIERS
+        .prefix(&asset)
+        .keys(deps.storage, None, None, cosmwasm_std::Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    for min_value in min_values {
+        TAX_TIERS.remove(deps.storage, (&asset, min_value));
+    }
+    for (min_value, rate) in &tiers {
+        TAX_TIERS.save(deps.storage, (&asset, min_value.u128()), rate)?;
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "update_tax_tiers")
+        .add_attribute("asset", asset)
+        .add_attribute("tiers", tiers.len().to_string()))
+}
+
+pub fn set_payout_address(
+    deps: DepsMut,
+    sender: Addr,
+    addr: String,
+) -> Result<Response, ContractError> {
+    let addr = deps.api.addr_validate(&addr)?;
+
+    if addr == sender {
+        PAYOUT_PREFERENCE.remove(deps.storage, sender.clone());
+    } else {
+        PAYOUT_PREFERENCE.save(deps.storage, sender.clone(), &addr)?;
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "set_payout_address")
+        .add_attribute("member", sender)
+        .add_attribute("payout_address", addr))
+}
+
+pub fn set_paused(deps: DepsMut, sender: Addr, value: bool) -> Result<Response, ContractError> {
+    // Ensure sender is authorized
+    if PrePropose::default().dao.load(deps.storage)? != sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    PAUSED.save(deps.storage, &value)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "set_paused")
+        .add_attribute("paused", value.to_string()))
+}
+
+pub fn update_blocked_assets(
+    deps: DepsMut,
+    sender: Addr,
+    to_add: Vec<String>,
+    to_remove: Vec<String>,
+) -> Result<Response, ContractError> {
+    // Ensure sender is authorized
+    if PrePropose::default().dao.load(deps.storage)? != sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    for asset in to_remove {
+        BLOCKED_ASSETS.remove(deps.storage, asset);
+    }
+
+    for asset in to_add {
+        BLOCKED_ASSETS.save(deps.storage, asset, &())?;
+    }
+
+    Ok(Response::new().add_attribute("action", "update_blocked_assets"))
 }
 
 pub fn update_rulesets(
     deps: DepsMut,
+    env: &Env,
     sender: Addr,
     to_add: Vec<NewRuleset>,
     to_disable: Vec<Uint128>,
+    reject_duplicates: bool,
 ) -> Result<Response, ContractError> {
     // Ensure sender is authorized
     if PrePropose::default().dao.load(deps.storage)? != sender {
@@ -103,6 +262,7 @@ pub fn update_rulesets(
             ruleset.is_enabled = false;
             Ok(ruleset)
         })?;
+        RULESETS_ENABLED.save(deps.storage, id.u128(), &false, env.block.height)?;
     }
 
     // Add new rulesets
@@ -115,14 +275,27 @@ pub fn update_rulesets(
         }
         current_id = current_id.checked_add(Uint128::one())?;
 
+        let hash = ruleset_hash(&ruleset.rules, &ruleset.description)?;
+        match RULESET_HASHES.may_load(deps.storage, hash.as_slice())? {
+            Some(id) if reject_duplicates => {
+                return Err(ContractError::DuplicateRuleset {
+                    id: Uint128::new(id),
+                });
+            }
+            Some(_) => {}
+            None => RULESET_HASHES.save(deps.storage, hash.as_slice(), &current_id.u128())?,
+        }
+
         let new_ruleset = Ruleset {
             category_id: ruleset.category_id,
             id: current_id,
             rules: ruleset.rules,
             description: ruleset.description,
             is_enabled: true,
+            currency: ruleset.currency,
         };
         rulesets().save(deps.storage, current_id.u128(), &new_ruleset)?;
+        RULESETS_ENABLED.save(deps.storage, current_id.u128(), &true, env.block.height)?;
     }
     RULESETS_COUNT.save(deps.storage, &current_id)?;
 
@@ -196,6 +369,7 @@ pub fn propose(
                     distribution: msg.distribution,
                     tax_cw20_msg: msg.tax_cw20_msg,
                     tax_cw721_msg: msg.tax_cw721_msg,
+                    evidence: None,
                 },
             )?,
             funds: vec![],
@@ -294,3 +468,80 @@ pub fn update_categories(
         .add_attribute("action", "update_categories")
         .add_attribute("category_count", current_id))
 }
+
+pub fn batch_distribute(
+    deps: DepsMut,
+    sender: Addr,
+    distributions: Vec<(String, Vec<MemberShare<String>>, String)>,
+) -> Result<Response, ContractError> {
+    // Ensure sender is authorized
+    if PrePropose::default().dao.load(deps.storage)? != sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let escrow_count = distributions.len();
+    let msgs = distributions
+        .into_iter()
+        .map(
+            |(escrow, shares, remainder_addr)| -> Result<_, ContractError> {
+                let escrow = deps.api.addr_validate(&escrow)?;
+                let member_percentages = MemberShare::to_percentages(shares)
+                    .map_err(|e| StdError::generic_err(e.to_string()))?;
+                let distribution = Distribution {
+                    member_percentages,
+                    remainder_addr,
+                };
+                distribution.validate_shares()?;
+
+                // The escrow's competition module processes it, so the arena tax still applies
+                let ownership: cw_ownable::Ownership<Addr> = deps
+                    .querier
+                    .query_wasm_smart(&escrow, &CompetitionEscrowQueryMsg::Ownership {})?;
+                let module = match ownership.owner {
+                    Some(owner) if competition_modules().has(deps.storage, owner.clone()) => owner,
+                    _ => {
+                        return Err(ContractError::StdError(StdError::GenericErr {
+                            msg: format!(
+                                "The escrow {} is not owned by a competition module",
+                                escrow
+                            ),
+                        }))
+                    }
+                };
+                let competition_id: Option<Uint128> = deps.querier.query_wasm_smart(
+                    &module,
+                    &cw_competition::msg::QueryBase::<Empty, Empty, Empty>::EscrowCompetition {
+                        escrow: escrow.to_string(),
+                    },
+                )?;
+                let competition_id =
+                    competition_id.ok_or(ContractError::StdError(StdError::GenericErr {
+                        msg: format!(
+                            "The escrow {} does not back an unprocessed competition",
+                            escrow
+                        ),
+                    }))?;
+
+                let msg = WasmMsg::Execute {
+                    contract_addr: module.to_string(),
+                    msg: to_json_binary(&ExecuteBase::<Empty, Empty>::ProcessCompetition {
+                        competition_id,
+                        distribution: Some(distribution),
+                        tax_cw20_msg: None,
+                        tax_cw721_msg: None,
+                        evidence: None,
+                    })?,
+                    funds: vec![],
+                };
+
+                // Failed distributions are caught in the reply instead of reverting the batch
+                Ok(SubMsg::reply_on_error(msg, BATCH_DISTRIBUTE_REPLY_ID))
+            },
+        )
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(Response::new()
+        .add_attribute("action", "batch_distribute")
+        .add_attribute("escrow_count", escrow_count.to_string())
+        .add_submessages(msgs))
+}
@@ -1,11 +1,16 @@
 use crate::state::{
-    competition_categories, get_rulesets_category_and_is_enabled_idx, CompetitionModule, KEYS, TAX,
+    competition_categories, get_rulesets_category_and_is_enabled_idx, CompetitionModule,
+    BLOCKED_ASSETS, COMPETITION_MODULES_ENABLED, KEYS, PAUSED, PAYOUT_PREFERENCE, RULESETS_ENABLED,
+    RULESET_HASHES, TAX, TAX_EXEMPT, TAX_SCHEDULE, TAX_TIERS,
 };
 use arena_core_interface::msg::{
     CompetitionCategory, CompetitionModuleQuery, CompetitionModuleResponse, DumpStateResponse,
-    Ruleset,
+    ModuleTotalValueLocked, Ruleset, TotalValueLockedResponse,
 };
-use cosmwasm_std::{Decimal, Deps, Empty, Env, StdResult, Uint128};
+use cosmwasm_std::{
+    Addr, Coin, Decimal, Deps, Empty, Env, HexBinary, StdError, StdResult, Uint128,
+};
+use cw_balance::BalanceVerified;
 use cw_paginate::paginate_indexed_map;
 use cw_storage_plus::Bound;
 use cw_utils::maybe_addr;
@@ -67,12 +72,96 @@ pub fn competition_modules(
     }
 }
 
-pub fn tax(deps: Deps, env: Env, height: Option<u64>) -> StdResult<Decimal> {
+pub fn tax(
+    deps: Deps,
+    env: Env,
+    height: Option<u64>,
+    addr: Option<String>,
+    escrow_value: Option<Coin>,
+) -> StdResult<Decimal> {
+    if let Some(addr) = addr {
+        if is_tax_exempt(deps, addr)? {
+            return Ok(Decimal::zero());
+        }
+    }
+
+    // Use the highest tier of the asset the escrow value reaches, if any are configured
+    if let Some(escrow_value) = escrow_value {
+        if let Some((_, rate)) = TAX_TIERS
+            .prefix(&escrow_value.denom)
+            .range(
+                deps.storage,
+                None,
+                Some(Bound::inclusive(escrow_value.amount.u128())),
+                cosmwasm_std::Order::Descending,
+            )
+            .next()
+            .transpose()?
+        {
+            return Ok(rate);
+        }
+    }
+
+    let height = height.unwrap_or(env.block.height);
+
+    // Use the latest scheduled step at or before the height
+    if let Some((_, rate)) = TAX_SCHEDULE
+        .range(
+            deps.storage,
+            None,
+            Some(Bound::inclusive(height)),
+            cosmwasm_std::Order::Descending,
+        )
+        .next()
+        .transpose()?
+    {
+        return Ok(rate);
+    }
+
     Ok(TAX
-        .may_load_at_height(deps.storage, height.unwrap_or(env.block.height))?
+        .may_load_at_height(deps.storage, height)?
         .unwrap_or(Decimal::zero()))
 }
 
+pub fn is_tax_exempt(deps: Deps, addr: String) -> StdResult<bool> {
+    let addr = deps.api.addr_validate(&addr)?;
+
+    Ok(TAX_EXEMPT.has(deps.storage, addr))
+}
+
+pub fn payout_address(deps: Deps, addr: String) -> StdResult<Option<Addr>> {
+    let addr = deps.api.addr_validate(&addr)?;
+
+    PAYOUT_PREFERENCE.may_load(deps.storage, addr)
+}
+
+pub fn is_paused(deps: Deps) -> StdResult<bool> {
+    Ok(PAUSED.may_load(deps.storage)?.unwrap_or_default())
+}
+
+pub fn blocked_assets(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<Vec<String>> {
+    let start_after_bound = start_after.map(Bound::exclusive);
+    let limit = limit.unwrap_or(10).min(30);
+
+    BLOCKED_ASSETS
+        .keys(
+            deps.storage,
+            start_after_bound,
+            None,
+            cosmwasm_std::Order::Ascending,
+        )
+        .take(limit as usize)
+        .collect()
+}
+
+pub fn is_asset_blocked(deps: Deps, asset: String) -> StdResult<bool> {
+    Ok(BLOCKED_ASSETS.has(deps.storage, asset))
+}
+
 pub fn rulesets(
     deps: Deps,
     category_id: Option<Uint128>,
@@ -126,6 +215,13 @@ pub fn ruleset(deps: Deps, id: Uint128) -> StdResult<Option<Ruleset>> {
     crate::state::rulesets().may_load(deps.storage, id.u128())
 }
 
+pub fn ruleset_by_hash(deps: Deps, hash: HexBinary) -> StdResult<Option<Ruleset>> {
+    RULESET_HASHES
+        .may_load(deps.storage, hash.as_slice())?
+        .map(|id| crate::state::rulesets().load(deps.storage, id))
+        .transpose()
+}
+
 pub fn categories(
     deps: Deps,
     start_after: Option<Uint128>,
@@ -197,10 +293,102 @@ pub fn competition_module(
     }
 }
 
-pub fn dump_state(deps: Deps, env: Env) -> StdResult<DumpStateResponse> {
+pub fn dump_state(deps: Deps, env: Env, height: Option<u64>) -> StdResult<DumpStateResponse> {
+    // Competition module statuses are resolved from their snapshots if a height is provided
+    let mut competition_modules = vec![];
+    for item in crate::state::competition_modules().range(
+        deps.storage,
+        None,
+        None,
+        cosmwasm_std::Order::Ascending,
+    ) {
+        let (addr, mut module) = item?;
+
+        if let Some(height) = height {
+            match COMPETITION_MODULES_ENABLED.may_load_at_height(deps.storage, addr, height)? {
+                Some(is_enabled) => module.is_enabled = is_enabled,
+                None => continue,
+            }
+        }
+
+        if module.is_enabled {
+            competition_modules.push(module.to_response(deps)?);
+        }
+    }
+
+    // Ruleset statuses are resolved the same way
+    let mut rulesets = vec![];
+    for item in
+        crate::state::rulesets().range(deps.storage, None, None, cosmwasm_std::Order::Ascending)
+    {
+        let (id, mut ruleset) = item?;
+
+        if let Some(height) = height {
+            match RULESETS_ENABLED.may_load_at_height(deps.storage, id, height)? {
+                Some(is_enabled) => ruleset.is_enabled = is_enabled,
+                None => continue,
+            }
+        }
+
+        if ruleset.is_enabled {
+            rulesets.push(ruleset);
+        }
+    }
+
     Ok(DumpStateResponse {
-        tax: tax(deps, env, None)?,
-        competition_modules: competition_modules(deps, None, None, None)?,
+        tax: tax(deps, env, height, None, None)?,
+        competition_modules,
+        rulesets,
+    })
+}
+
+pub fn total_value_locked(
+    deps: Deps,
+    module: Option<String>,
+    start_after: Option<Uint128>,
+    limit: Option<u32>,
+) -> StdResult<TotalValueLockedResponse> {
+    // Disabled modules are included, since their escrows still hold funds
+    let modules = match module {
+        Some(module) => {
+            let module = deps.api.addr_validate(&module)?;
+            if !crate::state::competition_modules().has(deps.storage, module.clone()) {
+                return Err(StdError::generic_err(format!(
+                    "{} is not a competition module",
+                    module
+                )));
+            }
+
+            vec![module]
+        }
+        None => crate::state::competition_modules()
+            .keys(deps.storage, None, None, cosmwasm_std::Order::Ascending)
+            .collect::<StdResult<Vec<_>>>()?,
+    };
+
+    let mut balance = BalanceVerified::default();
+    let mut module_totals = vec![];
+    for module in modules {
+        let response: cw_competition::state::TotalValueLockedResponse =
+            deps.querier.query_wasm_smart(
+                &module,
+                &cw_competition::msg::QueryBase::<Empty, Empty, Empty>::TotalValueLocked {
+                    start_after,
+                    limit,
+                },
+            )?;
+
+        balance = balance.checked_add(&response.balance)?;
+        module_totals.push(ModuleTotalValueLocked {
+            module,
+            balance: response.balance,
+            next_start_after: response.next_start_after,
+        });
+    }
+
+    Ok(TotalValueLockedResponse {
+        balance,
+        modules: module_totals,
     })
 }
 
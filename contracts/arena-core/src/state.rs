@@ -1,7 +1,10 @@
 use arena_core_interface::msg::{CompetitionCategory, Ruleset};
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{Addr, Decimal, Uint128};
-use cw_storage_plus::{Index, IndexList, IndexedMap, Item, MultiIndex, SnapshotItem, SnapshotMap};
+use cosmwasm_std::{to_json_vec, Addr, Decimal, HexBinary, StdResult, Uint128};
+use cw_storage_plus::{
+    Index, IndexList, IndexedMap, Item, Map, MultiIndex, SnapshotItem, SnapshotMap,
+};
+use sha2::{Digest, Sha256};
 
 pub const COMPETITION_CATEGORIES_COUNT: Item<Uint128> = Item::new("competition-categories-count");
 pub const COMPETITION_MODULES_COUNT: Item<Uint128> = Item::new("competition-modules-count");
@@ -11,13 +14,43 @@ pub const TAX: SnapshotItem<Decimal> = SnapshotItem::new(
     "tax__change",
     cw_storage_plus::Strategy::EveryBlock,
 );
+/// The tax effective from each height, including scheduled future steps
+/// Heights without an earlier entry fall back to the `TAX` snapshot
+pub const TAX_SCHEDULE: Map<u64, Decimal> = Map::new("tax_schedule");
+/// The tax of competitions whose escrow holds at least each minimum of an asset
+/// Keyed by the native denom or cw20 address, then the minimum amount
+pub const TAX_TIERS: Map<(&str, u128), Decimal> = Map::new("asset_tax_tiers");
 pub const RULESETS_COUNT: Item<Uint128> = Item::new("ruleset_count");
+/// Maps the content hash of a ruleset to the first ruleset created with it
+pub const RULESET_HASHES: Map<&[u8], u128> = Map::new("ruleset_hashes");
+/// Competition DAOs or creators which are not charged the arena tax
+pub const TAX_EXEMPT: Map<Addr, ()> = Map::new("tax_exempt");
+/// The address each member's escrow winnings are routed to
+pub const PAYOUT_PREFERENCE: Map<Addr, Addr> = Map::new("payout_preference");
+/// Blocks new competitions and escrow funding while set
+pub const PAUSED: Item<bool> = Item::new("paused");
+/// Native denoms and cw20 addresses which new escrow dues may not use
+pub const BLOCKED_ASSETS: Map<String, ()> = Map::new("blocked_assets");
 pub const KEYS: SnapshotMap<String, Addr> = SnapshotMap::new(
     "keys",
     "keys__check",
     "keys__change",
     cw_storage_plus::Strategy::EveryBlock,
 );
+/// Tracks whether each competition module is enabled for historical state dumps
+pub const COMPETITION_MODULES_ENABLED: SnapshotMap<Addr, bool> = SnapshotMap::new(
+    "competition_modules_enabled",
+    "competition_modules_enabled__check",
+    "competition_modules_enabled__change",
+    cw_storage_plus::Strategy::EveryBlock,
+);
+/// Tracks whether each ruleset is enabled for historical state dumps
+pub const RULESETS_ENABLED: SnapshotMap<u128, bool> = SnapshotMap::new(
+    "rulesets_enabled",
+    "rulesets_enabled__check",
+    "rulesets_enabled__change",
+    cw_storage_plus::Strategy::EveryBlock,
+);
 
 // Competition Modules
 
@@ -96,6 +129,13 @@ impl<'a> IndexList<Ruleset> for RulesetIndexes<'a> {
     }
 }
 
+/// Hashes the rules and description, ignoring the category and currency
+pub fn ruleset_hash(rules: &[String], description: &str) -> StdResult<HexBinary> {
+    let content = to_json_vec(&(rules, description))?;
+
+    Ok(HexBinary::from(Sha256::digest(content).to_vec()))
+}
+
 pub fn rulesets<'a>() -> IndexedMap<'a, u128, Ruleset, RulesetIndexes<'a>> {
     let indexes = RulesetIndexes {
         category_and_is_enabled: MultiIndex::new(
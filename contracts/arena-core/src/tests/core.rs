@@ -1,8 +1,10 @@
+use std::str::FromStr;
+
 use arena_core_interface::msg::{
     CompetitionCategory, EditCompetitionCategory, InstantiateExt, InstantiateMsg,
     NewCompetitionCategory, NewRuleset, Ruleset,
 };
-use cosmwasm_std::{to_json_binary, Addr, Decimal, Empty, Uint128, WasmMsg};
+use cosmwasm_std::{to_json_binary, Addr, Coin, Decimal, Empty, Uint128, WasmMsg};
 use cw4::Member;
 use cw_multi_test::{
     addons::MockApiBech32, next_block, App, AppResponse, BankKeeper, Contract, ContractWrapper,
@@ -160,6 +162,7 @@ pub fn setup_core_context(
                                                             "This is another rule".to_string(),
                                                         ],
                                                         description: "Test Ruleset 1".to_string(),
+                                                        currency: None,
                                                     },
                                                     NewRuleset {
                                                         category_id: Some(Uint128::one()),
@@ -168,6 +171,7 @@ pub fn setup_core_context(
                                                             "This is another rule".to_string(),
                                                         ],
                                                         description: "Test Ruleset 2".to_string(),
+                                                        currency: None,
                                                     },
                                                 ],
                                                 tax: Decimal::new(Uint128::from(
@@ -337,8 +341,10 @@ pub fn test_rulesets() {
                             category_id: Some(Uint128::one()),
                             rules: vec!["Rule 1".to_string(), "Rule 2".to_string()],
                             description: "Test Ruleset 3".to_string(),
+                            currency: None,
                         }],
                         to_disable: vec![],
+                        reject_duplicates: None,
                     },
                 })
                 .unwrap(),
@@ -378,6 +384,7 @@ pub fn test_rulesets() {
                     msg: arena_core_interface::msg::ExecuteExt::UpdateRulesets {
                         to_add: vec![],
                         to_disable: vec![Uint128::one()],
+                        reject_duplicates: None,
                     },
                 })
                 .unwrap(),
@@ -435,8 +442,10 @@ pub fn test_rulesets() {
                             category_id: Some(Uint128::from(9999u128)), // Non-existent category
                             rules: vec!["Rule 1".to_string(), "Rule 2".to_string()],
                             description: "Test Ruleset 4".to_string(),
+                            currency: None,
                         }],
                         to_disable: vec![],
+                        reject_duplicates: None,
                     },
                 })
                 .unwrap(),
@@ -447,3 +456,474 @@ pub fn test_rulesets() {
     );
     assert!(result.is_err());
 }
+
+#[test]
+pub fn test_ruleset_hash() {
+    let mut app = get_app();
+
+    let admin = app.api().addr_make(ADMIN);
+    let context = setup_core_context(
+        &mut app,
+        vec![Member {
+            addr: admin.to_string(),
+            weight: 1u64,
+        }],
+    );
+
+    let rules = vec!["Best of three".to_string()];
+    let description = "Hashed Ruleset".to_string();
+    let add_ruleset = |app: &mut App<BankKeeper, MockApiBech32>,
+                       reject_duplicates: Option<bool>| {
+        app.execute_contract(
+            admin.clone(),
+            context.sudo_proposal_addr.clone(),
+            &dao_proposal_sudo::msg::ExecuteMsg::Execute {
+                msgs: vec![WasmMsg::Execute {
+                    contract_addr: context.arena_core_addr.to_string(),
+                    funds: vec![],
+                    msg: to_json_binary(&arena_core_interface::msg::ExecuteMsg::Extension {
+                        msg: arena_core_interface::msg::ExecuteExt::UpdateRulesets {
+                            to_add: vec![NewRuleset {
+                                category_id: Some(Uint128::one()),
+                                rules: rules.clone(),
+                                description: description.clone(),
+                                currency: None,
+                            }],
+                            to_disable: vec![],
+                            reject_duplicates,
+                        },
+                    })
+                    .unwrap(),
+                }
+                .into()],
+            },
+            &[],
+        )
+    };
+
+    add_ruleset(&mut app, None).unwrap();
+
+    // The ruleset is found by the hash of its content
+    let hash = crate::state::ruleset_hash(&rules, &description).unwrap();
+    let query_msg = arena_core_interface::msg::QueryMsg::QueryExtension {
+        msg: arena_core_interface::msg::QueryExt::RulesetByHash { hash },
+    };
+    let ruleset: Option<Ruleset> = app
+        .wrap()
+        .query_wasm_smart(context.arena_core_addr.clone(), &query_msg)
+        .unwrap();
+    assert_eq!(ruleset.unwrap().id, Uint128::new(3));
+
+    // An exact duplicate is rejected when requested
+    let result = add_ruleset(&mut app, Some(true));
+    assert_eq!(
+        result.unwrap_err().root_cause().to_string(),
+        crate::ContractError::DuplicateRuleset {
+            id: Uint128::new(3)
+        }
+        .to_string()
+    );
+
+    // Duplicates are still allowed by default, and the hash keeps the original
+    add_ruleset(&mut app, None).unwrap();
+    let ruleset: Option<Ruleset> = app
+        .wrap()
+        .query_wasm_smart(context.arena_core_addr.clone(), &query_msg)
+        .unwrap();
+    assert_eq!(ruleset.unwrap().id, Uint128::new(3));
+}
+
+#[test]
+pub fn test_tax_exemptions() {
+    let mut app = get_app();
+
+    let admin = app.api().addr_make(ADMIN);
+    let exempt = app.api().addr_make("exempt");
+    let not_exempt = app.api().addr_make("not_exempt");
+    let context = setup_core_context(
+        &mut app,
+        vec![Member {
+            addr: admin.to_string(),
+            weight: 1u64,
+        }],
+    );
+
+    // Exempt an address from the tax
+    let result = app.execute_contract(
+        admin.clone(),
+        context.sudo_proposal_addr.clone(),
+        &dao_proposal_sudo::msg::ExecuteMsg::Execute {
+            msgs: vec![WasmMsg::Execute {
+                contract_addr: context.arena_core_addr.to_string(),
+                funds: vec![],
+                msg: to_json_binary(&arena_core_interface::msg::ExecuteMsg::Extension {
+                    msg: arena_core_interface::msg::ExecuteExt::UpdateTaxExemptions {
+                        to_add: vec![exempt.to_string()],
+                        to_remove: vec![],
+                    },
+                })
+                .unwrap(),
+            }
+            .into()],
+        },
+        &[],
+    );
+    assert!(result.is_ok());
+
+    // The exempt address is not taxed
+    let tax: Decimal = app
+        .wrap()
+        .query_wasm_smart(
+            context.arena_core_addr.clone(),
+            &arena_core_interface::msg::QueryMsg::QueryExtension {
+                msg: arena_core_interface::msg::QueryExt::Tax {
+                    height: None,
+                    addr: Some(exempt.to_string()),
+                    escrow_value: None,
+                },
+            },
+        )
+        .unwrap();
+    assert!(tax.is_zero());
+
+    // Other addresses are taxed as usual
+    let tax: Decimal = app
+        .wrap()
+        .query_wasm_smart(
+            context.arena_core_addr.clone(),
+            &arena_core_interface::msg::QueryMsg::QueryExtension {
+                msg: arena_core_interface::msg::QueryExt::Tax {
+                    height: None,
+                    addr: Some(not_exempt.to_string()),
+                    escrow_value: None,
+                },
+            },
+        )
+        .unwrap();
+    assert_eq!(tax, Decimal::new(Uint128::from(150000000000000000u128)));
+
+    // Only the DAO can update exemptions
+    let result = app.execute_contract(
+        not_exempt.clone(),
+        context.arena_core_addr.clone(),
+        &arena_core_interface::msg::ExecuteMsg::Extension {
+            msg: arena_core_interface::msg::ExecuteExt::UpdateTaxExemptions {
+                to_add: vec![not_exempt.to_string()],
+                to_remove: vec![],
+            },
+        },
+        &[],
+    );
+    assert!(result.is_err());
+}
+
+#[test]
+pub fn test_tax_tiers() {
+    let mut app = get_app();
+
+    let admin = app.api().addr_make(ADMIN);
+    let context = setup_core_context(
+        &mut app,
+        vec![Member {
+            addr: admin.to_string(),
+            weight: 1u64,
+        }],
+    );
+
+    // Pools of at least 1,000 are taxed 5% and pools of at least 100,000 are taxed 20%
+    let result = app.execute_contract(
+        admin.clone(),
+        context.sudo_proposal_addr.clone(),
+        &dao_proposal_sudo::msg::ExecuteMsg::Execute {
+            msgs: vec![WasmMsg::Execute {
+                contract_addr: context.arena_core_addr.to_string(),
+                funds: vec![],
+                msg: to_json_binary(&arena_core_interface::msg::ExecuteMsg::Extension {
+                    msg: arena_core_interface::msg::ExecuteExt::UpdateTaxTiers {
+                        asset: "juno".to_string(),
+                        tiers: vec![
+                            (Uint128::new(1_000), Decimal::percent(5)),
+                            (Uint128::new(100_000), Decimal::percent(20)),
+                        ],
+                    },
+                })
+                .unwrap(),
+            }
+            .into()],
+        },
+        &[],
+    );
+    assert!(result.is_ok());
+
+    let query_tax = |app: &App<BankKeeper, MockApiBech32>, escrow_value: Option<&str>| -> Decimal {
+        app.wrap()
+            .query_wasm_smart(
+                context.arena_core_addr.clone(),
+                &arena_core_interface::msg::QueryMsg::QueryExtension {
+                    msg: arena_core_interface::msg::QueryExt::Tax {
+                        height: None,
+                        addr: None,
+                        escrow_value: escrow_value.map(|x| Coin::from_str(x).unwrap()),
+                    },
+                },
+            )
+            .unwrap()
+    };
+
+    assert_eq!(query_tax(&app, Some("5000juno")), Decimal::percent(5));
+    assert_eq!(query_tax(&app, Some("100000juno")), Decimal::percent(20));
+    assert_eq!(query_tax(&app, Some("1000000juno")), Decimal::percent(20));
+
+    // Pools below every tier, of other assets, or without a value use the base tax
+    assert_eq!(query_tax(&app, Some("999juno")), Decimal::percent(15));
+    assert_eq!(query_tax(&app, Some("1000000uatom")), Decimal::percent(15));
+    assert_eq!(query_tax(&app, None), Decimal::percent(15));
+
+    // Only the DAO can update the tiers
+    let result = app.execute_contract(
+        admin.clone(),
+        context.arena_core_addr.clone(),
+        &arena_core_interface::msg::ExecuteMsg::Extension {
+            msg: arena_core_interface::msg::ExecuteExt::UpdateTaxTiers {
+                asset: "juno".to_string(),
+                tiers: vec![],
+            },
+        },
+        &[],
+    );
+    assert!(result.is_err());
+}
+
+#[test]
+pub fn test_dump_state_at_height() {
+    let mut app = get_app();
+
+    let admin = app.api().addr_make(ADMIN);
+    let context = setup_core_context(
+        &mut app,
+        vec![Member {
+            addr: admin.to_string(),
+            weight: 1u64,
+        }],
+    );
+    app.update_block(next_block);
+    let wager_module_id = app.store_code(arena_testing::contracts::arena_wager_module_contract());
+
+    // Update the tax, add a competition module, and disable a ruleset in the same block
+    let change_height = app.block_info().height;
+    let result = app.execute_contract(
+        admin.clone(),
+        context.sudo_proposal_addr.clone(),
+        &dao_proposal_sudo::msg::ExecuteMsg::Execute {
+            msgs: vec![
+                WasmMsg::Execute {
+                    contract_addr: context.arena_core_addr.to_string(),
+                    funds: vec![],
+                    msg: to_json_binary(&arena_core_interface::msg::ExecuteMsg::Extension {
+                        msg: arena_core_interface::msg::ExecuteExt::UpdateTax {
+                            tax: Decimal::percent(20),
+                            schedule: None,
+                        },
+                    })
+                    .unwrap(),
+                }
+                .into(),
+                WasmMsg::Execute {
+                    contract_addr: context.arena_core_addr.to_string(),
+                    funds: vec![],
+                    msg: to_json_binary(&arena_core_interface::msg::ExecuteMsg::Extension {
+                        msg: arena_core_interface::msg::ExecuteExt::UpdateCompetitionModules {
+                            to_add: vec![ModuleInstantiateInfo {
+                                code_id: wager_module_id,
+                                msg: to_json_binary(&arena_wager_module::msg::InstantiateMsg {
+                                    key: "Wagers".to_string(),
+                                    description: "This is a description".to_string(),
+                                    extension: Empty {},
+                                    max_rulesets_per_competition: None,
+                                })
+                                .unwrap(),
+                                admin: None,
+                                label: "arena-wager-module".to_string(),
+                            }],
+                            to_disable: vec![],
+                        },
+                    })
+                    .unwrap(),
+                }
+                .into(),
+                WasmMsg::Execute {
+                    contract_addr: context.arena_core_addr.to_string(),
+                    funds: vec![],
+                    msg: to_json_binary(&arena_core_interface::msg::ExecuteMsg::Extension {
+                        msg: arena_core_interface::msg::ExecuteExt::UpdateRulesets {
+                            to_add: vec![],
+                            to_disable: vec![Uint128::one()],
+                            reject_duplicates: None,
+                        },
+                    })
+                    .unwrap(),
+                }
+                .into(),
+            ],
+        },
+        &[],
+    );
+    assert!(result.is_ok());
+    app.update_block(next_block);
+
+    // The current state reflects the changes
+    let dump: arena_core_interface::msg::DumpStateResponse = app
+        .wrap()
+        .query_wasm_smart(
+            context.arena_core_addr.clone(),
+            &arena_core_interface::msg::QueryMsg::QueryExtension {
+                msg: arena_core_interface::msg::QueryExt::DumpState { height: None },
+            },
+        )
+        .unwrap();
+    assert_eq!(dump.tax, Decimal::percent(20));
+    assert_eq!(dump.competition_modules.len(), 1);
+    assert_eq!(dump.competition_modules[0].key, "Wagers");
+    assert_eq!(
+        dump.rulesets.iter().map(|x| x.id).collect::<Vec<_>>(),
+        vec![Uint128::new(2)]
+    );
+
+    // The state before the changes is still available
+    let dump: arena_core_interface::msg::DumpStateResponse = app
+        .wrap()
+        .query_wasm_smart(
+            context.arena_core_addr.clone(),
+            &arena_core_interface::msg::QueryMsg::QueryExtension {
+                msg: arena_core_interface::msg::QueryExt::DumpState {
+                    height: Some(change_height),
+                },
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        dump.tax,
+        Decimal::new(Uint128::from(150000000000000000u128))
+    );
+    assert!(dump.competition_modules.is_empty());
+    assert_eq!(
+        dump.rulesets.iter().map(|x| x.id).collect::<Vec<_>>(),
+        vec![Uint128::new(1), Uint128::new(2)]
+    );
+}
+
+#[test]
+pub fn test_tax_schedule() {
+    let mut app = get_app();
+
+    let admin = app.api().addr_make(ADMIN);
+    let context = setup_core_context(
+        &mut app,
+        vec![Member {
+            addr: admin.to_string(),
+            weight: 1u64,
+        }],
+    );
+    app.update_block(next_block);
+
+    let update_tax_msg =
+        |schedule: Vec<(u64, Decimal)>| dao_proposal_sudo::msg::ExecuteMsg::Execute {
+            msgs: vec![WasmMsg::Execute {
+                contract_addr: context.arena_core_addr.to_string(),
+                funds: vec![],
+                msg: to_json_binary(&arena_core_interface::msg::ExecuteMsg::Extension {
+                    msg: arena_core_interface::msg::ExecuteExt::UpdateTax {
+                        tax: Decimal::percent(10),
+                        schedule: Some(schedule),
+                    },
+                })
+                .unwrap(),
+            }
+            .into()],
+        };
+
+    // Schedule steps must come after the next block
+    let update_height = app.block_info().height;
+    let result = app.execute_contract(
+        admin.clone(),
+        context.sudo_proposal_addr.clone(),
+        &update_tax_msg(vec![(update_height + 1, Decimal::percent(25))]),
+        &[],
+    );
+    assert!(result.is_err());
+
+    // Start at 10% and step up to 25%
+    let step_height = update_height + 10;
+    let result = app.execute_contract(
+        admin.clone(),
+        context.sudo_proposal_addr.clone(),
+        &update_tax_msg(vec![(step_height, Decimal::percent(25))]),
+        &[],
+    );
+    assert!(result.is_ok());
+
+    let query_tax = |app: &App<BankKeeper, MockApiBech32>, height: u64| -> Decimal {
+        app.wrap()
+            .query_wasm_smart(
+                context.arena_core_addr.clone(),
+                &arena_core_interface::msg::QueryMsg::QueryExtension {
+                    msg: arena_core_interface::msg::QueryExt::Tax {
+                        height: Some(height),
+                        addr: None,
+                        escrow_value: None,
+                    },
+                },
+            )
+            .unwrap()
+    };
+
+    assert_eq!(
+        query_tax(&app, update_height),
+        Decimal::new(Uint128::from(150000000000000000u128))
+    );
+    assert_eq!(query_tax(&app, update_height + 1), Decimal::percent(10));
+    assert_eq!(query_tax(&app, step_height - 1), Decimal::percent(10));
+    assert_eq!(query_tax(&app, step_height), Decimal::percent(25));
+    assert_eq!(query_tax(&app, step_height + 100), Decimal::percent(25));
+
+    // A matching tier takes precedence over the scheduled tax
+    let result = app.execute_contract(
+        admin.clone(),
+        context.sudo_proposal_addr.clone(),
+        &dao_proposal_sudo::msg::ExecuteMsg::Execute {
+            msgs: vec![WasmMsg::Execute {
+                contract_addr: context.arena_core_addr.to_string(),
+                funds: vec![],
+                msg: to_json_binary(&arena_core_interface::msg::ExecuteMsg::Extension {
+                    msg: arena_core_interface::msg::ExecuteExt::UpdateTaxTiers {
+                        asset: "juno".to_string(),
+                        tiers: vec![(Uint128::new(1_000), Decimal::percent(5))],
+                    },
+                })
+                .unwrap(),
+            }
+            .into()],
+        },
+        &[],
+    );
+    assert!(result.is_ok());
+
+    let query_tiered_tax = |app: &App<BankKeeper, MockApiBech32>, escrow_value: &str| -> Decimal {
+        app.wrap()
+            .query_wasm_smart(
+                context.arena_core_addr.clone(),
+                &arena_core_interface::msg::QueryMsg::QueryExtension {
+                    msg: arena_core_interface::msg::QueryExt::Tax {
+                        height: Some(step_height),
+                        addr: None,
+                        escrow_value: Some(Coin::from_str(escrow_value).unwrap()),
+                    },
+                },
+            )
+            .unwrap()
+    };
+
+    assert_eq!(query_tiered_tax(&app, "1000juno"), Decimal::percent(5));
+    assert_eq!(query_tiered_tax(&app, "999juno"), Decimal::percent(25));
+    assert_eq!(query_tax(&app, step_height), Decimal::percent(25));
+}
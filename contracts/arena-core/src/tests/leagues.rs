@@ -1,15 +1,18 @@
-use std::str::FromStr;
+use std::{collections::BTreeSet, str::FromStr};
 
 use arena_league_module::{
     msg::{
-        CompetitionInstantiateExt, ExecuteExt, ExecuteMsg, InstantiateMsg, LeagueResponse,
-        MatchResult, MemberPoints, QueryExt, QueryMsg,
+        CompetitionInstantiateExt, ExecuteExt, ExecuteMsg, InstantiateMsg, LeagueProgress,
+        LeagueResponse, MatchResult, MemberPoints, QueryExt, QueryMsg, ScheduleAlgorithm,
+        ScheduledMatch, TeamStanding,
     },
-    state::{Match, Result, RoundResponse, TournamentExt},
+    state::{Match, MatchBonus, Result, RoundResponse, TournamentExt},
+};
+use cosmwasm_std::{
+    to_json_binary, Addr, Coin, Coins, Decimal, StdResult, Uint128, Uint64, WasmMsg,
 };
-use cosmwasm_std::{to_json_binary, Addr, Coin, Coins, Decimal, Uint128, Uint64, WasmMsg};
 use cw4::Member;
-use cw_balance::MemberBalanceUnchecked;
+use cw_balance::{Distribution, MemberBalanceUnchecked};
 use cw_competition::msg::ModuleInfo;
 use cw_multi_test::{addons::MockApiBech32, next_block, App, BankKeeper, Executor};
 use cw_utils::{Duration, Expiration};
@@ -60,6 +63,7 @@ fn setup_league_context(
                                     tax_cw721_msg: None,
                                     remainder_addr: core_context.dao_addr.to_string(),
                                 },
+                                max_rulesets_per_competition: None,
                             })
                             .unwrap(),
                             admin: None,
@@ -94,6 +98,8 @@ fn create_competition(
     members: Vec<cw4::Member>,
     dues: Option<Vec<MemberBalanceUnchecked>>,
     round_duration: Duration,
+    schedule_algorithm: Option<ScheduleAlgorithm>,
+    pivot: Option<Uint64>,
 ) -> Uint128 {
     let teams: Vec<String> = members.iter().map(|x| x.addr.to_string()).collect();
 
@@ -128,11 +134,24 @@ fn create_competition(
                     label: "DAO".to_owned(),
                 },
             },
-            escrow: dues.map(|x| ModuleInstantiateInfo {
-                code_id: context.league.escrow_id,
-                msg: to_json_binary(&arena_escrow::msg::InstantiateMsg { dues: x }).unwrap(),
-                admin: None,
-                label: "Escrow".to_owned(),
+            escrow: dues.map(|x| ModuleInfo::New {
+                info: ModuleInstantiateInfo {
+                    code_id: context.league.escrow_id,
+                    msg: to_json_binary(&arena_escrow::msg::InstantiateMsg {
+                        dues: x,
+                        oracle: None,
+                        competition_id: None,
+                        activation_quorum: None,
+                        min_participants: None,
+                        cancel_on_close: None,
+                        treasury: None,
+                        auto_lock_delay: None,
+                        metadata: None,
+                    })
+                    .unwrap(),
+                    admin: None,
+                    label: "Escrow".to_owned(),
+                },
             }),
             name: "This is a competition name".to_string(),
             description: "This is a description".to_string(),
@@ -143,6 +162,7 @@ fn create_competition(
                 "Rule 3".to_string(),
             ],
             rulesets: vec![],
+            result_authority: None,
             instantiate_extension: CompetitionInstantiateExt {
                 teams,
                 round_duration,
@@ -154,6 +174,10 @@ fn create_competition(
                     Decimal::from_ratio(20u128, 100u128),
                     Decimal::from_ratio(10u128, 100u128),
                 ],
+                schedule_algorithm,
+                pivot,
+                shuffle_seed: None,
+                max_extensions: None,
             },
         },
         &[],
@@ -236,6 +260,8 @@ fn test_create_competition() {
             },
         ]),
         Duration::Height(10u64),
+        None,
+        None,
     );
 
     // Get competition1
@@ -274,13 +300,15 @@ fn test_create_competition() {
                     match_number: Uint128::from(2u128),
                     team_1: users[2].clone(),
                     team_2: users[3].clone(),
-                    result: None
+                    result: None,
+                    bonus: None
                 },
                 Match {
                     match_number: Uint128::one(),
                     team_1: users[1].clone(),
                     team_2: users[4].clone(),
-                    result: None
+                    result: None,
+                    bonus: None
                 }
             ],
             expiration: Expiration::AtHeight(starting_height + 10u64),
@@ -310,13 +338,15 @@ fn test_create_competition() {
                     match_number: Uint128::from(4u128),
                     team_1: users[1].clone(),
                     team_2: users[2].clone(),
-                    result: None
+                    result: None,
+                    bonus: None
                 },
                 Match {
                     match_number: Uint128::from(3u128),
                     team_1: users[0].clone(),
                     team_2: users[4].clone(),
-                    result: None
+                    result: None,
+                    bonus: None
                 }
             ],
             expiration: Expiration::AtHeight(starting_height + 20u64),
@@ -339,12 +369,15 @@ fn test_create_competition() {
                             MatchResult {
                                 match_number: Uint128::one(),
                                 result: Some(Result::Team1),
+                                bonus: None,
                             },
                             MatchResult {
                                 match_number: Uint128::from(2u128),
                                 result: Some(Result::Draw),
+                                bonus: None,
                             },
                         ],
+                        evidence: None,
                     },
                 })
                 .unwrap(),
@@ -419,12 +452,15 @@ fn test_create_competition() {
                             MatchResult {
                                 match_number: Uint128::from(3u128),
                                 result: Some(Result::Team1),
+                                bonus: None,
                             },
                             MatchResult {
                                 match_number: Uint128::from(4u128),
                                 result: Some(Result::Team1),
+                                bonus: None,
                             },
                         ],
+                        evidence: None,
                     },
                 })
                 .unwrap(),
@@ -507,12 +543,15 @@ fn test_create_competition() {
                             MatchResult {
                                 match_number: Uint128::from(5u128),
                                 result: Some(Result::Team1),
+                                bonus: None,
                             },
                             MatchResult {
                                 match_number: Uint128::from(6u128),
                                 result: Some(Result::Team1),
+                                bonus: None,
                             },
                         ],
+                        evidence: None,
                     },
                 })
                 .unwrap(),
@@ -595,12 +634,15 @@ fn test_create_competition() {
                             MatchResult {
                                 match_number: Uint128::from(7u128),
                                 result: Some(Result::Team1),
+                                bonus: None,
                             },
                             MatchResult {
                                 match_number: Uint128::from(8u128),
                                 result: Some(Result::Team1),
+                                bonus: None,
                             },
                         ],
+                        evidence: None,
                     },
                 })
                 .unwrap(),
@@ -683,12 +725,15 @@ fn test_create_competition() {
                             MatchResult {
                                 match_number: Uint128::from(9u128),
                                 result: Some(Result::Team1),
+                                bonus: None,
                             },
                             MatchResult {
                                 match_number: Uint128::from(10u128),
                                 result: Some(Result::Team1),
+                                bonus: None,
                             },
                         ],
+                        evidence: None,
                     },
                 })
                 .unwrap(),
@@ -755,3 +800,2888 @@ fn test_create_competition() {
         }
     );
 }
+
+#[test]
+fn test_add_rounds() {
+    let mut app = get_app();
+    let users = [
+        app.api().addr_make("user1"),
+        app.api().addr_make("user2"),
+        app.api().addr_make("user3"),
+    ];
+    let admin = app.api().addr_make(ADMIN);
+
+    let core_context = setup_core_context(
+        &mut app,
+        vec![Member {
+            addr: admin.to_string(),
+            weight: 1u64,
+        }],
+    );
+    let league_context = setup_league_context(&mut app, &core_context);
+    let mut context = Context {
+        app,
+        core: core_context,
+        league: league_context,
+    };
+
+    // Create a 3 team league without an escrow
+    let starting_height = context.app.block_info().height;
+    let league_id = create_competition(
+        &mut context,
+        Expiration::AtHeight(starting_height + 100),
+        users
+            .iter()
+            .map(|x| Member {
+                addr: x.to_string(),
+                weight: 1u64,
+            })
+            .collect(),
+        None,
+        Duration::Height(10u64),
+        None,
+        None,
+    );
+
+    // Process the first 2 rounds, leaving the league active
+    for round_number in 1..=2u64 {
+        context.app.update_block(|x| x.height += 10);
+        let result = context.app.execute_contract(
+            admin.clone(),
+            context.core.sudo_proposal_addr.clone(),
+            &dao_proposal_sudo::msg::ExecuteMsg::Execute {
+                msgs: vec![WasmMsg::Execute {
+                    contract_addr: context.league.league_module_addr.to_string(),
+                    msg: to_json_binary(&ExecuteMsg::Extension {
+                        msg: ExecuteExt::ProcessMatch {
+                            league_id,
+                            round_number: Uint64::from(round_number),
+                            match_results: vec![MatchResult {
+                                match_number: Uint128::from(round_number),
+                                result: Some(Result::Team1),
+                                bonus: None,
+                            }],
+                            evidence: None,
+                        },
+                    })
+                    .unwrap(),
+                    funds: vec![],
+                }
+                .into()],
+            },
+            &[],
+        );
+        assert!(result.is_ok());
+    }
+
+    // Append a playoff round between the first 2 teams
+    let result = context.app.execute_contract(
+        admin.clone(),
+        context.core.sudo_proposal_addr.clone(),
+        &dao_proposal_sudo::msg::ExecuteMsg::Execute {
+            msgs: vec![WasmMsg::Execute {
+                contract_addr: context.league.league_module_addr.to_string(),
+                msg: to_json_binary(&ExecuteMsg::Extension {
+                    msg: ExecuteExt::AddRounds {
+                        league_id,
+                        teams_subset: vec![users[0].to_string(), users[1].to_string()],
+                        round_duration: Duration::Height(10u64),
+                        count: Uint64::one(),
+                    },
+                })
+                .unwrap(),
+                funds: vec![],
+            }
+            .into()],
+        },
+        &[],
+    );
+    assert!(result.is_ok());
+
+    let league: LeagueResponse = context
+        .app
+        .wrap()
+        .query_wasm_smart(
+            context.league.league_module_addr.clone(),
+            &QueryMsg::Competition {
+                competition_id: league_id,
+            },
+        )
+        .unwrap();
+    assert_eq!(league.extension.rounds, Uint64::from(4u64));
+    assert_eq!(league.extension.matches, Uint128::from(4u128));
+
+    // The playoff match continues the match numbering
+    let round4: RoundResponse = context
+        .app
+        .wrap()
+        .query_wasm_smart(
+            context.league.league_module_addr.clone(),
+            &QueryMsg::QueryExtension {
+                msg: QueryExt::Round {
+                    league_id,
+                    round_number: Uint64::from(4u64),
+                },
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        round4,
+        RoundResponse {
+            round_number: Uint64::from(4u64),
+            matches: vec![Match {
+                match_number: Uint128::from(4u128),
+                team_1: users[0].clone(),
+                team_2: users[1].clone(),
+                result: None,
+                bonus: None
+            }],
+            // The third round has not expired yet, so the playoff round starts after it
+            expiration: Expiration::AtHeight(starting_height + 40u64),
+        },
+    );
+
+    // Teams outside of the league cannot be added
+    let result = context.app.execute_contract(
+        admin.clone(),
+        context.core.sudo_proposal_addr.clone(),
+        &dao_proposal_sudo::msg::ExecuteMsg::Execute {
+            msgs: vec![WasmMsg::Execute {
+                contract_addr: context.league.league_module_addr.to_string(),
+                msg: to_json_binary(&ExecuteMsg::Extension {
+                    msg: ExecuteExt::AddRounds {
+                        league_id,
+                        teams_subset: vec![
+                            users[0].to_string(),
+                            context.app.api().addr_make("outsider").to_string(),
+                        ],
+                        round_duration: Duration::Height(10u64),
+                        count: Uint64::one(),
+                    },
+                })
+                .unwrap(),
+                funds: vec![],
+            }
+            .into()],
+        },
+        &[],
+    );
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_add_rounds_before_season_end() {
+    let mut app = get_app();
+    let users = [
+        app.api().addr_make("user1"),
+        app.api().addr_make("user2"),
+        app.api().addr_make("user3"),
+    ];
+    let admin = app.api().addr_make(ADMIN);
+
+    let core_context = setup_core_context(
+        &mut app,
+        vec![Member {
+            addr: admin.to_string(),
+            weight: 1u64,
+        }],
+    );
+    let league_context = setup_league_context(&mut app, &core_context);
+    let mut context = Context {
+        app,
+        core: core_context,
+        league: league_context,
+    };
+
+    // The regular season's 3 rounds expire at 10, 20, and 30 blocks
+    let starting_height = context.app.block_info().height;
+    let league_id = create_competition(
+        &mut context,
+        Expiration::AtHeight(starting_height + 100),
+        users
+            .iter()
+            .map(|x| Member {
+                addr: x.to_string(),
+                weight: 1u64,
+            })
+            .collect(),
+        None,
+        Duration::Height(10u64),
+        None,
+        None,
+    );
+
+    // Append 2 rounds before any round has expired
+    context
+        .app
+        .execute_contract(
+            admin.clone(),
+            context.core.sudo_proposal_addr.clone(),
+            &dao_proposal_sudo::msg::ExecuteMsg::Execute {
+                msgs: vec![WasmMsg::Execute {
+                    contract_addr: context.league.league_module_addr.to_string(),
+                    msg: to_json_binary(&ExecuteMsg::Extension {
+                        msg: ExecuteExt::AddRounds {
+                            league_id,
+                            teams_subset: vec![users[0].to_string(), users[1].to_string()],
+                            round_duration: Duration::Height(15u64),
+                            count: Uint64::from(2u64),
+                        },
+                    })
+                    .unwrap(),
+                    funds: vec![],
+                }
+                .into()],
+            },
+            &[],
+        )
+        .unwrap();
+
+    // The appended rounds follow the last round of the regular season
+    for (round_number, expiration) in [(4u64, 45u64), (5u64, 60u64)] {
+        let round: RoundResponse = context
+            .app
+            .wrap()
+            .query_wasm_smart(
+                context.league.league_module_addr.clone(),
+                &QueryMsg::QueryExtension {
+                    msg: QueryExt::Round {
+                        league_id,
+                        round_number: Uint64::from(round_number),
+                    },
+                },
+            )
+            .unwrap();
+        assert_eq!(
+            round.expiration,
+            Expiration::AtHeight(starting_height + expiration)
+        );
+    }
+}
+
+#[test]
+fn test_schedule_algorithms() {
+    let mut app = get_app();
+    let users: Vec<Addr> = (1..=6)
+        .map(|i| app.api().addr_make(&format!("user{}", i)))
+        .collect();
+    let admin = app.api().addr_make(ADMIN);
+
+    let core_context = setup_core_context(
+        &mut app,
+        vec![Member {
+            addr: admin.to_string(),
+            weight: 1u64,
+        }],
+    );
+    let league_context = setup_league_context(&mut app, &core_context);
+    let mut context = Context {
+        app,
+        core: core_context,
+        league: league_context,
+    };
+
+    for schedule_algorithm in [
+        ScheduleAlgorithm::CircleMethod,
+        ScheduleAlgorithm::BergerTables,
+    ] {
+        let starting_height = context.app.block_info().height;
+        let league_id = create_competition(
+            &mut context,
+            Expiration::AtHeight(starting_height + 100),
+            users
+                .iter()
+                .map(|x| Member {
+                    addr: x.to_string(),
+                    weight: 1u64,
+                })
+                .collect(),
+            None,
+            Duration::Height(10u64),
+            Some(schedule_algorithm.clone()),
+            None,
+        );
+
+        let league: LeagueResponse = context
+            .app
+            .wrap()
+            .query_wasm_smart(
+                context.league.league_module_addr.clone(),
+                &QueryMsg::Competition {
+                    competition_id: league_id,
+                },
+            )
+            .unwrap();
+        assert_eq!(league.extension.rounds, Uint64::from(5u64));
+        assert_eq!(league.extension.matches, Uint128::from(15u128));
+
+        // Every team plays once per round and every pairing is played exactly once
+        let mut pairings = std::collections::BTreeSet::new();
+        let mut rounds = vec![];
+        for round_number in 1..=5u64 {
+            let round: RoundResponse = context
+                .app
+                .wrap()
+                .query_wasm_smart(
+                    context.league.league_module_addr.clone(),
+                    &QueryMsg::QueryExtension {
+                        msg: QueryExt::Round {
+                            league_id,
+                            round_number: Uint64::from(round_number),
+                        },
+                    },
+                )
+                .unwrap();
+            assert_eq!(round.matches.len(), 3);
+
+            let mut teams = std::collections::BTreeSet::new();
+            for m in &round.matches {
+                assert!(teams.insert(m.team_1.clone()));
+                assert!(teams.insert(m.team_2.clone()));
+
+                let pairing = if m.team_1 < m.team_2 {
+                    (m.team_1.clone(), m.team_2.clone())
+                } else {
+                    (m.team_2.clone(), m.team_1.clone())
+                };
+                assert!(pairings.insert(pairing));
+            }
+            rounds.push(round);
+        }
+        assert_eq!(pairings.len(), 15);
+
+        // The Berger tables match the standard chess pairings
+        if schedule_algorithm == ScheduleAlgorithm::BergerTables {
+            let round2: Vec<(Addr, Addr)> = rounds[1]
+                .matches
+                .iter()
+                .map(|m| (m.team_1.clone(), m.team_2.clone()))
+                .collect();
+            assert_eq!(
+                round2,
+                vec![
+                    (users[5].clone(), users[3].clone()),
+                    (users[4].clone(), users[2].clone()),
+                    (users[0].clone(), users[1].clone()),
+                ]
+            );
+        }
+    }
+}
+
+#[test]
+fn test_standings() {
+    let mut app = get_app();
+    let users: Vec<Addr> = (1..=4)
+        .map(|i| app.api().addr_make(&format!("user{}", i)))
+        .collect();
+    let admin = app.api().addr_make(ADMIN);
+
+    let core_context = setup_core_context(
+        &mut app,
+        vec![Member {
+            addr: admin.to_string(),
+            weight: 1u64,
+        }],
+    );
+    let league_context = setup_league_context(&mut app, &core_context);
+    let mut context = Context {
+        app,
+        core: core_context,
+        league: league_context,
+    };
+
+    let starting_height = context.app.block_info().height;
+    let league_id = create_competition(
+        &mut context,
+        Expiration::AtHeight(starting_height + 100),
+        users
+            .iter()
+            .map(|x| Member {
+                addr: x.to_string(),
+                weight: 1u64,
+            })
+            .collect(),
+        None,
+        Duration::Height(10u64),
+        None,
+        None,
+    );
+
+    // Only the first match of round 1 is played: user1 beats user4
+    context.app.update_block(|x| x.height += 10);
+    let result = context.app.execute_contract(
+        admin.clone(),
+        context.core.sudo_proposal_addr.clone(),
+        &dao_proposal_sudo::msg::ExecuteMsg::Execute {
+            msgs: vec![WasmMsg::Execute {
+                contract_addr: context.league.league_module_addr.to_string(),
+                msg: to_json_binary(&ExecuteMsg::Extension {
+                    msg: ExecuteExt::ProcessMatch {
+                        league_id,
+                        round_number: Uint64::one(),
+                        match_results: vec![MatchResult {
+                            match_number: Uint128::one(),
+                            result: Some(Result::Team1),
+                            bonus: None,
+                        }],
+                        evidence: Some("ipfs://round1".to_string()),
+                    },
+                })
+                .unwrap(),
+                funds: vec![],
+            }
+            .into()],
+        },
+        &[],
+    );
+    assert!(result.is_ok());
+
+    // The evidence is only attached to the reported match
+    for (match_number, expected) in [(1u128, Some("ipfs://round1".to_string())), (2u128, None)] {
+        let evidence: Option<String> = context
+            .app
+            .wrap()
+            .query_wasm_smart(
+                context.league.league_module_addr.clone(),
+                &QueryMsg::QueryExtension {
+                    msg: QueryExt::MatchEvidence {
+                        league_id,
+                        round_number: Uint64::one(),
+                        match_number: Uint128::from(match_number),
+                    },
+                },
+            )
+            .unwrap();
+        assert_eq!(evidence, expected);
+    }
+
+    let standings: Vec<TeamStanding> = context
+        .app
+        .wrap()
+        .query_wasm_smart(
+            context.league.league_module_addr.clone(),
+            &QueryMsg::QueryExtension {
+                msg: QueryExt::Standings {
+                    league_id,
+                    round: Some(Uint64::one()),
+                },
+            },
+        )
+        .unwrap();
+    assert_eq!(standings.len(), 4);
+
+    let winner = standings.iter().find(|x| x.member == users[0]).unwrap();
+    assert_eq!(winner.wins, Uint64::one());
+    assert_eq!(winner.points, Uint128::from(3u128));
+    assert_eq!(winner.win_rate, Decimal::one());
+
+    let loser = standings.iter().find(|x| x.member == users[3]).unwrap();
+    assert_eq!(loser.losses, Uint64::one());
+    assert_eq!(loser.win_rate, Decimal::zero());
+
+    // Teams without a played match have a zero win rate
+    let unplayed = standings.iter().find(|x| x.member == users[1]).unwrap();
+    assert_eq!(unplayed.matches_played, Uint64::zero());
+    assert_eq!(unplayed.win_rate, Decimal::zero());
+
+    // The leaderboard still only lists teams that have played
+    let leaderboard: Vec<MemberPoints> = context
+        .app
+        .wrap()
+        .query_wasm_smart(
+            context.league.league_module_addr.clone(),
+            &QueryMsg::QueryExtension {
+                msg: QueryExt::Leaderboard {
+                    league_id,
+                    round: None,
+                },
+            },
+        )
+        .unwrap();
+    assert_eq!(leaderboard.len(), 2);
+}
+
+#[test]
+fn test_league_progress() {
+    let mut app = get_app();
+    let users: Vec<Addr> = (1..=4)
+        .map(|i| app.api().addr_make(&format!("user{}", i)))
+        .collect();
+    let admin = app.api().addr_make(ADMIN);
+
+    let core_context = setup_core_context(
+        &mut app,
+        vec![Member {
+            addr: admin.to_string(),
+            weight: 1u64,
+        }],
+    );
+    let league_context = setup_league_context(&mut app, &core_context);
+    let mut context = Context {
+        app,
+        core: core_context,
+        league: league_context,
+    };
+
+    let starting_height = context.app.block_info().height;
+    let league_id = create_competition(
+        &mut context,
+        Expiration::AtHeight(starting_height + 100),
+        users
+            .iter()
+            .map(|x| Member {
+                addr: x.to_string(),
+                weight: 1u64,
+            })
+            .collect(),
+        None,
+        Duration::Height(10u64),
+        None,
+        None,
+    );
+
+    // Play all of round 1 and one match of round 2
+    context.app.update_block(|x| x.height += 20);
+    for (round_number, match_numbers) in [(1u64, vec![1u128, 2u128]), (2u64, vec![3u128])] {
+        let result = context.app.execute_contract(
+            admin.clone(),
+            context.core.sudo_proposal_addr.clone(),
+            &dao_proposal_sudo::msg::ExecuteMsg::Execute {
+                msgs: vec![WasmMsg::Execute {
+                    contract_addr: context.league.league_module_addr.to_string(),
+                    msg: to_json_binary(&ExecuteMsg::Extension {
+                        msg: ExecuteExt::ProcessMatch {
+                            league_id,
+                            round_number: Uint64::from(round_number),
+                            match_results: match_numbers
+                                .into_iter()
+                                .map(|match_number| MatchResult {
+                                    match_number: Uint128::from(match_number),
+                                    result: Some(Result::Draw),
+                                    bonus: None,
+                                })
+                                .collect(),
+                            evidence: None,
+                        },
+                    })
+                    .unwrap(),
+                    funds: vec![],
+                }
+                .into()],
+            },
+            &[],
+        );
+        assert!(result.is_ok());
+    }
+
+    let progress: LeagueProgress = context
+        .app
+        .wrap()
+        .query_wasm_smart(
+            context.league.league_module_addr.clone(),
+            &QueryMsg::QueryExtension {
+                msg: QueryExt::LeagueProgress { league_id },
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        progress,
+        LeagueProgress {
+            total_matches: Uint128::from(6u128),
+            played_matches: Uint128::from(3u128),
+            remaining_matches: Uint128::from(3u128),
+            current_round: Uint64::from(2u64),
+            total_rounds: Uint64::from(3u64),
+        }
+    );
+}
+
+#[test]
+fn test_standings_at_round() {
+    let mut app = get_app();
+    let users: Vec<Addr> = (1..=4)
+        .map(|i| app.api().addr_make(&format!("user{}", i)))
+        .collect();
+    let admin = app.api().addr_make(ADMIN);
+
+    let core_context = setup_core_context(
+        &mut app,
+        vec![Member {
+            addr: admin.to_string(),
+            weight: 1u64,
+        }],
+    );
+    let league_context = setup_league_context(&mut app, &core_context);
+    let mut context = Context {
+        app,
+        core: core_context,
+        league: league_context,
+    };
+
+    let starting_height = context.app.block_info().height;
+    let league_id = create_competition(
+        &mut context,
+        Expiration::AtHeight(starting_height + 100),
+        users
+            .iter()
+            .map(|x| Member {
+                addr: x.to_string(),
+                weight: 1u64,
+            })
+            .collect(),
+        None,
+        Duration::Height(10u64),
+        None,
+        None,
+    );
+
+    // Play every match of the 3 rounds
+    context.app.update_block(|x| x.height += 30);
+    for round_number in 1u64..=3 {
+        let result = context.app.execute_contract(
+            admin.clone(),
+            context.core.sudo_proposal_addr.clone(),
+            &dao_proposal_sudo::msg::ExecuteMsg::Execute {
+                msgs: vec![WasmMsg::Execute {
+                    contract_addr: context.league.league_module_addr.to_string(),
+                    msg: to_json_binary(&ExecuteMsg::Extension {
+                        msg: ExecuteExt::ProcessMatch {
+                            league_id,
+                            round_number: Uint64::from(round_number),
+                            match_results: [round_number * 2 - 1, round_number * 2]
+                                .into_iter()
+                                .map(|match_number| MatchResult {
+                                    match_number: Uint128::from(match_number),
+                                    result: Some(Result::Team1),
+                                    bonus: None,
+                                })
+                                .collect(),
+                            evidence: None,
+                        },
+                    })
+                    .unwrap(),
+                    funds: vec![],
+                }
+                .into()],
+            },
+            &[],
+        );
+        assert!(result.is_ok());
+    }
+
+    let standings_at_round: Vec<TeamStanding> = context
+        .app
+        .wrap()
+        .query_wasm_smart(
+            context.league.league_module_addr.clone(),
+            &QueryMsg::QueryExtension {
+                msg: QueryExt::StandingsAtRound {
+                    league_id,
+                    round_number: Uint64::from(2u64),
+                },
+            },
+        )
+        .unwrap();
+    let final_standings: Vec<TeamStanding> = context
+        .app
+        .wrap()
+        .query_wasm_smart(
+            context.league.league_module_addr.clone(),
+            &QueryMsg::QueryExtension {
+                msg: QueryExt::Standings {
+                    league_id,
+                    round: None,
+                },
+            },
+        )
+        .unwrap();
+
+    // Every team has played 2 matches after round 2 and 3 by the end
+    assert_eq!(standings_at_round.len(), 4);
+    assert!(standings_at_round
+        .iter()
+        .all(|x| x.matches_played == Uint64::from(2u64)));
+    assert!(final_standings
+        .iter()
+        .all(|x| x.matches_played == Uint64::from(3u64)));
+    assert_ne!(standings_at_round, final_standings);
+
+    // Only 4 of the 6 matches have been awarded points after round 2
+    let total_points = |standings: &[TeamStanding]| {
+        standings
+            .iter()
+            .fold(Uint128::zero(), |acc, x| acc + x.points)
+    };
+    assert_eq!(total_points(&standings_at_round), Uint128::from(12u128));
+    assert_eq!(total_points(&final_standings), Uint128::from(18u128));
+
+    // A round that was never scheduled cannot be queried
+    let result: StdResult<Vec<TeamStanding>> = context.app.wrap().query_wasm_smart(
+        context.league.league_module_addr.clone(),
+        &QueryMsg::QueryExtension {
+            msg: QueryExt::StandingsAtRound {
+                league_id,
+                round_number: Uint64::from(4u64),
+            },
+        },
+    );
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_is_league_complete() {
+    let mut app = get_app();
+    let users: Vec<Addr> = (1..=3)
+        .map(|i| app.api().addr_make(&format!("user{}", i)))
+        .collect();
+    let admin = app.api().addr_make(ADMIN);
+
+    let core_context = setup_core_context(
+        &mut app,
+        vec![Member {
+            addr: admin.to_string(),
+            weight: 1u64,
+        }],
+    );
+    let league_context = setup_league_context(&mut app, &core_context);
+    let mut context = Context {
+        app,
+        core: core_context,
+        league: league_context,
+    };
+
+    // An odd number of teams gives each team a bye
+    let starting_height = context.app.block_info().height;
+    let league_id = create_competition(
+        &mut context,
+        Expiration::AtHeight(starting_height + 100),
+        users
+            .iter()
+            .map(|x| Member {
+                addr: x.to_string(),
+                weight: 1u64,
+            })
+            .collect(),
+        None,
+        Duration::Height(10u64),
+        None,
+        None,
+    );
+    let league: LeagueResponse = context
+        .app
+        .wrap()
+        .query_wasm_smart(
+            context.league.league_module_addr.clone(),
+            &QueryMsg::Competition {
+                competition_id: league_id,
+            },
+        )
+        .unwrap();
+
+    let is_league_complete = |context: &Context| -> bool {
+        context
+            .app
+            .wrap()
+            .query_wasm_smart(
+                context.league.league_module_addr.clone(),
+                &QueryMsg::QueryExtension {
+                    msg: QueryExt::IsLeagueComplete { league_id },
+                },
+            )
+            .unwrap()
+    };
+
+    context
+        .app
+        .update_block(|x| x.height += 10 * league.extension.rounds.u64());
+    for round_number in 1..=league.extension.rounds.u64() {
+        assert!(!is_league_complete(&context));
+
+        let round: RoundResponse = context
+            .app
+            .wrap()
+            .query_wasm_smart(
+                context.league.league_module_addr.clone(),
+                &QueryMsg::QueryExtension {
+                    msg: QueryExt::Round {
+                        league_id,
+                        round_number: Uint64::from(round_number),
+                    },
+                },
+            )
+            .unwrap();
+        let result = context.app.execute_contract(
+            admin.clone(),
+            context.core.sudo_proposal_addr.clone(),
+            &dao_proposal_sudo::msg::ExecuteMsg::Execute {
+                msgs: vec![WasmMsg::Execute {
+                    contract_addr: context.league.league_module_addr.to_string(),
+                    msg: to_json_binary(&ExecuteMsg::Extension {
+                        msg: ExecuteExt::ProcessMatch {
+                            league_id,
+                            round_number: Uint64::from(round_number),
+                            match_results: round
+                                .matches
+                                .iter()
+                                .map(|m| MatchResult {
+                                    match_number: m.match_number,
+                                    result: Some(Result::Draw),
+                                    bonus: None,
+                                })
+                                .collect(),
+                            evidence: None,
+                        },
+                    })
+                    .unwrap(),
+                    funds: vec![],
+                }
+                .into()],
+            },
+            &[],
+        );
+        assert!(result.is_ok());
+    }
+
+    assert!(is_league_complete(&context));
+}
+
+#[test]
+fn test_next_match() {
+    let mut app = get_app();
+    let users: Vec<Addr> = (1..=4)
+        .map(|i| app.api().addr_make(&format!("user{}", i)))
+        .collect();
+    let admin = app.api().addr_make(ADMIN);
+
+    let core_context = setup_core_context(
+        &mut app,
+        vec![Member {
+            addr: admin.to_string(),
+            weight: 1u64,
+        }],
+    );
+    let league_context = setup_league_context(&mut app, &core_context);
+    let mut context = Context {
+        app,
+        core: core_context,
+        league: league_context,
+    };
+
+    let starting_height = context.app.block_info().height;
+    let league_id = create_competition(
+        &mut context,
+        Expiration::AtHeight(starting_height + 100),
+        users
+            .iter()
+            .map(|x| Member {
+                addr: x.to_string(),
+                weight: 1u64,
+            })
+            .collect(),
+        None,
+        Duration::Height(10u64),
+        None,
+        None,
+    );
+
+    // Round 1 has expired, so the next match is in round 2
+    context.app.update_block(|x| x.height += 10);
+    let round: RoundResponse = context
+        .app
+        .wrap()
+        .query_wasm_smart(
+            context.league.league_module_addr.clone(),
+            &QueryMsg::QueryExtension {
+                msg: QueryExt::Round {
+                    league_id,
+                    round_number: Uint64::from(2u64),
+                },
+            },
+        )
+        .unwrap();
+    let expected = round
+        .matches
+        .iter()
+        .find_map(|m| {
+            if m.team_1 == users[0] {
+                Some((m.match_number, m.team_2.clone()))
+            } else if m.team_2 == users[0] {
+                Some((m.match_number, m.team_1.clone()))
+            } else {
+                None
+            }
+        })
+        .unwrap();
+
+    let query_next_match = |context: &Context, team: &Addr| -> Option<ScheduledMatch> {
+        context
+            .app
+            .wrap()
+            .query_wasm_smart(
+                context.league.league_module_addr.clone(),
+                &QueryMsg::QueryExtension {
+                    msg: QueryExt::NextMatch {
+                        league_id,
+                        team: team.to_string(),
+                    },
+                },
+            )
+            .unwrap()
+    };
+
+    assert_eq!(
+        query_next_match(&context, &users[0]),
+        Some(ScheduledMatch {
+            round_number: Uint64::from(2u64),
+            match_number: expected.0,
+            opponent: expected.1,
+            expiration: round.expiration,
+        })
+    );
+
+    // Every round has expired, so there are no remaining matches
+    context.app.update_block(|x| x.height += 20);
+    assert_eq!(query_next_match(&context, &users[0]), None);
+}
+
+#[test]
+fn test_create_competition_zero_round_duration() {
+    let mut app = get_app();
+    let users: Vec<Addr> = (1..=3)
+        .map(|i| app.api().addr_make(&format!("user{}", i)))
+        .collect();
+    let admin = app.api().addr_make(ADMIN);
+
+    let core_context = setup_core_context(
+        &mut app,
+        vec![Member {
+            addr: admin.to_string(),
+            weight: 1u64,
+        }],
+    );
+    let league_context = setup_league_context(&mut app, &core_context);
+    let mut context = Context {
+        app,
+        core: core_context,
+        league: league_context,
+    };
+
+    // Rounds without a duration would expire as soon as they are created
+    let starting_height = context.app.block_info().height;
+    let result = context.app.execute_contract(
+        admin.clone(),
+        context.league.league_module_addr.clone(),
+        &ExecuteMsg::CreateCompetition {
+            category_id: Some(Uint128::one()),
+            host: ModuleInfo::Existing {
+                addr: admin.to_string(),
+            },
+            escrow: None,
+            name: "This is a competition name".to_string(),
+            description: "This is a description".to_string(),
+            expiration: Expiration::AtHeight(starting_height + 100),
+            rules: vec!["Rule 1".to_string()],
+            rulesets: vec![],
+            result_authority: None,
+            instantiate_extension: CompetitionInstantiateExt {
+                teams: users.iter().map(|x| x.to_string()).collect(),
+                round_duration: Duration::Height(0u64),
+                match_win_points: Uint128::from(3u128),
+                match_draw_points: Uint128::one(),
+                match_lose_points: Uint128::zero(),
+                distribution: vec![Decimal::one()],
+                schedule_algorithm: None,
+                pivot: None,
+                shuffle_seed: None,
+                max_extensions: None,
+            },
+        },
+        &[],
+    );
+    assert!(result.is_err());
+    assert_eq!(
+        result.unwrap_err().root_cause().to_string(),
+        "InvalidExpiration"
+    );
+}
+
+#[test]
+fn test_create_tiebreak() {
+    let mut app = get_app();
+    let users: Vec<Addr> = (1..=2)
+        .map(|i| app.api().addr_make(&format!("user{}", i)))
+        .collect();
+    let admin = app.api().addr_make(ADMIN);
+
+    let core_context = setup_core_context(
+        &mut app,
+        vec![Member {
+            addr: admin.to_string(),
+            weight: 1u64,
+        }],
+    );
+    let league_context = setup_league_context(&mut app, &core_context);
+    let mut context = Context {
+        app,
+        core: core_context,
+        league: league_context,
+    };
+
+    let starting_height = context.app.block_info().height;
+    let league_id = create_competition(
+        &mut context,
+        Expiration::AtHeight(starting_height + 100),
+        users
+            .iter()
+            .map(|x| Member {
+                addr: x.to_string(),
+                weight: 1u64,
+            })
+            .collect(),
+        None,
+        Duration::Height(10u64),
+        None,
+        None,
+    );
+
+    let create_tiebreak = |context: &mut Context| {
+        context.app.execute_contract(
+            admin.clone(),
+            context.core.sudo_proposal_addr.clone(),
+            &dao_proposal_sudo::msg::ExecuteMsg::Execute {
+                msgs: vec![WasmMsg::Execute {
+                    contract_addr: context.league.league_module_addr.to_string(),
+                    msg: to_json_binary(&ExecuteMsg::Extension {
+                        msg: ExecuteExt::CreateTiebreak {
+                            league_id,
+                            round_duration: Duration::Height(10u64),
+                        },
+                    })
+                    .unwrap(),
+                    funds: vec![],
+                }
+                .into()],
+            },
+            &[],
+        )
+    };
+
+    // A tiebreak cannot be created while matches are pending
+    assert!(create_tiebreak(&mut context).is_err());
+
+    // The only match is drawn, leaving both teams level at the top
+    context.app.update_block(|x| x.height += 10);
+    let result = context.app.execute_contract(
+        admin.clone(),
+        context.core.sudo_proposal_addr.clone(),
+        &dao_proposal_sudo::msg::ExecuteMsg::Execute {
+            msgs: vec![WasmMsg::Execute {
+                contract_addr: context.league.league_module_addr.to_string(),
+                msg: to_json_binary(&ExecuteMsg::Extension {
+                    msg: ExecuteExt::ProcessMatch {
+                        league_id,
+                        round_number: Uint64::one(),
+                        match_results: vec![MatchResult {
+                            match_number: Uint128::one(),
+                            result: Some(Result::Draw),
+                            bonus: None,
+                        }],
+                        evidence: None,
+                    },
+                })
+                .unwrap(),
+                funds: vec![],
+            }
+            .into()],
+        },
+        &[],
+    );
+    assert!(result.is_ok());
+
+    let result = create_tiebreak(&mut context);
+    assert!(result.is_ok());
+
+    let league: LeagueResponse = context
+        .app
+        .wrap()
+        .query_wasm_smart(
+            context.league.league_module_addr.clone(),
+            &QueryMsg::Competition {
+                competition_id: league_id,
+            },
+        )
+        .unwrap();
+    assert_eq!(league.extension.rounds, Uint64::from(2u64));
+    assert_eq!(league.extension.matches, Uint128::from(2u128));
+
+    let round2: RoundResponse = context
+        .app
+        .wrap()
+        .query_wasm_smart(
+            context.league.league_module_addr.clone(),
+            &QueryMsg::QueryExtension {
+                msg: QueryExt::Round {
+                    league_id,
+                    round_number: Uint64::from(2u64),
+                },
+            },
+        )
+        .unwrap();
+    assert_eq!(round2.matches.len(), 1);
+    let tiebreak = &round2.matches[0];
+    assert!(users.contains(&tiebreak.team_1));
+    assert!(users.contains(&tiebreak.team_2));
+    assert_ne!(tiebreak.team_1, tiebreak.team_2);
+
+    // Only one tiebreak is scheduled while it is unplayed
+    assert!(create_tiebreak(&mut context).is_err());
+}
+
+#[test]
+fn test_split_top_tie() {
+    let mut app = get_app();
+    let users: Vec<Addr> = (1..=3)
+        .map(|i| app.api().addr_make(&format!("user{}", i)))
+        .collect();
+    let admin = app.api().addr_make(ADMIN);
+    let dues = "1000juno";
+
+    set_balances(
+        &mut app,
+        users
+            .iter()
+            .map(|x| (x.clone(), Coins::from_str(dues).unwrap()))
+            .collect(),
+    );
+    let core_context = setup_core_context(
+        &mut app,
+        vec![Member {
+            addr: admin.to_string(),
+            weight: 1u64,
+        }],
+    );
+    let league_context = setup_league_context(&mut app, &core_context);
+    let mut context = Context {
+        app,
+        core: core_context,
+        league: league_context,
+    };
+
+    // A league which can never be extended past its rounds
+    let starting_height = context.app.block_info().height;
+    let result = context
+        .app
+        .execute_contract(
+            admin.clone(),
+            context.league.league_module_addr.clone(),
+            &ExecuteMsg::CreateCompetition {
+                category_id: Some(Uint128::one()),
+                host: ModuleInfo::Existing {
+                    addr: admin.to_string(),
+                },
+                escrow: Some(ModuleInfo::New {
+                    info: ModuleInstantiateInfo {
+                        code_id: context.league.escrow_id,
+                        msg: to_json_binary(&arena_escrow::msg::InstantiateMsg {
+                            dues: users
+                                .iter()
+                                .map(|x| MemberBalanceUnchecked {
+                                    addr: x.to_string(),
+                                    balance: cw_balance::BalanceUnchecked {
+                                        native: vec![Coin::from_str(dues).unwrap()],
+                                        cw20: vec![],
+                                        cw721: vec![],
+                                    },
+                                })
+                                .collect(),
+                            oracle: None,
+                            competition_id: None,
+                            activation_quorum: None,
+                            min_participants: None,
+                            cancel_on_close: None,
+                            treasury: None,
+                            auto_lock_delay: None,
+                            metadata: None,
+                        })
+                        .unwrap(),
+                        admin: None,
+                        label: "Escrow".to_owned(),
+                    },
+                }),
+                name: "This is a competition name".to_string(),
+                description: "This is a description".to_string(),
+                expiration: Expiration::AtHeight(starting_height + 60),
+                rules: vec!["Rule 1".to_string()],
+                rulesets: vec![],
+                result_authority: None,
+                instantiate_extension: CompetitionInstantiateExt {
+                    teams: users.iter().map(|x| x.to_string()).collect(),
+                    round_duration: Duration::Height(10u64),
+                    match_win_points: Uint128::from(3u128),
+                    match_draw_points: Uint128::one(),
+                    match_lose_points: Uint128::zero(),
+                    distribution: vec![
+                        Decimal::from_ratio(70u128, 100u128),
+                        Decimal::from_ratio(20u128, 100u128),
+                        Decimal::from_ratio(10u128, 100u128),
+                    ],
+                    schedule_algorithm: None,
+                    pivot: None,
+                    shuffle_seed: None,
+                    max_extensions: Some(0),
+                },
+            },
+            &[],
+        )
+        .unwrap();
+    let league_id = Uint128::from_str(&get_attr_value(&result, "competition_id").unwrap()).unwrap();
+    let query_league = |context: &Context| -> LeagueResponse {
+        context
+            .app
+            .wrap()
+            .query_wasm_smart(
+                context.league.league_module_addr.clone(),
+                &QueryMsg::Competition {
+                    competition_id: league_id,
+                },
+            )
+            .unwrap()
+    };
+    let escrow = query_league(&context).escrow.unwrap();
+
+    for user in &users {
+        context
+            .app
+            .execute_contract(
+                user.clone(),
+                escrow.clone(),
+                &arena_escrow::msg::ExecuteMsg::ReceiveNative {},
+                &[Coin::from_str(dues).unwrap()],
+            )
+            .unwrap();
+    }
+
+    let draw_round = |context: &mut Context, round_number: u64| {
+        context
+            .app
+            .execute_contract(
+                admin.clone(),
+                context.league.league_module_addr.clone(),
+                &ExecuteMsg::Extension {
+                    msg: ExecuteExt::ProcessRound {
+                        league_id,
+                        round_number: Uint64::from(round_number),
+                        results: vec![],
+                        default_result: Some(Result::Draw),
+                        evidence: None,
+                    },
+                },
+                &[],
+            )
+            .unwrap()
+    };
+    let create_tiebreak = |context: &mut Context| {
+        context
+            .app
+            .execute_contract(
+                admin.clone(),
+                context.league.league_module_addr.clone(),
+                &ExecuteMsg::Extension {
+                    msg: ExecuteExt::CreateTiebreak {
+                        league_id,
+                        round_duration: Duration::Height(10u64),
+                    },
+                },
+                &[],
+            )
+            .unwrap()
+    };
+
+    // Every match is drawn, so the league waits for a tiebreak
+    context.app.update_block(|x| x.height += 100);
+    let rounds = query_league(&context).extension.rounds.u64();
+    for round_number in 1..=rounds {
+        draw_round(&mut context, round_number);
+    }
+    assert_eq!(
+        query_league(&context).status,
+        cw_competition::state::CompetitionStatus::Active
+    );
+
+    // Tiebreaks extend the league without counting against its extensions, even when drawn
+    let mut tied_teams = BTreeSet::new();
+    for round_number in rounds + 1..=rounds + 2 {
+        let result = create_tiebreak(&mut context);
+        for key in ["team_1", "team_2"] {
+            tied_teams.insert(get_attr_value(&result, key).unwrap());
+        }
+
+        context.app.update_block(|x| x.height += 10);
+        let result = draw_round(&mut context, round_number);
+        assert_eq!(
+            get_attr_value(&result, "tiebreak_required"),
+            Some("true".to_string())
+        );
+    }
+
+    // Only the admin dao can give up on breaking the tie
+    let split_msg = ExecuteMsg::Extension {
+        msg: ExecuteExt::SplitTopTie { league_id },
+    };
+    let result = context.app.execute_contract(
+        admin.clone(),
+        context.league.league_module_addr.clone(),
+        &split_msg,
+        &[],
+    );
+    assert!(result.is_err());
+
+    let result = context.app.execute_contract(
+        admin.clone(),
+        context.core.sudo_proposal_addr.clone(),
+        &dao_proposal_sudo::msg::ExecuteMsg::Execute {
+            msgs: vec![WasmMsg::Execute {
+                contract_addr: context.league.league_module_addr.to_string(),
+                msg: to_json_binary(&split_msg).unwrap(),
+                funds: vec![],
+            }
+            .into()],
+        },
+        &[],
+    );
+    assert!(result.is_ok());
+    assert_eq!(
+        query_league(&context).status,
+        cw_competition::state::CompetitionStatus::Inactive
+    );
+
+    // The tied teams share the first two positions of the 2550 pool, and the last team keeps its 10%
+    let mut payouts = vec![];
+    for user in &users {
+        context
+            .app
+            .execute_contract(
+                user.clone(),
+                escrow.clone(),
+                &arena_escrow::msg::ExecuteMsg::Withdraw {
+                    cw20_msg: None,
+                    cw721_msg: None,
+                },
+                &[],
+            )
+            .unwrap();
+        let balance = context
+            .app
+            .wrap()
+            .query_balance(user.to_string(), "juno")
+            .unwrap();
+        payouts.push((user.to_string(), balance.amount.u128()));
+    }
+    for (user, payout) in payouts {
+        if tied_teams.contains(&user) {
+            assert!(payout == 1_147 || payout == 1_148);
+        } else {
+            assert_eq!(payout, 255);
+        }
+    }
+}
+
+#[test]
+fn test_circle_method_pivot() {
+    let mut app = get_app();
+    let users: Vec<Addr> = (1..=3)
+        .map(|i| app.api().addr_make(&format!("user{}", i)))
+        .collect();
+    let admin = app.api().addr_make(ADMIN);
+
+    let core_context = setup_core_context(
+        &mut app,
+        vec![Member {
+            addr: admin.to_string(),
+            weight: 1u64,
+        }],
+    );
+    let league_context = setup_league_context(&mut app, &core_context);
+    let mut context = Context {
+        app,
+        core: core_context,
+        league: league_context,
+    };
+
+    // With 3 teams, one team rests each round
+    let mut bye_sequences = vec![];
+    for pivot in [0u64, 1u64] {
+        let starting_height = context.app.block_info().height;
+        let league_id = create_competition(
+            &mut context,
+            Expiration::AtHeight(starting_height + 100),
+            users
+                .iter()
+                .map(|x| Member {
+                    addr: x.to_string(),
+                    weight: 1u64,
+                })
+                .collect(),
+            None,
+            Duration::Height(10u64),
+            None,
+            Some(Uint64::from(pivot)),
+        );
+
+        let mut byes = vec![];
+        for round_number in 1..=3u64 {
+            let round: RoundResponse = context
+                .app
+                .wrap()
+                .query_wasm_smart(
+                    context.league.league_module_addr.clone(),
+                    &QueryMsg::QueryExtension {
+                        msg: QueryExt::Round {
+                            league_id,
+                            round_number: Uint64::from(round_number),
+                        },
+                    },
+                )
+                .unwrap();
+            assert_eq!(round.matches.len(), 1);
+
+            let bye = users
+                .iter()
+                .find(|x| **x != round.matches[0].team_1 && **x != round.matches[0].team_2)
+                .unwrap();
+            byes.push(bye.clone());
+        }
+        bye_sequences.push(byes);
+    }
+
+    assert_eq!(
+        bye_sequences[0],
+        vec![users[0].clone(), users[1].clone(), users[2].clone()]
+    );
+    assert_eq!(
+        bye_sequences[1],
+        vec![users[1].clone(), users[0].clone(), users[2].clone()]
+    );
+}
+
+#[test]
+fn test_match_bonus_points() {
+    let mut app = get_app();
+    let users: Vec<Addr> = (1..=4)
+        .map(|i| app.api().addr_make(&format!("user{}", i)))
+        .collect();
+    let admin = app.api().addr_make(ADMIN);
+
+    let core_context = setup_core_context(
+        &mut app,
+        vec![Member {
+            addr: admin.to_string(),
+            weight: 1u64,
+        }],
+    );
+    let league_context = setup_league_context(&mut app, &core_context);
+    let mut context = Context {
+        app,
+        core: core_context,
+        league: league_context,
+    };
+
+    let starting_height = context.app.block_info().height;
+    let league_id = create_competition(
+        &mut context,
+        Expiration::AtHeight(starting_height + 100),
+        users
+            .iter()
+            .map(|x| Member {
+                addr: x.to_string(),
+                weight: 1u64,
+            })
+            .collect(),
+        None,
+        Duration::Height(10u64),
+        None,
+        None,
+    );
+    context.app.update_block(|x| x.height += 10);
+
+    let process_match = |match_result: MatchResult| dao_proposal_sudo::msg::ExecuteMsg::Execute {
+        msgs: vec![WasmMsg::Execute {
+            contract_addr: context.league.league_module_addr.to_string(),
+            msg: to_json_binary(&ExecuteMsg::Extension {
+                msg: ExecuteExt::ProcessMatch {
+                    league_id,
+                    round_number: Uint64::one(),
+                    match_results: vec![match_result],
+                    evidence: None,
+                },
+            })
+            .unwrap(),
+            funds: vec![],
+        }
+        .into()],
+    };
+
+    // Bonus points cannot be applied to an unresolved match
+    let msg = process_match(MatchResult {
+        match_number: Uint128::one(),
+        result: None,
+        bonus: Some(MatchBonus {
+            team_1: Uint128::zero(),
+            team_2: Uint128::one(),
+        }),
+    });
+    let result = context.app.execute_contract(
+        admin.clone(),
+        context.core.sudo_proposal_addr.clone(),
+        &msg,
+        &[],
+    );
+    assert!(result.is_err());
+    assert_eq!(
+        result.unwrap_err().root_cause().to_string(),
+        "Generic error: Bonus points cannot be applied to an unresolved match"
+    );
+
+    // user1 beats user4, who earns a bonus point for the close loss
+    let msg = process_match(MatchResult {
+        match_number: Uint128::one(),
+        result: Some(Result::Team1),
+        bonus: Some(MatchBonus {
+            team_1: Uint128::zero(),
+            team_2: Uint128::one(),
+        }),
+    });
+    let result = context.app.execute_contract(
+        admin.clone(),
+        context.core.sudo_proposal_addr.clone(),
+        &msg,
+        &[],
+    );
+    assert!(result.is_ok());
+
+    let round: RoundResponse = context
+        .app
+        .wrap()
+        .query_wasm_smart(
+            context.league.league_module_addr.clone(),
+            &QueryMsg::QueryExtension {
+                msg: QueryExt::Round {
+                    league_id,
+                    round_number: Uint64::one(),
+                },
+            },
+        )
+        .unwrap();
+    let played = round
+        .matches
+        .iter()
+        .find(|x| x.match_number == Uint128::one())
+        .unwrap();
+    assert_eq!(
+        played.bonus,
+        Some(MatchBonus {
+            team_1: Uint128::zero(),
+            team_2: Uint128::one(),
+        })
+    );
+
+    let standings: Vec<TeamStanding> = context
+        .app
+        .wrap()
+        .query_wasm_smart(
+            context.league.league_module_addr.clone(),
+            &QueryMsg::QueryExtension {
+                msg: QueryExt::Standings {
+                    league_id,
+                    round: None,
+                },
+            },
+        )
+        .unwrap();
+
+    let winner = standings.iter().find(|x| x.member == users[0]).unwrap();
+    assert_eq!(winner.points, Uint128::from(3u128));
+
+    let loser = standings.iter().find(|x| x.member == users[3]).unwrap();
+    assert_eq!(loser.losses, Uint64::one());
+    assert_eq!(loser.points, Uint128::one());
+}
+
+#[test]
+fn test_league_teams() {
+    let mut app = get_app();
+    let users: Vec<Addr> = (1..=5)
+        .map(|i| app.api().addr_make(&format!("user{}", i)))
+        .collect();
+    let admin = app.api().addr_make(ADMIN);
+
+    let core_context = setup_core_context(
+        &mut app,
+        vec![Member {
+            addr: admin.to_string(),
+            weight: 1u64,
+        }],
+    );
+    let league_context = setup_league_context(&mut app, &core_context);
+    let mut context = Context {
+        app,
+        core: core_context,
+        league: league_context,
+    };
+
+    let starting_height = context.app.block_info().height;
+    let league_id = create_competition(
+        &mut context,
+        Expiration::AtHeight(starting_height + 100),
+        users
+            .iter()
+            .map(|x| Member {
+                addr: x.to_string(),
+                weight: 1u64,
+            })
+            .collect(),
+        None,
+        Duration::Height(10u64),
+        None,
+        None,
+    );
+
+    // The teams are stored as provided, including the one sitting out with a bye
+    let teams: Vec<Addr> = context
+        .app
+        .wrap()
+        .query_wasm_smart(
+            context.league.league_module_addr.clone(),
+            &QueryMsg::QueryExtension {
+                msg: QueryExt::Teams { league_id },
+            },
+        )
+        .unwrap();
+    assert_eq!(teams, users);
+}
+
+#[test]
+fn test_void_match() {
+    let mut app = get_app();
+    let users: Vec<Addr> = (1..=4)
+        .map(|i| app.api().addr_make(&format!("user{}", i)))
+        .collect();
+    let admin = app.api().addr_make(ADMIN);
+
+    let core_context = setup_core_context(
+        &mut app,
+        vec![Member {
+            addr: admin.to_string(),
+            weight: 1u64,
+        }],
+    );
+    let league_context = setup_league_context(&mut app, &core_context);
+    let mut context = Context {
+        app,
+        core: core_context,
+        league: league_context,
+    };
+
+    let starting_height = context.app.block_info().height;
+    let league_id = create_competition(
+        &mut context,
+        Expiration::AtHeight(starting_height + 100),
+        users
+            .iter()
+            .map(|x| Member {
+                addr: x.to_string(),
+                weight: 1u64,
+            })
+            .collect(),
+        None,
+        Duration::Height(10u64),
+        None,
+        None,
+    );
+    context.app.update_block(|x| x.height += 10);
+
+    let query_standings = |context: &Context| -> Vec<TeamStanding> {
+        context
+            .app
+            .wrap()
+            .query_wasm_smart(
+                context.league.league_module_addr.clone(),
+                &QueryMsg::QueryExtension {
+                    msg: QueryExt::Standings {
+                        league_id,
+                        round: None,
+                    },
+                },
+            )
+            .unwrap()
+    };
+    let execute_ext = |msg: ExecuteExt| dao_proposal_sudo::msg::ExecuteMsg::Execute {
+        msgs: vec![WasmMsg::Execute {
+            contract_addr: context.league.league_module_addr.to_string(),
+            msg: to_json_binary(&ExecuteMsg::Extension { msg }).unwrap(),
+            funds: vec![],
+        }
+        .into()],
+    };
+    let void_msg = ExecuteExt::VoidMatch {
+        league_id,
+        round_number: Uint64::one(),
+        match_number: Uint128::one(),
+    };
+    let before = query_standings(&context);
+
+    // user1 beats user4 with a bonus point for the loser
+    let result = context.app.execute_contract(
+        admin.clone(),
+        context.core.sudo_proposal_addr.clone(),
+        &execute_ext(ExecuteExt::ProcessMatch {
+            league_id,
+            round_number: Uint64::one(),
+            match_results: vec![MatchResult {
+                match_number: Uint128::one(),
+                result: Some(Result::Team1),
+                bonus: Some(MatchBonus {
+                    team_1: Uint128::zero(),
+                    team_2: Uint128::one(),
+                }),
+            }],
+            evidence: Some("ipfs://round1".to_string()),
+        }),
+        &[],
+    );
+    assert!(result.is_ok());
+    assert_ne!(query_standings(&context), before);
+
+    // Only the admin dao can void a match
+    let result = context.app.execute_contract(
+        users[0].clone(),
+        context.league.league_module_addr.clone(),
+        &ExecuteMsg::Extension {
+            msg: void_msg.clone(),
+        },
+        &[],
+    );
+    assert!(result.is_err());
+
+    let result = context.app.execute_contract(
+        admin.clone(),
+        context.core.sudo_proposal_addr.clone(),
+        &execute_ext(void_msg.clone()),
+        &[],
+    );
+    assert!(result.is_ok());
+
+    // The standings return to their state before the match
+    assert_eq!(query_standings(&context), before);
+
+    let evidence: Option<String> = context
+        .app
+        .wrap()
+        .query_wasm_smart(
+            context.league.league_module_addr.clone(),
+            &QueryMsg::QueryExtension {
+                msg: QueryExt::MatchEvidence {
+                    league_id,
+                    round_number: Uint64::one(),
+                    match_number: Uint128::one(),
+                },
+            },
+        )
+        .unwrap();
+    assert_eq!(evidence, None);
+
+    // A voided match has no result left to void
+    let result = context.app.execute_contract(
+        admin.clone(),
+        context.core.sudo_proposal_addr.clone(),
+        &execute_ext(void_msg),
+        &[],
+    );
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_void_match_before_settling() {
+    let mut app = get_app();
+    let users: Vec<Addr> = (1..=4)
+        .map(|i| app.api().addr_make(&format!("user{}", i)))
+        .collect();
+    let admin = app.api().addr_make(ADMIN);
+    let dues = "1000juno";
+
+    set_balances(
+        &mut app,
+        users
+            .iter()
+            .map(|x| (x.clone(), Coins::from_str(dues).unwrap()))
+            .collect(),
+    );
+    let core_context = setup_core_context(
+        &mut app,
+        vec![Member {
+            addr: admin.to_string(),
+            weight: 1u64,
+        }],
+    );
+    let league_context = setup_league_context(&mut app, &core_context);
+    let mut context = Context {
+        app,
+        core: core_context,
+        league: league_context,
+    };
+
+    let starting_height = context.app.block_info().height;
+    let league_id = create_competition(
+        &mut context,
+        Expiration::AtHeight(starting_height + 1000),
+        users
+            .iter()
+            .map(|x| Member {
+                addr: x.to_string(),
+                weight: 1u64,
+            })
+            .collect(),
+        Some(
+            users
+                .iter()
+                .map(|x| MemberBalanceUnchecked {
+                    addr: x.to_string(),
+                    balance: cw_balance::BalanceUnchecked {
+                        native: vec![Coin::from_str(dues).unwrap()],
+                        cw20: vec![],
+                        cw721: vec![],
+                    },
+                })
+                .collect(),
+        ),
+        Duration::Height(10u64),
+        None,
+        None,
+    );
+    let query_league = |context: &Context| -> LeagueResponse {
+        context
+            .app
+            .wrap()
+            .query_wasm_smart(
+                context.league.league_module_addr.clone(),
+                &QueryMsg::Competition {
+                    competition_id: league_id,
+                },
+            )
+            .unwrap()
+    };
+    let escrow = query_league(&context).escrow.unwrap();
+
+    // Fund the escrow to activate the league
+    for user in &users {
+        context
+            .app
+            .execute_contract(
+                user.clone(),
+                escrow.clone(),
+                &arena_escrow::msg::ExecuteMsg::ReceiveNative {},
+                &[Coin::from_str(dues).unwrap()],
+            )
+            .unwrap();
+    }
+    context.app.update_block(|x| x.height += 100);
+
+    let execute_ext = |msg: ExecuteExt| dao_proposal_sudo::msg::ExecuteMsg::Execute {
+        msgs: vec![WasmMsg::Execute {
+            contract_addr: context.league.league_module_addr.to_string(),
+            msg: to_json_binary(&ExecuteMsg::Extension { msg }).unwrap(),
+            funds: vec![],
+        }
+        .into()],
+    };
+
+    // Each round's matches are won by the team listed first among the users
+    let match_results = |context: &Context, round_number: Uint64| -> Vec<MatchResult> {
+        let round: RoundResponse = context
+            .app
+            .wrap()
+            .query_wasm_smart(
+                context.league.league_module_addr.clone(),
+                &QueryMsg::QueryExtension {
+                    msg: QueryExt::Round {
+                        league_id,
+                        round_number,
+                    },
+                },
+            )
+            .unwrap();
+        let position = |team: &Addr| users.iter().position(|x| x == team).unwrap();
+
+        round
+            .matches
+            .iter()
+            .map(|m| MatchResult {
+                match_number: m.match_number,
+                result: Some(if position(&m.team_1) < position(&m.team_2) {
+                    Result::Team1
+                } else {
+                    Result::Team2
+                }),
+                bonus: None,
+            })
+            .collect()
+    };
+    let rounds = query_league(&context).extension.rounds.u64();
+    let mut results: Vec<(Uint64, MatchResult)> = (1..=rounds)
+        .flat_map(|round_number| {
+            let round_number = Uint64::from(round_number);
+            match_results(&context, round_number)
+                .into_iter()
+                .map(move |x| (round_number, x))
+        })
+        .collect();
+    let last = results.pop().unwrap();
+
+    // Report the first match, then void it and report it again
+    let (round_number, first) = results[0].clone();
+    let void_msg = ExecuteExt::VoidMatch {
+        league_id,
+        round_number,
+        match_number: first.match_number,
+    };
+    for msg in [None, Some(void_msg), None] {
+        let msg = msg.unwrap_or(ExecuteExt::ProcessMatch {
+            league_id,
+            round_number,
+            match_results: vec![first.clone()],
+            evidence: None,
+        });
+        context
+            .app
+            .execute_contract(
+                admin.clone(),
+                context.core.sudo_proposal_addr.clone(),
+                &execute_ext(msg),
+                &[],
+            )
+            .unwrap();
+    }
+
+    // Report every other match except the last
+    for (round_number, match_result) in results.into_iter().skip(1) {
+        context
+            .app
+            .execute_contract(
+                admin.clone(),
+                context.core.sudo_proposal_addr.clone(),
+                &execute_ext(ExecuteExt::ProcessMatch {
+                    league_id,
+                    round_number,
+                    match_results: vec![match_result],
+                    evidence: None,
+                }),
+                &[],
+            )
+            .unwrap();
+    }
+
+    // The re-reported match is only counted once, so the league has not settled early
+    let league = query_league(&context);
+    assert_eq!(
+        league.extension.processed_matches,
+        league.extension.matches - Uint128::one()
+    );
+    assert_eq!(
+        league.status,
+        cw_competition::state::CompetitionStatus::Active
+    );
+
+    // The last match settles the league
+    let (round_number, match_result) = last;
+    context
+        .app
+        .execute_contract(
+            admin.clone(),
+            context.core.sudo_proposal_addr.clone(),
+            &execute_ext(ExecuteExt::ProcessMatch {
+                league_id,
+                round_number,
+                match_results: vec![match_result],
+                evidence: None,
+            }),
+            &[],
+        )
+        .unwrap();
+    assert_eq!(
+        query_league(&context).status,
+        cw_competition::state::CompetitionStatus::Inactive
+    );
+}
+
+#[test]
+fn test_settle_league() {
+    let mut app = get_app();
+    let users: Vec<Addr> = (1..=4)
+        .map(|i| app.api().addr_make(&format!("user{}", i)))
+        .collect();
+    let admin = app.api().addr_make(ADMIN);
+    let dues = "1000juno";
+
+    set_balances(
+        &mut app,
+        users
+            .iter()
+            .map(|x| (x.clone(), Coins::from_str(dues).unwrap()))
+            .collect(),
+    );
+    let core_context = setup_core_context(
+        &mut app,
+        vec![Member {
+            addr: admin.to_string(),
+            weight: 1u64,
+        }],
+    );
+    let league_context = setup_league_context(&mut app, &core_context);
+    let mut context = Context {
+        app,
+        core: core_context,
+        league: league_context,
+    };
+
+    let starting_height = context.app.block_info().height;
+    let league_id = create_competition(
+        &mut context,
+        Expiration::AtHeight(starting_height + 1000),
+        users
+            .iter()
+            .map(|x| Member {
+                addr: x.to_string(),
+                weight: 1u64,
+            })
+            .collect(),
+        Some(
+            users
+                .iter()
+                .map(|x| MemberBalanceUnchecked {
+                    addr: x.to_string(),
+                    balance: cw_balance::BalanceUnchecked {
+                        native: vec![Coin::from_str(dues).unwrap()],
+                        cw20: vec![],
+                        cw721: vec![],
+                    },
+                })
+                .collect(),
+        ),
+        Duration::Height(10u64),
+        None,
+        None,
+    );
+    let league: LeagueResponse = context
+        .app
+        .wrap()
+        .query_wasm_smart(
+            context.league.league_module_addr.clone(),
+            &QueryMsg::Competition {
+                competition_id: league_id,
+            },
+        )
+        .unwrap();
+    let escrow = league.escrow.unwrap();
+
+    // Fund the escrow to activate the league
+    for user in &users {
+        context
+            .app
+            .execute_contract(
+                user.clone(),
+                escrow.clone(),
+                &arena_escrow::msg::ExecuteMsg::ReceiveNative {},
+                &[Coin::from_str(dues).unwrap()],
+            )
+            .unwrap();
+    }
+    context.app.update_block(|x| x.height += 100);
+
+    let execute_ext = |msg: ExecuteExt| dao_proposal_sudo::msg::ExecuteMsg::Execute {
+        msgs: vec![WasmMsg::Execute {
+            contract_addr: context.league.league_module_addr.to_string(),
+            msg: to_json_binary(&ExecuteMsg::Extension { msg }).unwrap(),
+            funds: vec![],
+        }
+        .into()],
+    };
+    let settle_msg = ExecuteExt::SettleLeague {
+        league_id,
+        payout_curve: vec![
+            Decimal::percent(50),
+            Decimal::percent(30),
+            Decimal::percent(20),
+        ],
+    };
+
+    // A league with its own distribution is distributed when its last match is processed
+    let result = context.app.execute_contract(
+        admin.clone(),
+        context.core.sudo_proposal_addr.clone(),
+        &execute_ext(settle_msg.clone()),
+        &[],
+    );
+    assert_eq!(
+        result.unwrap_err().root_cause().to_string(),
+        "Generic error: The league is distributed when its last match is processed"
+    );
+
+    // Clearing the distribution leaves the league to be settled by the payout curve,
+    // so processing the final match below does not distribute the escrow
+    let result = context.app.execute_contract(
+        admin.clone(),
+        context.core.sudo_proposal_addr.clone(),
+        &execute_ext(ExecuteExt::UpdateDistribution {
+            league_id,
+            distribution: vec![],
+        }),
+        &[],
+    );
+    assert!(result.is_ok());
+
+    // Resolve every match in favor of the team listed first, leaving a clear final table
+    for round_number in 1..=league.extension.rounds.u64() {
+        let round_number = Uint64::from(round_number);
+
+        // The league cannot settle while matches are unresolved
+        let result = context.app.execute_contract(
+            admin.clone(),
+            context.core.sudo_proposal_addr.clone(),
+            &execute_ext(settle_msg.clone()),
+            &[],
+        );
+        assert!(result.is_err());
+
+        let round: RoundResponse = context
+            .app
+            .wrap()
+            .query_wasm_smart(
+                context.league.league_module_addr.clone(),
+                &QueryMsg::QueryExtension {
+                    msg: QueryExt::Round {
+                        league_id,
+                        round_number,
+                    },
+                },
+            )
+            .unwrap();
+        let position = |team: &Addr| users.iter().position(|x| x == team).unwrap();
+        let match_results = round
+            .matches
+            .iter()
+            .map(|m| MatchResult {
+                match_number: m.match_number,
+                result: Some(if position(&m.team_1) < position(&m.team_2) {
+                    Result::Team1
+                } else {
+                    Result::Team2
+                }),
+                bonus: None,
+            })
+            .collect();
+
+        let result = context.app.execute_contract(
+            admin.clone(),
+            context.core.sudo_proposal_addr.clone(),
+            &execute_ext(ExecuteExt::ProcessMatch {
+                league_id,
+                round_number,
+                match_results,
+                evidence: None,
+            }),
+            &[],
+        );
+        assert!(result.is_ok());
+    }
+
+    let result = context.app.execute_contract(
+        admin.clone(),
+        context.core.sudo_proposal_addr.clone(),
+        &execute_ext(settle_msg),
+        &[],
+    );
+    assert!(result.is_ok());
+
+    // The 4000juno pool is taxed 15% before being split 50/30/20
+    for (user, amount) in users.iter().zip(["1700juno", "1020juno", "680juno"]) {
+        let balance: Option<cw_balance::BalanceVerified> = context
+            .app
+            .wrap()
+            .query_wasm_smart(
+                escrow.clone(),
+                &arena_escrow::msg::QueryMsg::Balance {
+                    addr: user.to_string(),
+                },
+            )
+            .unwrap();
+        assert_eq!(
+            balance.unwrap().native,
+            vec![Coin::from_str(amount).unwrap()]
+        );
+    }
+    let balance: Option<cw_balance::BalanceVerified> = context
+        .app
+        .wrap()
+        .query_wasm_smart(
+            escrow,
+            &arena_escrow::msg::QueryMsg::Balance {
+                addr: users[3].to_string(),
+            },
+        )
+        .unwrap();
+    assert!(balance.is_none());
+
+    let league: LeagueResponse = context
+        .app
+        .wrap()
+        .query_wasm_smart(
+            context.league.league_module_addr.clone(),
+            &QueryMsg::Competition {
+                competition_id: league_id,
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        league.status,
+        cw_competition::state::CompetitionStatus::Inactive
+    );
+}
+
+#[test]
+fn test_shuffle_seed() {
+    let mut app = get_app();
+    let users: Vec<Addr> = (1..=8)
+        .map(|i| app.api().addr_make(&format!("user{}", i)))
+        .collect();
+    let admin = app.api().addr_make(ADMIN);
+
+    let core_context = setup_core_context(
+        &mut app,
+        vec![Member {
+            addr: admin.to_string(),
+            weight: 1u64,
+        }],
+    );
+    let league_context = setup_league_context(&mut app, &core_context);
+    let mut context = Context {
+        app,
+        core: core_context,
+        league: league_context,
+    };
+    let starting_height = context.app.block_info().height;
+
+    // Returns every round's matches for a league created with the seed
+    let mut schedule = |shuffle_seed: Option<Uint64>| -> Vec<RoundResponse> {
+        let result = context
+            .app
+            .execute_contract(
+                admin.clone(),
+                context.league.league_module_addr.clone(),
+                &ExecuteMsg::CreateCompetition {
+                    category_id: Some(Uint128::one()),
+                    host: ModuleInfo::Existing {
+                        addr: admin.to_string(),
+                    },
+                    escrow: None,
+                    name: "This is a competition name".to_string(),
+                    description: "This is a description".to_string(),
+                    expiration: Expiration::AtHeight(starting_height + 100),
+                    rules: vec!["Rule 1".to_string()],
+                    rulesets: vec![],
+                    result_authority: None,
+                    instantiate_extension: CompetitionInstantiateExt {
+                        teams: users.iter().map(|x| x.to_string()).collect(),
+                        round_duration: Duration::Height(10u64),
+                        match_win_points: Uint128::from(3u128),
+                        match_draw_points: Uint128::one(),
+                        match_lose_points: Uint128::zero(),
+                        distribution: vec![Decimal::one()],
+                        schedule_algorithm: None,
+                        pivot: None,
+                        shuffle_seed,
+                        max_extensions: None,
+                    },
+                },
+                &[],
+            )
+            .unwrap();
+        let league_id =
+            Uint128::from_str(&get_attr_value(&result, "competition_id").unwrap()).unwrap();
+
+        (1..=7u64)
+            .map(|round_number| {
+                context
+                    .app
+                    .wrap()
+                    .query_wasm_smart(
+                        context.league.league_module_addr.clone(),
+                        &QueryMsg::QueryExtension {
+                            msg: QueryExt::Round {
+                                league_id,
+                                round_number: Uint64::from(round_number),
+                            },
+                        },
+                    )
+                    .unwrap()
+            })
+            .collect()
+    };
+
+    let unshuffled = schedule(None);
+    let seeded = schedule(Some(Uint64::from(7u64)));
+    let reseeded = schedule(Some(Uint64::from(7u64)));
+    let other_seed = schedule(Some(Uint64::from(8u64)));
+
+    // The same seed reproduces the schedule, while a different seed reorders it
+    assert_eq!(seeded, reseeded);
+    assert_ne!(seeded, other_seed);
+    assert_ne!(seeded, unshuffled);
+
+    // Shuffling only reorders the pairings within each round
+    let pairings = |rounds: &Vec<RoundResponse>| -> Vec<BTreeSet<(Addr, Addr)>> {
+        rounds
+            .iter()
+            .map(|round| {
+                round
+                    .matches
+                    .iter()
+                    .map(|x| (x.team_1.clone(), x.team_2.clone()))
+                    .collect()
+            })
+            .collect()
+    };
+    assert_eq!(pairings(&seeded), pairings(&unshuffled));
+}
+
+#[test]
+fn test_max_extensions() {
+    let mut app = get_app();
+    let users = [
+        app.api().addr_make("user1"),
+        app.api().addr_make("user2"),
+        app.api().addr_make("user3"),
+    ];
+    let admin = app.api().addr_make(ADMIN);
+
+    let core_context = setup_core_context(
+        &mut app,
+        vec![Member {
+            addr: admin.to_string(),
+            weight: 1u64,
+        }],
+    );
+    let league_context = setup_league_context(&mut app, &core_context);
+    let mut context = Context {
+        app,
+        core: core_context,
+        league: league_context,
+    };
+
+    // A 3 team league with room for 2 more rounds, which may be extended once
+    let starting_height = context.app.block_info().height;
+    let result = context
+        .app
+        .execute_contract(
+            admin.clone(),
+            context.league.league_module_addr.clone(),
+            &ExecuteMsg::CreateCompetition {
+                category_id: Some(Uint128::one()),
+                host: ModuleInfo::Existing {
+                    addr: admin.to_string(),
+                },
+                escrow: None,
+                name: "This is a competition name".to_string(),
+                description: "This is a description".to_string(),
+                expiration: Expiration::AtHeight(starting_height + 60),
+                rules: vec!["Rule 1".to_string()],
+                rulesets: vec![],
+                result_authority: None,
+                instantiate_extension: CompetitionInstantiateExt {
+                    teams: users.iter().map(|x| x.to_string()).collect(),
+                    round_duration: Duration::Height(10u64),
+                    match_win_points: Uint128::from(3u128),
+                    match_draw_points: Uint128::one(),
+                    match_lose_points: Uint128::zero(),
+                    distribution: vec![Decimal::one()],
+                    schedule_algorithm: None,
+                    pivot: None,
+                    shuffle_seed: None,
+                    max_extensions: Some(1),
+                },
+            },
+            &[],
+        )
+        .unwrap();
+    let league_id = Uint128::from_str(&get_attr_value(&result, "competition_id").unwrap()).unwrap();
+
+    let mut add_rounds = |count: u64| {
+        context.app.execute_contract(
+            admin.clone(),
+            context.league.league_module_addr.clone(),
+            &ExecuteMsg::Extension {
+                msg: ExecuteExt::AddRounds {
+                    league_id,
+                    teams_subset: vec![users[0].to_string(), users[1].to_string()],
+                    round_duration: Duration::Height(10u64),
+                    count: Uint64::from(count),
+                },
+            },
+            &[],
+        )
+    };
+
+    // Rounds within the current expiration do not use the allowance
+    assert!(add_rounds(1).is_ok());
+    assert!(add_rounds(1).is_ok());
+
+    // The first extension is allowed
+    assert!(add_rounds(4).is_ok());
+
+    // The next extension is rejected
+    let result = add_rounds(5);
+    assert_eq!(
+        result.unwrap_err().root_cause().to_string(),
+        "ExtensionLimitReached"
+    );
+
+    let league: LeagueResponse = context
+        .app
+        .wrap()
+        .query_wasm_smart(
+            context.league.league_module_addr.clone(),
+            &QueryMsg::Competition {
+                competition_id: league_id,
+            },
+        )
+        .unwrap();
+    assert_eq!(league.extension.max_extensions, Some(1));
+    assert_eq!(league.extension.rounds, Uint64::from(9u64));
+}
+
+#[test]
+fn test_projected_payout() {
+    let mut app = get_app();
+    let users: Vec<Addr> = (1..=4)
+        .map(|i| app.api().addr_make(&format!("user{}", i)))
+        .collect();
+    let admin = app.api().addr_make(ADMIN);
+
+    let core_context = setup_core_context(
+        &mut app,
+        vec![Member {
+            addr: admin.to_string(),
+            weight: 1u64,
+        }],
+    );
+    let league_context = setup_league_context(&mut app, &core_context);
+    let mut context = Context {
+        app,
+        core: core_context,
+        league: league_context,
+    };
+
+    let starting_height = context.app.block_info().height;
+    let league_id = create_competition(
+        &mut context,
+        Expiration::AtHeight(starting_height + 100),
+        users
+            .iter()
+            .map(|x| Member {
+                addr: x.to_string(),
+                weight: 1u64,
+            })
+            .collect(),
+        None,
+        Duration::Height(10u64),
+        None,
+        None,
+    );
+
+    let query_projected_payout =
+        |context: &Context, payout_curve: Vec<Decimal>| -> StdResult<Distribution<String>> {
+            context.app.wrap().query_wasm_smart(
+                context.league.league_module_addr.clone(),
+                &QueryMsg::QueryExtension {
+                    msg: QueryExt::ProjectedPayout {
+                        league_id,
+                        payout_curve,
+                    },
+                },
+            )
+        };
+    let payout_curve = vec![Decimal::percent(60), Decimal::percent(40)];
+
+    // The payout curve can't pay more teams than the league has
+    assert!(query_projected_payout(&context, vec![Decimal::percent(20); 5]).is_err());
+
+    // Only the first round is resolved, with the team listed first winning each match
+    context.app.update_block(|x| x.height += 10);
+    let round: RoundResponse = context
+        .app
+        .wrap()
+        .query_wasm_smart(
+            context.league.league_module_addr.clone(),
+            &QueryMsg::QueryExtension {
+                msg: QueryExt::Round {
+                    league_id,
+                    round_number: Uint64::one(),
+                },
+            },
+        )
+        .unwrap();
+    let position = |team: &Addr| users.iter().position(|x| x == team).unwrap();
+    let winners: BTreeSet<String> = round
+        .matches
+        .iter()
+        .map(|m| {
+            if position(&m.team_1) < position(&m.team_2) {
+                m.team_1.to_string()
+            } else {
+                m.team_2.to_string()
+            }
+        })
+        .collect();
+    let result = context.app.execute_contract(
+        admin.clone(),
+        context.core.sudo_proposal_addr.clone(),
+        &dao_proposal_sudo::msg::ExecuteMsg::Execute {
+            msgs: vec![WasmMsg::Execute {
+                contract_addr: context.league.league_module_addr.to_string(),
+                msg: to_json_binary(&ExecuteMsg::Extension {
+                    msg: ExecuteExt::ProcessMatch {
+                        league_id,
+                        round_number: Uint64::one(),
+                        match_results: round
+                            .matches
+                            .iter()
+                            .map(|m| MatchResult {
+                                match_number: m.match_number,
+                                result: Some(if position(&m.team_1) < position(&m.team_2) {
+                                    Result::Team1
+                                } else {
+                                    Result::Team2
+                                }),
+                                bonus: None,
+                            })
+                            .collect(),
+                        evidence: None,
+                    },
+                })
+                .unwrap(),
+                funds: vec![],
+            }
+            .into()],
+        },
+        &[],
+    );
+    assert!(result.is_ok());
+
+    // The round 1 winners lead the table level on points, so they split the paid positions
+    let projection = query_projected_payout(&context, payout_curve.clone()).unwrap();
+    assert_eq!(projection.member_percentages.len(), 2);
+    assert_eq!(
+        projection
+            .member_percentages
+            .iter()
+            .map(|x| x.addr.clone())
+            .collect::<BTreeSet<_>>(),
+        winners
+    );
+    assert_eq!(
+        projection
+            .member_percentages
+            .iter()
+            .map(|x| x.percentage)
+            .collect::<Vec<_>>(),
+        vec![Decimal::percent(50); 2]
+    );
+    assert_eq!(
+        projection.remainder_addr,
+        projection.member_percentages[0].addr
+    );
+
+    // A tie across a payout boundary shares the positions it spans
+    let projection = query_projected_payout(
+        &context,
+        vec![
+            Decimal::percent(50),
+            Decimal::percent(30),
+            Decimal::percent(20),
+        ],
+    )
+    .unwrap();
+    assert_eq!(projection.member_percentages.len(), 4);
+    for member_percentage in &projection.member_percentages {
+        let expected = if winners.contains(&member_percentage.addr) {
+            Decimal::percent(40)
+        } else {
+            Decimal::percent(10)
+        };
+        assert_eq!(member_percentage.percentage, expected);
+    }
+}
+
+#[test]
+fn test_remaining_fixtures() {
+    let mut app = get_app();
+    let users: Vec<Addr> = (1..=4)
+        .map(|i| app.api().addr_make(&format!("user{}", i)))
+        .collect();
+    let admin = app.api().addr_make(ADMIN);
+
+    let core_context = setup_core_context(
+        &mut app,
+        vec![Member {
+            addr: admin.to_string(),
+            weight: 1u64,
+        }],
+    );
+    let league_context = setup_league_context(&mut app, &core_context);
+    let mut context = Context {
+        app,
+        core: core_context,
+        league: league_context,
+    };
+
+    let starting_height = context.app.block_info().height;
+    let league_id = create_competition(
+        &mut context,
+        Expiration::AtHeight(starting_height + 100),
+        users
+            .iter()
+            .map(|x| Member {
+                addr: x.to_string(),
+                weight: 1u64,
+            })
+            .collect(),
+        None,
+        Duration::Height(10u64),
+        None,
+        None,
+    );
+
+    let query_remaining_fixtures = |context: &Context| -> Vec<RoundResponse> {
+        context
+            .app
+            .wrap()
+            .query_wasm_smart(
+                context.league.league_module_addr.clone(),
+                &QueryMsg::QueryExtension {
+                    msg: QueryExt::RemainingFixtures { league_id },
+                },
+            )
+            .unwrap()
+    };
+    let process_draws = |context: &mut Context, round_number: u64, match_numbers: &[Uint128]| {
+        let result = context.app.execute_contract(
+            admin.clone(),
+            context.core.sudo_proposal_addr.clone(),
+            &dao_proposal_sudo::msg::ExecuteMsg::Execute {
+                msgs: vec![WasmMsg::Execute {
+                    contract_addr: context.league.league_module_addr.to_string(),
+                    msg: to_json_binary(&ExecuteMsg::Extension {
+                        msg: ExecuteExt::ProcessMatch {
+                            league_id,
+                            round_number: Uint64::from(round_number),
+                            match_results: match_numbers
+                                .iter()
+                                .map(|match_number| MatchResult {
+                                    match_number: *match_number,
+                                    result: Some(Result::Draw),
+                                    bonus: None,
+                                })
+                                .collect(),
+                            evidence: None,
+                        },
+                    })
+                    .unwrap(),
+                    funds: vec![],
+                }
+                .into()],
+            },
+            &[],
+        );
+        assert!(result.is_ok());
+    };
+
+    // Every round is remaining before any match is played
+    let fixtures = query_remaining_fixtures(&context);
+    assert_eq!(fixtures.len(), 3);
+    assert!(fixtures.iter().all(|x| x.matches.len() == 2));
+
+    // Round 1 is played out and round 2 is half played
+    context.app.update_block(|x| x.height += 20);
+    let round_1_matches: Vec<Uint128> =
+        fixtures[0].matches.iter().map(|m| m.match_number).collect();
+    let round_2_played = fixtures[1].matches[0].match_number;
+    let round_2_unplayed = fixtures[1].matches[1].match_number;
+    process_draws(&mut context, 1, &round_1_matches);
+    process_draws(&mut context, 2, &[round_2_played]);
+
+    // Only round 2's unplayed match and all of round 3 remain
+    let remaining = query_remaining_fixtures(&context);
+    assert_eq!(remaining.len(), 2);
+    assert_eq!(remaining[0].round_number, Uint64::from(2u64));
+    assert_eq!(remaining[0].expiration, fixtures[1].expiration);
+    assert_eq!(
+        remaining[0]
+            .matches
+            .iter()
+            .map(|m| m.match_number)
+            .collect::<Vec<_>>(),
+        vec![round_2_unplayed]
+    );
+    assert_eq!(remaining[1], fixtures[2]);
+    assert!(remaining
+        .iter()
+        .flat_map(|x| &x.matches)
+        .all(|m| m.result.is_none()));
+}
+
+#[test]
+fn test_process_round() {
+    let mut app = get_app();
+    let users: Vec<Addr> = (1..=4)
+        .map(|i| app.api().addr_make(&format!("user{}", i)))
+        .collect();
+    let admin = app.api().addr_make(ADMIN);
+
+    let core_context = setup_core_context(
+        &mut app,
+        vec![Member {
+            addr: admin.to_string(),
+            weight: 1u64,
+        }],
+    );
+    let league_context = setup_league_context(&mut app, &core_context);
+    let mut context = Context {
+        app,
+        core: core_context,
+        league: league_context,
+    };
+
+    let starting_height = context.app.block_info().height;
+    let league_id = create_competition(
+        &mut context,
+        Expiration::AtHeight(starting_height + 100),
+        users
+            .iter()
+            .map(|x| Member {
+                addr: x.to_string(),
+                weight: 1u64,
+            })
+            .collect(),
+        None,
+        Duration::Height(10u64),
+        None,
+        None,
+    );
+    context.app.update_block(|x| x.height += 20);
+
+    let query_round = |context: &Context, round_number: u64| -> RoundResponse {
+        context
+            .app
+            .wrap()
+            .query_wasm_smart(
+                context.league.league_module_addr.clone(),
+                &QueryMsg::QueryExtension {
+                    msg: QueryExt::Round {
+                        league_id,
+                        round_number: Uint64::from(round_number),
+                    },
+                },
+            )
+            .unwrap()
+    };
+    let process_round = |context: &mut Context,
+                         round_number: u64,
+                         results: Vec<(Uint128, Result)>,
+                         default_result: Option<Result>| {
+        context.app.execute_contract(
+            admin.clone(),
+            context.core.sudo_proposal_addr.clone(),
+            &dao_proposal_sudo::msg::ExecuteMsg::Execute {
+                msgs: vec![WasmMsg::Execute {
+                    contract_addr: context.league.league_module_addr.to_string(),
+                    msg: to_json_binary(&ExecuteMsg::Extension {
+                        msg: ExecuteExt::ProcessRound {
+                            league_id,
+                            round_number: Uint64::from(round_number),
+                            results,
+                            default_result,
+                            evidence: None,
+                        },
+                    })
+                    .unwrap(),
+                    funds: vec![],
+                }
+                .into()],
+            },
+            &[],
+        )
+    };
+
+    let round_1 = query_round(&context, 1);
+    let match_numbers: Vec<Uint128> = round_1.matches.iter().map(|m| m.match_number).collect();
+
+    // A round can't be partially reported
+    let result = process_round(
+        &mut context,
+        1,
+        vec![(match_numbers[0], Result::Team1)],
+        None,
+    );
+    assert!(result.is_err());
+    assert!(query_round(&context, 1)
+        .matches
+        .iter()
+        .all(|m| m.result.is_none()));
+
+    // Matches from another round are rejected
+    let round_2 = query_round(&context, 2);
+    let result = process_round(
+        &mut context,
+        1,
+        vec![
+            (match_numbers[0], Result::Team1),
+            (match_numbers[1], Result::Team2),
+            (round_2.matches[0].match_number, Result::Team1),
+        ],
+        None,
+    );
+    assert!(result.is_err());
+
+    // The full round is reported in one call
+    let result = process_round(
+        &mut context,
+        1,
+        vec![
+            (match_numbers[0], Result::Team1),
+            (match_numbers[1], Result::Team2),
+        ],
+        None,
+    );
+    assert!(result.is_ok());
+    let round_1 = query_round(&context, 1);
+    for m in &round_1.matches {
+        let expected = if m.match_number == match_numbers[0] {
+            Result::Team1
+        } else {
+            Result::Team2
+        };
+        assert_eq!(m.result, Some(expected));
+    }
+
+    // Unreported matches take the default result
+    let result = process_round(
+        &mut context,
+        2,
+        vec![(round_2.matches[0].match_number, Result::Team1)],
+        Some(Result::Draw),
+    );
+    assert!(result.is_ok());
+    let round_2 = query_round(&context, 2);
+    assert_eq!(round_2.matches[0].result, Some(Result::Team1));
+    assert_eq!(round_2.matches[1].result, Some(Result::Draw));
+}
@@ -1,19 +1,26 @@
 use std::str::FromStr;
 
 use arena_core_interface::msg::{
-    CompetitionModuleQuery, CompetitionModuleResponse, ProposeMessage, QueryExt,
+    CompetitionModuleQuery, CompetitionModuleResponse, ProposeMessage, QueryExt, Ruleset,
+};
+use arena_wager_module::msg::{
+    CompetitionInstantiateExt, ExecuteExt, ExecuteMsg, InstantiateMsg, QueryMsg, WagerResponse,
 };
-use arena_wager_module::msg::{EmptyWrapper, ExecuteMsg, InstantiateMsg, QueryMsg, WagerResponse};
 use cosmwasm_std::{
-    to_json_binary, Addr, Coin, Coins, CosmosMsg, Decimal, Empty, Uint128, WasmMsg,
+    to_json_binary, Addr, Coin, Coins, CosmosMsg, Decimal, Empty, StdResult, Uint128, WasmMsg,
 };
 use cw4::Member;
 use cw_balance::{
     BalanceVerified, Distribution, MemberBalanceChecked, MemberBalanceUnchecked, MemberPercentage,
+    MemberShare,
 };
 use cw_competition::{
-    msg::ModuleInfo,
-    state::{CompetitionListItemResponse, CompetitionStatus},
+    escrow::CompetitionEscrowRosterEntry,
+    msg::{result_commitment, ModuleInfo},
+    state::{
+        CompetitionListItemResponse, CompetitionStatus, EscrowAttentionReason,
+        EscrowAttentionResponse, TotalValueLockedResponse,
+    },
 };
 use cw_multi_test::{addons::MockApiBech32, next_block, App, BankKeeper, Executor};
 use cw_utils::Expiration;
@@ -42,6 +49,14 @@ pub struct WagerContext {
 pub fn setup_wager_context(
     app: &mut App<BankKeeper, MockApiBech32>,
     core_context: &CoreContext,
+) -> WagerContext {
+    setup_capped_wager_context(app, core_context, None)
+}
+
+fn setup_capped_wager_context(
+    app: &mut App<BankKeeper, MockApiBech32>,
+    core_context: &CoreContext,
+    max_rulesets_per_competition: Option<u32>,
 ) -> WagerContext {
     let wager_module_id = app.store_code(arena_testing::contracts::arena_wager_module_contract());
     let escrow_id = app.store_code(arena_testing::contracts::arena_dao_escrow_contract());
@@ -63,6 +78,7 @@ pub fn setup_wager_context(
                                 key: wagers_key.clone(),
                                 description: "This is a description".to_string(),
                                 extension: Empty {},
+                                max_rulesets_per_competition,
                             })
                             .unwrap(),
                             admin: None,
@@ -92,60 +108,85 @@ pub fn setup_wager_context(
     }
 }
 
+fn create_competition_msg(
+    context: &Context,
+    expiration: Expiration,
+    members: Vec<cw4::Member>,
+    escrow: Option<ModuleInfo>,
+) -> ExecuteMsg {
+    ExecuteMsg::CreateCompetition {
+        category_id: Some(Uint128::one()),
+        host: ModuleInfo::New {
+            info: ModuleInstantiateInfo {
+                code_id: context.core.dao_core_id,
+                msg: to_json_binary(&super::helpers::get_competition_dao_instantiate_msg(
+                    context.core.cw4_id,
+                    context.core.cw4_voting_module_id,
+                    context.core.dao_proposal_single_id,
+                    dao_proposal_single::msg::InstantiateMsg {
+                        threshold: dao_voting::threshold::Threshold::AbsolutePercentage {
+                            percentage: dao_voting::threshold::PercentageThreshold::Majority {},
+                        },
+                        min_voting_period: None,
+                        max_voting_period: cw_utils_v16::Duration::Height(10u64),
+                        only_members_execute: false,
+                        allow_revoting: false,
+                        pre_propose_info:
+                            dao_voting::pre_propose::PreProposeInfo::AnyoneMayPropose {},
+                        close_proposal_on_execution_failure: true,
+                    },
+                    members,
+                ))
+                .unwrap(),
+                admin: None,
+                label: "DAO".to_owned(),
+            },
+        },
+        escrow,
+        name: "This is a competition name".to_string(),
+        description: "This is a description".to_string(),
+        expiration,
+        rules: vec![
+            "Rule 1".to_string(),
+            "Rule 2".to_string(),
+            "Rule 3".to_string(),
+        ],
+        rulesets: vec![],
+        result_authority: None,
+        instantiate_extension: CompetitionInstantiateExt::default(),
+    }
+}
+
 fn create_competition(
     context: &mut Context,
     expiration: Expiration,
     members: Vec<cw4::Member>,
     dues: Option<Vec<MemberBalanceUnchecked>>,
 ) -> Uint128 {
+    let escrow = dues.map(|x| ModuleInfo::New {
+        info: ModuleInstantiateInfo {
+            code_id: context.wager.escrow_id,
+            msg: to_json_binary(&arena_escrow::msg::InstantiateMsg {
+                dues: x,
+                oracle: None,
+                competition_id: None,
+                activation_quorum: None,
+                min_participants: None,
+                cancel_on_close: None,
+                treasury: None,
+                auto_lock_delay: None,
+                metadata: None,
+            })
+            .unwrap(),
+            admin: None,
+            label: "Escrow".to_owned(),
+        },
+    });
+    let msg = create_competition_msg(context, expiration, members, escrow);
     let result = context.app.execute_contract(
         context.app.api().addr_make(ADMIN),
         context.wager.wager_module_addr.clone(), // errors out bc dao not set
-        &ExecuteMsg::CreateCompetition {
-            category_id: Some(Uint128::one()),
-            host: ModuleInfo::New {
-                info: ModuleInstantiateInfo {
-                    code_id: context.core.dao_core_id,
-                    msg: to_json_binary(&super::helpers::get_competition_dao_instantiate_msg(
-                        context.core.cw4_id,
-                        context.core.cw4_voting_module_id,
-                        context.core.dao_proposal_single_id,
-                        dao_proposal_single::msg::InstantiateMsg {
-                            threshold: dao_voting::threshold::Threshold::AbsolutePercentage {
-                                percentage: dao_voting::threshold::PercentageThreshold::Majority {},
-                            },
-                            min_voting_period: None,
-                            max_voting_period: cw_utils_v16::Duration::Height(10u64),
-                            only_members_execute: false,
-                            allow_revoting: false,
-                            pre_propose_info:
-                                dao_voting::pre_propose::PreProposeInfo::AnyoneMayPropose {},
-                            close_proposal_on_execution_failure: true,
-                        },
-                        members,
-                    ))
-                    .unwrap(),
-                    admin: None,
-                    label: "DAO".to_owned(),
-                },
-            },
-            escrow: dues.map(|x| ModuleInstantiateInfo {
-                code_id: context.wager.escrow_id,
-                msg: to_json_binary(&arena_escrow::msg::InstantiateMsg { dues: x }).unwrap(),
-                admin: None,
-                label: "Escrow".to_owned(),
-            }),
-            name: "This is a competition name".to_string(),
-            description: "This is a description".to_string(),
-            expiration,
-            rules: vec![
-                "Rule 1".to_string(),
-                "Rule 2".to_string(),
-                "Rule 3".to_string(),
-            ],
-            rulesets: vec![],
-            instantiate_extension: EmptyWrapper::new(),
-        },
+        &msg,
         &[],
     );
     assert!(result.is_ok());
@@ -253,7 +294,8 @@ fn test_create_competition() {
                 "Rule 3".to_string(),
             ],
             rulesets: vec![Uint128::from(9999u128)],
-            instantiate_extension: EmptyWrapper::new(),
+            result_authority: None,
+            instantiate_extension: CompetitionInstantiateExt::default(),
         },
         &[],
     );
@@ -385,6 +427,7 @@ fn test_create_competition() {
                         }),
                         tax_cw20_msg: None,
                         tax_cw721_msg: None,
+                        evidence: Some("ipfs://result".to_string()),
                     },
                 )
                 .unwrap(),
@@ -500,6 +543,19 @@ fn test_create_competition() {
         )
         .unwrap();
     assert!(result.is_some());
+
+    // The evidence reported with the result is stored
+    let evidence: Option<String> = context
+        .app
+        .wrap()
+        .query_wasm_smart(
+            context.wager.wager_module_addr.clone(),
+            &QueryMsg::ResultEvidence {
+                competition_id: competition1_id,
+            },
+        )
+        .unwrap();
+    assert_eq!(evidence, Some("ipfs://result".to_string()));
 }
 
 #[test]
@@ -942,6 +998,7 @@ fn test_preset_distribution() {
                         }),
                         tax_cw20_msg: None,
                         tax_cw721_msg: None,
+                        evidence: None,
                     },
                 )
                 .unwrap(),
@@ -1191,6 +1248,7 @@ fn test_competition_draw() {
                         distribution: None,
                         tax_cw20_msg: None,
                         tax_cw721_msg: None,
+                        evidence: None,
                     },
                 )
                 .unwrap(),
@@ -1346,3 +1404,3371 @@ fn test_competition_draw() {
         .unwrap();
     assert!(result.is_none());
 }
+
+#[test]
+fn test_create_competition_disabled_ruleset() {
+    let mut app = get_app();
+
+    let admin = app.api().addr_make(ADMIN);
+    let core_context = setup_core_context(
+        &mut app,
+        vec![Member {
+            addr: admin.to_string(),
+            weight: 1u64,
+        }],
+    );
+    let wager_context = setup_wager_context(&mut app, &core_context);
+    let mut context = Context {
+        app,
+        core: core_context,
+        wager: wager_context,
+    };
+    let starting_height = context.app.block_info().height;
+
+    // Disable the first ruleset
+    let result = context.app.execute_contract(
+        admin.clone(),
+        context.core.sudo_proposal_addr.clone(),
+        &dao_proposal_sudo::msg::ExecuteMsg::Execute {
+            msgs: vec![WasmMsg::Execute {
+                contract_addr: context.core.arena_core_addr.to_string(),
+                funds: vec![],
+                msg: to_json_binary(&arena_core_interface::msg::ExecuteMsg::Extension {
+                    msg: arena_core_interface::msg::ExecuteExt::UpdateRulesets {
+                        to_add: vec![],
+                        to_disable: vec![Uint128::one()],
+                        reject_duplicates: None,
+                    },
+                })
+                .unwrap(),
+            }
+            .into()],
+        },
+        &[],
+    );
+    assert!(result.is_ok());
+
+    let create_msg = |rulesets: Vec<Uint128>| ExecuteMsg::CreateCompetition {
+        category_id: Some(Uint128::one()),
+        host: ModuleInfo::Existing {
+            addr: admin.to_string(),
+        },
+        escrow: None,
+        name: "This is a competition name".to_string(),
+        description: "This is a description".to_string(),
+        expiration: Expiration::AtHeight(starting_height + 10),
+        rules: vec!["Rule 1".to_string()],
+        rulesets,
+        result_authority: None,
+        instantiate_extension: CompetitionInstantiateExt::default(),
+    };
+
+    // Competitions cannot use the disabled ruleset
+    let result = context.app.execute_contract(
+        admin.clone(),
+        context.wager.wager_module_addr.clone(),
+        &create_msg(vec![Uint128::new(2), Uint128::one()]),
+        &[],
+    );
+    assert!(result.is_err());
+    assert_eq!(
+        result.unwrap_err().root_cause().to_string(),
+        "InvalidRuleset"
+    );
+
+    // The enabled ruleset is still usable
+    let result = context.app.execute_contract(
+        admin.clone(),
+        context.wager.wager_module_addr.clone(),
+        &create_msg(vec![Uint128::new(2)]),
+        &[],
+    );
+    assert!(result.is_ok());
+}
+
+#[test]
+fn test_competition_co_winners() {
+    let mut app = get_app();
+    let user1 = app.api().addr_make("user1");
+    let user2 = app.api().addr_make("user2");
+    let wager_amount = "10000juno";
+    let admin = app.api().addr_make(ADMIN);
+
+    set_balances(
+        &mut app,
+        vec![
+            (user1.clone(), Coins::from_str(wager_amount).unwrap()),
+            (user2.clone(), Coins::from_str(wager_amount).unwrap()),
+        ],
+    );
+    let core_context = setup_core_context(
+        &mut app,
+        vec![Member {
+            addr: admin.to_string(),
+            weight: 1u64,
+        }],
+    );
+    let wager_context = setup_wager_context(&mut app, &core_context);
+    let mut context = Context {
+        app,
+        core: core_context,
+        wager: wager_context,
+    };
+
+    let starting_height = context.app.block_info().height;
+
+    // Create competiton
+    let competition_id = create_competition(
+        &mut context,
+        Expiration::AtHeight(starting_height + 10),
+        vec![
+            cw4::Member {
+                addr: user1.to_string(),
+                weight: 1u64,
+            },
+            cw4::Member {
+                addr: user2.to_string(),
+                weight: 1u64,
+            },
+        ],
+        Some(vec![
+            MemberBalanceUnchecked {
+                addr: user1.to_string(),
+                balance: cw_balance::BalanceUnchecked {
+                    native: vec![Coin::from_str(wager_amount).unwrap()],
+                    cw20: vec![],
+                    cw721: vec![],
+                },
+            },
+            MemberBalanceUnchecked {
+                addr: user2.to_string(),
+                balance: cw_balance::BalanceUnchecked {
+                    native: vec![Coin::from_str(wager_amount).unwrap()],
+                    cw20: vec![],
+                    cw721: vec![],
+                },
+            },
+        ]),
+    );
+    let competition: WagerResponse = context
+        .app
+        .wrap()
+        .query_wasm_smart(
+            context.wager.wager_module_addr.clone(),
+            &QueryMsg::Competition { competition_id },
+        )
+        .unwrap();
+    let escrow = competition.escrow.unwrap();
+
+    // Fund escrow
+    for user in [&user1, &user2] {
+        context
+            .app
+            .execute_contract(
+                user.clone(),
+                escrow.clone(),
+                &arena_escrow::msg::ExecuteMsg::ReceiveNative {},
+                &[Coin::from_str(wager_amount).unwrap()],
+            )
+            .unwrap();
+    }
+
+    // A single co-winner is not allowed
+    let process_msg = |winners: Vec<String>| dao_proposal_sudo::msg::ExecuteMsg::Execute {
+        msgs: vec![WasmMsg::Execute {
+            contract_addr: context.wager.wager_module_addr.to_string(),
+            msg: to_json_binary(&ExecuteMsg::Extension {
+                msg: ExecuteExt::ProcessCoWinners {
+                    competition_id,
+                    winners,
+                    evidence: None,
+                },
+            })
+            .unwrap(),
+            funds: vec![],
+        }
+        .into()],
+    };
+    let msg = process_msg(vec![user1.to_string()]);
+    let result = context.app.execute_contract(
+        admin.clone(),
+        context.core.sudo_proposal_addr.clone(),
+        &msg,
+        &[],
+    );
+    assert!(result.is_err());
+
+    // Both users are co-winners through the arena DAO
+    let msg = process_msg(vec![user1.to_string(), user2.to_string()]);
+    let result = context.app.execute_contract(
+        admin.clone(),
+        context.core.sudo_proposal_addr.clone(),
+        &msg,
+        &[],
+    );
+    assert!(result.is_ok());
+
+    // The co-winner split is recorded as a result, unlike a draw
+    let result: Option<Distribution<String>> = context
+        .app
+        .wrap()
+        .query_wasm_smart(
+            context.wager.wager_module_addr.clone(),
+            &QueryMsg::Result { competition_id },
+        )
+        .unwrap();
+    assert_eq!(result.unwrap().member_percentages.len(), 2);
+
+    // Withdraw the split pool
+    for user in [&user1, &user2] {
+        let result = context.app.execute_contract(
+            user.clone(),
+            escrow.clone(),
+            &arena_escrow::msg::ExecuteMsg::Withdraw {
+                cw20_msg: None,
+                cw721_msg: None,
+            },
+            &[],
+        );
+        assert!(result.is_ok());
+    }
+
+    // Assert correct balances user 1 - 20_000*.85/2, user 2 - 20_000*.85/2, dao - 20_000*.15
+    for user in [&user1, &user2] {
+        let balance = context
+            .app
+            .wrap()
+            .query_balance(user.to_string(), "juno")
+            .unwrap();
+        assert_eq!(balance.amount, Uint128::from(8_500u128));
+    }
+    let balance = context
+        .app
+        .wrap()
+        .query_balance(context.core.dao_addr.to_string(), "juno")
+        .unwrap();
+    assert_eq!(balance.amount, Uint128::from(3_000u128));
+}
+
+#[test]
+fn test_is_canonical_escrow() {
+    let mut app = get_app();
+    let user1 = app.api().addr_make("user1");
+    let user2 = app.api().addr_make("user2");
+    let fake_escrow = app.api().addr_make("fake_escrow");
+    let admin = app.api().addr_make(ADMIN);
+
+    let core_context = setup_core_context(
+        &mut app,
+        vec![Member {
+            addr: admin.to_string(),
+            weight: 1u64,
+        }],
+    );
+    let wager_context = setup_wager_context(&mut app, &core_context);
+    let mut context = Context {
+        app,
+        core: core_context,
+        wager: wager_context,
+    };
+
+    let starting_height = context.app.block_info().height;
+    let competition_id = create_competition(
+        &mut context,
+        Expiration::AtHeight(starting_height + 10),
+        vec![
+            cw4::Member {
+                addr: user1.to_string(),
+                weight: 1u64,
+            },
+            cw4::Member {
+                addr: user2.to_string(),
+                weight: 1u64,
+            },
+        ],
+        Some(vec![
+            MemberBalanceUnchecked {
+                addr: user1.to_string(),
+                balance: cw_balance::BalanceUnchecked {
+                    native: vec![Coin::from_str("10000juno").unwrap()],
+                    cw20: vec![],
+                    cw721: vec![],
+                },
+            },
+            MemberBalanceUnchecked {
+                addr: user2.to_string(),
+                balance: cw_balance::BalanceUnchecked {
+                    native: vec![Coin::from_str("10000juno").unwrap()],
+                    cw20: vec![],
+                    cw721: vec![],
+                },
+            },
+        ]),
+    );
+    let competition: WagerResponse = context
+        .app
+        .wrap()
+        .query_wasm_smart(
+            context.wager.wager_module_addr.clone(),
+            &QueryMsg::Competition { competition_id },
+        )
+        .unwrap();
+
+    // The competition's escrow is canonical
+    let is_canonical: bool = context
+        .app
+        .wrap()
+        .query_wasm_smart(
+            context.wager.wager_module_addr.clone(),
+            &QueryMsg::IsCanonicalEscrow {
+                competition_id,
+                addr: competition.escrow.unwrap().to_string(),
+            },
+        )
+        .unwrap();
+    assert!(is_canonical);
+
+    // Any other address is not
+    let is_canonical: bool = context
+        .app
+        .wrap()
+        .query_wasm_smart(
+            context.wager.wager_module_addr.clone(),
+            &QueryMsg::IsCanonicalEscrow {
+                competition_id,
+                addr: fake_escrow.to_string(),
+            },
+        )
+        .unwrap();
+    assert!(!is_canonical);
+}
+
+#[test]
+fn test_approve_distribution() {
+    let mut app = get_app();
+    let user1 = app.api().addr_make("user1");
+    let user2 = app.api().addr_make("user2");
+    let wager_amount = "10000juno";
+    let admin = app.api().addr_make(ADMIN);
+
+    set_balances(
+        &mut app,
+        vec![
+            (user1.clone(), Coins::from_str(wager_amount).unwrap()),
+            (user2.clone(), Coins::from_str(wager_amount).unwrap()),
+        ],
+    );
+    let core_context = setup_core_context(
+        &mut app,
+        vec![Member {
+            addr: admin.to_string(),
+            weight: 1u64,
+        }],
+    );
+    let wager_context = setup_wager_context(&mut app, &core_context);
+    let mut context = Context {
+        app,
+        core: core_context,
+        wager: wager_context,
+    };
+
+    let starting_height = context.app.block_info().height;
+    let competition_id = create_competition(
+        &mut context,
+        Expiration::AtHeight(starting_height + 10),
+        vec![
+            cw4::Member {
+                addr: user1.to_string(),
+                weight: 1u64,
+            },
+            cw4::Member {
+                addr: user2.to_string(),
+                weight: 1u64,
+            },
+        ],
+        Some(vec![
+            MemberBalanceUnchecked {
+                addr: user1.to_string(),
+                balance: cw_balance::BalanceUnchecked {
+                    native: vec![Coin::from_str(wager_amount).unwrap()],
+                    cw20: vec![],
+                    cw721: vec![],
+                },
+            },
+            MemberBalanceUnchecked {
+                addr: user2.to_string(),
+                balance: cw_balance::BalanceUnchecked {
+                    native: vec![Coin::from_str(wager_amount).unwrap()],
+                    cw20: vec![],
+                    cw721: vec![],
+                },
+            },
+        ]),
+    );
+    let competition: WagerResponse = context
+        .app
+        .wrap()
+        .query_wasm_smart(
+            context.wager.wager_module_addr.clone(),
+            &QueryMsg::Competition { competition_id },
+        )
+        .unwrap();
+    let escrow = competition.escrow.unwrap();
+
+    // Fund escrow
+    for user in [&user1, &user2] {
+        context
+            .app
+            .execute_contract(
+                user.clone(),
+                escrow.clone(),
+                &arena_escrow::msg::ExecuteMsg::ReceiveNative {},
+                &[Coin::from_str(wager_amount).unwrap()],
+            )
+            .unwrap();
+    }
+
+    // Both members agree that user1 takes the pool
+    let approve_msg = arena_escrow::msg::ExecuteMsg::ApproveDistribution {
+        distribution: Distribution::<String> {
+            member_percentages: vec![MemberPercentage {
+                addr: user1.to_string(),
+                percentage: Decimal::one(),
+            }],
+            remainder_addr: user1.to_string(),
+        },
+    };
+    let result = context
+        .app
+        .execute_contract(user1.clone(), escrow.clone(), &approve_msg, &[]);
+    assert!(result.is_ok());
+    assert_eq!(
+        get_attr_value(&result.unwrap(), "is_approved"),
+        Some("false".to_string())
+    );
+
+    // Non-members cannot approve
+    let result = context
+        .app
+        .execute_contract(admin.clone(), escrow.clone(), &approve_msg, &[]);
+    assert!(result.is_err());
+
+    // The final approval has the competition module process the agreed result
+    let result = context
+        .app
+        .execute_contract(user2.clone(), escrow.clone(), &approve_msg, &[]);
+    assert!(result.is_ok());
+    assert_eq!(
+        get_attr_value(&result.unwrap(), "is_approved"),
+        Some("true".to_string())
+    );
+    let competition: WagerResponse = context
+        .app
+        .wrap()
+        .query_wasm_smart(
+            context.wager.wager_module_addr.clone(),
+            &QueryMsg::Competition { competition_id },
+        )
+        .unwrap();
+    assert_eq!(competition.status, CompetitionStatus::Inactive);
+
+    // The competition cannot be processed a second time
+    let result = context.app.execute_contract(
+        admin.clone(),
+        context.wager.wager_module_addr.clone(),
+        &ExecuteMsg::ProcessCompetition {
+            competition_id,
+            distribution: None,
+            tax_cw20_msg: None,
+            tax_cw721_msg: None,
+            evidence: None,
+        },
+        &[],
+    );
+    assert!(result.is_err());
+
+    let result = context.app.execute_contract(
+        user1.clone(),
+        escrow.clone(),
+        &arena_escrow::msg::ExecuteMsg::Withdraw {
+            cw20_msg: None,
+            cw721_msg: None,
+        },
+        &[],
+    );
+    assert!(result.is_ok());
+
+    let balance = context
+        .app
+        .wrap()
+        .query_balance(user1.to_string(), "juno")
+        .unwrap();
+    assert_eq!(balance.amount, Uint128::from(17_000u128));
+    let balance: Option<BalanceVerified> = context
+        .app
+        .wrap()
+        .query_wasm_smart(
+            escrow,
+            &arena_escrow::msg::QueryMsg::Balance {
+                addr: user2.to_string(),
+            },
+        )
+        .unwrap();
+    assert!(balance.is_none());
+}
+
+#[test]
+fn test_tax_tiers() {
+    let mut app = get_app();
+    let user1 = app.api().addr_make("user1");
+    let user2 = app.api().addr_make("user2");
+    let wager_amount = "10000juno";
+    let admin = app.api().addr_make(ADMIN);
+
+    set_balances(
+        &mut app,
+        vec![
+            (user1.clone(), Coins::from_str(wager_amount).unwrap()),
+            (user2.clone(), Coins::from_str(wager_amount).unwrap()),
+        ],
+    );
+    let core_context = setup_core_context(
+        &mut app,
+        vec![Member {
+            addr: admin.to_string(),
+            weight: 1u64,
+        }],
+    );
+    let wager_context = setup_wager_context(&mut app, &core_context);
+    let mut context = Context {
+        app,
+        core: core_context,
+        wager: wager_context,
+    };
+
+    // Pools of at least 20,000 juno are taxed 5% instead of the base 15%
+    let result = context.app.execute_contract(
+        admin.clone(),
+        context.core.sudo_proposal_addr.clone(),
+        &dao_proposal_sudo::msg::ExecuteMsg::Execute {
+            msgs: vec![WasmMsg::Execute {
+                contract_addr: context.core.arena_core_addr.to_string(),
+                funds: vec![],
+                msg: to_json_binary(&arena_core_interface::msg::ExecuteMsg::Extension {
+                    msg: arena_core_interface::msg::ExecuteExt::UpdateTaxTiers {
+                        asset: "juno".to_string(),
+                        tiers: vec![(Uint128::new(20_000), Decimal::percent(5))],
+                    },
+                })
+                .unwrap(),
+            }
+            .into()],
+        },
+        &[],
+    );
+    assert!(result.is_ok());
+
+    let starting_height = context.app.block_info().height;
+    let competition_id = create_competition(
+        &mut context,
+        Expiration::AtHeight(starting_height + 10),
+        vec![
+            cw4::Member {
+                addr: user1.to_string(),
+                weight: 1u64,
+            },
+            cw4::Member {
+                addr: user2.to_string(),
+                weight: 1u64,
+            },
+        ],
+        Some(vec![
+            MemberBalanceUnchecked {
+                addr: user1.to_string(),
+                balance: cw_balance::BalanceUnchecked {
+                    native: vec![Coin::from_str(wager_amount).unwrap()],
+                    cw20: vec![],
+                    cw721: vec![],
+                },
+            },
+            MemberBalanceUnchecked {
+                addr: user2.to_string(),
+                balance: cw_balance::BalanceUnchecked {
+                    native: vec![Coin::from_str(wager_amount).unwrap()],
+                    cw20: vec![],
+                    cw721: vec![],
+                },
+            },
+        ]),
+    );
+    let competition: WagerResponse = context
+        .app
+        .wrap()
+        .query_wasm_smart(
+            context.wager.wager_module_addr.clone(),
+            &QueryMsg::Competition { competition_id },
+        )
+        .unwrap();
+    let escrow = competition.escrow.unwrap();
+
+    // Fund escrow
+    for user in [&user1, &user2] {
+        context
+            .app
+            .execute_contract(
+                user.clone(),
+                escrow.clone(),
+                &arena_escrow::msg::ExecuteMsg::ReceiveNative {},
+                &[Coin::from_str(wager_amount).unwrap()],
+            )
+            .unwrap();
+    }
+
+    // Both members agree that user1 takes the pool
+    let approve_msg = arena_escrow::msg::ExecuteMsg::ApproveDistribution {
+        distribution: Distribution::<String> {
+            member_percentages: vec![MemberPercentage {
+                addr: user1.to_string(),
+                percentage: Decimal::one(),
+            }],
+            remainder_addr: user1.to_string(),
+        },
+    };
+    let result = context
+        .app
+        .execute_contract(user1.clone(), escrow.clone(), &approve_msg, &[]);
+    assert!(result.is_ok());
+
+    // The final approval has the competition module process the agreed result
+    let result = context
+        .app
+        .execute_contract(user2.clone(), escrow.clone(), &approve_msg, &[]);
+    assert!(result.is_ok());
+    let competition: WagerResponse = context
+        .app
+        .wrap()
+        .query_wasm_smart(
+            context.wager.wager_module_addr.clone(),
+            &QueryMsg::Competition { competition_id },
+        )
+        .unwrap();
+    assert_eq!(competition.status, CompetitionStatus::Inactive);
+
+    let result = context.app.execute_contract(
+        user1.clone(),
+        escrow.clone(),
+        &arena_escrow::msg::ExecuteMsg::Withdraw {
+            cw20_msg: None,
+            cw721_msg: None,
+        },
+        &[],
+    );
+    assert!(result.is_ok());
+
+    // The escrow's juno reached the tier, so user1 receives 20_000*.95
+    let balance = context
+        .app
+        .wrap()
+        .query_balance(user1.to_string(), "juno")
+        .unwrap();
+    assert_eq!(balance.amount, Uint128::from(19_000u128));
+}
+
+#[test]
+fn test_jailed_competition_refund() {
+    let mut app = get_app();
+
+    let user1 = app.api().addr_make("user1");
+    let user2 = app.api().addr_make("user2");
+    let admin = app.api().addr_make(ADMIN);
+    let wager_amount_uint128 = Uint128::from(10_000u128);
+    let wager_amount = format!("{}{}", wager_amount_uint128, "juno");
+
+    set_balances(
+        &mut app,
+        vec![
+            (user1.clone(), Coins::from_str(&wager_amount).unwrap()),
+            (user2.clone(), Coins::from_str(&wager_amount).unwrap()),
+        ],
+    );
+    let core_context = setup_core_context(
+        &mut app,
+        vec![Member {
+            addr: admin.to_string(),
+            weight: 1u64,
+        }],
+    );
+    let wager_context = setup_wager_context(&mut app, &core_context);
+    let mut context = Context {
+        app,
+        core: core_context,
+        wager: wager_context,
+    };
+
+    // Create competition
+    let starting_height = context.app.block_info().height;
+    let competition1_id = create_competition(
+        &mut context,
+        Expiration::AtHeight(starting_height + 1),
+        vec![
+            cw4::Member {
+                addr: user1.to_string(),
+                weight: 1u64,
+            },
+            cw4::Member {
+                addr: user2.to_string(),
+                weight: 1u64,
+            },
+        ],
+        Some(vec![
+            MemberBalanceUnchecked {
+                addr: user1.to_string(),
+                balance: cw_balance::BalanceUnchecked {
+                    native: vec![Coin::from_str(&wager_amount).unwrap()],
+                    cw20: vec![],
+                    cw721: vec![],
+                },
+            },
+            MemberBalanceUnchecked {
+                addr: user2.to_string(),
+                balance: cw_balance::BalanceUnchecked {
+                    native: vec![Coin::from_str(&wager_amount).unwrap()],
+                    cw20: vec![],
+                    cw721: vec![],
+                },
+            },
+        ]),
+    );
+    let competition1: WagerResponse = context
+        .app
+        .wrap()
+        .query_wasm_smart(
+            context.wager.wager_module_addr.clone(),
+            &QueryMsg::Competition {
+                competition_id: competition1_id,
+            },
+        )
+        .unwrap();
+    let escrow_addr = competition1.escrow.unwrap();
+
+    // Fund escrow
+    for user in [&user1, &user2] {
+        context
+            .app
+            .execute_contract(
+                user.clone(),
+                escrow_addr.clone(),
+                &arena_escrow::msg::ExecuteMsg::ReceiveNative {},
+                &[Coin::from_str(&wager_amount).unwrap()],
+            )
+            .unwrap();
+    }
+    context.app.update_block(next_block);
+
+    // Jail without a distribution
+    let result = context.app.execute_contract(
+        user1.clone(),
+        context.wager.wager_module_addr.clone(),
+        &ExecuteMsg::JailCompetition {
+            propose_message: ProposeMessage {
+                id: competition1_id,
+                title: "Title".to_string(),
+                description: "Description".to_string(),
+                distribution: None,
+                tax_cw20_msg: None,
+                tax_cw721_msg: None,
+            },
+        },
+        &[],
+    );
+    assert!(result.is_ok());
+
+    // The escrow stays locked until the DAO decides
+    let is_locked: bool = context
+        .app
+        .wrap()
+        .query_wasm_smart(
+            escrow_addr.clone(),
+            &arena_escrow::msg::QueryMsg::IsLocked {},
+        )
+        .unwrap();
+    assert!(is_locked);
+
+    // Vote and execute jail
+    let result = context.app.execute_contract(
+        admin.clone(),
+        context.core.proposal_module_addr.clone(),
+        &dao_proposal_single::msg::ExecuteMsg::Vote {
+            proposal_id: 1u64,
+            vote: dao_voting::voting::Vote::Yes,
+            rationale: None,
+        },
+        &[],
+    );
+    assert!(result.is_ok());
+
+    let result = context.app.execute_contract(
+        admin.clone(),
+        context.core.proposal_module_addr.clone(),
+        &dao_proposal_single::msg::ExecuteMsg::Execute { proposal_id: 1u64 },
+        &[],
+    );
+    assert!(result.is_ok());
+
+    let is_locked: bool = context
+        .app
+        .wrap()
+        .query_wasm_smart(
+            escrow_addr.clone(),
+            &arena_escrow::msg::QueryMsg::IsLocked {},
+        )
+        .unwrap();
+    assert!(!is_locked);
+
+    // Both users are refunded their own balance
+    for user in [&user1, &user2] {
+        let result = context.app.execute_contract(
+            user.clone(),
+            escrow_addr.clone(),
+            &arena_escrow::msg::ExecuteMsg::Withdraw {
+                cw20_msg: None,
+                cw721_msg: None,
+            },
+            &[],
+        );
+        assert!(result.is_ok());
+    }
+
+    // Assert correct balances users - 10_000*.85, dao - 20_000*.15
+    let balance = context
+        .app
+        .wrap()
+        .query_balance(context.core.dao_addr.to_string(), "juno")
+        .unwrap();
+    assert_eq!(balance.amount, Uint128::from(3_000u128));
+    for user in [&user1, &user2] {
+        let balance = context
+            .app
+            .wrap()
+            .query_balance(user.to_string(), "juno")
+            .unwrap();
+        assert_eq!(balance.amount, Uint128::from(8_500u128));
+    }
+}
+
+#[test]
+fn test_total_value_locked() {
+    let mut app = get_app();
+
+    let user1 = app.api().addr_make("user1");
+    let user2 = app.api().addr_make("user2");
+    let admin = app.api().addr_make(ADMIN);
+    let wager_amount = "10000juno";
+
+    set_balances(
+        &mut app,
+        vec![
+            (user1.clone(), Coins::from_str("20000juno").unwrap()),
+            (user2.clone(), Coins::from_str("20000juno").unwrap()),
+        ],
+    );
+    let core_context = setup_core_context(
+        &mut app,
+        vec![Member {
+            addr: admin.to_string(),
+            weight: 1u64,
+        }],
+    );
+    let wager_context = setup_wager_context(&mut app, &core_context);
+    let mut context = Context {
+        app,
+        core: core_context,
+        wager: wager_context,
+    };
+
+    // Create 2 competitions with escrows
+    let starting_height = context.app.block_info().height;
+    let mut escrows = vec![];
+    for _ in 0..2 {
+        let competition_id = create_competition(
+            &mut context,
+            Expiration::AtHeight(starting_height + 100),
+            vec![
+                cw4::Member {
+                    addr: user1.to_string(),
+                    weight: 1u64,
+                },
+                cw4::Member {
+                    addr: user2.to_string(),
+                    weight: 1u64,
+                },
+            ],
+            Some(vec![
+                MemberBalanceUnchecked {
+                    addr: user1.to_string(),
+                    balance: cw_balance::BalanceUnchecked {
+                        native: vec![Coin::from_str(wager_amount).unwrap()],
+                        cw20: vec![],
+                        cw721: vec![],
+                    },
+                },
+                MemberBalanceUnchecked {
+                    addr: user2.to_string(),
+                    balance: cw_balance::BalanceUnchecked {
+                        native: vec![Coin::from_str(wager_amount).unwrap()],
+                        cw20: vec![],
+                        cw721: vec![],
+                    },
+                },
+            ]),
+        );
+        let competition: WagerResponse = context
+            .app
+            .wrap()
+            .query_wasm_smart(
+                context.wager.wager_module_addr.clone(),
+                &QueryMsg::Competition { competition_id },
+            )
+            .unwrap();
+        escrows.push(competition.escrow.unwrap());
+    }
+
+    // Fully fund the first escrow and partially fund the second
+    for (user, escrow) in [
+        (&user1, &escrows[0]),
+        (&user2, &escrows[0]),
+        (&user2, &escrows[1]),
+    ] {
+        context
+            .app
+            .execute_contract(
+                user.clone(),
+                escrow.clone(),
+                &arena_escrow::msg::ExecuteMsg::ReceiveNative {},
+                &[Coin::from_str(wager_amount).unwrap()],
+            )
+            .unwrap();
+    }
+
+    let query_tvl_page = |context: &Context,
+                          module: Option<&Addr>,
+                          start_after: Option<Uint128>,
+                          limit: Option<u32>|
+     -> arena_core_interface::msg::TotalValueLockedResponse {
+        context
+            .app
+            .wrap()
+            .query_wasm_smart(
+                context.core.arena_core_addr.clone(),
+                &arena_core_interface::msg::QueryMsg::QueryExtension {
+                    msg: QueryExt::TotalValueLocked {
+                        module: module.map(|x| x.to_string()),
+                        start_after,
+                        limit,
+                    },
+                },
+            )
+            .unwrap()
+    };
+    let query_tvl = |context: &Context| query_tvl_page(context, None, None, None).balance.native;
+    assert_eq!(
+        query_tvl(&context),
+        vec![Coin::from_str("30000juno").unwrap()]
+    );
+
+    // Each module reports where its next page starts, until its competitions run out
+    let tvl = query_tvl_page(&context, None, None, Some(1));
+    assert_eq!(tvl.modules.len(), 1);
+    assert_eq!(tvl.modules[0].module, context.wager.wager_module_addr);
+    assert_eq!(
+        tvl.modules[0].balance.native,
+        vec![Coin::from_str("20000juno").unwrap()]
+    );
+    assert_eq!(tvl.modules[0].next_start_after, Some(Uint128::one()));
+
+    let tvl = query_tvl_page(
+        &context,
+        Some(&context.wager.wager_module_addr),
+        tvl.modules[0].next_start_after,
+        Some(1),
+    );
+    assert_eq!(
+        tvl.balance.native,
+        vec![Coin::from_str("10000juno").unwrap()]
+    );
+    assert_eq!(tvl.modules[0].next_start_after, None);
+
+    // Only competition modules can be paged
+    let result: StdResult<arena_core_interface::msg::TotalValueLockedResponse> =
+        context.app.wrap().query_wasm_smart(
+            context.core.arena_core_addr.clone(),
+            &arena_core_interface::msg::QueryMsg::QueryExtension {
+                msg: QueryExt::TotalValueLocked {
+                    module: Some(user1.to_string()),
+                    start_after: None,
+                    limit: None,
+                },
+            },
+        );
+    assert!(result.is_err());
+
+    // Withdrawing from the unlocked escrow reduces the total
+    context
+        .app
+        .execute_contract(
+            user2.clone(),
+            escrows[1].clone(),
+            &arena_escrow::msg::ExecuteMsg::Withdraw {
+                cw20_msg: None,
+                cw721_msg: None,
+            },
+            &[],
+        )
+        .unwrap();
+
+    assert_eq!(
+        query_tvl(&context),
+        vec![Coin::from_str("20000juno").unwrap()]
+    );
+
+    // A processed competition is no longer counted, even before its winnings are withdrawn
+    let approve_msg = arena_escrow::msg::ExecuteMsg::ApproveDistribution {
+        distribution: Distribution::<String> {
+            member_percentages: vec![MemberPercentage {
+                addr: user1.to_string(),
+                percentage: Decimal::one(),
+            }],
+            remainder_addr: user1.to_string(),
+        },
+    };
+    for user in [&user1, &user2] {
+        context
+            .app
+            .execute_contract(user.clone(), escrows[0].clone(), &approve_msg, &[])
+            .unwrap();
+    }
+    assert!(query_tvl(&context).is_empty());
+}
+
+#[test]
+fn test_result_authority() {
+    let mut app = get_app();
+    let admin = app.api().addr_make(ADMIN);
+    let referee = app.api().addr_make("referee");
+    let random = app.api().addr_make("random");
+
+    let core_context = setup_core_context(
+        &mut app,
+        vec![Member {
+            addr: admin.to_string(),
+            weight: 1u64,
+        }],
+    );
+    let wager_context = setup_wager_context(&mut app, &core_context);
+    let mut context = Context {
+        app,
+        core: core_context,
+        wager: wager_context,
+    };
+
+    // A wager without an escrow is active immediately
+    let starting_height = context.app.block_info().height;
+    let result = context
+        .app
+        .execute_contract(
+            admin.clone(),
+            context.wager.wager_module_addr.clone(),
+            &ExecuteMsg::CreateCompetition {
+                category_id: Some(Uint128::one()),
+                host: ModuleInfo::Existing {
+                    addr: admin.to_string(),
+                },
+                escrow: None,
+                name: "This is a competition name".to_string(),
+                description: "This is a description".to_string(),
+                expiration: Expiration::AtHeight(starting_height + 10),
+                rules: vec!["Rule 1".to_string()],
+                rulesets: vec![],
+                result_authority: Some(referee.to_string()),
+                instantiate_extension: CompetitionInstantiateExt::default(),
+            },
+            &[],
+        )
+        .unwrap();
+    let competition_id =
+        Uint128::from_str(&get_attr_value(&result, "competition_id").unwrap()).unwrap();
+
+    let process_msg = ExecuteMsg::ProcessCompetition {
+        competition_id,
+        distribution: None,
+        tax_cw20_msg: None,
+        tax_cw721_msg: None,
+        evidence: Some("ipfs://referee".to_string()),
+    };
+
+    // Other addresses cannot report the result
+    let result = context.app.execute_contract(
+        random,
+        context.wager.wager_module_addr.clone(),
+        &process_msg,
+        &[],
+    );
+    assert_eq!(result.unwrap_err().root_cause().to_string(), "Unauthorized");
+
+    // The referee reports the result
+    context
+        .app
+        .execute_contract(
+            referee.clone(),
+            context.wager.wager_module_addr.clone(),
+            &process_msg,
+            &[],
+        )
+        .unwrap();
+
+    let wager: WagerResponse = context
+        .app
+        .wrap()
+        .query_wasm_smart(
+            context.wager.wager_module_addr.clone(),
+            &QueryMsg::Competition { competition_id },
+        )
+        .unwrap();
+    assert_eq!(wager.result_authority, Some(referee));
+
+    let evidence: Option<String> = context
+        .app
+        .wrap()
+        .query_wasm_smart(
+            context.wager.wager_module_addr.clone(),
+            &QueryMsg::ResultEvidence { competition_id },
+        )
+        .unwrap();
+    assert_eq!(evidence, Some("ipfs://referee".to_string()));
+}
+
+#[test]
+fn test_commit_reveal_result() {
+    let mut app = get_app();
+    let admin = app.api().addr_make(ADMIN);
+    let user1 = app.api().addr_make("user1");
+
+    let core_context = setup_core_context(
+        &mut app,
+        vec![Member {
+            addr: admin.to_string(),
+            weight: 1u64,
+        }],
+    );
+    let wager_context = setup_wager_context(&mut app, &core_context);
+    let mut context = Context {
+        app,
+        core: core_context,
+        wager: wager_context,
+    };
+
+    let starting_height = context.app.block_info().height;
+    let result = context
+        .app
+        .execute_contract(
+            admin.clone(),
+            context.wager.wager_module_addr.clone(),
+            &ExecuteMsg::CreateCompetition {
+                category_id: Some(Uint128::one()),
+                host: ModuleInfo::Existing {
+                    addr: admin.to_string(),
+                },
+                escrow: None,
+                name: "This is a competition name".to_string(),
+                description: "This is a description".to_string(),
+                expiration: Expiration::AtHeight(starting_height + 10),
+                rules: vec!["Rule 1".to_string()],
+                rulesets: vec![],
+                result_authority: None,
+                instantiate_extension: CompetitionInstantiateExt::default(),
+            },
+            &[],
+        )
+        .unwrap();
+    let competition_id =
+        Uint128::from_str(&get_attr_value(&result, "competition_id").unwrap()).unwrap();
+
+    let distribution = Some(Distribution::<String> {
+        member_percentages: vec![MemberPercentage {
+            addr: user1.to_string(),
+            percentage: Decimal::one(),
+        }],
+        remainder_addr: user1.to_string(),
+    });
+    context
+        .app
+        .execute_contract(
+            admin.clone(),
+            context.wager.wager_module_addr.clone(),
+            &ExecuteMsg::CommitResult {
+                competition_id,
+                hash: result_commitment(competition_id, &distribution, "secret").unwrap(),
+            },
+            &[],
+        )
+        .unwrap();
+
+    // The host cannot replace an unrevealed commitment
+    let result = context.app.execute_contract(
+        admin.clone(),
+        context.wager.wager_module_addr.clone(),
+        &ExecuteMsg::CommitResult {
+            competition_id,
+            hash: result_commitment(competition_id, &None, "secret").unwrap(),
+        },
+        &[],
+    );
+    assert_eq!(
+        result.unwrap_err().root_cause().to_string(),
+        "ResultCommitted"
+    );
+
+    // The committed result cannot be bypassed
+    let result = context.app.execute_contract(
+        admin.clone(),
+        context.wager.wager_module_addr.clone(),
+        &ExecuteMsg::ProcessCompetition {
+            competition_id,
+            distribution: distribution.clone(),
+            tax_cw20_msg: None,
+            tax_cw721_msg: None,
+            evidence: None,
+        },
+        &[],
+    );
+    assert_eq!(
+        result.unwrap_err().root_cause().to_string(),
+        "ResultCommitted"
+    );
+
+    // The admin dao can replace the commitment
+    let result = context.app.execute_contract(
+        admin.clone(),
+        context.core.sudo_proposal_addr.clone(),
+        &dao_proposal_sudo::msg::ExecuteMsg::Execute {
+            msgs: vec![WasmMsg::Execute {
+                contract_addr: context.wager.wager_module_addr.to_string(),
+                funds: vec![],
+                msg: to_json_binary(&ExecuteMsg::CommitResult {
+                    competition_id,
+                    hash: result_commitment(competition_id, &distribution, "replaced").unwrap(),
+                })
+                .unwrap(),
+            }
+            .into()],
+        },
+        &[],
+    );
+    assert!(result.is_ok());
+
+    // A reveal that does not match the commitment is rejected, including the replaced one
+    for nonce in ["guess", "secret"] {
+        let result = context.app.execute_contract(
+            admin.clone(),
+            context.wager.wager_module_addr.clone(),
+            &ExecuteMsg::RevealResult {
+                competition_id,
+                distribution: distribution.clone(),
+                nonce: nonce.to_string(),
+                evidence: None,
+            },
+            &[],
+        );
+        assert_eq!(
+            result.unwrap_err().root_cause().to_string(),
+            "InvalidReveal"
+        );
+    }
+
+    context
+        .app
+        .execute_contract(
+            admin.clone(),
+            context.wager.wager_module_addr.clone(),
+            &ExecuteMsg::RevealResult {
+                competition_id,
+                distribution,
+                nonce: "replaced".to_string(),
+                evidence: Some("ipfs://result".to_string()),
+            },
+            &[],
+        )
+        .unwrap();
+
+    // The revealed result keeps its evidence
+    let evidence: Option<String> = context
+        .app
+        .wrap()
+        .query_wasm_smart(
+            context.wager.wager_module_addr.clone(),
+            &QueryMsg::ResultEvidence { competition_id },
+        )
+        .unwrap();
+    assert_eq!(evidence, Some("ipfs://result".to_string()));
+
+    let result: Option<Distribution<Addr>> = context
+        .app
+        .wrap()
+        .query_wasm_smart(
+            context.wager.wager_module_addr.clone(),
+            &QueryMsg::Result { competition_id },
+        )
+        .unwrap();
+    assert_eq!(
+        result,
+        Some(Distribution {
+            member_percentages: vec![MemberPercentage {
+                addr: user1.clone(),
+                percentage: Decimal::one(),
+            }],
+            remainder_addr: user1,
+        })
+    );
+}
+
+#[test]
+fn test_jail_committed_result() {
+    let mut app = get_app();
+
+    let user1 = app.api().addr_make("user1");
+    let user2 = app.api().addr_make("user2");
+    let admin = app.api().addr_make(ADMIN);
+    let wager_amount = "10000juno";
+
+    set_balances(
+        &mut app,
+        vec![
+            (user1.clone(), Coins::from_str(wager_amount).unwrap()),
+            (user2.clone(), Coins::from_str(wager_amount).unwrap()),
+        ],
+    );
+    let core_context = setup_core_context(
+        &mut app,
+        vec![Member {
+            addr: admin.to_string(),
+            weight: 1u64,
+        }],
+    );
+    let wager_context = setup_wager_context(&mut app, &core_context);
+    let mut context = Context {
+        app,
+        core: core_context,
+        wager: wager_context,
+    };
+
+    let starting_height = context.app.block_info().height;
+    let competition_id = create_competition(
+        &mut context,
+        Expiration::AtHeight(starting_height + 1),
+        vec![
+            cw4::Member {
+                addr: user1.to_string(),
+                weight: 1u64,
+            },
+            cw4::Member {
+                addr: user2.to_string(),
+                weight: 1u64,
+            },
+        ],
+        Some(
+            [&user1, &user2]
+                .iter()
+                .map(|x| MemberBalanceUnchecked {
+                    addr: x.to_string(),
+                    balance: cw_balance::BalanceUnchecked {
+                        native: vec![Coin::from_str(wager_amount).unwrap()],
+                        cw20: vec![],
+                        cw721: vec![],
+                    },
+                })
+                .collect(),
+        ),
+    );
+    let competition: WagerResponse = context
+        .app
+        .wrap()
+        .query_wasm_smart(
+            context.wager.wager_module_addr.clone(),
+            &QueryMsg::Competition { competition_id },
+        )
+        .unwrap();
+    let escrow_addr = competition.escrow.unwrap();
+
+    for user in [&user1, &user2] {
+        context
+            .app
+            .execute_contract(
+                user.clone(),
+                escrow_addr.clone(),
+                &arena_escrow::msg::ExecuteMsg::ReceiveNative {},
+                &[Coin::from_str(wager_amount).unwrap()],
+            )
+            .unwrap();
+    }
+
+    // The host commits a result it never reveals
+    context
+        .app
+        .execute_contract(
+            competition.host.clone(),
+            context.wager.wager_module_addr.clone(),
+            &ExecuteMsg::CommitResult {
+                competition_id,
+                hash: result_commitment(competition_id, &None, "secret").unwrap(),
+            },
+            &[],
+        )
+        .unwrap();
+
+    context.app.update_block(next_block);
+    context
+        .app
+        .execute_contract(
+            user1.clone(),
+            context.wager.wager_module_addr.clone(),
+            &ExecuteMsg::JailCompetition {
+                propose_message: ProposeMessage {
+                    id: competition_id,
+                    title: "Title".to_string(),
+                    description: "Description".to_string(),
+                    distribution: None,
+                    tax_cw20_msg: None,
+                    tax_cw721_msg: None,
+                },
+            },
+            &[],
+        )
+        .unwrap();
+
+    // The dao settles the jailed competition despite the unrevealed commitment
+    let result = context.app.execute_contract(
+        admin.clone(),
+        context.core.sudo_proposal_addr.clone(),
+        &dao_proposal_sudo::msg::ExecuteMsg::Execute {
+            msgs: vec![WasmMsg::Execute {
+                contract_addr: context.wager.wager_module_addr.to_string(),
+                funds: vec![],
+                msg: to_json_binary(&ExecuteMsg::ProcessCompetition {
+                    competition_id,
+                    distribution: Some(Distribution::<String> {
+                        member_percentages: vec![MemberPercentage {
+                            addr: user1.to_string(),
+                            percentage: Decimal::one(),
+                        }],
+                        remainder_addr: user1.to_string(),
+                    }),
+                    tax_cw20_msg: None,
+                    tax_cw721_msg: None,
+                    evidence: None,
+                })
+                .unwrap(),
+            }
+            .into()],
+        },
+        &[],
+    );
+    assert!(result.is_ok());
+
+    let competition: WagerResponse = context
+        .app
+        .wrap()
+        .query_wasm_smart(
+            context.wager.wager_module_addr.clone(),
+            &QueryMsg::Competition { competition_id },
+        )
+        .unwrap();
+    assert_eq!(competition.status, CompetitionStatus::Inactive);
+
+    // The committed result can no longer be revealed
+    let result = context.app.execute_contract(
+        competition.host.clone(),
+        context.wager.wager_module_addr.clone(),
+        &ExecuteMsg::RevealResult {
+            competition_id,
+            distribution: None,
+            nonce: "secret".to_string(),
+            evidence: None,
+        },
+        &[],
+    );
+    assert!(result.is_err());
+
+    // Assert correct balances user 1 - 20_000*.85
+    context
+        .app
+        .execute_contract(
+            user1.clone(),
+            escrow_addr.clone(),
+            &arena_escrow::msg::ExecuteMsg::Withdraw {
+                cw20_msg: None,
+                cw721_msg: None,
+            },
+            &[],
+        )
+        .unwrap();
+    let balance = context
+        .app
+        .wrap()
+        .query_balance(user1.to_string(), "juno")
+        .unwrap();
+    assert_eq!(balance.amount, Uint128::from(17_000u128));
+}
+
+#[test]
+fn test_create_competition_expired() {
+    let mut app = get_app();
+    let admin = app.api().addr_make(ADMIN);
+
+    let core_context = setup_core_context(
+        &mut app,
+        vec![Member {
+            addr: admin.to_string(),
+            weight: 1u64,
+        }],
+    );
+    let wager_context = setup_wager_context(&mut app, &core_context);
+    let mut context = Context {
+        app,
+        core: core_context,
+        wager: wager_context,
+    };
+
+    // An expiration at the current height is already expired
+    let starting_height = context.app.block_info().height;
+    let result = context.app.execute_contract(
+        admin.clone(),
+        context.wager.wager_module_addr.clone(),
+        &ExecuteMsg::CreateCompetition {
+            category_id: Some(Uint128::one()),
+            host: ModuleInfo::Existing {
+                addr: admin.to_string(),
+            },
+            escrow: None,
+            name: "This is a competition name".to_string(),
+            description: "This is a description".to_string(),
+            expiration: Expiration::AtHeight(starting_height),
+            rules: vec!["Rule 1".to_string()],
+            rulesets: vec![],
+            result_authority: None,
+            instantiate_extension: CompetitionInstantiateExt::default(),
+        },
+        &[],
+    );
+    assert!(result.is_err());
+    assert_eq!(
+        result.unwrap_err().root_cause().to_string(),
+        "InvalidExpiration"
+    );
+}
+
+#[test]
+fn test_create_competition_existing_escrow() {
+    let mut app = get_app();
+    let user1 = app.api().addr_make("user1");
+    let user2 = app.api().addr_make("user2");
+    let admin = app.api().addr_make(ADMIN);
+    let wager_amount = "10000juno";
+
+    set_balances(
+        &mut app,
+        vec![
+            (user1.clone(), Coins::from_str(wager_amount).unwrap()),
+            (user2.clone(), Coins::from_str(wager_amount).unwrap()),
+        ],
+    );
+    let core_context = setup_core_context(
+        &mut app,
+        vec![Member {
+            addr: admin.to_string(),
+            weight: 1u64,
+        }],
+    );
+    let wager_context = setup_wager_context(&mut app, &core_context);
+    let mut context = Context {
+        app,
+        core: core_context,
+        wager: wager_context,
+    };
+
+    // Deploy an escrow outside of the competition module
+    let escrow_addr = context
+        .app
+        .instantiate_contract(
+            context.wager.escrow_id,
+            admin.clone(),
+            &arena_escrow::msg::InstantiateMsg {
+                dues: [&user1, &user2]
+                    .iter()
+                    .map(|x| MemberBalanceUnchecked {
+                        addr: x.to_string(),
+                        balance: cw_balance::BalanceUnchecked {
+                            native: vec![Coin::from_str(wager_amount).unwrap()],
+                            cw20: vec![],
+                            cw721: vec![],
+                        },
+                    })
+                    .collect(),
+                oracle: None,
+                competition_id: None,
+                activation_quorum: None,
+                min_participants: None,
+                cancel_on_close: None,
+                treasury: None,
+                auto_lock_delay: None,
+                metadata: None,
+            },
+            &[],
+            "Escrow",
+            None,
+        )
+        .unwrap();
+
+    let members = vec![
+        Member {
+            addr: user1.to_string(),
+            weight: 1u64,
+        },
+        Member {
+            addr: user2.to_string(),
+            weight: 1u64,
+        },
+    ];
+    let expiration = Expiration::AtHeight(context.app.block_info().height + 100);
+    let msg = create_competition_msg(
+        &context,
+        expiration,
+        members,
+        Some(ModuleInfo::Existing {
+            addr: escrow_addr.to_string(),
+        }),
+    );
+
+    // The escrow must be handed over to the competition module first
+    let result = context.app.execute_contract(
+        admin.clone(),
+        context.wager.wager_module_addr.clone(),
+        &msg,
+        &[],
+    );
+    assert_eq!(
+        result.unwrap_err().root_cause().to_string(),
+        "EscrowNotOwned"
+    );
+
+    context
+        .app
+        .execute_contract(
+            admin.clone(),
+            escrow_addr.clone(),
+            &arena_escrow::msg::ExecuteMsg::UpdateOwnership(
+                cw_ownable::Action::TransferOwnership {
+                    new_owner: context.wager.wager_module_addr.to_string(),
+                    expiry: None,
+                },
+            ),
+            &[],
+        )
+        .unwrap();
+
+    let result = context.app.execute_contract(
+        admin.clone(),
+        context.wager.wager_module_addr.clone(),
+        &msg,
+        &[],
+    );
+    assert!(result.is_ok());
+    let competition_id =
+        Uint128::from_str(&get_attr_value(&result.unwrap(), "competition_id").unwrap()).unwrap();
+
+    // The competition adopts the escrow, which now reports to the module
+    let competition: WagerResponse = context
+        .app
+        .wrap()
+        .query_wasm_smart(
+            context.wager.wager_module_addr.clone(),
+            &QueryMsg::Competition { competition_id },
+        )
+        .unwrap();
+    assert_eq!(competition.escrow, Some(escrow_addr.clone()));
+    assert_eq!(competition.status, CompetitionStatus::Pending);
+
+    let ownership: cw_ownable::Ownership<Addr> = context
+        .app
+        .wrap()
+        .query_wasm_smart(
+            escrow_addr.clone(),
+            &arena_escrow::msg::QueryMsg::Ownership {},
+        )
+        .unwrap();
+    assert_eq!(
+        ownership.owner,
+        Some(context.wager.wager_module_addr.clone())
+    );
+
+    // Funding the adopted escrow activates the competition
+    for user in [&user1, &user2] {
+        context
+            .app
+            .execute_contract(
+                user.clone(),
+                escrow_addr.clone(),
+                &arena_escrow::msg::ExecuteMsg::ReceiveNative {},
+                &[Coin::from_str(wager_amount).unwrap()],
+            )
+            .unwrap();
+    }
+    let competition: WagerResponse = context
+        .app
+        .wrap()
+        .query_wasm_smart(
+            context.wager.wager_module_addr.clone(),
+            &QueryMsg::Competition { competition_id },
+        )
+        .unwrap();
+    assert_eq!(competition.status, CompetitionStatus::Active);
+}
+
+#[test]
+fn test_escrow_adopted_once() {
+    let mut app = get_app();
+    let user1 = app.api().addr_make("user1");
+    let user2 = app.api().addr_make("user2");
+    let admin = app.api().addr_make(ADMIN);
+    let wager_amount = "10000juno";
+
+    set_balances(
+        &mut app,
+        vec![
+            (user1.clone(), Coins::from_str(wager_amount).unwrap()),
+            (user2.clone(), Coins::from_str(wager_amount).unwrap()),
+        ],
+    );
+    let core_context = setup_core_context(
+        &mut app,
+        vec![Member {
+            addr: admin.to_string(),
+            weight: 1u64,
+        }],
+    );
+    let wager_context = setup_wager_context(&mut app, &core_context);
+    let mut context = Context {
+        app,
+        core: core_context,
+        wager: wager_context,
+    };
+
+    // An existing escrow is handed to the competition module up front
+    let escrow_addr = context
+        .app
+        .instantiate_contract(
+            context.wager.escrow_id,
+            context.wager.wager_module_addr.clone(),
+            &arena_escrow::msg::InstantiateMsg {
+                dues: [&user1, &user2]
+                    .iter()
+                    .map(|x| MemberBalanceUnchecked {
+                        addr: x.to_string(),
+                        balance: cw_balance::BalanceUnchecked {
+                            native: vec![Coin::from_str(wager_amount).unwrap()],
+                            cw20: vec![],
+                            cw721: vec![],
+                        },
+                    })
+                    .collect(),
+                oracle: None,
+                competition_id: None,
+                activation_quorum: None,
+                min_participants: None,
+                cancel_on_close: None,
+                treasury: None,
+                auto_lock_delay: None,
+                metadata: None,
+            },
+            &[],
+            "Escrow",
+            None,
+        )
+        .unwrap();
+
+    let create_msg = ExecuteMsg::CreateCompetition {
+        category_id: Some(Uint128::one()),
+        host: ModuleInfo::Existing {
+            addr: admin.to_string(),
+        },
+        escrow: Some(ModuleInfo::Existing {
+            addr: escrow_addr.to_string(),
+        }),
+        name: "This is a competition name".to_string(),
+        description: "This is a description".to_string(),
+        expiration: Expiration::AtHeight(context.app.block_info().height + 100),
+        rules: vec!["Rule 1".to_string()],
+        rulesets: vec![],
+        result_authority: None,
+        instantiate_extension: CompetitionInstantiateExt::default(),
+    };
+    let result = context
+        .app
+        .execute_contract(
+            admin.clone(),
+            context.wager.wager_module_addr.clone(),
+            &create_msg,
+            &[],
+        )
+        .unwrap();
+    let competition_id =
+        Uint128::from_str(&get_attr_value(&result, "competition_id").unwrap()).unwrap();
+
+    // The escrow is tagged with the competition which adopted it
+    assert!(result.events.iter().any(|event| {
+        event.ty == "wasm"
+            && event
+                .attributes
+                .iter()
+                .any(|x| x.key == "action" && x.value == "set_competition_id")
+            && event
+                .attributes
+                .iter()
+                .any(|x| x.key == "competition_id" && x.value == competition_id.to_string())
+    }));
+
+    // Another competition cannot adopt the same escrow
+    let result = context.app.execute_contract(
+        admin.clone(),
+        context.wager.wager_module_addr.clone(),
+        &create_msg,
+        &[],
+    );
+    assert!(result.is_err());
+    assert!(result
+        .unwrap_err()
+        .root_cause()
+        .to_string()
+        .contains("The escrow is already used by another competition"));
+
+    // Funding the escrow activates the competition
+    for user in [&user1, &user2] {
+        context
+            .app
+            .execute_contract(
+                user.clone(),
+                escrow_addr.clone(),
+                &arena_escrow::msg::ExecuteMsg::ReceiveNative {},
+                &[Coin::from_str(wager_amount).unwrap()],
+            )
+            .unwrap();
+    }
+    let competition: WagerResponse = context
+        .app
+        .wrap()
+        .query_wasm_smart(
+            context.wager.wager_module_addr.clone(),
+            &QueryMsg::Competition { competition_id },
+        )
+        .unwrap();
+    assert_eq!(competition.status, CompetitionStatus::Active);
+
+    let tvl: TotalValueLockedResponse = context
+        .app
+        .wrap()
+        .query_wasm_smart(
+            context.wager.wager_module_addr.clone(),
+            &QueryMsg::TotalValueLocked {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        tvl.balance.native,
+        vec![Coin::from_str("20000juno").unwrap()]
+    );
+
+    context
+        .app
+        .execute_contract(
+            admin.clone(),
+            context.wager.wager_module_addr.clone(),
+            &ExecuteMsg::ProcessCompetition {
+                competition_id,
+                distribution: Some(Distribution::<String> {
+                    member_percentages: vec![MemberPercentage {
+                        addr: user1.to_string(),
+                        percentage: Decimal::one(),
+                    }],
+                    remainder_addr: context.core.dao_addr.to_string(),
+                }),
+                tax_cw20_msg: None,
+                tax_cw721_msg: None,
+                evidence: None,
+            },
+            &[],
+        )
+        .unwrap();
+    context
+        .app
+        .execute_contract(
+            user1.clone(),
+            escrow_addr.clone(),
+            &arena_escrow::msg::ExecuteMsg::Withdraw {
+                cw20_msg: None,
+                cw721_msg: None,
+            },
+            &[],
+        )
+        .unwrap();
+
+    // Assert correct balances user 1 - 20_000*.85
+    let balance = context
+        .app
+        .wrap()
+        .query_balance(user1.to_string(), "juno")
+        .unwrap();
+    assert_eq!(balance.amount, Uint128::from(17_000u128));
+}
+
+#[test]
+fn test_competition_rulesets() {
+    let mut app = get_app();
+
+    let admin = app.api().addr_make(ADMIN);
+    let core_context = setup_core_context(
+        &mut app,
+        vec![Member {
+            addr: admin.to_string(),
+            weight: 1u64,
+        }],
+    );
+    let wager_context = setup_wager_context(&mut app, &core_context);
+    let mut context = Context {
+        app,
+        core: core_context,
+        wager: wager_context,
+    };
+    let starting_height = context.app.block_info().height;
+
+    let result = context.app.execute_contract(
+        admin.clone(),
+        context.wager.wager_module_addr.clone(),
+        &ExecuteMsg::CreateCompetition {
+            category_id: Some(Uint128::one()),
+            host: ModuleInfo::Existing {
+                addr: admin.to_string(),
+            },
+            escrow: None,
+            name: "This is a competition name".to_string(),
+            description: "This is a description".to_string(),
+            expiration: Expiration::AtHeight(starting_height + 10),
+            rules: vec!["Rule 1".to_string()],
+            rulesets: vec![Uint128::one(), Uint128::new(2)],
+            result_authority: None,
+            instantiate_extension: CompetitionInstantiateExt::default(),
+        },
+        &[],
+    );
+    assert!(result.is_ok());
+    let competition_id =
+        Uint128::from_str(&get_attr_value(&result.unwrap(), "competition_id").unwrap()).unwrap();
+
+    // Both rulesets are resolved from the arena core in one query
+    let rulesets: Vec<Ruleset> = context
+        .app
+        .wrap()
+        .query_wasm_smart(
+            context.wager.wager_module_addr.clone(),
+            &QueryMsg::CompetitionRulesets { competition_id },
+        )
+        .unwrap();
+    assert_eq!(rulesets.len(), 2);
+    assert_eq!(rulesets[0].id, Uint128::one());
+    assert_eq!(rulesets[0].description, "Test Ruleset 1");
+    assert_eq!(rulesets[1].id, Uint128::new(2));
+    assert_eq!(rulesets[1].description, "Test Ruleset 2");
+    assert_eq!(
+        rulesets[1].rules,
+        vec![
+            "This is a rule".to_string(),
+            "This is another rule".to_string()
+        ]
+    );
+}
+
+#[test]
+fn test_payout_preference() {
+    let mut app = get_app();
+    let user1 = app.api().addr_make("user1");
+    let user2 = app.api().addr_make("user2");
+    let payout = app.api().addr_make("payout");
+    let wager_amount = "10000juno";
+    let admin = app.api().addr_make(ADMIN);
+
+    set_balances(
+        &mut app,
+        vec![
+            (user1.clone(), Coins::from_str(wager_amount).unwrap()),
+            (user2.clone(), Coins::from_str(wager_amount).unwrap()),
+        ],
+    );
+    let core_context = setup_core_context(
+        &mut app,
+        vec![Member {
+            addr: admin.to_string(),
+            weight: 1u64,
+        }],
+    );
+    let wager_context = setup_wager_context(&mut app, &core_context);
+    let mut context = Context {
+        app,
+        core: core_context,
+        wager: wager_context,
+    };
+
+    // user1 routes their winnings to another address
+    context
+        .app
+        .execute_contract(
+            user1.clone(),
+            context.core.arena_core_addr.clone(),
+            &arena_core_interface::msg::ExecuteMsg::Extension {
+                msg: arena_core_interface::msg::ExecuteExt::SetPayoutAddress {
+                    addr: payout.to_string(),
+                },
+            },
+            &[],
+        )
+        .unwrap();
+    let payout_address: Option<Addr> = context
+        .app
+        .wrap()
+        .query_wasm_smart(
+            context.core.arena_core_addr.clone(),
+            &arena_core_interface::msg::QueryMsg::QueryExtension {
+                msg: QueryExt::PayoutAddress {
+                    addr: user1.to_string(),
+                },
+            },
+        )
+        .unwrap();
+    assert_eq!(payout_address, Some(payout.clone()));
+
+    let starting_height = context.app.block_info().height;
+    let competition_id = create_competition(
+        &mut context,
+        Expiration::AtHeight(starting_height + 10),
+        vec![
+            cw4::Member {
+                addr: user1.to_string(),
+                weight: 1u64,
+            },
+            cw4::Member {
+                addr: user2.to_string(),
+                weight: 1u64,
+            },
+        ],
+        Some(
+            [&user1, &user2]
+                .iter()
+                .map(|x| MemberBalanceUnchecked {
+                    addr: x.to_string(),
+                    balance: cw_balance::BalanceUnchecked {
+                        native: vec![Coin::from_str(wager_amount).unwrap()],
+                        cw20: vec![],
+                        cw721: vec![],
+                    },
+                })
+                .collect(),
+        ),
+    );
+    let competition: WagerResponse = context
+        .app
+        .wrap()
+        .query_wasm_smart(
+            context.wager.wager_module_addr.clone(),
+            &QueryMsg::Competition { competition_id },
+        )
+        .unwrap();
+    let escrow = competition.escrow.unwrap();
+
+    for user in [&user1, &user2] {
+        context
+            .app
+            .execute_contract(
+                user.clone(),
+                escrow.clone(),
+                &arena_escrow::msg::ExecuteMsg::ReceiveNative {},
+                &[Coin::from_str(wager_amount).unwrap()],
+            )
+            .unwrap();
+    }
+
+    // Both members agree that user1 takes the pool
+    let approve_msg = arena_escrow::msg::ExecuteMsg::ApproveDistribution {
+        distribution: Distribution::<String> {
+            member_percentages: vec![MemberPercentage {
+                addr: user1.to_string(),
+                percentage: Decimal::one(),
+            }],
+            remainder_addr: user1.to_string(),
+        },
+    };
+    for user in [&user1, &user2] {
+        context
+            .app
+            .execute_contract(user.clone(), escrow.clone(), &approve_msg, &[])
+            .unwrap();
+    }
+
+    // The taxed winnings are credited to the preferred address
+    let query_balance = |context: &Context, addr: &Addr| -> Option<BalanceVerified> {
+        context
+            .app
+            .wrap()
+            .query_wasm_smart(
+                escrow.clone(),
+                &arena_escrow::msg::QueryMsg::Balance {
+                    addr: addr.to_string(),
+                },
+            )
+            .unwrap()
+    };
+    assert_eq!(
+        query_balance(&context, &payout).unwrap().native,
+        vec![Coin::from_str("17000juno").unwrap()]
+    );
+    assert!(query_balance(&context, &user1).is_none());
+}
+
+#[test]
+fn test_ruleset_currency() {
+    let mut app = get_app();
+    let user1 = app.api().addr_make("user1");
+    let user2 = app.api().addr_make("user2");
+    let admin = app.api().addr_make(ADMIN);
+
+    set_balances(
+        &mut app,
+        vec![
+            (user1.clone(), Coins::from_str("10000juno").unwrap()),
+            (user2.clone(), Coins::from_str("10000juno").unwrap()),
+        ],
+    );
+    let core_context = setup_core_context(
+        &mut app,
+        vec![Member {
+            addr: admin.to_string(),
+            weight: 1u64,
+        }],
+    );
+    let wager_context = setup_wager_context(&mut app, &core_context);
+    let mut context = Context {
+        app,
+        core: core_context,
+        wager: wager_context,
+    };
+
+    // Add a ruleset which requires juno
+    context
+        .app
+        .execute_contract(
+            admin.clone(),
+            context.core.sudo_proposal_addr.clone(),
+            &dao_proposal_sudo::msg::ExecuteMsg::Execute {
+                msgs: vec![WasmMsg::Execute {
+                    contract_addr: context.core.arena_core_addr.to_string(),
+                    funds: vec![],
+                    msg: to_json_binary(&arena_core_interface::msg::ExecuteMsg::Extension {
+                        msg: arena_core_interface::msg::ExecuteExt::UpdateRulesets {
+                            to_add: vec![arena_core_interface::msg::NewRuleset {
+                                category_id: Some(Uint128::one()),
+                                rules: vec!["Juno only".to_string()],
+                                description: "Juno Ruleset".to_string(),
+                                currency: Some("juno".to_string()),
+                            }],
+                            to_disable: vec![],
+                            reject_duplicates: None,
+                        },
+                    })
+                    .unwrap(),
+                }
+                .into()],
+            },
+            &[],
+        )
+        .unwrap();
+
+    // The dues omit their denom
+    let dues = [&user1, &user2]
+        .iter()
+        .map(|x| MemberBalanceUnchecked {
+            addr: x.to_string(),
+            balance: cw_balance::BalanceUnchecked {
+                native: vec![Coin {
+                    denom: "".to_string(),
+                    amount: Uint128::new(10000),
+                }],
+                cw20: vec![],
+                cw721: vec![],
+            },
+        })
+        .collect();
+    let escrow = ModuleInfo::New {
+        info: ModuleInstantiateInfo {
+            code_id: context.wager.escrow_id,
+            msg: to_json_binary(&arena_escrow::msg::InstantiateMsg {
+                dues,
+                oracle: None,
+                competition_id: None,
+                activation_quorum: None,
+                min_participants: None,
+                cancel_on_close: None,
+                treasury: None,
+                auto_lock_delay: None,
+                metadata: None,
+            })
+            .unwrap(),
+            admin: None,
+            label: "Escrow".to_owned(),
+        },
+    };
+    let starting_height = context.app.block_info().height;
+    let mut msg = create_competition_msg(
+        &context,
+        Expiration::AtHeight(starting_height + 10),
+        [&user1, &user2]
+            .iter()
+            .map(|x| cw4::Member {
+                addr: x.to_string(),
+                weight: 1u64,
+            })
+            .collect(),
+        Some(escrow),
+    );
+    if let ExecuteMsg::CreateCompetition { rulesets, .. } = &mut msg {
+        *rulesets = vec![Uint128::new(3)];
+    }
+    let result = context
+        .app
+        .execute_contract(
+            admin.clone(),
+            context.wager.wager_module_addr.clone(),
+            &msg,
+            &[],
+        )
+        .unwrap();
+    let competition_id =
+        Uint128::from_str(&get_attr_value(&result, "competition_id").unwrap()).unwrap();
+    let competition: WagerResponse = context
+        .app
+        .wrap()
+        .query_wasm_smart(
+            context.wager.wager_module_addr.clone(),
+            &QueryMsg::Competition { competition_id },
+        )
+        .unwrap();
+    let escrow = competition.escrow.unwrap();
+
+    // The dues are denominated in the ruleset's currency
+    let due: Option<BalanceVerified> = context
+        .app
+        .wrap()
+        .query_wasm_smart(
+            escrow.clone(),
+            &arena_escrow::msg::QueryMsg::Due {
+                addr: user1.to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        due.unwrap().native,
+        vec![Coin::from_str("10000juno").unwrap()]
+    );
+
+    // Paying in juno funds the escrow
+    for user in [&user1, &user2] {
+        context
+            .app
+            .execute_contract(
+                user.clone(),
+                escrow.clone(),
+                &arena_escrow::msg::ExecuteMsg::ReceiveNative {},
+                &[Coin::from_str("10000juno").unwrap()],
+            )
+            .unwrap();
+    }
+    let is_funded: bool = context
+        .app
+        .wrap()
+        .query_wasm_smart(
+            escrow,
+            &arena_escrow::msg::QueryMsg::IsFunded {
+                addr: user2.to_string(),
+            },
+        )
+        .unwrap();
+    assert!(is_funded);
+}
+
+#[test]
+fn test_max_rulesets_per_competition() {
+    let mut app = get_app();
+
+    let admin = app.api().addr_make(ADMIN);
+    let core_context = setup_core_context(
+        &mut app,
+        vec![Member {
+            addr: admin.to_string(),
+            weight: 1u64,
+        }],
+    );
+    let wager_context = setup_capped_wager_context(&mut app, &core_context, Some(1));
+    let mut context = Context {
+        app,
+        core: core_context,
+        wager: wager_context,
+    };
+    let starting_height = context.app.block_info().height;
+
+    let create_competition_msg = |rulesets: Vec<Uint128>| ExecuteMsg::CreateCompetition {
+        category_id: Some(Uint128::one()),
+        host: ModuleInfo::Existing {
+            addr: admin.to_string(),
+        },
+        escrow: None,
+        name: "This is a competition name".to_string(),
+        description: "This is a description".to_string(),
+        expiration: Expiration::AtHeight(starting_height + 10),
+        rules: vec!["Rule 1".to_string()],
+        rulesets,
+        result_authority: None,
+        instantiate_extension: CompetitionInstantiateExt::default(),
+    };
+
+    // One ruleset over the cap is rejected
+    let result = context.app.execute_contract(
+        admin.clone(),
+        context.wager.wager_module_addr.clone(),
+        &create_competition_msg(vec![Uint128::one(), Uint128::new(2)]),
+        &[],
+    );
+    assert_eq!(
+        result.unwrap_err().root_cause().to_string(),
+        "TooManyRulesets"
+    );
+
+    // Exactly the cap is allowed
+    let result = context.app.execute_contract(
+        admin.clone(),
+        context.wager.wager_module_addr.clone(),
+        &create_competition_msg(vec![Uint128::one()]),
+        &[],
+    );
+    assert!(result.is_ok());
+}
+
+#[test]
+fn test_escrows_needing_attention() {
+    let mut app = get_app();
+
+    let user1 = app.api().addr_make("user1");
+    let user2 = app.api().addr_make("user2");
+    let admin = app.api().addr_make(ADMIN);
+    let wager_amount = "10000juno";
+
+    set_balances(
+        &mut app,
+        vec![
+            (user1.clone(), Coins::from_str("20000juno").unwrap()),
+            (user2.clone(), Coins::from_str("20000juno").unwrap()),
+        ],
+    );
+    let core_context = setup_core_context(
+        &mut app,
+        vec![Member {
+            addr: admin.to_string(),
+            weight: 1u64,
+        }],
+    );
+    let wager_context = setup_wager_context(&mut app, &core_context);
+    let mut context = Context {
+        app,
+        core: core_context,
+        wager: wager_context,
+    };
+
+    let members = vec![
+        cw4::Member {
+            addr: user1.to_string(),
+            weight: 1u64,
+        },
+        cw4::Member {
+            addr: user2.to_string(),
+            weight: 1u64,
+        },
+    ];
+    let dues = vec![
+        MemberBalanceUnchecked {
+            addr: user1.to_string(),
+            balance: cw_balance::BalanceUnchecked {
+                native: vec![Coin::from_str(wager_amount).unwrap()],
+                cw20: vec![],
+                cw721: vec![],
+            },
+        },
+        MemberBalanceUnchecked {
+            addr: user2.to_string(),
+            balance: cw_balance::BalanceUnchecked {
+                native: vec![Coin::from_str(wager_amount).unwrap()],
+                cw20: vec![],
+                cw721: vec![],
+            },
+        },
+    ];
+
+    // An unfunded, a funded, a long-running and an escrowless competition
+    let starting_height = context.app.block_info().height;
+    let unfunded_id = create_competition(
+        &mut context,
+        Expiration::AtHeight(starting_height + 10),
+        members.clone(),
+        Some(dues.clone()),
+    );
+    let unresolved_id = create_competition(
+        &mut context,
+        Expiration::AtHeight(starting_height + 10),
+        members.clone(),
+        Some(dues.clone()),
+    );
+    let healthy_id = create_competition(
+        &mut context,
+        Expiration::AtHeight(starting_height + 1000),
+        members.clone(),
+        Some(dues),
+    );
+    let escrowless_id = create_competition(
+        &mut context,
+        Expiration::AtHeight(starting_height + 10),
+        members,
+        None,
+    );
+
+    let escrow = |context: &Context, competition_id: Uint128| -> Addr {
+        let competition: WagerResponse = context
+            .app
+            .wrap()
+            .query_wasm_smart(
+                context.wager.wager_module_addr.clone(),
+                &QueryMsg::Competition { competition_id },
+            )
+            .unwrap();
+        competition.escrow.unwrap()
+    };
+    let unfunded_escrow = escrow(&context, unfunded_id);
+    let unresolved_escrow = escrow(&context, unresolved_id);
+
+    // Fund one member of the first escrow and both members of the second
+    for (user, escrow) in [
+        (&user1, &unfunded_escrow),
+        (&user1, &unresolved_escrow),
+        (&user2, &unresolved_escrow),
+    ] {
+        context
+            .app
+            .execute_contract(
+                user.clone(),
+                escrow.clone(),
+                &arena_escrow::msg::ExecuteMsg::ReceiveNative {},
+                &[Coin::from_str(wager_amount).unwrap()],
+            )
+            .unwrap();
+    }
+
+    let query_msg = QueryMsg::EscrowsNeedingAttention {
+        competition_ids: vec![
+            unfunded_id,
+            unresolved_id,
+            healthy_id,
+            escrowless_id,
+            Uint128::new(99),
+        ],
+    };
+
+    // Nothing needs attention before the competitions expire
+    let stuck: Vec<EscrowAttentionResponse> = context
+        .app
+        .wrap()
+        .query_wasm_smart(context.wager.wager_module_addr.clone(), &query_msg)
+        .unwrap();
+    assert!(stuck.is_empty());
+
+    context.app.update_block(|block| block.height += 20);
+
+    let stuck: Vec<EscrowAttentionResponse> = context
+        .app
+        .wrap()
+        .query_wasm_smart(context.wager.wager_module_addr.clone(), &query_msg)
+        .unwrap();
+    assert_eq!(
+        stuck,
+        vec![
+            EscrowAttentionResponse {
+                competition_id: unfunded_id,
+                escrow: unfunded_escrow,
+                reason: EscrowAttentionReason::Unfunded,
+            },
+            EscrowAttentionResponse {
+                competition_id: unresolved_id,
+                escrow: unresolved_escrow,
+                reason: EscrowAttentionReason::Unresolved,
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_competition_funding() {
+    let mut app = get_app();
+
+    let user1 = app.api().addr_make("user1");
+    let user2 = app.api().addr_make("user2");
+    let admin = app.api().addr_make(ADMIN);
+    let wager_amount = "10000juno";
+
+    set_balances(
+        &mut app,
+        vec![
+            (user1.clone(), Coins::from_str("20000juno").unwrap()),
+            (user2.clone(), Coins::from_str("20000juno").unwrap()),
+        ],
+    );
+    let core_context = setup_core_context(
+        &mut app,
+        vec![Member {
+            addr: admin.to_string(),
+            weight: 1u64,
+        }],
+    );
+    let wager_context = setup_wager_context(&mut app, &core_context);
+    let mut context = Context {
+        app,
+        core: core_context,
+        wager: wager_context,
+    };
+
+    let starting_height = context.app.block_info().height;
+    let members = vec![
+        cw4::Member {
+            addr: user1.to_string(),
+            weight: 1u64,
+        },
+        cw4::Member {
+            addr: user2.to_string(),
+            weight: 1u64,
+        },
+    ];
+    let competition_id = create_competition(
+        &mut context,
+        Expiration::AtHeight(starting_height + 100),
+        members.clone(),
+        Some(
+            [&user1, &user2]
+                .iter()
+                .map(|user| MemberBalanceUnchecked {
+                    addr: user.to_string(),
+                    balance: cw_balance::BalanceUnchecked {
+                        native: vec![Coin::from_str(wager_amount).unwrap()],
+                        cw20: vec![],
+                        cw721: vec![],
+                    },
+                })
+                .collect(),
+        ),
+    );
+    let escrowless_id = create_competition(
+        &mut context,
+        Expiration::AtHeight(starting_height + 100),
+        members,
+        None,
+    );
+    let competition: WagerResponse = context
+        .app
+        .wrap()
+        .query_wasm_smart(
+            context.wager.wager_module_addr.clone(),
+            &QueryMsg::Competition { competition_id },
+        )
+        .unwrap();
+    let escrow = competition.escrow.unwrap();
+
+    // Only the first user funds
+    context
+        .app
+        .execute_contract(
+            user1.clone(),
+            escrow.clone(),
+            &arena_escrow::msg::ExecuteMsg::ReceiveNative {},
+            &[Coin::from_str(wager_amount).unwrap()],
+        )
+        .unwrap();
+
+    // The module reports the escrow's roster
+    let funding: Vec<CompetitionEscrowRosterEntry> = context
+        .app
+        .wrap()
+        .query_wasm_smart(
+            context.wager.wager_module_addr.clone(),
+            &QueryMsg::CompetitionFunding { competition_id },
+        )
+        .unwrap();
+    let roster: Vec<CompetitionEscrowRosterEntry> = context
+        .app
+        .wrap()
+        .query_wasm_smart(
+            escrow,
+            &arena_escrow::msg::QueryMsg::Roster {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(funding, roster);
+    assert_eq!(funding.len(), 2);
+    let entry = |addr: &Addr| funding.iter().find(|x| &x.addr == addr).unwrap();
+    assert!(entry(&user1).is_funded);
+    assert!(entry(&user1).balance.is_some());
+    assert!(!entry(&user2).is_funded);
+    assert!(entry(&user2).balance.is_none());
+
+    // A competition without an escrow has no funding
+    let funding: Vec<CompetitionEscrowRosterEntry> = context
+        .app
+        .wrap()
+        .query_wasm_smart(
+            context.wager.wager_module_addr.clone(),
+            &QueryMsg::CompetitionFunding {
+                competition_id: escrowless_id,
+            },
+        )
+        .unwrap();
+    assert!(funding.is_empty());
+}
+
+#[test]
+fn test_paused() {
+    let mut app = get_app();
+    let user1 = app.api().addr_make("user1");
+    let user2 = app.api().addr_make("user2");
+    let wager_amount = "10000juno";
+    let admin = app.api().addr_make(ADMIN);
+
+    set_balances(
+        &mut app,
+        vec![
+            (user1.clone(), Coins::from_str(wager_amount).unwrap()),
+            (user2.clone(), Coins::from_str(wager_amount).unwrap()),
+        ],
+    );
+    let core_context = setup_core_context(
+        &mut app,
+        vec![Member {
+            addr: admin.to_string(),
+            weight: 1u64,
+        }],
+    );
+    let wager_context = setup_wager_context(&mut app, &core_context);
+    let mut context = Context {
+        app,
+        core: core_context,
+        wager: wager_context,
+    };
+
+    let members = vec![
+        cw4::Member {
+            addr: user1.to_string(),
+            weight: 1u64,
+        },
+        cw4::Member {
+            addr: user2.to_string(),
+            weight: 1u64,
+        },
+    ];
+    let dues: Vec<MemberBalanceUnchecked> = [&user1, &user2]
+        .iter()
+        .map(|x| MemberBalanceUnchecked {
+            addr: x.to_string(),
+            balance: cw_balance::BalanceUnchecked {
+                native: vec![Coin::from_str(wager_amount).unwrap()],
+                cw20: vec![],
+                cw721: vec![],
+            },
+        })
+        .collect();
+    let starting_height = context.app.block_info().height;
+    let competition_id = create_competition(
+        &mut context,
+        Expiration::AtHeight(starting_height + 10),
+        members.clone(),
+        Some(dues),
+    );
+    let competition: WagerResponse = context
+        .app
+        .wrap()
+        .query_wasm_smart(
+            context.wager.wager_module_addr.clone(),
+            &QueryMsg::Competition { competition_id },
+        )
+        .unwrap();
+    let escrow = competition.escrow.unwrap();
+
+    let set_paused = |context: &mut Context, value: bool| {
+        context
+            .app
+            .execute_contract(
+                admin.clone(),
+                context.core.sudo_proposal_addr.clone(),
+                &dao_proposal_sudo::msg::ExecuteMsg::Execute {
+                    msgs: vec![WasmMsg::Execute {
+                        contract_addr: context.core.arena_core_addr.to_string(),
+                        funds: vec![],
+                        msg: to_json_binary(&arena_core_interface::msg::ExecuteMsg::Extension {
+                            msg: arena_core_interface::msg::ExecuteExt::SetPaused { value },
+                        })
+                        .unwrap(),
+                    }
+                    .into()],
+                },
+                &[],
+            )
+            .unwrap();
+    };
+
+    // Only the DAO can pause the arena
+    let result = context.app.execute_contract(
+        admin.clone(),
+        context.core.arena_core_addr.clone(),
+        &arena_core_interface::msg::ExecuteMsg::Extension {
+            msg: arena_core_interface::msg::ExecuteExt::SetPaused { value: true },
+        },
+        &[],
+    );
+    assert!(result.is_err());
+
+    set_paused(&mut context, true);
+    let is_paused: bool = context
+        .app
+        .wrap()
+        .query_wasm_smart(
+            context.core.arena_core_addr.clone(),
+            &arena_core_interface::msg::QueryMsg::QueryExtension {
+                msg: QueryExt::IsPaused {},
+            },
+        )
+        .unwrap();
+    assert!(is_paused);
+
+    // Competitions can't be created while paused
+    let msg = create_competition_msg(
+        &context,
+        Expiration::AtHeight(starting_height + 10),
+        members.clone(),
+        None,
+    );
+    let result = context.app.execute_contract(
+        admin.clone(),
+        context.wager.wager_module_addr.clone(),
+        &msg,
+        &[],
+    );
+    assert!(result.is_err());
+    assert_eq!(result.unwrap_err().root_cause().to_string(), "Paused");
+
+    // Existing escrows can't be funded while paused
+    let result = context.app.execute_contract(
+        user1.clone(),
+        escrow.clone(),
+        &arena_escrow::msg::ExecuteMsg::ReceiveNative {},
+        &[Coin::from_str(wager_amount).unwrap()],
+    );
+    assert!(result.is_err());
+    assert_eq!(result.unwrap_err().root_cause().to_string(), "Paused");
+
+    // Both resume once unpaused
+    set_paused(&mut context, false);
+    context
+        .app
+        .execute_contract(
+            user1.clone(),
+            escrow,
+            &arena_escrow::msg::ExecuteMsg::ReceiveNative {},
+            &[Coin::from_str(wager_amount).unwrap()],
+        )
+        .unwrap();
+    create_competition(
+        &mut context,
+        Expiration::AtHeight(starting_height + 10),
+        members,
+        None,
+    );
+}
+
+#[test]
+fn test_competition_module_by_key() {
+    let mut app = get_app();
+    let user1 = app.api().addr_make("user1");
+    let user2 = app.api().addr_make("user2");
+    let admin = app.api().addr_make(ADMIN);
+
+    let core_context = setup_core_context(
+        &mut app,
+        vec![Member {
+            addr: admin.to_string(),
+            weight: 1u64,
+        }],
+    );
+    let wager_context = setup_wager_context(&mut app, &core_context);
+    let mut context = Context {
+        app,
+        core: core_context,
+        wager: wager_context,
+    };
+
+    let starting_height = context.app.block_info().height;
+    create_competition(
+        &mut context,
+        Expiration::AtHeight(starting_height + 10),
+        vec![
+            cw4::Member {
+                addr: user1.to_string(),
+                weight: 1u64,
+            },
+            cw4::Member {
+                addr: user2.to_string(),
+                weight: 1u64,
+            },
+        ],
+        None,
+    );
+
+    // The key resolves to the whole registered module in one query
+    let competition_module: Option<CompetitionModuleResponse<String>> = context
+        .app
+        .wrap()
+        .query_wasm_smart(
+            context.core.arena_core_addr.clone(),
+            &arena_core_interface::msg::QueryMsg::QueryExtension {
+                msg: QueryExt::CompetitionModule {
+                    query: CompetitionModuleQuery::Key(context.wager.wagers_key.clone(), None),
+                },
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        competition_module,
+        Some(CompetitionModuleResponse {
+            key: context.wager.wagers_key.clone(),
+            addr: context.wager.wager_module_addr.to_string(),
+            is_enabled: true,
+            competition_count: Uint128::one(),
+        })
+    );
+
+    // Unknown keys resolve to nothing
+    let competition_module: Option<CompetitionModuleResponse<String>> = context
+        .app
+        .wrap()
+        .query_wasm_smart(
+            context.core.arena_core_addr.clone(),
+            &arena_core_interface::msg::QueryMsg::QueryExtension {
+                msg: QueryExt::CompetitionModule {
+                    query: CompetitionModuleQuery::Key("Unknown".to_string(), None),
+                },
+            },
+        )
+        .unwrap();
+    assert!(competition_module.is_none());
+}
+
+#[test]
+fn test_co_winners_remainder() {
+    let mut app = get_app();
+    let users: Vec<Addr> = (1..=3)
+        .map(|i| app.api().addr_make(&format!("user{}", i)))
+        .collect();
+    let treasury = app.api().addr_make("treasury");
+    let wager_amount = "10000juno";
+    let admin = app.api().addr_make(ADMIN);
+
+    set_balances(
+        &mut app,
+        users
+            .iter()
+            .map(|x| (x.clone(), Coins::from_str(wager_amount).unwrap()))
+            .collect(),
+    );
+    let core_context = setup_core_context(
+        &mut app,
+        vec![Member {
+            addr: admin.to_string(),
+            weight: 1u64,
+        }],
+    );
+    let wager_context = setup_wager_context(&mut app, &core_context);
+    let mut context = Context {
+        app,
+        core: core_context,
+        wager: wager_context,
+    };
+
+    // The wager sends its rounding dust to the treasury
+    let starting_height = context.app.block_info().height;
+    let escrow = ModuleInfo::New {
+        info: ModuleInstantiateInfo {
+            code_id: context.wager.escrow_id,
+            msg: to_json_binary(&arena_escrow::msg::InstantiateMsg {
+                dues: users
+                    .iter()
+                    .map(|x| MemberBalanceUnchecked {
+                        addr: x.to_string(),
+                        balance: cw_balance::BalanceUnchecked {
+                            native: vec![Coin::from_str(wager_amount).unwrap()],
+                            cw20: vec![],
+                            cw721: vec![],
+                        },
+                    })
+                    .collect(),
+                oracle: None,
+                competition_id: None,
+                activation_quorum: None,
+                min_participants: None,
+                cancel_on_close: None,
+                treasury: None,
+                auto_lock_delay: None,
+                metadata: None,
+            })
+            .unwrap(),
+            admin: None,
+            label: "Escrow".to_owned(),
+        },
+    };
+    let mut msg = create_competition_msg(
+        &context,
+        Expiration::AtHeight(starting_height + 10),
+        users
+            .iter()
+            .map(|x| cw4::Member {
+                addr: x.to_string(),
+                weight: 1u64,
+            })
+            .collect(),
+        Some(escrow),
+    );
+    if let ExecuteMsg::CreateCompetition {
+        instantiate_extension,
+        ..
+    } = &mut msg
+    {
+        instantiate_extension.remainder_addr = Some(treasury.to_string());
+    }
+    let result = context
+        .app
+        .execute_contract(
+            admin.clone(),
+            context.wager.wager_module_addr.clone(),
+            &msg,
+            &[],
+        )
+        .unwrap();
+    let competition_id =
+        Uint128::from_str(&get_attr_value(&result, "competition_id").unwrap()).unwrap();
+    let competition: WagerResponse = context
+        .app
+        .wrap()
+        .query_wasm_smart(
+            context.wager.wager_module_addr.clone(),
+            &QueryMsg::Competition { competition_id },
+        )
+        .unwrap();
+    assert_eq!(competition.extension.remainder_addr, Some(treasury.clone()));
+    let escrow = competition.escrow.unwrap();
+
+    for user in &users {
+        context
+            .app
+            .execute_contract(
+                user.clone(),
+                escrow.clone(),
+                &arena_escrow::msg::ExecuteMsg::ReceiveNative {},
+                &[Coin::from_str(wager_amount).unwrap()],
+            )
+            .unwrap();
+    }
+
+    // The taxed pool doesn't split evenly three ways
+    let result = context.app.execute_contract(
+        admin.clone(),
+        context.core.sudo_proposal_addr.clone(),
+        &dao_proposal_sudo::msg::ExecuteMsg::Execute {
+            msgs: vec![WasmMsg::Execute {
+                contract_addr: context.wager.wager_module_addr.to_string(),
+                msg: to_json_binary(&ExecuteMsg::Extension {
+                    msg: ExecuteExt::ProcessCoWinners {
+                        competition_id,
+                        winners: users.iter().map(|x| x.to_string()).collect(),
+                        evidence: None,
+                    },
+                })
+                .unwrap(),
+                funds: vec![],
+            }
+            .into()],
+        },
+        &[],
+    );
+    assert!(result.is_ok());
+
+    let query_balance = |context: &Context, addr: &Addr| -> Option<BalanceVerified> {
+        context
+            .app
+            .wrap()
+            .query_wasm_smart(
+                escrow.clone(),
+                &arena_escrow::msg::QueryMsg::Balance {
+                    addr: addr.to_string(),
+                },
+            )
+            .unwrap()
+    };
+
+    // The dust goes to the treasury, leaving the first co-winner level with the second
+    let treasury_balance = query_balance(&context, &treasury).unwrap();
+    assert!(!treasury_balance.native[0].amount.is_zero());
+    assert_eq!(
+        query_balance(&context, &users[0]),
+        query_balance(&context, &users[1])
+    );
+}
+
+#[test]
+fn test_blocked_assets() {
+    let mut app = get_app();
+    let user1 = app.api().addr_make("user1");
+    let user2 = app.api().addr_make("user2");
+    let wager_amount = "10000juno";
+    let admin = app.api().addr_make(ADMIN);
+
+    let core_context = setup_core_context(
+        &mut app,
+        vec![Member {
+            addr: admin.to_string(),
+            weight: 1u64,
+        }],
+    );
+    let wager_context = setup_wager_context(&mut app, &core_context);
+    let mut context = Context {
+        app,
+        core: core_context,
+        wager: wager_context,
+    };
+
+    let members = vec![
+        cw4::Member {
+            addr: user1.to_string(),
+            weight: 1u64,
+        },
+        cw4::Member {
+            addr: user2.to_string(),
+            weight: 1u64,
+        },
+    ];
+    let dues: Vec<MemberBalanceUnchecked> = [&user1, &user2]
+        .iter()
+        .map(|x| MemberBalanceUnchecked {
+            addr: x.to_string(),
+            balance: cw_balance::BalanceUnchecked {
+                native: vec![Coin::from_str(wager_amount).unwrap()],
+                cw20: vec![],
+                cw721: vec![],
+            },
+        })
+        .collect();
+    let starting_height = context.app.block_info().height;
+
+    let update_blocked_assets = |context: &mut Context, to_add: Vec<&str>, to_remove: Vec<&str>| {
+        context
+            .app
+            .execute_contract(
+                admin.clone(),
+                context.core.sudo_proposal_addr.clone(),
+                &dao_proposal_sudo::msg::ExecuteMsg::Execute {
+                    msgs: vec![WasmMsg::Execute {
+                        contract_addr: context.core.arena_core_addr.to_string(),
+                        funds: vec![],
+                        msg: to_json_binary(&arena_core_interface::msg::ExecuteMsg::Extension {
+                            msg: arena_core_interface::msg::ExecuteExt::UpdateBlockedAssets {
+                                to_add: to_add.iter().map(|x| x.to_string()).collect(),
+                                to_remove: to_remove.iter().map(|x| x.to_string()).collect(),
+                            },
+                        })
+                        .unwrap(),
+                    }
+                    .into()],
+                },
+                &[],
+            )
+            .unwrap();
+    };
+
+    // Only the DAO can block assets
+    let result = context.app.execute_contract(
+        admin.clone(),
+        context.core.arena_core_addr.clone(),
+        &arena_core_interface::msg::ExecuteMsg::Extension {
+            msg: arena_core_interface::msg::ExecuteExt::UpdateBlockedAssets {
+                to_add: vec!["juno".to_string()],
+                to_remove: vec![],
+            },
+        },
+        &[],
+    );
+    assert!(result.is_err());
+
+    update_blocked_assets(&mut context, vec!["juno", "atom"], vec![]);
+    let is_blocked: bool = context
+        .app
+        .wrap()
+        .query_wasm_smart(
+            context.core.arena_core_addr.clone(),
+            &arena_core_interface::msg::QueryMsg::QueryExtension {
+                msg: QueryExt::IsAssetBlocked {
+                    asset: "juno".to_string(),
+                },
+            },
+        )
+        .unwrap();
+    assert!(is_blocked);
+    let blocked_assets: Vec<String> = context
+        .app
+        .wrap()
+        .query_wasm_smart(
+            context.core.arena_core_addr.clone(),
+            &arena_core_interface::msg::QueryMsg::QueryExtension {
+                msg: QueryExt::BlockedAssets {
+                    start_after: None,
+                    limit: None,
+                },
+            },
+        )
+        .unwrap();
+    assert_eq!(blocked_assets, vec!["atom".to_string(), "juno".to_string()]);
+
+    // A competition with dues in a blocked denom is rejected
+    let escrow = ModuleInfo::New {
+        info: ModuleInstantiateInfo {
+            code_id: context.wager.escrow_id,
+            msg: to_json_binary(&arena_escrow::msg::InstantiateMsg {
+                dues: dues.clone(),
+                oracle: None,
+                competition_id: None,
+                activation_quorum: None,
+                min_participants: None,
+                cancel_on_close: None,
+                treasury: None,
+                auto_lock_delay: None,
+                metadata: None,
+            })
+            .unwrap(),
+            admin: None,
+            label: "Escrow".to_owned(),
+        },
+    };
+    let msg = create_competition_msg(
+        &context,
+        Expiration::AtHeight(starting_height + 10),
+        members.clone(),
+        Some(escrow),
+    );
+    let result = context.app.execute_contract(
+        admin.clone(),
+        context.wager.wager_module_addr.clone(),
+        &msg,
+        &[],
+    );
+    assert!(result.is_err());
+    assert_eq!(result.unwrap_err().root_cause().to_string(), "BlockedAsset");
+
+    // The competition can be created once the denom is unblocked
+    update_blocked_assets(&mut context, vec![], vec!["juno"]);
+    create_competition(
+        &mut context,
+        Expiration::AtHeight(starting_height + 10),
+        members,
+        Some(dues),
+    );
+}
+
+#[test]
+fn test_batch_distribute() {
+    let mut app = get_app();
+
+    let admin = app.api().addr_make(ADMIN);
+    let user1 = app.api().addr_make("user1");
+    let user2 = app.api().addr_make("user2");
+    let wager_amount = "100juno";
+
+    set_balances(
+        &mut app,
+        vec![
+            (user1.clone(), Coins::from_str("300juno").unwrap()),
+            (user2.clone(), Coins::from_str("300juno").unwrap()),
+        ],
+    );
+    let core_context = setup_core_context(
+        &mut app,
+        vec![Member {
+            addr: admin.to_string(),
+            weight: 1u64,
+        }],
+    );
+    let wager_context = setup_wager_context(&mut app, &core_context);
+    let mut context = Context {
+        app,
+        core: core_context,
+        wager: wager_context,
+    };
+
+    let starting_height = context.app.block_info().height;
+    let mut competitions = vec![];
+    for _ in 0..3 {
+        let competition_id = create_competition(
+            &mut context,
+            Expiration::AtHeight(starting_height + 100),
+            [&user1, &user2]
+                .iter()
+                .map(|x| cw4::Member {
+                    addr: x.to_string(),
+                    weight: 1u64,
+                })
+                .collect(),
+            Some(
+                [&user1, &user2]
+                    .iter()
+                    .map(|x| MemberBalanceUnchecked {
+                        addr: x.to_string(),
+                        balance: cw_balance::BalanceUnchecked {
+                            native: vec![Coin::from_str(wager_amount).unwrap()],
+                            cw20: vec![],
+                            cw721: vec![],
+                        },
+                    })
+                    .collect(),
+            ),
+        );
+        let competition: WagerResponse = context
+            .app
+            .wrap()
+            .query_wasm_smart(
+                context.wager.wager_module_addr.clone(),
+                &QueryMsg::Competition { competition_id },
+            )
+            .unwrap();
+        competitions.push((competition_id, competition.escrow.unwrap()));
+    }
+
+    // The first two escrows are fully funded, but the last is still pending
+    for (user, (_, escrow)) in [
+        (&user1, &competitions[0]),
+        (&user2, &competitions[0]),
+        (&user1, &competitions[1]),
+        (&user2, &competitions[1]),
+        (&user1, &competitions[2]),
+    ] {
+        context
+            .app
+            .execute_contract(
+                user.clone(),
+                escrow.clone(),
+                &arena_escrow::msg::ExecuteMsg::ReceiveNative {},
+                &[Coin::from_str(wager_amount).unwrap()],
+            )
+            .unwrap();
+    }
+
+    let shares = vec![
+        MemberShare {
+            addr: user1.to_string(),
+            shares: Uint128::one(),
+        },
+        MemberShare {
+            addr: user2.to_string(),
+            shares: Uint128::one(),
+        },
+    ];
+    let result = context.app.execute_contract(
+        admin.clone(),
+        context.core.sudo_proposal_addr.clone(),
+        &dao_proposal_sudo::msg::ExecuteMsg::Execute {
+            msgs: vec![WasmMsg::Execute {
+                contract_addr: context.core.arena_core_addr.to_string(),
+                funds: vec![],
+                msg: to_json_binary(&arena_core_interface::msg::ExecuteMsg::Extension {
+                    msg: arena_core_interface::msg::ExecuteExt::BatchDistribute {
+                        distributions: competitions
+                            .iter()
+                            .map(|(_, escrow)| {
+                                (escrow.to_string(), shares.clone(), user1.to_string())
+                            })
+                            .collect(),
+                    },
+                })
+                .unwrap(),
+            }
+            .into()],
+        },
+        &[],
+    );
+    assert!(result.is_ok());
+
+    // The pending competition fails without reverting the others
+    assert!(result.unwrap().events.iter().any(|event| event
+        .attributes
+        .iter()
+        .any(|attr| attr.key == "action" && attr.value == "batch_distribute_failure")));
+
+    for (i, (competition_id, escrow)) in competitions.iter().enumerate() {
+        let competition: WagerResponse = context
+            .app
+            .wrap()
+            .query_wasm_smart(
+                context.wager.wager_module_addr.clone(),
+                &QueryMsg::Competition {
+                    competition_id: *competition_id,
+                },
+            )
+            .unwrap();
+        let balance: Option<BalanceVerified> = context
+            .app
+            .wrap()
+            .query_wasm_smart(
+                escrow.clone(),
+                &arena_escrow::msg::QueryMsg::Balance {
+                    addr: user2.to_string(),
+                },
+            )
+            .unwrap();
+
+        // Each processed escrow is taxed 15% before being split evenly
+        if i < 2 {
+            assert_eq!(competition.status, CompetitionStatus::Inactive);
+            assert_eq!(
+                balance.unwrap().native,
+                vec![Coin::from_str("85juno").unwrap()]
+            );
+        } else {
+            assert_eq!(competition.status, CompetitionStatus::Pending);
+            assert!(balance.is_none());
+        }
+    }
+}
@@ -1,19 +1,25 @@
 use crate::{
     execute,
-    msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg},
+    msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg, Quorum},
     query,
-    state::{self, DUE, HAS_DISTRIBUTED, INITIAL_DUE, IS_LOCKED},
+    state::{
+        self, ACTIVATION_QUORUM, AUTO_LOCK_DELAY, CANCEL_ON_CLOSE, COMPETITION_ID, DUE,
+        HAS_DISTRIBUTED, HOUSE, INITIAL_DUE, IS_LOCKED, LISTENERS, METADATA, MIN_PARTICIPANTS,
+        ORACLE, SCHEDULED_LOCK, TOTAL_BALANCE, TREASURY,
+    },
     ContractError,
 };
 use cosmwasm_std::{
-    entry_point, to_json_binary, Binary, Deps, DepsMut, Env, MessageInfo, Response, StdResult,
+    entry_point, to_json_binary, Binary, Decimal, Deps, DepsMut, Env, MessageInfo, Reply, Response,
+    StdError, StdResult,
 };
 use cw2::set_contract_version;
-use cw_balance::MemberBalanceUnchecked;
+use cw_balance::{BalanceVerified, MemberBalanceChecked, MemberBalanceUnchecked};
 
 // version info for migration info
 pub(crate) const CONTRACT_NAME: &str = "crates.io:arena-escrow";
 pub(crate) const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+pub(crate) const SCHEMA_VERSION: u32 = 2;
 
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
@@ -23,14 +29,76 @@ pub fn instantiate(
     msg: InstantiateMsg,
 ) -> Result<Response, ContractError> {
     set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
-    instantiate_contract(deps, info, msg.dues)?;
+    if let Some(oracle) = &msg.oracle {
+        ORACLE.save(deps.storage, &deps.api.addr_validate(oracle)?)?;
+    }
+    if let Some(competition_id) = msg.competition_id {
+        COMPETITION_ID.save(deps.storage, &competition_id)?;
+    }
+    if let Some(quorum) = &msg.activation_quorum {
+        let is_valid = match quorum {
+            Quorum::Members { count } => *count > 0 && *count as usize <= msg.dues.len(),
+            Quorum::Percentage { value } => !value.is_zero() && *value <= Decimal::one(),
+        };
+        if !is_valid {
+            return Err(ContractError::StdError(StdError::generic_err(
+                "The activation quorum must be reachable by the members",
+            )));
+        }
+        ACTIVATION_QUORUM.save(deps.storage, quorum)?;
+    }
+    if let Some(min_participants) = msg.min_participants {
+        if min_participants as usize > msg.dues.len() {
+            return Err(ContractError::StdError(StdError::generic_err(
+                "The minimum participants cannot exceed the members",
+            )));
+        }
+        MIN_PARTICIPANTS.save(deps.storage, &min_participants)?;
+    }
+    if let Some(auto_lock_delay) = msg.auto_lock_delay {
+        AUTO_LOCK_DELAY.save(deps.storage, &auto_lock_delay)?;
+    }
+    if let Some(metadata) = &msg.metadata {
+        METADATA.save(deps.storage, metadata)?;
+    }
+    if let Some(treasury) = &msg.treasury {
+        TREASURY.save(deps.storage, &deps.api.addr_validate(treasury)?)?;
+    }
+    if msg.cancel_on_close.unwrap_or_default() {
+        CANCEL_ON_CLOSE.save(deps.storage, &true)?;
+    }
+
+    // Seed the prize pool with any funds sent by the instantiator
+    let house = BalanceVerified {
+        native: info.funds.clone(),
+        cw20: vec![],
+        cw721: vec![],
+    };
+    if !house.is_empty() {
+        TOTAL_BALANCE.save(deps.storage, &house, env.block.height)?;
+        HOUSE.save(
+            deps.storage,
+            &MemberBalanceChecked {
+                addr: info.sender.clone(),
+                balance: house.clone(),
+            },
+        )?;
+    }
+
+    instantiate_contract(deps, &env, info, msg.dues)?;
     Ok(Response::new()
         .add_attribute("action", "instantiate")
+        .add_attribute("house", house.to_string())
+        .add_attributes(
+            msg.competition_id
+                .map(|id| ("competition_id", id.to_string())),
+        )
         .add_attribute("addr", env.contract.address))
 }
 
 pub fn instantiate_contract(
     deps: DepsMut,
+    env: &Env,
     info: MessageInfo,
     due: Vec<MemberBalanceUnchecked>,
 ) -> Result<(), ContractError> {
@@ -39,14 +107,9 @@ pub fn instantiate_contract(
             msg: "None due".to_string(),
         });
     }
-    if due.len() == 1 {
-        return Err(ContractError::InvalidDue {
-            msg: "Only one due".to_string(),
-        });
-    }
 
     cw_ownable::initialize_owner(deps.storage, deps.api, Some(info.sender.as_str()))?;
-    IS_LOCKED.save(deps.storage, &false)?;
+    IS_LOCKED.save(deps.storage, &false, env.block.height)?;
     HAS_DISTRIBUTED.save(deps.storage, &false)?;
     for member_balance in due {
         let member_balance = member_balance.into_checked(deps.as_ref())?;
@@ -58,6 +121,7 @@ pub fn instantiate_contract(
                 },
             ));
         }
+        execute::assert_assets_allowed(deps.as_ref(), &member_balance.balance)?;
 
         INITIAL_DUE.save(deps.storage, &member_balance.addr, &member_balance.balance)?;
         DUE.save(deps.storage, &member_balance.addr, &member_balance.balance)?;
@@ -74,27 +138,82 @@ pub fn execute(
     msg: ExecuteMsg,
 ) -> Result<Response, ContractError> {
     match msg {
-        ExecuteMsg::ReceiveNative {} => execute::receive_native(deps, info),
+        ExecuteMsg::ReceiveNative {} => execute::receive_native(deps, env, info),
+        ExecuteMsg::ReceiveDeposit {} => execute::receive_deposit(deps, info),
         ExecuteMsg::Withdraw {
             cw20_msg,
             cw721_msg,
-        } => execute::withdraw(deps, info, cw20_msg, cw721_msg),
+        } => execute::withdraw(deps, env, info, cw20_msg, cw721_msg),
         ExecuteMsg::SetDistribution { distribution } => {
             execute::set_distribution(deps, info, distribution)
         }
+        ExecuteMsg::ClearDistribution {} => execute::clear_distribution(deps, info),
         ExecuteMsg::Receive(cw20_receive_msg) => {
-            execute::receive_cw20(deps, info, cw20_receive_msg)
+            execute::receive_cw20(deps, env, info, cw20_receive_msg)
         }
         ExecuteMsg::ReceiveNft(cw721_receive_msg) => {
-            execute::receive_cw721(deps, info, cw721_receive_msg)
+            execute::receive_cw721(deps, env, info, cw721_receive_msg)
         }
         ExecuteMsg::Distribute(competition_escrow_distribute_msg) => execute::distribute(
             deps,
+            env,
             info,
             competition_escrow_distribute_msg.distribution,
+            competition_escrow_distribute_msg.bonus_distribution,
+            competition_escrow_distribute_msg.require_full_funding,
+            competition_escrow_distribute_msg.rounding,
             competition_escrow_distribute_msg.tax_info,
+            competition_escrow_distribute_msg.fixed_amounts,
+            competition_escrow_distribute_msg.treasury,
+            competition_escrow_distribute_msg.nft_mode,
         ),
-        ExecuteMsg::Lock { value } => execute::lock(deps, info, value),
+        ExecuteMsg::ApproveDistribution { distribution } => {
+            execute::approve_distribution(deps, env, info, distribution)
+        }
+        ExecuteMsg::PayoutBatch { limit } => execute::payout_batch(deps, env, limit),
+        ExecuteMsg::WithdrawNfts {
+            collection,
+            token_ids,
+            cw721_msg,
+        } => execute::withdraw_nfts(deps, env, info, collection, token_ids, cw721_msg),
+        ExecuteMsg::WithdrawCollection {
+            collection,
+            cw721_msg,
+        } => execute::withdraw_collection(deps, env, info, collection, cw721_msg),
+        ExecuteMsg::Lock { value } => execute::lock(deps, env, info, value),
+        ExecuteMsg::MutualCancel {} => execute::mutual_cancel(deps, env, info),
+        ExecuteMsg::SettleSolo { member_wins } => {
+            execute::settle_solo(deps, env, info, member_wins)
+        }
+        ExecuteMsg::LockScheduled {} => execute::lock_scheduled(deps, env),
+        ExecuteMsg::NoContest {
+            fee,
+            refund_priority,
+        } => execute::no_contest(deps, env, info, fee, refund_priority),
+        ExecuteMsg::AbsorbEscrow { other } => execute::absorb_escrow(deps, env, info, other),
+        ExecuteMsg::ExpectAbsorb { by } => execute::expect_absorb(deps, info, by),
+        ExecuteMsg::TransferToEscrow {} => execute::transfer_to_escrow(deps, env, info),
+        ExecuteMsg::UpdateListeners { to_add, to_remove } => {
+            execute::update_listeners(deps, info, to_add, to_remove)
+        }
+        ExecuteMsg::ConsolidatePool {
+            router,
+            target_denom,
+            min_output,
+        } => execute::consolidate_pool(deps, env, info, router, target_denom, min_output),
+        ExecuteMsg::UpdateMetadata { metadata } => execute::update_metadata(deps, info, metadata),
+        ExecuteMsg::SetCompetitionId { competition_id } => {
+            execute::set_competition_id(deps, info, competition_id)
+        }
+        ExecuteMsg::UpdateResolvers { to_add, to_remove } => {
+            execute::update_resolvers(deps, info, to_add, to_remove)
+        }
+        ExecuteMsg::PullCw20 {
+            token,
+            owner,
+            amount,
+        } => execute::pull_cw20(deps, env, token, owner, amount),
+        ExecuteMsg::SetDueDenom { denom } => execute::set_due_denom(deps, info, denom),
         ExecuteMsg::UpdateOwnership(action) => {
             let ownership = cw_ownable::update_ownership(deps, &env.block, &info.sender, action)?;
             Ok(Response::new().add_attributes(ownership.into_attributes()))
@@ -107,22 +226,85 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
         QueryMsg::Balance { addr } => to_json_binary(&query::balance(deps, addr)?),
         QueryMsg::Due { addr } => to_json_binary(&query::due(deps, addr)?),
+        QueryMsg::Deposit { addr } => to_json_binary(&query::deposit(deps, addr)?),
+        QueryMsg::NetPosition { addr } => to_json_binary(&query::net_position(deps, addr)?),
         QueryMsg::TotalBalance {} => to_json_binary(&query::total_balance(deps)?),
         QueryMsg::IsLocked {} => to_json_binary(&query::is_locked(deps)),
+        QueryMsg::Metadata {} => to_json_binary(&METADATA.may_load(deps.storage)?),
+        QueryMsg::ScheduledLock {} => to_json_binary(&SCHEDULED_LOCK.may_load(deps.storage)?),
+        QueryMsg::DistributionRemainder { distribution } => {
+            to_json_binary(&query::distribution_remainder(deps, distribution)?)
+        }
+        QueryMsg::EffectiveDistribution {
+            distribution,
+            remainder_addr,
+            bonus_distribution,
+            require_full_funding,
+            rounding,
+            tax,
+            fixed_amounts,
+            nft_mode,
+        } => to_json_binary(&query::effective_distribution(
+            deps,
+            distribution,
+            remainder_addr,
+            bonus_distribution,
+            require_full_funding,
+            rounding,
+            tax,
+            fixed_amounts,
+            nft_mode,
+        )?),
+        QueryMsg::TotalBalanceAt { height } => {
+            to_json_binary(&query::total_balance_at(deps, height)?)
+        }
+        QueryMsg::IsLockedAt { height } => to_json_binary(&query::is_locked_at(deps, height)?),
         QueryMsg::Distribution { addr } => to_json_binary(&query::distribution(deps, addr)?),
+        QueryMsg::MemberTimeline { addr } => to_json_binary(&query::member_timeline(deps, addr)?),
         QueryMsg::IsFunded { addr } => to_json_binary(&query::is_funded(deps, addr)?),
         QueryMsg::IsFullyFunded {} => to_json_binary(&state::is_fully_funded(deps)),
+        QueryMsg::WillActivateOnFunding { addr, balance } => {
+            to_json_binary(&query::will_activate_on_funding(deps, addr, balance)?)
+        }
+        QueryMsg::FundedAt { addr } => to_json_binary(&query::funded_at(deps, addr)?),
         QueryMsg::Balances { start_after, limit } => {
             to_json_binary(&query::balances(deps, start_after, limit)?)
         }
+        QueryMsg::Roster { start_after, limit } => {
+            to_json_binary(&query::roster(deps, start_after, limit)?)
+        }
         QueryMsg::Dues { start_after, limit } => {
             to_json_binary(&query::dues(deps, start_after, limit)?)
         }
+        QueryMsg::OutstandingFunding {} => to_json_binary(&query::outstanding_funding(deps)?),
         QueryMsg::InitialDues { start_after, limit } => {
             to_json_binary(&query::initial_dues(deps, start_after, limit)?)
         }
         QueryMsg::Ownership {} => to_json_binary(&cw_ownable::get_ownership(deps.storage)?),
         QueryMsg::DumpState { addr } => to_json_binary(&query::dump_state(deps, addr)?),
+        QueryMsg::DistributionHistory {} => to_json_binary(&query::distribution_history(deps)?),
+        QueryMsg::HeldAssets {} => to_json_binary(&query::held_assets(deps)?),
+        QueryMsg::Listeners {} => {
+            to_json_binary(&LISTENERS.may_load(deps.storage)?.unwrap_or_default())
+        }
+        QueryMsg::Resolvers {} => to_json_binary(&query::resolvers(deps)?),
+        QueryMsg::ContractInfo {} => to_json_binary(&query::contract_info(deps)?),
+        QueryMsg::House {} => to_json_binary(&HOUSE.may_load(deps.storage)?),
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn reply(deps: DepsMut, env: Env, msg: Reply) -> Result<Response, ContractError> {
+    match msg.id {
+        execute::PULL_CW20_REPLY_ID => execute::pull_cw20_reply(deps, env),
+        execute::CONSOLIDATE_REPLY_ID => execute::consolidate_reply(deps, env),
+        // The owner rejected the activation, so the escrow is unlocked
+        execute::ACTIVATE_REPLY_ID => execute::activate_reply(deps, env, msg.result.unwrap_err()),
+        // A listener failing to handle an event should not block the escrow
+        execute::LISTENER_REPLY_ID => Ok(Response::new()
+            .add_attribute("action", "listener_failed")
+            .add_attribute("error", msg.result.unwrap_err())),
+        _ => Err(ContractError::UnknownReplyId { id: msg.id }),
     }
 }
 
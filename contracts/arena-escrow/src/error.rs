@@ -1,4 +1,7 @@
-use cosmwasm_std::{CheckedFromRatioError, CheckedMultiplyFractionError, OverflowError, StdError};
+use cosmwasm_std::{
+    CheckedFromRatioError, CheckedMultiplyFractionError, Decimal, OverflowError, OverflowOperation,
+    StdError, Uint128,
+};
 use cw_balance::BalanceError;
 use cw_ownable::OwnershipError;
 use thiserror::Error;
@@ -37,4 +40,65 @@ pub enum ContractError {
 
     #[error("EmptyBalance")]
     EmptyBalance {},
+
+    #[error("NotDistributed")]
+    NotDistributed {},
+
+    #[error("AlreadyDistributed")]
+    AlreadyDistributed {},
+
+    #[error("BalanceOverflow")]
+    BalanceOverflow {},
+
+    #[error("InsufficientBalance")]
+    InsufficientBalance {},
+
+    #[error("NoFunds")]
+    NoFunds {},
+
+    #[error("NotAParticipant")]
+    NotAParticipant {},
+
+    #[error("AlreadyFunded")]
+    AlreadyFunded {},
+
+    #[error("UnknownReplyId")]
+    UnknownReplyId { id: u64 },
+
+    #[error("ShareTotalOverflow")]
+    ShareTotalOverflow {},
+
+    #[error("NotEnoughParticipants")]
+    NotEnoughParticipants { min_participants: u32 },
+
+    #[error("RequiredNft")]
+    RequiredNft { token_id: String },
+
+    #[error("FeeTooHigh")]
+    FeeTooHigh { max: Decimal },
+
+    #[error("Slippage")]
+    Slippage {
+        min_output: Uint128,
+        received: Uint128,
+    },
+
+    #[error("Paused")]
+    Paused {},
+
+    #[error("BlockedAsset")]
+    BlockedAsset { asset: String },
+}
+
+impl ContractError {
+    /// Maps the overflow errors from cw-balance arithmetic to typed variants
+    pub fn from_balance_math(err: StdError) -> Self {
+        match err {
+            StdError::Overflow { source } => match source.operation {
+                OverflowOperation::Sub => ContractError::InsufficientBalance {},
+                _ => ContractError::BalanceOverflow {},
+            },
+            err => ContractError::StdError(err),
+        }
+    }
 }
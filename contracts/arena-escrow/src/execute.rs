@@ -1,60 +1,123 @@
+use std::collections::BTreeSet;
+
 use cosmwasm_std::{
-    to_json_binary, Addr, Binary, CosmosMsg, DepsMut, Empty, MessageInfo, Response, StdResult,
+    to_json_binary, Addr, Binary, Coin, CosmosMsg, Decimal, Deps, DepsMut, Empty, Env, Event,
+    MessageInfo, Response, StdError, StdResult, Storage, SubMsg, Uint128,
 };
 use cw20::{Cw20CoinVerified, Cw20ReceiveMsg};
 use cw721::Cw721ReceiveMsg;
-use cw_balance::{BalanceVerified, Cw721CollectionVerified, Distribution};
-use cw_competition::escrow::TaxInformation;
+use cw_balance::{
+    BalanceVerified, Cw721CollectionVerified, Distribution, MemberBalanceChecked, MemberPercentage,
+    NftMode, RoundingMode,
+};
+use cw_competition::escrow::{CompetitionEscrowEvent, TaxInformation, TreasuryPayout};
 use cw_ownable::{assert_owner, get_ownership};
+use cw_storage_plus::Map;
 
 use crate::{
-    query::is_locked,
+    msg::{
+        EscrowMetadata, ExecuteMsg, OracleQueryMsg, QueryMsg, RefundPriority, RouterExecuteMsg,
+        TreasuryExecuteMsg,
+    },
+    query::{is_locked, MemberTimelineResponse},
     state::{
-        is_fully_funded, BALANCE, DUE, HAS_DISTRIBUTED, INITIAL_DUE, IS_LOCKED,
-        PRESET_DISTRIBUTION, TAX_AT_WITHDRAWAL, TOTAL_BALANCE,
+        assert_resolver, funded_count, is_activated, is_fully_funded, is_funded, is_quorum_met,
+        DistributionRecord, PendingConsolidation, PendingPull, ACTIVATION_QUORUM, APPROVALS,
+        AUTHORIZED_RESOLVERS, AUTO_LOCK_DELAY, BALANCE, CANCEL_ON_CLOSE, COMPETITION_ID, DEPOSIT,
+        DISTRIBUTION_HISTORY, DUE, EXPECTED_ABSORBER, FUNDED_AT, HAS_DISTRIBUTED, HOUSE,
+        INITIAL_DUE, IS_LOCKED, LISTENERS, METADATA, MIN_PARTICIPANTS, MUTUAL_CANCELS, NET_FUNDED,
+        ORACLE, PENDING_CONSOLIDATION, PENDING_PULL, PRESET_DISTRIBUTION, SCHEDULED_LOCK,
+        TAX_AT_WITHDRAWAL, TOTAL_BALANCE, TREASURY,
     },
     ContractError,
 };
 
+pub const LISTENER_REPLY_ID: u64 = 1;
+pub const PULL_CW20_REPLY_ID: u64 = 2;
+pub const ACTIVATE_REPLY_ID: u64 = 3;
+pub const CONSOLIDATE_REPLY_ID: u64 = 4;
+
+/// The largest administrative fee a no contest resolution may take
+pub const MAX_NO_CONTEST_FEE: Decimal = Decimal::percent(10);
+
 pub fn withdraw(
     deps: DepsMut,
+    env: Env,
     info: MessageInfo,
     cw20_msg: Option<Binary>,
     cw721_msg: Option<Binary>,
 ) -> Result<Response, ContractError> {
+    // Deposits are refundable even while the prize pool is locked
+    let deposit_msgs = match DEPOSIT.may_load(deps.storage, &info.sender)? {
+        Some(deposit) => {
+            DEPOSIT.remove(deps.storage, &info.sender);
+            deposit.transmit_all(
+                deps.as_ref(),
+                &info.sender,
+                cw20_msg.clone(),
+                cw721_msg.clone(),
+            )?
+        }
+        None => vec![],
+    };
+
     if is_locked(deps.as_ref()) {
-        return Err(ContractError::Locked {});
+        if deposit_msgs.is_empty() {
+            return Err(ContractError::Locked {});
+        }
+
+        return Ok(Response::new()
+            .add_attribute("action", "withdraw")
+            .add_attribute("addr", info.sender)
+            .add_attribute("bucket", "deposit")
+            .add_messages(deposit_msgs));
     }
 
     // Initialize total_balance based on processing status
     let mut total_balance = TOTAL_BALANCE.may_load(deps.storage)?.unwrap_or_default();
+    let mut is_closed = false;
 
     // Load and process balance for each address
-    let msgs = if let Some(mut balance) = BALANCE.may_load(deps.storage, &info.sender)? {
+    let mut msgs = if let Some(mut balance) = BALANCE.may_load(deps.storage, &info.sender)? {
         if balance.is_empty() {
             return Err(ContractError::EmptyBalance {});
         }
 
         // If the total balance has already been taxed, then deduct at the individual level
         if let Some(tax) = TAX_AT_WITHDRAWAL.may_load(deps.storage)? {
-            balance = balance.checked_sub(&balance.checked_mul_floor(tax)?)?;
+            balance = balance
+                .checked_sub(&balance.checked_mul_floor(tax)?)
+                .map_err(ContractError::from_balance_math)?;
         }
 
         // Update total balance and related storage entries
         BALANCE.remove(deps.storage, &info.sender);
-        total_balance = total_balance.checked_sub(&balance)?;
+        FUNDED_AT.remove(deps.storage, &info.sender);
+        APPROVALS.remove(deps.storage, &info.sender);
+        total_balance = total_balance
+            .checked_sub(&balance)
+            .map_err(ContractError::from_balance_math)?;
 
         if !HAS_DISTRIBUTED.load(deps.storage)? {
             // Set due to the initial due
             let initial_due = &INITIAL_DUE.load(deps.storage, &info.sender)?;
             DUE.save(deps.storage, &info.sender, initial_due)?;
+            reduce_net_funded(deps.storage, &info.sender, &balance)?;
+
+            // The escrow closes once the last member withdraws before funding completes
+            is_closed = total_balance.is_empty() && funded_count(deps.as_ref()) == 0;
+
+            // A withdrawal during the delay cancels the scheduled lock if funding is now short
+            if !is_activated(deps.as_ref())? {
+                SCHEDULED_LOCK.remove(deps.storage);
+            }
         }
 
         // Update or remove total balance
         if total_balance.is_empty() {
-            TOTAL_BALANCE.remove(deps.storage);
+            TOTAL_BALANCE.remove(deps.storage, env.block.height)?;
         } else {
-            TOTAL_BALANCE.save(deps.storage, &total_balance)?;
+            TOTAL_BALANCE.save(deps.storage, &total_balance, env.block.height)?;
         }
 
         balance.transmit_all(
@@ -67,264 +130,2271 @@ pub fn withdraw(
         vec![]
     };
 
+    let mut events = vec![];
+    let mut listener_msgs = vec![];
+    if is_closed {
+        events.push(Event::new("closed").add_attributes(competition_id_attribute(deps.as_ref())?));
+        listener_msgs = notify_listeners(deps.as_ref(), vec![CompetitionEscrowEvent::Closed {}])?;
+
+        if CANCEL_ON_CLOSE.may_load(deps.storage)?.unwrap_or_default() {
+            if let Some(owner) = get_ownership(deps.storage)?.owner {
+                msgs.push(CosmosMsg::Wasm(cosmwasm_std::WasmMsg::Execute {
+                    contract_addr: owner.to_string(),
+                    msg: to_json_binary(
+                        &cw_competition::msg::ExecuteBase::<Empty, Empty>::Cancel {},
+                    )?,
+                    funds: vec![],
+                }));
+            }
+        }
+    }
+
     Ok(Response::new()
         .add_attribute("action", "withdraw")
         .add_attribute("addr", info.sender)
-        .add_messages(msgs))
+        .add_attribute("closed", is_closed.to_string())
+        .add_events(events)
+        .add_messages(deposit_msgs)
+        .add_messages(msgs)
+        .add_submessages(listener_msgs))
 }
 
-pub fn set_distribution(
+pub fn mutual_cancel(
     deps: DepsMut,
+    env: Env,
     info: MessageInfo,
-    distribution: Option<Distribution<String>>,
 ) -> Result<Response, ContractError> {
-    if let Some(distribution) = &distribution {
-        // Validate
-        let distribution = distribution.into_checked(deps.as_ref())?;
-
-        // Save distribution in the state
-        PRESET_DISTRIBUTION.save(deps.storage, &info.sender, &distribution)?;
-    } else {
-        PRESET_DISTRIBUTION.remove(deps.storage, &info.sender);
+    if HAS_DISTRIBUTED.load(deps.storage)? {
+        return Err(ContractError::StdError(StdError::GenericErr {
+            msg: "The escrow has already been distributed".to_string(),
+        }));
+    }
+    // A lock that is only scheduled has not activated the escrow yet
+    if is_activated(deps.as_ref())? && SCHEDULED_LOCK.may_load(deps.storage)?.is_none() {
+        return Err(ContractError::StdError(StdError::GenericErr {
+            msg: "The escrow has already activated".to_string(),
+        }));
+    }
+    if !INITIAL_DUE.has(deps.storage, &info.sender) {
+        return Err(ContractError::NotAParticipant {});
+    }
+    if !BALANCE.has(deps.storage, &info.sender) {
+        return Err(ContractError::EmptyBalance {});
     }
 
-    Ok(Response::new()
-        .add_attribute("action", "set_distribution")
-        .add_attribute(
-            "distribution",
-            distribution
-                .map(|some| some.to_string())
-                .unwrap_or("None".to_owned()),
-        ))
-}
+    MUTUAL_CANCELS.save(deps.storage, &info.sender, &())?;
 
-// This function receives native tokens and updates the balance
-pub fn receive_native(deps: DepsMut, info: MessageInfo) -> Result<Response, ContractError> {
-    let balance = BalanceVerified {
-        native: info.funds,
-        cw20: vec![],
-        cw721: vec![],
-    };
+    // Refund once every member who has paid in agrees, even if the escrow was locked
+    let balances = BALANCE
+        .range(deps.storage, None, None, cosmwasm_std::Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    let is_cancelled = balances
+        .iter()
+        .all(|(addr, _)| MUTUAL_CANCELS.has(deps.storage, addr));
 
-    receive_balance(deps, info.sender, balance)
-}
+    let mut msgs = vec![];
+    if is_cancelled {
+        let mut total_balance = TOTAL_BALANCE.may_load(deps.storage)?.unwrap_or_default();
+        for (addr, balance) in balances {
+            total_balance = total_balance
+                .checked_sub(&balance)
+                .map_err(ContractError::from_balance_math)?;
+            let initial_due = INITIAL_DUE.load(deps.storage, &addr)?;
+            DUE.save(deps.storage, &addr, &initial_due)?;
+            FUNDED_AT.remove(deps.storage, &addr);
+            reduce_net_funded(deps.storage, &addr, &balance)?;
 
-// This function receives CW20 tokens and updates the balance
-pub fn receive_cw20(
-    deps: DepsMut,
-    info: MessageInfo,
-    cw20_receive_msg: Cw20ReceiveMsg,
-) -> Result<Response, ContractError> {
-    let sender_addr = deps.api.addr_validate(&cw20_receive_msg.sender)?;
-    let cw20_balance = vec![Cw20CoinVerified {
-        address: info.sender,
-        amount: cw20_receive_msg.amount,
-    }];
+            if !balance.is_empty() {
+                msgs.extend(balance.transmit_all(deps.as_ref(), &addr, None, None)?);
+            }
+        }
 
-    let balance = BalanceVerified {
-        native: info.funds,
-        cw20: cw20_balance,
-        cw721: vec![],
-    };
+        BALANCE.clear(deps.storage);
+        APPROVALS.clear(deps.storage);
+        MUTUAL_CANCELS.clear(deps.storage);
+        SCHEDULED_LOCK.remove(deps.storage);
+        if total_balance.is_empty() {
+            TOTAL_BALANCE.remove(deps.storage, env.block.height)?;
+        } else {
+            TOTAL_BALANCE.save(deps.storage, &total_balance, env.block.height)?;
+        }
+        IS_LOCKED.save(deps.storage, &false, env.block.height)?;
+    }
 
-    receive_balance(deps, sender_addr, balance)
+    Ok(Response::new()
+        .add_attribute("action", "mutual_cancel")
+        .add_attribute("addr", info.sender)
+        .add_attribute("is_cancelled", is_cancelled.to_string())
+        .add_messages(msgs))
 }
 
-// This function receives CW721 tokens and updates the balance
-pub fn receive_cw721(
+pub fn no_contest(
     deps: DepsMut,
+    env: Env,
     info: MessageInfo,
-    cw721_receive_msg: Cw721ReceiveMsg,
+    fee: Decimal,
+    refund_priority: Option<RefundPriority>,
 ) -> Result<Response, ContractError> {
-    let sender_addr = deps.api.addr_validate(&cw721_receive_msg.sender)?;
-    let cw721_balance = vec![Cw721CollectionVerified {
-        address: info.sender,
-        token_ids: vec![cw721_receive_msg.token_id],
-    }];
+    assert_owner(deps.storage, &info.sender)?;
 
-    let balance = BalanceVerified {
-        native: info.funds,
-        cw20: vec![],
-        cw721: cw721_balance,
-    };
+    if HAS_DISTRIBUTED.load(deps.storage)? {
+        return Err(ContractError::StdError(StdError::GenericErr {
+            msg: "The escrow has already been distributed".to_string(),
+        }));
+    }
+    if fee > MAX_NO_CONTEST_FEE {
+        return Err(ContractError::FeeTooHigh {
+            max: MAX_NO_CONTEST_FEE,
+        });
+    }
+    let treasury = TREASURY.may_load(deps.storage)?;
+    if !fee.is_zero() && treasury.is_none() {
+        return Err(ContractError::StdError(StdError::GenericErr {
+            msg: "A treasury must be configured to take a fee".to_string(),
+        }));
+    }
 
-    receive_balance(deps, sender_addr, balance)
-}
+    let refund_priority = refund_priority.unwrap_or(RefundPriority::ProRata);
+    let mut balances = BALANCE
+        .range(deps.storage, None, None, cosmwasm_std::Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    if refund_priority == RefundPriority::FundingOrder {
+        let mut funded_at = vec![];
+        for (addr, balance) in balances {
+            let height = FUNDED_AT.may_load(deps.storage, &addr)?.unwrap_or(u64::MAX);
+            funded_at.push((height, addr, balance));
+        }
 
-fn receive_balance(
-    deps: DepsMut,
-    addr: Addr,
-    balance: BalanceVerified,
-) -> Result<Response, ContractError> {
-    if !INITIAL_DUE.has(deps.storage, &addr) {
-        return Err(ContractError::InvalidDue {
-            msg: "User is not a participant".to_string(),
-        });
+        // The sort is stable, so members funded at the same height keep their address order
+        funded_at.sort_by_key(|x| x.0);
+        balances = funded_at
+            .into_iter()
+            .map(|(_, addr, balance)| (addr, balance))
+            .collect();
     }
+    let house = HOUSE.may_load(deps.storage)?;
 
-    // Update the stored balance for the given address
-    let updated_balance =
-        BALANCE.update(deps.storage, &addr, |maybe_balance| match maybe_balance {
-            Some(existing_balance) => existing_balance.checked_add(&balance),
-            None => Ok(balance),
-        })?;
+    // The refunds are capped at what the escrow still holds of each asset
+    let claims: Vec<BalanceVerified> = balances
+        .iter()
+        .map(|(_, balance)| balance.clone())
+        .chain(house.iter().map(|house| house.balance.clone()))
+        .collect();
+    let claimed = claims
+        .iter()
+        .try_fold(BalanceVerified::default(), |total, x| total.checked_add(x))
+        .map_err(ContractError::from_balance_math)?;
+    let held = held_pool(deps.as_ref(), &env, &claimed)?;
+    let mut refunds = cap_refunds(claims, &claimed, &held, &refund_priority).into_iter();
 
-    let due_balance = DUE.load(deps.storage, &addr)?;
-    let remaining_due = updated_balance.difference(&due_balance)?;
+    // Each member is refunded what they paid in less their share of the fee
+    let mut total_balance = TOTAL_BALANCE.may_load(deps.storage)?.unwrap_or_default();
+    let mut total_fee = BalanceVerified::default();
+    let mut msgs = vec![];
+    for ((addr, balance), refund) in balances.into_iter().zip(refunds.by_ref()) {
+        total_balance = total_balance
+            .checked_sub(&balance)
+            .map_err(ContractError::from_balance_math)?;
+        reduce_net_funded(deps.storage, &addr, &balance)?;
 
-    let mut msgs: Vec<CosmosMsg> = vec![];
+        let member_fee = refund.checked_mul_floor(fee)?;
+        let refund = refund
+            .checked_sub(&member_fee)
+            .map_err(ContractError::from_balance_math)?;
+        total_fee = total_fee
+            .checked_add(&member_fee)
+            .map_err(ContractError::from_balance_math)?;
 
-    // Handle the case where the due balance is fully paid
-    if remaining_due.is_empty() {
-        DUE.remove(deps.storage, &addr);
+        if !refund.is_empty() {
+            msgs.extend(refund.transmit_all(deps.as_ref(), &addr, None, None)?);
+        }
+    }
 
-        // Lock if fully funded and send activation message if needed
-        if is_fully_funded(deps.as_ref()) {
-            IS_LOCKED.save(deps.storage, &true)?;
+    // The seeded funds are returned to the instantiator without a fee
+    if let Some(house) = house {
+        total_balance = total_balance
+            .checked_sub(&house.balance)
+            .map_err(ContractError::from_balance_math)?;
 
-            if let Some(owner) = get_ownership(deps.storage)?.owner {
-                msgs.push(CosmosMsg::Wasm(cosmwasm_std::WasmMsg::Execute {
-                    contract_addr: owner.to_string(),
-                    msg: to_json_binary(
-                        &cw_competition::msg::ExecuteBase::<Empty, Empty>::Activate {},
-                    )?,
-                    funds: vec![],
-                }));
-            }
+        let refund = refunds.next().unwrap_or_default();
+        if !refund.is_empty() {
+            msgs.extend(refund.transmit_all(deps.as_ref(), &house.addr, None, None)?);
         }
-    } else {
-        DUE.save(deps.storage, &addr, &remaining_due)?;
     }
 
-    // Update the total balance in storage
-    if TOTAL_BALANCE.exists(deps.storage) {
-        TOTAL_BALANCE.update(deps.storage, |total| total.checked_add(&updated_balance))?;
+    if let Some(treasury) = treasury.filter(|_| !total_fee.is_empty()) {
+        msgs.extend(total_fee.transmit_all(deps.as_ref(), &treasury, None, None)?);
+    }
+
+    BALANCE.clear(deps.storage);
+    if total_balance.is_empty() {
+        TOTAL_BALANCE.remove(deps.storage, env.block.height)?;
     } else {
-        TOTAL_BALANCE.save(deps.storage, &updated_balance)?;
+        TOTAL_BALANCE.save(deps.storage, &total_balance, env.block.height)?;
     }
+    IS_LOCKED.save(deps.storage, &false, env.block.height)?;
+    HAS_DISTRIBUTED.save(deps.storage, &true)?;
+    DUE.clear(deps.storage);
+    PRESET_DISTRIBUTION.clear(deps.storage);
+    APPROVALS.clear(deps.storage);
+    MUTUAL_CANCELS.clear(deps.storage);
+    SCHEDULED_LOCK.remove(deps.storage);
 
     Ok(Response::new()
-        .add_attribute("action", "receive_balance")
-        .add_attribute("balance", updated_balance.to_string())
+        .add_attribute("action", "no_contest")
+        .add_attributes(competition_id_attribute(deps.as_ref())?)
+        .add_attribute("fee", fee.to_string())
+        .add_attribute("total_fee", total_fee.to_string())
         .add_messages(msgs))
 }
 
-pub fn distribute(
-    deps: DepsMut,
-    info: MessageInfo,
-    distribution: Option<Distribution<String>>,
-    tax_info: Option<TaxInformation<String>>,
-) -> Result<Response, ContractError> {
-    // Ensure the sender is the owner
-    assert_owner(deps.storage, &info.sender)?;
+/// The fungible assets of the claimed balance the escrow holds, less the members' deposits
+fn held_pool(
+    deps: Deps,
+    env: &Env,
+    claimed: &BalanceVerified,
+) -> Result<BalanceVerified, ContractError> {
+    let deposits = DEPOSIT
+        .range(deps.storage, None, None, cosmwasm_std::Order::Ascending)
+        .try_fold(BalanceVerified::default(), |total, x| {
+            total.checked_add(&x?.1)
+        })?;
 
-    // Load the total balance available for distribution
-    let mut total_balance = TOTAL_BALANCE.load(deps.storage)?;
+    let mut held = BalanceVerified::default();
+    for coin in &claimed.native {
+        let balance = deps
+            .querier
+            .query_balance(&env.contract.address, &coin.denom)?;
+        let deposited = deposits
+            .native
+            .iter()
+            .find(|x| x.denom == coin.denom)
+            .map_or(Uint128::zero(), |x| x.amount);
 
-    // Validate the tax info
-    let validated_tax_info = tax_info
-        .as_ref()
-        .map(|tax_info| tax_info.into_checked(deps.as_ref()))
-        .transpose()?;
+        held.native.push(Coin {
+            denom: coin.denom.clone(),
+            amount: balance.amount.saturating_sub(deposited),
+        });
+    }
+    for cw20_coin in &claimed.cw20 {
+        let response: cw20::BalanceResponse = deps.querier.query_wasm_smart(
+            &cw20_coin.address,
+            &cw20::Cw20QueryMsg::Balance {
+                address: env.contract.address.to_string(),
+            },
+        )?;
+        let deposited = deposits
+            .cw20
+            .iter()
+            .find(|x| x.address == cw20_coin.address)
+            .map_or(Uint128::zero(), |x| x.amount);
 
-    // Process the tax
-    // This will automatically be sent to the receiver
-    let msgs = if let Some(tax_info) = validated_tax_info {
-        let tax = total_balance.checked_mul_floor(tax_info.tax)?;
+        held.cw20.push(Cw20CoinVerified {
+            address: cw20_coin.address.clone(),
+            amount: response.balance.saturating_sub(deposited),
+        });
+    }
 
-        total_balance =
-            TOTAL_BALANCE.update(deps.storage, |x| -> StdResult<_> { x.checked_sub(&tax) })?;
+    Ok(held)
+}
 
-        // If funds are not split, then we should have the tax at withdrawal
-        if distribution.is_none() {
-            TAX_AT_WITHDRAWAL.save(deps.storage, &tax_info.tax)?;
-        }
+/// Caps the refunds, given in priority order, at the fungible assets the escrow holds
+/// NFTs are refunded as recorded
+fn cap_refunds(
+    mut refunds: Vec<BalanceVerified>,
+    claimed: &BalanceVerified,
+    held: &BalanceVerified,
+    refund_priority: &RefundPriority,
+) -> Vec<BalanceVerified> {
+    let cap = |amount: Uint128, claimed: Uint128, held: Uint128, remaining: &mut Uint128| {
+        let capped = match refund_priority {
+            RefundPriority::FundingOrder => amount.min(*remaining),
+            RefundPriority::ProRata => amount.multiply_ratio(held, claimed),
+        };
+        *remaining = remaining.saturating_sub(capped);
 
-        if !tax.is_empty() {
-            tax.transmit_all(
-                deps.as_ref(),
-                &tax_info.receiver,
-                tax_info.cw20_msg,
-                tax_info.cw721_msg,
-            )?
-        } else {
-            vec![]
-        }
-    } else {
-        vec![]
+        capped
     };
 
-    // Clear the existing balance storage and update with new distribution
-    if let Some(distribution) = &distribution {
-        let distribution = distribution.into_checked(deps.as_ref())?;
+    for coin in &claimed.native {
+        let held_amount = held
+            .native
+            .iter()
+            .find(|x| x.denom == coin.denom)
+            .map_or(Uint128::zero(), |x| x.amount);
+        if held_amount >= coin.amount {
+            continue;
+        }
 
-        // Calculate the distribution amounts based on the total balance and distribution
-        let distributed_amounts = total_balance.split(&distribution)?;
+        let mut remaining = held_amount;
+        for refund in refunds.iter_mut() {
+            for x in refund.native.iter_mut().filter(|x| x.denom == coin.denom) {
+                x.amount = cap(x.amount, coin.amount, held_amount, &mut remaining);
+            }
+        }
+    }
+    for cw20_coin in &claimed.cw20 {
+        let held_amount = held
+            .cw20
+            .iter()
+            .find(|x| x.address == cw20_coin.address)
+            .map_or(Uint128::zero(), |x| x.amount);
+        if held_amount >= cw20_coin.amount {
+            continue;
+        }
 
-        BALANCE.clear(deps.storage);
-        for distributed_amount in distributed_amounts {
-            // Check for preset distribution and apply if available
-            if let Some(preset) =
-                PRESET_DISTRIBUTION.may_load(deps.storage, &distributed_amount.addr)?
+        let mut remaining = held_amount;
+        for refund in refunds.iter_mut() {
+            for x in refund
+                .cw20
+                .iter_mut()
+                .filter(|x| x.address == cw20_coin.address)
             {
-                let new_balances = distributed_amount.balance.split(&preset)?;
-                for new_balance in new_balances {
-                    BALANCE.update(
-                        deps.storage,
-                        &new_balance.addr,
-                        |old_balance| -> Result<_, ContractError> {
-                            match old_balance {
-                                Some(old_balance) => {
-                                    Ok(old_balance.checked_add(&new_balance.balance)?)
-                                }
-                                None => Ok(new_balance.balance),
-                            }
-                        },
-                    )?;
-                }
-            } else {
-                BALANCE.update(
-                    deps.storage,
-                    &distributed_amount.addr,
-                    |old_balance| -> Result<_, ContractError> {
-                        match old_balance {
-                            Some(old_balance) => {
-                                Ok(old_balance.checked_add(&distributed_amount.balance)?)
-                            }
-                            None => Ok(distributed_amount.balance),
-                        }
-                    },
-                )?;
+                x.amount = cap(x.amount, cw20_coin.amount, held_amount, &mut remaining);
             }
         }
     }
 
-    IS_LOCKED.save(deps.storage, &false)?;
-    HAS_DISTRIBUTED.save(deps.storage, &true)?;
+    for refund in refunds.iter_mut() {
+        refund.native.retain(|x| !x.amount.is_zero());
+        refund.cw20.retain(|x| !x.amount.is_zero());
+    }
 
-    // Clear the contract state
-    DUE.clear(deps.storage);
-    PRESET_DISTRIBUTION.clear(deps.storage);
+    refunds
+}
+
+pub fn absorb_escrow(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    other: String,
+) -> Result<Response, ContractError> {
+    assert_owner(deps.storage, &info.sender)?;
+
+    let other = deps.api.addr_validate(&other)?;
+    if other == env.contract.address {
+        return Err(ContractError::StdError(StdError::GenericErr {
+            msg: "An escrow cannot absorb itself".to_string(),
+        }));
+    }
+    if HAS_DISTRIBUTED.load(deps.storage)? {
+        return Err(ContractError::StdError(StdError::GenericErr {
+            msg: "The escrow has already been distributed".to_string(),
+        }));
+    }
+    if is_locked(deps.as_ref()) {
+        return Err(ContractError::Locked {});
+    }
+    // Members joining an activated escrow would have no say in its activation
+    if is_activated(deps.as_ref())? {
+        return Err(ContractError::StdError(StdError::GenericErr {
+            msg: "The escrow has already been activated".to_string(),
+        }));
+    }
+
+    // Read the other escrow's state before it transfers its balance
+    let balances: Vec<MemberBalanceChecked> = deps.querier.query_wasm_smart(
+        &other,
+        &QueryMsg::Balances {
+            start_after: None,
+            limit: None,
+        },
+    )?;
+    let initial_dues: Vec<MemberBalanceChecked> = deps.querier.query_wasm_smart(
+        &other,
+        &QueryMsg::InitialDues {
+            start_after: None,
+            limit: None,
+        },
+    )?;
+    let dues: Vec<MemberBalanceChecked> = deps.querier.query_wasm_smart(
+        &other,
+        &QueryMsg::Dues {
+            start_after: None,
+            limit: None,
+        },
+    )?;
+    let other_total: Option<BalanceVerified> = deps
+        .querier
+        .query_wasm_smart(&other, &QueryMsg::TotalBalance {})?;
+    let other_house: Option<MemberBalanceChecked> =
+        deps.querier.query_wasm_smart(&other, &QueryMsg::House {})?;
+    let mut timelines: Vec<(Addr, MemberTimelineResponse)> = vec![];
+    for addr in initial_dues
+        .iter()
+        .chain(&balances)
+        .map(|x| &x.addr)
+        .collect::<BTreeSet<_>>()
+    {
+        let timeline = deps.querier.query_wasm_smart(
+            &other,
+            &QueryMsg::MemberTimeline {
+                addr: addr.to_string(),
+            },
+        )?;
+        timelines.push((addr.clone(), timeline));
+    }
+    let net_funded: Vec<MemberBalanceChecked> = timelines
+        .iter()
+        .filter_map(|(addr, timeline)| {
+            timeline
+                .net_funded
+                .clone()
+                .map(|balance| MemberBalanceChecked {
+                    addr: addr.clone(),
+                    balance,
+                })
+        })
+        .collect();
+
+    // Members of both escrows have their balances and dues summed
+    merge_members(deps.storage, &BALANCE, &balances)?;
+    merge_members(deps.storage, &INITIAL_DUE, &initial_dues)?;
+    merge_members(deps.storage, &DUE, &dues)?;
+    merge_members(deps.storage, &NET_FUNDED, &net_funded)?;
+
+    for (addr, timeline) in timelines {
+        // A member is funded once the dues of both escrows are paid, at the later of the two heights
+        if DUE.has(deps.storage, &addr) {
+            FUNDED_AT.remove(deps.storage, &addr);
+        } else if let Some(funded_at) = timeline.funded_at {
+            let funded_at = FUNDED_AT
+                .may_load(deps.storage, &addr)?
+                .map_or(funded_at, |x| x.max(funded_at));
+            FUNDED_AT.save(deps.storage, &addr, &funded_at)?;
+        }
+
+        // A distribution preset in this escrow is kept over the other's
+        if let Some(distribution) = timeline.preset_distribution {
+            if !PRESET_DISTRIBUTION.has(deps.storage, &addr) {
+                PRESET_DISTRIBUTION.save(deps.storage, &addr, &distribution)?;
+            }
+        }
+    }
+
+    if let Some(other_house) = other_house {
+        let house = match HOUSE.may_load(deps.storage)? {
+            Some(house) => MemberBalanceChecked {
+                addr: house.addr,
+                balance: house
+                    .balance
+                    .checked_add(&other_house.balance)
+                    .map_err(ContractError::from_balance_math)?,
+            },
+            None => other_house,
+        };
+        HOUSE.save(deps.storage, &house)?;
+    }
+
+    let other_total = other_total.unwrap_or_default();
+    if !other_total.is_empty() {
+        let total_balance = TOTAL_BALANCE
+            .may_load(deps.storage)?
+            .unwrap_or_default()
+            .checked_add(&other_total)
+            .map_err(ContractError::from_balance_math)?;
+        TOTAL_BALANCE.save(deps.storage, &total_balance, env.block.height)?;
+    }
 
     Ok(Response::new()
-        .add_attribute("action", "handle_competition_result")
-        .add_attribute(
-            "tax",
-            tax_info
-                .map(|some| some.tax.to_string())
-                .unwrap_or("None".to_owned()),
-        )
-        .add_messages(msgs))
+        .add_attribute("action", "absorb_escrow")
+        .add_attribute("other", other.to_string())
+        .add_attribute("absorbed", other_total.to_string())
+        .add_message(CosmosMsg::Wasm(cosmwasm_std::WasmMsg::Execute {
+            contract_addr: other.to_string(),
+            msg: to_json_binary(&ExecuteMsg::TransferToEscrow {})?,
+            funds: vec![],
+        })))
+}
+
+fn merge_members<'a>(
+    storage: &mut dyn Storage,
+    map: &Map<'a, &'a Addr, BalanceVerified>,
+    members: &'a [MemberBalanceChecked],
+) -> Result<(), ContractError> {
+    for member in members {
+        let balance = match map.may_load(storage, &member.addr)? {
+            Some(existing) => existing
+                .checked_add(&member.balance)
+                .map_err(ContractError::from_balance_math)?,
+            None => member.balance.clone(),
+        };
+        map.save(storage, &member.addr, &balance)?;
+    }
+
+    Ok(())
 }
 
-pub fn lock(deps: DepsMut, info: MessageInfo, value: bool) -> Result<Response, ContractError> {
+pub fn expect_absorb(
+    deps: DepsMut,
+    info: MessageInfo,
+    by: String,
+) -> Result<Response, ContractError> {
     assert_owner(deps.storage, &info.sender)?;
 
-    // Save the locked state to storage
-    IS_LOCKED.save(deps.storage, &value)?;
+    if HAS_DISTRIBUTED.load(deps.storage)? {
+        return Err(ContractError::StdError(StdError::GenericErr {
+            msg: "The escrow has already been distributed".to_string(),
+        }));
+    }
+
+    let by = deps.api.addr_validate(&by)?;
+    EXPECTED_ABSORBER.save(deps.storage, &by)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "expect_absorb")
+        .add_attribute("by", by.to_string()))
+}
+
+pub fn transfer_to_escrow(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    if HAS_DISTRIBUTED.load(deps.storage)? {
+        return Err(ContractError::StdError(StdError::GenericErr {
+            msg: "The escrow has already been distributed".to_string(),
+        }));
+    }
+    if is_locked(deps.as_ref()) {
+        return Err(ContractError::Locked {});
+    }
+
+    // Only the escrow the owner expects to absorb this one, or the owner, may take its balance
+    let owner = get_ownership(deps.storage)?.owner;
+    if owner.as_ref() != Some(&info.sender)
+        && EXPECTED_ABSORBER.may_load(deps.storage)?.as_ref() != Some(&info.sender)
+    {
+        return Err(ContractError::OwnershipError(
+            cw_ownable::OwnershipError::NotOwner,
+        ));
+    }
+
+    let total_balance = TOTAL_BALANCE.may_load(deps.storage)?.unwrap_or_default();
+    let msgs = if total_balance.is_empty() {
+        vec![]
+    } else {
+        total_balance.transmit_all(deps.as_ref(), &info.sender, None, None)?
+    };
+
+    BALANCE.clear(deps.storage);
+    DUE.clear(deps.storage);
+    PRESET_DISTRIBUTION.clear(deps.storage);
+    APPROVALS.clear(deps.storage);
+    MUTUAL_CANCELS.clear(deps.storage);
+    HOUSE.remove(deps.storage);
+    SCHEDULED_LOCK.remove(deps.storage);
+    EXPECTED_ABSORBER.remove(deps.storage);
+    TOTAL_BALANCE.remove(deps.storage, env.block.height)?;
+    HAS_DISTRIBUTED.save(deps.storage, &true)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "transfer_to_escrow")
+        .add_attribute("escrow", info.sender.to_string())
+        .add_attribute("balance", total_balance.to_string())
+        .add_messages(msgs))
+}
+
+pub fn withdraw_nfts(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    collection: String,
+    token_ids: Vec<String>,
+    cw721_msg: Option<Binary>,
+) -> Result<Response, ContractError> {
+    // Distributed balances are no longer tied to the dues
+    if HAS_DISTRIBUTED.load(deps.storage)? {
+        return Err(ContractError::StdError(StdError::GenericErr {
+            msg: "Cannot withdraw surplus NFTs after distribution".to_string(),
+        }));
+    }
+
+    let collection = deps.api.addr_validate(&collection)?;
+    let initial_due = INITIAL_DUE
+        .may_load(deps.storage, &info.sender)?
+        .ok_or(ContractError::NotAParticipant {})?;
+
+    // NFTs in the due are part of the prize pool
+    if let Some(required) = initial_due.cw721.iter().find(|x| x.address == collection) {
+        if let Some(token_id) = token_ids.iter().find(|x| required.token_ids.contains(x)) {
+            return Err(ContractError::RequiredNft {
+                token_id: token_id.clone(),
+            });
+        }
+    }
+
+    let surplus = BalanceVerified {
+        native: vec![],
+        cw20: vec![],
+        cw721: vec![Cw721CollectionVerified {
+            address: collection,
+            token_ids,
+        }],
+    };
+
+    let balance = BALANCE
+        .may_load(deps.storage, &info.sender)?
+        .ok_or(ContractError::EmptyBalance {})?
+        .checked_sub(&surplus)
+        .map_err(ContractError::from_balance_math)?;
+    if balance.is_empty() {
+        BALANCE.remove(deps.storage, &info.sender);
+    } else {
+        BALANCE.save(deps.storage, &info.sender, &balance)?;
+    }
+    reduce_net_funded(deps.storage, &info.sender, &surplus)?;
+
+    let total_balance = TOTAL_BALANCE
+        .load(deps.storage)?
+        .checked_sub(&surplus)
+        .map_err(ContractError::from_balance_math)?;
+    if total_balance.is_empty() {
+        TOTAL_BALANCE.remove(deps.storage, env.block.height)?;
+    } else {
+        TOTAL_BALANCE.save(deps.storage, &total_balance, env.block.height)?;
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "withdraw_nfts")
+        .add_attribute("addr", info.sender.to_string())
+        .add_attribute("nfts", surplus.to_string())
+        .add_messages(surplus.transmit_all(deps.as_ref(), &info.sender, None, cw721_msg)?))
+}
+
+pub fn withdraw_collection(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    collection: String,
+    cw721_msg: Option<Binary>,
+) -> Result<Response, ContractError> {
+    if is_locked(deps.as_ref()) {
+        return Err(ContractError::Locked {});
+    }
+    // Distributed balances are withdrawn as a whole
+    if HAS_DISTRIBUTED.load(deps.storage)? {
+        return Err(ContractError::StdError(StdError::GenericErr {
+            msg: "Cannot withdraw a single collection after distribution".to_string(),
+        }));
+    }
+
+    let collection = deps.api.addr_validate(&collection)?;
+    let initial_due = INITIAL_DUE
+        .may_load(deps.storage, &info.sender)?
+        .ok_or(ContractError::NotAParticipant {})?;
+    let balance = BALANCE
+        .may_load(deps.storage, &info.sender)?
+        .ok_or(ContractError::EmptyBalance {})?;
+
+    let refund = balance.cw721_collection(&collection);
+    if refund.is_empty() {
+        return Err(ContractError::EmptyBalance {});
+    }
+
+    let balance = balance
+        .checked_sub(&refund)
+        .map_err(ContractError::from_balance_math)?;
+    if balance.is_empty() {
+        BALANCE.remove(deps.storage, &info.sender);
+    } else {
+        BALANCE.save(deps.storage, &info.sender, &balance)?;
+    }
+    reduce_net_funded(deps.storage, &info.sender, &refund)?;
+
+    let total_balance = TOTAL_BALANCE
+        .load(deps.storage)?
+        .checked_sub(&refund)
+        .map_err(ContractError::from_balance_math)?;
+    if total_balance.is_empty() {
+        TOTAL_BALANCE.remove(deps.storage, env.block.height)?;
+    } else {
+        TOTAL_BALANCE.save(deps.storage, &total_balance, env.block.height)?;
+    }
+
+    // Refunding NFTs of the due reopens it
+    let due = balance.difference(&initial_due)?;
+    if !due.is_empty() {
+        DUE.save(deps.storage, &info.sender, &due)?;
+        FUNDED_AT.remove(deps.storage, &info.sender);
+        APPROVALS.remove(deps.storage, &info.sender);
+
+        // As with a withdrawal, the scheduled lock is cancelled if funding is now short
+        if !is_activated(deps.as_ref())? {
+            SCHEDULED_LOCK.remove(deps.storage);
+        }
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "withdraw_collection")
+        .add_attribute("addr", info.sender.to_string())
+        .add_attribute("nfts", refund.to_string())
+        .add_messages(refund.transmit_all(deps.as_ref(), &info.sender, None, cw721_msg)?))
+}
+
+// Funding from before the net position was tracked may be refunded, so it saturates at zero
+fn reduce_net_funded(
+    storage: &mut dyn Storage,
+    addr: &Addr,
+    withdrawn: &BalanceVerified,
+) -> Result<(), ContractError> {
+    if let Some(net_funded) = NET_FUNDED.may_load(storage, addr)? {
+        let net_funded = withdrawn.difference(&net_funded)?;
+        if net_funded.is_empty() {
+            NET_FUNDED.remove(storage, addr);
+        } else {
+            NET_FUNDED.save(storage, addr, &net_funded)?;
+        }
+    }
+
+    Ok(())
+}
+
+pub fn set_due_denom(
+    deps: DepsMut,
+    info: MessageInfo,
+    denom: String,
+) -> Result<Response, ContractError> {
+    assert_owner(deps.storage, &info.sender)?;
+
+    if denom.is_empty() {
+        return Err(ContractError::InvalidDue {
+            msg: "The denom cannot be empty".to_string(),
+        });
+    }
+
+    let initial_dues = INITIAL_DUE
+        .range(deps.storage, None, None, cosmwasm_std::Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    for (addr, initial_due) in initial_dues {
+        let initial_due = fill_denom(initial_due, &denom)?;
+        assert_assets_allowed(deps.as_ref(), &initial_due)?;
+        INITIAL_DUE.save(deps.storage, &addr, &initial_due)?;
+
+        if let Some(due) = DUE.may_load(deps.storage, &addr)? {
+            DUE.save(deps.storage, &addr, &fill_denom(due, &denom)?)?;
+        }
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "set_due_denom")
+        .add_attribute("denom", denom))
+}
+
+// Coins without a denom are merged into any existing amount of the given denom
+fn fill_denom(balance: BalanceVerified, denom: &str) -> Result<BalanceVerified, ContractError> {
+    let (unset, native): (Vec<_>, Vec<_>) =
+        balance.native.into_iter().partition(|x| x.denom.is_empty());
+    let filled = BalanceVerified {
+        native: unset
+            .into_iter()
+            .map(|x| Coin {
+                denom: denom.to_string(),
+                amount: x.amount,
+            })
+            .collect(),
+        cw20: vec![],
+        cw721: vec![],
+    };
+
+    BalanceVerified {
+        native,
+        cw20: balance.cw20,
+        cw721: balance.cw721,
+    }
+    .checked_add(&filled)
+    .map_err(ContractError::from_balance_math)
+}
+
+pub fn set_distribution(
+    deps: DepsMut,
+    info: MessageInfo,
+    distribution: Option<Distribution<String>>,
+) -> Result<Response, ContractError> {
+    if let Some(distribution) = &distribution {
+        // Validate
+        let distribution = distribution.into_checked(deps.as_ref())?;
+
+        // Save distribution in the state
+        PRESET_DISTRIBUTION.save(deps.storage, &info.sender, &distribution)?;
+    } else {
+        PRESET_DISTRIBUTION.remove(deps.storage, &info.sender);
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "set_distribution")
+        .add_attribute(
+            "distribution",
+            distribution
+                .map(|some| some.to_string())
+                .unwrap_or("None".to_owned()),
+        ))
+}
+
+pub fn clear_distribution(deps: DepsMut, info: MessageInfo) -> Result<Response, ContractError> {
+    // Without a preset, the sender receives their share directly at distribution
+    PRESET_DISTRIBUTION.remove(deps.storage, &info.sender);
+
+    Ok(Response::new()
+        .add_attribute("action", "clear_distribution")
+        .add_attribute("addr", info.sender))
+}
+
+// This function receives native tokens and updates the balance
+pub fn receive_native(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    if info.funds.is_empty() {
+        return Err(ContractError::NoFunds {});
+    }
+
+    let balance = BalanceVerified {
+        native: info.funds,
+        cw20: vec![],
+        cw721: vec![],
+    };
+
+    receive_balance(deps, env, info.sender, balance)
+}
+
+pub fn receive_deposit(deps: DepsMut, info: MessageInfo) -> Result<Response, ContractError> {
+    if !INITIAL_DUE.has(deps.storage, &info.sender) {
+        return Err(ContractError::StdError(StdError::GenericErr {
+            msg: "Only members can make a deposit".to_string(),
+        }));
+    }
+
+    let balance = BalanceVerified {
+        native: info.funds,
+        cw20: vec![],
+        cw721: vec![],
+    };
+    if balance.is_empty() {
+        return Err(ContractError::EmptyBalance {});
+    }
+
+    DEPOSIT.update(
+        deps.storage,
+        &info.sender,
+        |deposit| -> Result<_, ContractError> {
+            match deposit {
+                Some(deposit) => deposit
+                    .checked_add(&balance)
+                    .map_err(ContractError::from_balance_math),
+                None => Ok(balance),
+            }
+        },
+    )?;
+
+    Ok(Response::new()
+        .add_attribute("action", "receive_deposit")
+        .add_attribute("addr", info.sender))
+}
+
+// This function receives CW20 tokens and updates the balance
+pub fn receive_cw20(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    cw20_receive_msg: Cw20ReceiveMsg,
+) -> Result<Response, ContractError> {
+    let sender_addr = deps.api.addr_validate(&cw20_receive_msg.sender)?;
+    let cw20_balance = vec![Cw20CoinVerified {
+        address: info.sender,
+        amount: cw20_receive_msg.amount,
+    }];
+
+    let balance = BalanceVerified {
+        native: info.funds,
+        cw20: cw20_balance,
+        cw721: vec![],
+    };
+
+    receive_balance(deps, env, sender_addr, balance)
+}
+
+pub fn pull_cw20(
+    deps: DepsMut,
+    env: Env,
+    token: String,
+    owner: String,
+    amount: Uint128,
+) -> Result<Response, ContractError> {
+    let token = deps.api.addr_validate(&token)?;
+    let owner = deps.api.addr_validate(&owner)?;
+    if amount.is_zero() {
+        return Err(ContractError::EmptyBalance {});
+    }
+    if !INITIAL_DUE.has(deps.storage, &owner) {
+        return Err(ContractError::NotAParticipant {});
+    }
+    if !DUE.has(deps.storage, &owner) {
+        return Err(ContractError::AlreadyFunded {});
+    }
+
+    // The credited amount is confirmed against the escrow's balance in the reply
+    let balance_before = query_cw20_balance(deps.as_ref(), &token, &env.contract.address)?;
+    PENDING_PULL.save(
+        deps.storage,
+        &PendingPull {
+            owner: owner.clone(),
+            token: token.clone(),
+            balance_before,
+        },
+    )?;
+
+    let msg = cosmwasm_std::WasmMsg::Execute {
+        contract_addr: token.to_string(),
+        msg: to_json_binary(&cw20::Cw20ExecuteMsg::TransferFrom {
+            owner: owner.to_string(),
+            recipient: env.contract.address.to_string(),
+            amount,
+        })?,
+        funds: vec![],
+    };
+
+    Ok(Response::new()
+        .add_attribute("action", "pull_cw20")
+        .add_attribute("owner", owner)
+        .add_attribute("token", token)
+        .add_attribute("amount", amount)
+        .add_submessage(SubMsg::reply_on_success(msg, PULL_CW20_REPLY_ID)))
+}
+
+pub fn consolidate_pool(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    router: String,
+    target_denom: String,
+    min_output: Uint128,
+) -> Result<Response, ContractError> {
+    assert_resolver(deps.as_ref(), &info.sender)?;
+    let router = deps.api.addr_validate(&router)?;
+
+    if HAS_DISTRIBUTED.load(deps.storage)? {
+        return Err(ContractError::StdError(StdError::GenericErr {
+            msg: "The escrow has already been distributed".to_string(),
+        }));
+    }
+    if !is_locked(deps.as_ref()) {
+        return Err(ContractError::StdError(StdError::GenericErr {
+            msg: "The escrow must be locked before its pool is consolidated".to_string(),
+        }));
+    }
+
+    let swaps: Vec<Coin> = TOTAL_BALANCE
+        .may_load(deps.storage)?
+        .unwrap_or_default()
+        .native
+        .into_iter()
+        .filter(|x| x.denom != target_denom && !x.amount.is_zero())
+        .collect();
+    if swaps.is_empty() {
+        return Err(ContractError::EmptyBalance {});
+    }
+
+    // The received amounts are measured against the escrow's balance in each reply
+    let balance_before = deps
+        .querier
+        .query_balance(&env.contract.address, &target_denom)?
+        .amount;
+    PENDING_CONSOLIDATION.save(
+        deps.storage,
+        &PendingConsolidation {
+            target_denom: target_denom.clone(),
+            min_output,
+            swaps: swaps.clone(),
+            balance_before,
+            received: Uint128::zero(),
+        },
+    )?;
+
+    let msgs = swaps
+        .into_iter()
+        .map(|coin| -> StdResult<SubMsg> {
+            Ok(SubMsg::reply_on_success(
+                cosmwasm_std::WasmMsg::Execute {
+                    contract_addr: router.to_string(),
+                    msg: to_json_binary(&RouterExecuteMsg::Swap {
+                        ask_denom: target_denom.clone(),
+                    })?,
+                    funds: vec![coin],
+                },
+                CONSOLIDATE_REPLY_ID,
+            ))
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+
+    Ok(Response::new()
+        .add_attribute("action", "consolidate_pool")
+        .add_attribute("router", router)
+        .add_attribute("target_denom", target_denom)
+        .add_submessages(msgs))
+}
+
+/// Credits the output of a single swap, converting each holder of the swapped denom pro rata
+pub fn consolidate_reply(deps: DepsMut, env: Env) -> Result<Response, ContractError> {
+    let mut pending = PENDING_CONSOLIDATION.load(deps.storage)?;
+    let swapped = pending.swaps.remove(0);
+
+    let balance_after = deps
+        .querier
+        .query_balance(&env.contract.address, &pending.target_denom)?
+        .amount;
+    let received = balance_after.checked_sub(pending.balance_before)?;
+    let output = Coin {
+        denom: pending.target_denom.clone(),
+        amount: received,
+    };
+
+    // The members and the house hold the swapped denom
+    let mut holders: Vec<(Option<Addr>, BalanceVerified)> = BALANCE
+        .range(deps.storage, None, None, cosmwasm_std::Order::Ascending)
+        .map(|x| x.map(|(addr, balance)| (Some(addr), balance)))
+        .collect::<StdResult<_>>()?;
+    let house = HOUSE.may_load(deps.storage)?;
+    if let Some(house) = &house {
+        holders.push((None, house.balance.clone()));
+    }
+    let held = |balance: &BalanceVerified| {
+        balance
+            .native
+            .iter()
+            .find(|x| x.denom == swapped.denom)
+            .map(|x| x.amount)
+            .unwrap_or_default()
+    };
+
+    // Rounding dust goes to the largest holder, the first of them on a tie
+    let shares = holders
+        .iter()
+        .map(|(_, balance)| received.checked_multiply_ratio(held(balance), swapped.amount))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| ContractError::StdError(StdError::generic_err(e.to_string())))?;
+    let dust = received.checked_sub(shares.iter().sum())?;
+    let largest = holders
+        .iter()
+        .enumerate()
+        .rev()
+        .max_by_key(|(_, (_, balance))| held(balance))
+        .map(|(i, _)| i);
+
+    for (i, ((holder, balance), share)) in holders.into_iter().zip(shares).enumerate() {
+        let amount = held(&balance);
+        if amount.is_zero() {
+            continue;
+        }
+        let share = if Some(i) == largest {
+            share.checked_add(dust)?
+        } else {
+            share
+        };
+
+        let balance = balance
+            .checked_sub(&BalanceVerified {
+                native: vec![Coin {
+                    denom: swapped.denom.clone(),
+                    amount,
+                }],
+                cw20: vec![],
+                cw721: vec![],
+            })
+            .and_then(|x| {
+                x.checked_add(&BalanceVerified {
+                    native: vec![Coin {
+                        denom: pending.target_denom.clone(),
+                        amount: share,
+                    }],
+                    cw20: vec![],
+                    cw721: vec![],
+                })
+            })
+            .map_err(ContractError::from_balance_math)?;
+        match holder {
+            Some(addr) => BALANCE.save(deps.storage, &addr, &balance)?,
+            None => {
+                if let Some(house) = &house {
+                    HOUSE.save(
+                        deps.storage,
+                        &MemberBalanceChecked {
+                            addr: house.addr.clone(),
+                            balance,
+                        },
+                    )?;
+                }
+            }
+        }
+    }
+
+    let total_balance = TOTAL_BALANCE
+        .load(deps.storage)?
+        .checked_sub(&BalanceVerified {
+            native: vec![swapped.clone()],
+            cw20: vec![],
+            cw721: vec![],
+        })
+        .and_then(|x| {
+            x.checked_add(&BalanceVerified {
+                native: vec![output.clone()],
+                cw20: vec![],
+                cw721: vec![],
+            })
+        })
+        .map_err(ContractError::from_balance_math)?;
+    TOTAL_BALANCE.save(deps.storage, &total_balance, env.block.height)?;
+
+    pending.received = pending.received.checked_add(received)?;
+    pending.balance_before = balance_after;
+    if pending.swaps.is_empty() {
+        PENDING_CONSOLIDATION.remove(deps.storage);
+
+        // Falling short reverts every swap of the consolidation
+        if pending.received < pending.min_output {
+            return Err(ContractError::Slippage {
+                min_output: pending.min_output,
+                received: pending.received,
+            });
+        }
+    } else {
+        PENDING_CONSOLIDATION.save(deps.storage, &pending)?;
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "consolidate_reply")
+        .add_attribute("swapped", swapped.to_string())
+        .add_attribute("received", output.to_string()))
+}
+
+pub fn activate_reply(deps: DepsMut, env: Env, error: String) -> Result<Response, ContractError> {
+    IS_LOCKED.save(deps.storage, &false, env.block.height)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "activation_failed")
+        .add_attribute("error", error.clone())
+        .add_event(
+            Event::new("activation_failed")
+                .add_attributes(competition_id_attribute(deps.as_ref())?)
+                .add_attribute("error", error),
+        ))
+}
+
+pub fn pull_cw20_reply(deps: DepsMut, env: Env) -> Result<Response, ContractError> {
+    let pending = PENDING_PULL.load(deps.storage)?;
+    PENDING_PULL.remove(deps.storage);
+
+    let balance_after = query_cw20_balance(deps.as_ref(), &pending.token, &env.contract.address)?;
+    let pulled = balance_after.checked_sub(pending.balance_before)?;
+    if pulled.is_zero() {
+        return Err(ContractError::EmptyBalance {});
+    }
+
+    let balance = BalanceVerified {
+        native: vec![],
+        cw20: vec![Cw20CoinVerified {
+            address: pending.token,
+            amount: pulled,
+        }],
+        cw721: vec![],
+    };
+
+    receive_balance(deps, env, pending.owner, balance)
+}
+
+fn query_cw20_balance(deps: Deps, token: &Addr, addr: &Addr) -> StdResult<Uint128> {
+    let response: cw20::BalanceResponse = deps.querier.query_wasm_smart(
+        token,
+        &cw20::Cw20QueryMsg::Balance {
+            address: addr.to_string(),
+        },
+    )?;
+
+    Ok(response.balance)
+}
+
+// This function receives CW721 tokens and updates the balance
+pub fn receive_cw721(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    cw721_receive_msg: Cw721ReceiveMsg,
+) -> Result<Response, ContractError> {
+    let sender_addr = deps.api.addr_validate(&cw721_receive_msg.sender)?;
+    let cw721_balance = vec![Cw721CollectionVerified {
+        address: info.sender,
+        token_ids: vec![cw721_receive_msg.token_id],
+    }];
+
+    let balance = BalanceVerified {
+        native: info.funds,
+        cw20: vec![],
+        cw721: cw721_balance,
+    };
+
+    receive_balance(deps, env, sender_addr, balance)
+}
+
+fn receive_balance(
+    mut deps: DepsMut,
+    env: Env,
+    addr: Addr,
+    balance: BalanceVerified,
+) -> Result<Response, ContractError> {
+    if !INITIAL_DUE.has(deps.storage, &addr) {
+        return Err(ContractError::NotAParticipant {});
+    }
+    if !DUE.has(deps.storage, &addr) {
+        return Err(ContractError::AlreadyFunded {});
+    }
+    if is_arena_paused(deps.as_ref())? {
+        return Err(ContractError::Paused {});
+    }
+
+    NET_FUNDED.update(deps.storage, &addr, |net_funded| match net_funded {
+        Some(net_funded) => net_funded
+            .checked_add(&balance)
+            .map_err(ContractError::from_balance_math),
+        None => Ok(balance.clone()),
+    })?;
+
+    // Update the stored balance for the given address
+    let updated_balance =
+        BALANCE.update(deps.storage, &addr, |maybe_balance| match maybe_balance {
+            Some(existing_balance) => existing_balance
+                .checked_add(&balance)
+                .map_err(ContractError::from_balance_math),
+            None => Ok(balance.clone()),
+        })?;
+
+    let due_balance = DUE.load(deps.storage, &addr)?;
+    let mut remaining_due = updated_balance.difference(&due_balance)?;
+
+    // Allow alternative denoms to cover the fungible due if an oracle is configured
+    if !remaining_due.is_empty() {
+        if let Some(oracle) = ORACLE.may_load(deps.storage)? {
+            remaining_due = cover_with_oracle(
+                deps.as_ref(),
+                &oracle,
+                &updated_balance,
+                &due_balance,
+                remaining_due,
+            )?;
+        }
+    }
+
+    let mut msgs: Vec<SubMsg> = vec![];
+    let mut events = vec![];
+
+    // Handle the case where the due balance is fully paid
+    if remaining_due.is_empty() {
+        DUE.remove(deps.storage, &addr);
+        FUNDED_AT.save(deps.storage, &addr, &env.block.height)?;
+        events.push(CompetitionEscrowEvent::Funded {
+            addr: addr.to_string(),
+        });
+
+        // Lock when fully funded, or when the activation quorum is first met, and activate
+        let should_activate = match ACTIVATION_QUORUM.may_load(deps.storage)? {
+            Some(quorum) => !is_locked(deps.as_ref()) && is_quorum_met(deps.as_ref(), &quorum)?,
+            None => is_fully_funded(deps.as_ref()),
+        };
+        if should_activate {
+            match AUTO_LOCK_DELAY.may_load(deps.storage)? {
+                // Members can still withdraw until the scheduled lock is applied
+                Some(delay) => {
+                    if SCHEDULED_LOCK.may_load(deps.storage)?.is_none() {
+                        SCHEDULED_LOCK.save(deps.storage, &delay.after(&env.block))?;
+                    }
+                }
+                None => {
+                    msgs.extend(lock_and_activate(deps.branch(), &env)?);
+                    events.push(CompetitionEscrowEvent::Locked {});
+                }
+            }
+        }
+    } else {
+        DUE.save(deps.storage, &addr, &remaining_due)?;
+    }
+
+    // Only the deposit is added, since earlier partial deposits are already counted
+    let total_balance = match TOTAL_BALANCE.may_load(deps.storage)? {
+        Some(total) => total
+            .checked_add(&balance)
+            .map_err(ContractError::from_balance_math)?,
+        None => balance,
+    };
+    TOTAL_BALANCE.save(deps.storage, &total_balance, env.block.height)?;
+
+    let is_activating = !msgs.is_empty();
+
+    Ok(Response::new()
+        .add_attribute("action", "receive_balance")
+        .add_attributes(competition_id_attribute(deps.as_ref())?)
+        .add_attribute("balance", updated_balance.to_string())
+        .add_attribute("activated", is_activating.to_string())
+        .add_submessages(msgs)
+        .add_submessages(notify_listeners(deps.as_ref(), events)?))
+}
+
+// A rejected activation unlocks the escrow in the reply instead of trapping the funds
+fn lock_and_activate(deps: DepsMut, env: &Env) -> Result<Vec<SubMsg>, ContractError> {
+    IS_LOCKED.save(deps.storage, &true, env.block.height)?;
+
+    let mut msgs = vec![];
+    if let Some(owner) = get_ownership(deps.storage)?.owner {
+        msgs.push(SubMsg::reply_on_error(
+            CosmosMsg::Wasm(cosmwasm_std::WasmMsg::Execute {
+                contract_addr: owner.to_string(),
+                msg: to_json_binary(
+                    &cw_competition::msg::ExecuteBase::<Empty, Empty>::Activate {},
+                )?,
+                funds: vec![],
+            }),
+            ACTIVATE_REPLY_ID,
+        ));
+    }
+
+    Ok(msgs)
+}
+
+/// Anyone can apply the lock once its delay has passed
+pub fn lock_scheduled(mut deps: DepsMut, env: Env) -> Result<Response, ContractError> {
+    let scheduled_lock = SCHEDULED_LOCK
+        .may_load(deps.storage)?
+        .ok_or(ContractError::StdError(StdError::GenericErr {
+            msg: "No lock is scheduled".to_string(),
+        }))?;
+    if !scheduled_lock.is_expired(&env.block) {
+        return Err(ContractError::StdError(StdError::GenericErr {
+            msg: format!("The lock is scheduled for {}", scheduled_lock),
+        }));
+    }
+    if HAS_DISTRIBUTED.load(deps.storage)? || !is_activated(deps.as_ref())? {
+        return Err(ContractError::StdError(StdError::GenericErr {
+            msg: "The escrow is no longer ready to lock".to_string(),
+        }));
+    }
+
+    SCHEDULED_LOCK.remove(deps.storage);
+    let msgs = lock_and_activate(deps.branch(), &env)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "lock_scheduled")
+        .add_attributes(competition_id_attribute(deps.as_ref())?)
+        .add_attribute("activated", (!msgs.is_empty()).to_string())
+        .add_submessages(msgs)
+        .add_submessages(notify_listeners(
+            deps.as_ref(),
+            vec![CompetitionEscrowEvent::Locked {}],
+        )?))
+}
+
+// Sends each event to every listener without letting a failing listener revert the escrow
+fn notify_listeners(deps: Deps, events: Vec<CompetitionEscrowEvent>) -> StdResult<Vec<SubMsg>> {
+    let listeners = LISTENERS.may_load(deps.storage)?.unwrap_or_default();
+
+    let mut msgs = vec![];
+    for event in events {
+        for listener in &listeners {
+            msgs.push(SubMsg::reply_on_error(
+                event.clone().into_cosmos_msg(listener)?,
+                LISTENER_REPLY_ID,
+            ));
+        }
+    }
+
+    Ok(msgs)
+}
+
+pub fn update_listeners(
+    deps: DepsMut,
+    info: MessageInfo,
+    to_add: Vec<String>,
+    to_remove: Vec<String>,
+) -> Result<Response, ContractError> {
+    assert_owner(deps.storage, &info.sender)?;
+
+    let mut listeners = LISTENERS.may_load(deps.storage)?.unwrap_or_default();
+    for addr in to_add {
+        let addr = deps.api.addr_validate(&addr)?;
+        if !listeners.contains(&addr) {
+            listeners.push(addr);
+        }
+    }
+    let to_remove = to_remove
+        .iter()
+        .map(|x| deps.api.addr_validate(x))
+        .collect::<StdResult<Vec<_>>>()?;
+    listeners.retain(|x| !to_remove.contains(x));
+    LISTENERS.save(deps.storage, &listeners)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "update_listeners")
+        .add_attribute("listeners", listeners.len().to_string()))
+}
+
+pub fn update_metadata(
+    deps: DepsMut,
+    info: MessageInfo,
+    metadata: Option<EscrowMetadata>,
+) -> Result<Response, ContractError> {
+    assert_owner(deps.storage, &info.sender)?;
+
+    match &metadata {
+        Some(metadata) => METADATA.save(deps.storage, metadata)?,
+        None => METADATA.remove(deps.storage),
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "update_metadata")
+        .add_attribute("has_metadata", metadata.is_some().to_string()))
+}
+
+pub fn set_competition_id(
+    deps: DepsMut,
+    info: MessageInfo,
+    competition_id: Uint128,
+) -> Result<Response, ContractError> {
+    assert_owner(deps.storage, &info.sender)?;
+
+    COMPETITION_ID.save(deps.storage, &competition_id)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "set_competition_id")
+        .add_attribute("competition_id", competition_id))
+}
+
+pub fn update_resolvers(
+    deps: DepsMut,
+    info: MessageInfo,
+    to_add: Vec<String>,
+    to_remove: Vec<String>,
+) -> Result<Response, ContractError> {
+    assert_owner(deps.storage, &info.sender)?;
+
+    for addr in to_add {
+        let addr = deps.api.addr_validate(&addr)?;
+        AUTHORIZED_RESOLVERS.save(deps.storage, &addr, &())?;
+    }
+    for addr in to_remove {
+        let addr = deps.api.addr_validate(&addr)?;
+        AUTHORIZED_RESOLVERS.remove(deps.storage, &addr);
+    }
+
+    Ok(Response::new().add_attribute("action", "update_resolvers"))
+}
+
+// Clears the fungible remaining due if the oracle value of the tokens paid outside of the due covers it
+pub(crate) fn cover_with_oracle(
+    deps: Deps,
+    oracle: &Addr,
+    balance: &BalanceVerified,
+    due: &BalanceVerified,
+    remaining_due: BalanceVerified,
+) -> Result<BalanceVerified, ContractError> {
+    let query_price = |denom: String| -> StdResult<Decimal> {
+        deps.querier
+            .query_wasm_smart(oracle, &OracleQueryMsg::Price { denom })
+    };
+
+    // Value what is still owed, rounding up
+    let mut due_value = Uint128::zero();
+    for coin in &remaining_due.native {
+        let price = query_price(coin.denom.clone())?;
+        due_value = due_value.checked_add(coin.amount.checked_mul_ceil(price)?)?;
+    }
+    for token in &remaining_due.cw20 {
+        let price = query_price(token.address.to_string())?;
+        due_value = due_value.checked_add(token.amount.checked_mul_ceil(price)?)?;
+    }
+
+    if due_value.is_zero() {
+        return Ok(remaining_due);
+    }
+
+    // Value the alternative tokens paid, rounding down
+    let mut paid_value = Uint128::zero();
+    for coin in balance
+        .native
+        .iter()
+        .filter(|x| !due.native.iter().any(|y| y.denom == x.denom))
+    {
+        let price = query_price(coin.denom.clone())?;
+        paid_value = paid_value.checked_add(coin.amount.checked_mul_floor(price)?)?;
+    }
+    for token in balance
+        .cw20
+        .iter()
+        .filter(|x| !due.cw20.iter().any(|y| y.address == x.address))
+    {
+        let price = query_price(token.address.to_string())?;
+        paid_value = paid_value.checked_add(token.amount.checked_mul_floor(price)?)?;
+    }
+
+    if paid_value >= due_value {
+        Ok(BalanceVerified {
+            native: vec![],
+            cw20: vec![],
+            cw721: remaining_due.cw721,
+        })
+    } else {
+        Ok(remaining_due)
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn distribute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    distribution: Option<Distribution<String>>,
+    bonus_distribution: Option<Distribution<String>>,
+    require_full_funding: Option<bool>,
+    rounding: Option<RoundingMode>,
+    tax_info: Option<TaxInformation<String>>,
+    fixed_amounts: Option<Vec<(String, BalanceVerified)>>,
+    treasury: Option<TreasuryPayout<String>>,
+    nft_mode: Option<NftMode>,
+) -> Result<Response, ContractError> {
+    // Ensure the sender is the owner or an authorized resolver
+    assert_resolver(deps.as_ref(), &info.sender)?;
+
+    distribute_balance(
+        deps,
+        env,
+        distribution,
+        bonus_distribution,
+        require_full_funding,
+        rounding.unwrap_or_default(),
+        tax_info,
+        fixed_amounts,
+        treasury,
+        nft_mode.unwrap_or_default(),
+    )
+}
+
+/// A solo challenge against the house has a single winner taking the whole balance
+pub fn settle_solo(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    member_wins: bool,
+) -> Result<Response, ContractError> {
+    assert_resolver(deps.as_ref(), &info.sender)?;
+
+    let members = INITIAL_DUE
+        .keys(deps.storage, None, None, cosmwasm_std::Order::Ascending)
+        .take(2)
+        .collect::<StdResult<Vec<_>>>()?;
+    let member = match members.as_slice() {
+        [member] => member.clone(),
+        _ => {
+            return Err(ContractError::StdError(StdError::GenericErr {
+                msg: "Only an escrow with one member can be settled solo".to_string(),
+            }))
+        }
+    };
+    let winner = if member_wins {
+        member
+    } else {
+        HOUSE
+            .may_load(deps.storage)?
+            .ok_or(ContractError::StdError(StdError::GenericErr {
+                msg: "The escrow has no house to win".to_string(),
+            }))?
+            .addr
+    };
+
+    let response = distribute_balance(
+        deps,
+        env,
+        Some(Distribution::<String> {
+            member_percentages: vec![MemberPercentage {
+                addr: winner.to_string(),
+                percentage: Decimal::one(),
+            }],
+            remainder_addr: winner.to_string(),
+        }),
+        None,
+        Some(true),
+        RoundingMode::default(),
+        None,
+        None,
+        None,
+        NftMode::default(),
+    )?;
+
+    Ok(response
+        .add_attribute("solo_winner", winner.to_string())
+        .add_attribute("member_wins", member_wins.to_string()))
+}
+
+fn add_balance(deps: DepsMut, addr: &Addr, balance: &BalanceVerified) -> Result<(), ContractError> {
+    BALANCE.update(
+        deps.storage,
+        addr,
+        |old_balance| -> Result<_, ContractError> {
+            match old_balance {
+                Some(old_balance) => old_balance
+                    .checked_add(balance)
+                    .map_err(ContractError::from_balance_math),
+                None => Ok(balance.clone()),
+            }
+        },
+    )?;
+
+    Ok(())
+}
+
+/// Resolves the arena core reported by the competition module owning this escrow
+/// An owner which is not a competition module, or predates the query, confirms no core
+pub(crate) fn query_arena_core(deps: Deps) -> Option<Addr> {
+    let owner = get_ownership(deps.storage).ok()?.owner?;
+
+    deps.querier
+        .query_wasm_smart(
+            owner,
+            &cw_competition::msg::QueryBase::<Empty, Empty, Empty>::ArenaCore {},
+        )
+        .ok()
+}
+
+/// Whether the arena core has paused funding, which can't be the case without a confirmed core
+/// A confirmed core that fails to answer is treated as paused
+fn is_arena_paused(deps: Deps) -> StdResult<bool> {
+    match query_arena_core(deps) {
+        Some(arena_core) => deps.querier.query_wasm_smart(
+            arena_core,
+            &arena_core_interface::msg::QueryMsg::QueryExtension {
+                msg: arena_core_interface::msg::QueryExt::IsPaused {},
+            },
+        ),
+        None => Ok(false),
+    }
+}
+
+/// Rejects the balance if the arena core blocks any of its native denoms or cw20 tokens
+/// Nothing is blocked without a confirmed core, but a confirmed core that fails to answer rejects the balance
+pub(crate) fn assert_assets_allowed(
+    deps: Deps,
+    balance: &BalanceVerified,
+) -> Result<(), ContractError> {
+    let arena_core = match query_arena_core(deps) {
+        Some(arena_core) => arena_core,
+        None => return Ok(()),
+    };
+
+    let assets = balance
+        .native
+        .iter()
+        .filter(|x| !x.denom.is_empty())
+        .map(|x| x.denom.clone())
+        .chain(balance.cw20.iter().map(|x| x.address.to_string()));
+    for asset in assets {
+        let is_blocked: bool = deps.querier.query_wasm_smart(
+            &arena_core,
+            &arena_core_interface::msg::QueryMsg::QueryExtension {
+                msg: arena_core_interface::msg::QueryExt::IsAssetBlocked {
+                    asset: asset.clone(),
+                },
+            },
+        )?;
+
+        if is_blocked {
+            return Err(ContractError::BlockedAsset { asset });
+        }
+    }
+
+    Ok(())
+}
+
+/// The member's preferred payout address, falling back to the member if none is set
+fn query_payout_addr(deps: Deps, arena_core: Option<&Addr>, addr: &Addr) -> Addr {
+    arena_core
+        .and_then(|arena_core| {
+            deps.querier
+                .query_wasm_smart::<Option<Addr>>(
+                    arena_core,
+                    &arena_core_interface::msg::QueryMsg::QueryExtension {
+                        msg: arena_core_interface::msg::QueryExt::PayoutAddress {
+                            addr: addr.to_string(),
+                        },
+                    },
+                )
+                .ok()
+                .flatten()
+        })
+        .unwrap_or_else(|| addr.clone())
+}
+
+/// Splits each share by its recipient's preset distribution and routes the leaves to their payout addresses
+pub(crate) fn apply_presets(
+    deps: Deps,
+    arena_core: Option<&Addr>,
+    distributed_amounts: Vec<MemberBalanceChecked>,
+    rounding: &RoundingMode,
+) -> Result<Vec<MemberBalanceChecked>, ContractError> {
+    let mut payouts = vec![];
+    for distributed_amount in distributed_amounts {
+        // Check for preset distribution and apply if available
+        let leaves = match PRESET_DISTRIBUTION.may_load(deps.storage, &distributed_amount.addr)? {
+            Some(preset) => distributed_amount
+                .balance
+                .split_with_rounding(&preset, rounding)?,
+            None => vec![distributed_amount],
+        };
+
+        for leaf in leaves {
+            payouts.push(MemberBalanceChecked {
+                addr: query_payout_addr(deps, arena_core, &leaf.addr),
+                balance: leaf.balance,
+            });
+        }
+    }
+
+    Ok(payouts)
+}
+
+/// The tax and credits a distribution makes, in the order they are credited
+pub(crate) struct BalanceSplit {
+    pub tax: BalanceVerified,
+    pub credits: Vec<MemberBalanceChecked>,
+}
+
+pub(crate) fn validate_fixed_amounts(
+    deps: Deps,
+    fixed_amounts: Vec<(String, BalanceVerified)>,
+) -> Result<Vec<MemberBalanceChecked>, ContractError> {
+    fixed_amounts
+        .into_iter()
+        .map(|(addr, balance)| {
+            Ok(MemberBalanceChecked {
+                addr: deps.api.addr_validate(&addr)?,
+                balance,
+            })
+        })
+        .collect()
+}
+
+/// Splits the escrow's balance without changing any state
+/// Distributing and the effective distribution query both rely on this, so they cannot drift apart
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn split_balance(
+    deps: Deps,
+    distribution: Option<&Distribution<Addr>>,
+    bonus_distribution: Option<&Distribution<Addr>>,
+    require_full_funding: Option<bool>,
+    rounding: &RoundingMode,
+    tax: Option<Decimal>,
+    fixed_amounts: Vec<MemberBalanceChecked>,
+    nft_mode: &NftMode,
+) -> Result<BalanceSplit, ContractError> {
+    // Only refunds are allowed until enough members have funded
+    if let Some(min_participants) = MIN_PARTICIPANTS.may_load(deps.storage)? {
+        if distribution.is_some() && funded_count(deps) < min_participants as usize {
+            return Err(ContractError::NotEnoughParticipants { min_participants });
+        }
+    }
+
+    // Load the total balance available for distribution
+    let mut total_balance = TOTAL_BALANCE.load(deps.storage)?;
+
+    // Set aside the partial payments of unfunded members when only funded members are paid out
+    let mut credits = vec![];
+    match (require_full_funding, distribution) {
+        (Some(true), _) if !is_fully_funded(deps) => {
+            return Err(ContractError::NotFullyFunded {});
+        }
+        (Some(false), Some(distribution)) => {
+            if let Some(member) = distribution
+                .member_percentages
+                .iter()
+                .find(|x| !INITIAL_DUE.has(deps.storage, &x.addr) || !is_funded(deps, &x.addr))
+            {
+                return Err(ContractError::InvalidDistribution {
+                    msg: format!("{} is not a funded member", member.addr),
+                });
+            }
+
+            let unfunded = DUE
+                .keys(deps.storage, None, None, cosmwasm_std::Order::Ascending)
+                .collect::<StdResult<Vec<_>>>()?;
+            for addr in unfunded {
+                if let Some(balance) = BALANCE.may_load(deps.storage, &addr)? {
+                    total_balance = total_balance
+                        .checked_sub(&balance)
+                        .map_err(ContractError::from_balance_math)?;
+                    credits.push(MemberBalanceChecked { addr, balance });
+                }
+            }
+        }
+        _ => {}
+    }
+
+    // The tax comes off the pool before it is split
+    let tax_amount = match tax {
+        Some(tax) => total_balance.checked_mul_floor(tax)?,
+        None => BalanceVerified::default(),
+    };
+    total_balance = total_balance
+        .checked_sub(&tax_amount)
+        .map_err(ContractError::from_balance_math)?;
+
+    // A bonus distribution splits the house balance apart from the entry fees
+    let house = HOUSE.may_load(deps.storage)?;
+    let bonus = match (bonus_distribution, &house) {
+        (Some(bonus_distribution), Some(house)) => {
+            let mut house_balance = house.balance.clone();
+
+            // The house share is taxed up front unless the tax is taken at withdrawal
+            if let Some(tax) = tax.filter(|_| distribution.is_some()) {
+                house_balance = house_balance
+                    .checked_sub(&house_balance.checked_mul_floor(tax)?)
+                    .map_err(ContractError::from_balance_math)?;
+            }
+
+            Some((
+                house_balance.clone(),
+                house_balance.split_with_rounding(bonus_distribution, rounding)?,
+            ))
+        }
+        _ => None,
+    };
+
+    // Winnings are routed to any payout address the recipients set in the arena core
+    let arena_core = query_arena_core(deps);
+
+    if let Some(distribution) = distribution {
+        // Calculate the distribution amounts based on the entry fees and distribution
+        let pool = match &bonus {
+            Some((house_balance, _)) => total_balance
+                .checked_sub(house_balance)
+                .map_err(ContractError::from_balance_math)?,
+            None => total_balance,
+        };
+
+        // Fixed amounts come out of the pool first, leaving the surplus to the shares
+        let fixed_total = fixed_amounts
+            .iter()
+            .try_fold(BalanceVerified::default(), |total, x| {
+                total.checked_add(&x.balance)
+            })
+            .map_err(ContractError::from_balance_math)?;
+        let pool =
+            pool.checked_sub(&fixed_total)
+                .map_err(|_| ContractError::InvalidDistribution {
+                    msg: "The fixed amounts exceed the total balance".to_string(),
+                })?;
+        let mut distributed_amounts = match nft_mode {
+            NftMode::Remainder => pool.split_with_rounding(distribution, rounding)?,
+            // The NFTs are drawn apart from the fungible split
+            NftMode::RandomAssign { seed } => {
+                let mut split = BalanceVerified {
+                    cw721: vec![],
+                    ..pool.clone()
+                }
+                .split_with_rounding(distribution, rounding)?;
+                split.extend(pool.assign_cw721(distribution, seed.u64())?);
+                split
+            }
+            NftMode::RoundRobin => {
+                let mut split = BalanceVerified {
+                    cw721: vec![],
+                    ..pool.clone()
+                }
+                .split_with_rounding(distribution, rounding)?;
+                split.extend(pool.apportion_cw721(distribution)?);
+                split
+            }
+        };
+        distributed_amounts.extend(fixed_amounts);
+        credits.extend(apply_presets(
+            deps,
+            arena_core.as_ref(),
+            distributed_amounts,
+            rounding,
+        )?);
+    }
+
+    match (bonus, house) {
+        (Some((_, bonus_amounts)), _) => {
+            for bonus_amount in bonus_amounts {
+                credits.push(MemberBalanceChecked {
+                    addr: query_payout_addr(deps, arena_core.as_ref(), &bonus_amount.addr),
+                    balance: bonus_amount.balance,
+                });
+            }
+        }
+        (None, Some(house)) if distribution.is_none() => {
+            // Refunds return the seeded funds to the instantiator
+            credits.push(house);
+        }
+        _ => {}
+    }
+
+    Ok(BalanceSplit {
+        tax: tax_amount,
+        credits,
+    })
+}
+
+fn distribute_balance(
+    mut deps: DepsMut,
+    env: Env,
+    distribution: Option<Distribution<String>>,
+    bonus_distribution: Option<Distribution<String>>,
+    require_full_funding: Option<bool>,
+    rounding: RoundingMode,
+    tax_info: Option<TaxInformation<String>>,
+    fixed_amounts: Option<Vec<(String, BalanceVerified)>>,
+    treasury: Option<TreasuryPayout<String>>,
+    nft_mode: NftMode,
+) -> Result<Response, ContractError> {
+    if HAS_DISTRIBUTED.load(deps.storage)? {
+        return Err(ContractError::AlreadyDistributed {});
+    }
+
+    // Bound the share weights before they reach the split math
+    for distribution in [&distribution, &bonus_distribution].into_iter().flatten() {
+        distribution
+            .total_weight()
+            .map_err(|_| ContractError::ShareTotalOverflow {})?;
+    }
+
+    // Validate every recipient before any state is changed
+    let validated_distribution = distribution
+        .as_ref()
+        .map(|distribution| distribution.into_checked(deps.as_ref()))
+        .transpose()?;
+    let validated_bonus_distribution = bonus_distribution
+        .as_ref()
+        .map(|bonus_distribution| bonus_distribution.into_checked(deps.as_ref()))
+        .transpose()?;
+    let validated_fixed_amounts = fixed_amounts
+        .map(|fixed_amounts| {
+            if distribution.is_none() {
+                return Err(ContractError::InvalidDistribution {
+                    msg: "Fixed amounts require a distribution to receive the surplus".to_string(),
+                });
+            }
+
+            validate_fixed_amounts(deps.as_ref(), fixed_amounts)
+        })
+        .transpose()?
+        .unwrap_or_default();
+    let validated_treasury = treasury
+        .map(|treasury| {
+            if distribution.is_none() {
+                return Err(ContractError::InvalidDistribution {
+                    msg: "A treasury payout requires a distribution".to_string(),
+                });
+            }
+
+            Ok(treasury.into_checked(deps.as_ref())?)
+        })
+        .transpose()?;
+
+    // Validate the tax info
+    let validated_tax_info = tax_info
+        .as_ref()
+        .map(|tax_info| tax_info.into_checked(deps.as_ref()))
+        .transpose()?;
+
+    let split = split_balance(
+        deps.as_ref(),
+        validated_distribution.as_ref(),
+        validated_bonus_distribution.as_ref(),
+        require_full_funding,
+        &rounding,
+        validated_tax_info.as_ref().map(|tax_info| tax_info.tax),
+        validated_fixed_amounts,
+        &nft_mode,
+    )?;
+
+    // Process the tax
+    // This will automatically be sent to the receiver
+    let mut msgs = if let Some(tax_info) = validated_tax_info {
+        let taxed_total = TOTAL_BALANCE
+            .load(deps.storage)?
+            .checked_sub(&split.tax)
+            .map_err(ContractError::from_balance_math)?;
+        TOTAL_BALANCE.save(deps.storage, &taxed_total, env.block.height)?;
+
+        // If funds are not split, then we should have the tax at withdrawal
+        if distribution.is_none() {
+            TAX_AT_WITHDRAWAL.save(deps.storage, &tax_info.tax)?;
+        }
+
+        if !split.tax.is_empty() {
+            split.tax.transmit_all(
+                deps.as_ref(),
+                &tax_info.receiver,
+                tax_info.cw20_msg,
+                tax_info.cw721_msg,
+            )?
+        } else {
+            vec![]
+        }
+    } else {
+        vec![]
+    };
+
+    // Clear the existing balance storage and update with new distribution
+    if validated_distribution.is_some() {
+        BALANCE.clear(deps.storage);
+    }
+    for credit in split.credits {
+        add_balance(deps.branch(), &credit.addr, &credit.balance)?;
+    }
+
+    IS_LOCKED.save(deps.storage, &false, env.block.height)?;
+    HAS_DISTRIBUTED.save(deps.storage, &true)?;
+    SCHEDULED_LOCK.remove(deps.storage);
+
+    // Record the distribution, dropping the oldest records beyond the limit
+    let tax_at_withdrawal = TAX_AT_WITHDRAWAL.may_load(deps.storage)?;
+    let recipients = BALANCE
+        .range(deps.storage, None, None, cosmwasm_std::Order::Ascending)
+        .map(|x| -> Result<_, ContractError> {
+            let (addr, mut balance) = x?;
+            if let Some(tax) = tax_at_withdrawal {
+                balance = balance
+                    .checked_sub(&balance.checked_mul_floor(tax)?)
+                    .map_err(ContractError::from_balance_math)?;
+            }
+            Ok(MemberBalanceChecked { addr, balance })
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    let mut history = DISTRIBUTION_HISTORY
+        .may_load(deps.storage)?
+        .unwrap_or_default();
+    history.push(DistributionRecord {
+        timestamp: env.block.time,
+        recipients,
+    });
+    if history.len() > MAX_DISTRIBUTION_HISTORY {
+        history.drain(..history.len() - MAX_DISTRIBUTION_HISTORY);
+    }
+    DISTRIBUTION_HISTORY.save(deps.storage, &history)?;
+
+    // The treasury is paid now so its reference travels with the transfer
+    if let Some(treasury) = validated_treasury {
+        if let Some(balance) = BALANCE.may_load(deps.storage, &treasury.addr)? {
+            BALANCE.remove(deps.storage, &treasury.addr);
+            let total_balance = TOTAL_BALANCE
+                .load(deps.storage)?
+                .checked_sub(&balance)
+                .map_err(ContractError::from_balance_math)?;
+            if total_balance.is_empty() {
+                TOTAL_BALANCE.remove(deps.storage, env.block.height)?;
+            } else {
+                TOTAL_BALANCE.save(deps.storage, &total_balance, env.block.height)?;
+            }
+
+            let treasury_msg = to_json_binary(&TreasuryExecuteMsg::ReceiveDistribution {
+                reference: treasury.reference,
+            })?;
+            if !balance.native.is_empty() {
+                msgs.push(CosmosMsg::Wasm(cosmwasm_std::WasmMsg::Execute {
+                    contract_addr: treasury.addr.to_string(),
+                    msg: treasury_msg.clone(),
+                    funds: balance.native.clone(),
+                }));
+            }
+            msgs.extend(
+                BalanceVerified {
+                    native: vec![],
+                    cw20: balance.cw20,
+                    cw721: balance.cw721,
+                }
+                .send_all(
+                    &treasury.addr,
+                    Some(treasury_msg.clone()),
+                    Some(treasury_msg),
+                )?,
+            );
+        }
+    }
+
+    // Clear the contract state
+    DUE.clear(deps.storage);
+    PRESET_DISTRIBUTION.clear(deps.storage);
+    APPROVALS.clear(deps.storage);
+    MUTUAL_CANCELS.clear(deps.storage);
+
+    Ok(Response::new()
+        .add_attribute("action", "handle_competition_result")
+        .add_attributes(competition_id_attribute(deps.as_ref())?)
+        .add_attribute(
+            "tax",
+            tax_info
+                .map(|some| some.tax.to_string())
+                .unwrap_or("None".to_owned()),
+        )
+        .add_messages(msgs)
+        .add_submessages(notify_listeners(
+            deps.as_ref(),
+            vec![CompetitionEscrowEvent::Distributed {}],
+        )?))
+}
+
+pub fn approve_distribution(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    distribution: Distribution<String>,
+) -> Result<Response, ContractError> {
+    if HAS_DISTRIBUTED.load(deps.storage)? {
+        return Err(ContractError::StdError(StdError::GenericErr {
+            msg: "The escrow has already been distributed".to_string(),
+        }));
+    }
+    if !INITIAL_DUE.has(deps.storage, &info.sender) || !is_funded(deps.as_ref(), &info.sender) {
+        return Err(ContractError::StdError(StdError::GenericErr {
+            msg: "Only funded members can approve a distribution".to_string(),
+        }));
+    }
+
+    // Approvals are compared on the validated distribution
+    let approval = to_json_binary(&distribution.into_checked(deps.as_ref())?)?;
+    APPROVALS.save(deps.storage, &info.sender, &approval)?;
+
+    // Distribute once every member is funded and has approved the same distribution
+    let is_approved = is_fully_funded(deps.as_ref())
+        && INITIAL_DUE
+            .keys(deps.storage, None, None, cosmwasm_std::Order::Ascending)
+            .collect::<StdResult<Vec<_>>>()?
+            .iter()
+            .map(|member| APPROVALS.may_load(deps.storage, member))
+            .collect::<StdResult<Vec<_>>>()?
+            .iter()
+            .all(|x| x.as_ref() == Some(&approval));
+
+    // The agreed result settles the escrow, so a pending lock is no longer applied
+    if is_approved {
+        SCHEDULED_LOCK.remove(deps.storage);
+    }
+
+    let response = if !is_approved {
+        Response::new()
+    } else if query_arena_core(deps.as_ref()).is_some() {
+        // A competition module processes the approved result, so the competition is closed and taxed
+        let owner = get_ownership(deps.storage)?
+            .owner
+            .ok_or(ContractError::OwnershipError(
+                cw_ownable::OwnershipError::NoOwner,
+            ))?;
+
+        Response::new().add_message(CosmosMsg::Wasm(cosmwasm_std::WasmMsg::Execute {
+            contract_addr: owner.to_string(),
+            msg: to_json_binary(
+                &cw_competition::msg::ExecuteBase::<Empty, Empty>::ProcessApproved { distribution },
+            )?,
+            funds: vec![],
+        }))
+    } else {
+        distribute_balance(
+            deps,
+            env,
+            Some(distribution),
+            None,
+            None,
+            RoundingMode::default(),
+            None,
+            None,
+            None,
+            NftMode::default(),
+        )?
+    };
+
+    Ok(response
+        .add_attribute("action", "approve_distribution")
+        .add_attribute("addr", info.sender)
+        .add_attribute("is_approved", is_approved.to_string()))
+}
+
+pub fn payout_batch(deps: DepsMut, env: Env, limit: u32) -> Result<Response, ContractError> {
+    if is_locked(deps.as_ref()) {
+        return Err(ContractError::Locked {});
+    }
+    if !HAS_DISTRIBUTED.load(deps.storage)? {
+        return Err(ContractError::NotDistributed {});
+    }
+
+    let tax = TAX_AT_WITHDRAWAL.may_load(deps.storage)?;
+    let mut total_balance = TOTAL_BALANCE.may_load(deps.storage)?.unwrap_or_default();
+
+    // Paid balances are removed, so the next batch starts at the first unpaid member
+    // Members are paid in ascending address order, each with their native, cw20, then cw721 messages
+    let balances = BALANCE
+        .range(deps.storage, None, None, cosmwasm_std::Order::Ascending)
+        .take(limit as usize)
+        .collect::<StdResult<Vec<_>>>()?;
+
+    let mut msgs = vec![];
+    for (addr, mut balance) in balances {
+        BALANCE.remove(deps.storage, &addr);
+        FUNDED_AT.remove(deps.storage, &addr);
+
+        if balance.is_empty() {
+            continue;
+        }
+
+        // If the total balance has already been taxed, then deduct at the individual level
+        if let Some(tax) = tax {
+            balance = balance
+                .checked_sub(&balance.checked_mul_floor(tax)?)
+                .map_err(ContractError::from_balance_math)?;
+        }
+
+        total_balance = total_balance
+            .checked_sub(&balance)
+            .map_err(ContractError::from_balance_math)?;
+        msgs.extend(balance.transmit_all(deps.as_ref(), &addr, None, None)?);
+    }
+
+    if total_balance.is_empty() {
+        TOTAL_BALANCE.remove(deps.storage, env.block.height)?;
+    } else {
+        TOTAL_BALANCE.save(deps.storage, &total_balance, env.block.height)?;
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "payout_batch")
+        .add_attributes(competition_id_attribute(deps.as_ref())?)
+        .add_attribute("is_complete", BALANCE.is_empty(deps.storage).to_string())
+        .add_messages(msgs))
+}
+
+// The competition id attribute, if the escrow was instantiated with one
+fn competition_id_attribute(deps: Deps) -> StdResult<Option<(&'static str, String)>> {
+    Ok(COMPETITION_ID
+        .may_load(deps.storage)?
+        .map(|id| ("competition_id", id.to_string())))
+}
+
+pub fn lock(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    value: bool,
+) -> Result<Response, ContractError> {
+    assert_resolver(deps.as_ref(), &info.sender)?;
+
+    // Save the locked state to storage
+    IS_LOCKED.save(deps.storage, &value, env.block.height)?;
+
+    let events = if value {
+        vec![CompetitionEscrowEvent::Locked {}]
+    } else {
+        vec![]
+    };
 
     // Build and return the response
     Ok(Response::new()
         .add_attribute("action", "handle_competition_state_changed")
-        .add_attribute("is_locked", value.to_string()))
+        .add_attribute("is_locked", value.to_string())
+        .add_submessages(notify_listeners(deps.as_ref(), events)?))
 }
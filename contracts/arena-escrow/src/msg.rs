@@ -1,19 +1,69 @@
 #[allow(unused_imports)]
-use crate::query::DumpStateResponse;
+use crate::{
+    query::{
+        ContractInfoResponse, DumpStateResponse, HeldAssetsResponse, MemberTimelineResponse,
+        RosterEntry,
+    },
+    state::DistributionRecord,
+};
 use cosmwasm_schema::{cw_serde, QueryResponses};
-use cosmwasm_std::Binary;
+use cosmwasm_std::{Binary, Decimal, Uint128};
 use cw20::Cw20ReceiveMsg;
 use cw721::Cw721ReceiveMsg;
 #[allow(unused_imports)]
 use cw_balance::{
-    BalanceVerified, Distribution, MemberBalanceChecked, MemberBalanceUnchecked, MemberPercentage,
+    BalanceUnchecked, BalanceVerified, Distribution, MemberBalanceChecked, MemberBalanceUnchecked,
+    MemberPercentage, NftMode, RoundingMode,
 };
 use cw_competition::escrow::CompetitionEscrowDistributeMsg;
 use cw_ownable::{cw_ownable_execute, cw_ownable_query};
+use cw_utils::{Duration, Expiration};
 
 #[cw_serde]
 pub struct InstantiateMsg {
     pub dues: Vec<MemberBalanceUnchecked>,
+    /// An optional price oracle used to accept fungible dues in other denoms
+    pub oracle: Option<String>,
+    /// The competition this escrow belongs to, used to tag emitted events
+    pub competition_id: Option<Uint128>,
+    /// Activates the competition once enough members are funded instead of waiting for all of them
+    pub activation_quorum: Option<Quorum>,
+    /// The number of funded members required before the escrow can be distributed
+    /// Refunds are always allowed
+    pub min_participants: Option<u32>,
+    /// Asks the owning competition module to cancel the competition if the escrow closes unfunded
+    pub cancel_on_close: Option<bool>,
+    /// Receives the administrative fee taken when the owner resolves the escrow as no contest
+    pub treasury: Option<String>,
+    /// Delays the lock and activation after full funding, so members can still withdraw
+    pub auto_lock_delay: Option<Duration>,
+    /// Describes the escrow for display
+    pub metadata: Option<EscrowMetadata>,
+}
+
+#[cw_serde]
+pub struct EscrowMetadata {
+    pub title: String,
+    pub description: Option<String>,
+    pub url: Option<String>,
+}
+
+/// How an escrow holding less of an asset than it owes shares the shortfall between refunds
+#[cw_serde]
+pub enum RefundPriority {
+    /// Members are refunded in full in the order they were funded, then the house
+    /// Members who never finished funding come last among the members
+    FundingOrder,
+    /// Every refund of the asset is reduced by the same ratio
+    ProRata,
+}
+
+#[cw_serde]
+pub enum Quorum {
+    /// A minimum number of funded members
+    Members { count: u32 },
+    /// A minimum share of the members funded
+    Percentage { value: Decimal },
 }
 
 #[cw_ownable_execute]
@@ -26,13 +76,101 @@ pub enum ExecuteMsg {
     SetDistribution {
         distribution: Option<Distribution<String>>,
     },
+    /// Removes the sender's preset distribution
+    ClearDistribution {},
     ReceiveNative {},
+    /// Receives a refundable native deposit, which is kept apart from the prize pool
+    ReceiveDeposit {},
     Receive(Cw20ReceiveMsg),
     ReceiveNft(Cw721ReceiveMsg),
     Distribute(CompetitionEscrowDistributeMsg),
+    /// Approves a distribution of the escrow
+    /// Once every member is funded and has approved the same distribution, it is applied without the owner
+    /// An escrow owned by a competition module has the module process it, so the arena tax is taken
+    ApproveDistribution {
+        distribution: Distribution<String>,
+    },
+    /// Transmits up to `limit` members' balances after distribution
+    /// This can be called repeatedly by anyone until every balance is paid out
+    PayoutBatch {
+        limit: u32,
+    },
+    /// Withdraws NFTs sent beyond the member's due, even while locked
+    WithdrawNfts {
+        collection: String,
+        token_ids: Vec<String>,
+        cw721_msg: Option<Binary>,
+    },
+    /// Withdraws the sender's tokens of a single cw721 collection, leaving the rest held
+    WithdrawCollection {
+        collection: String,
+        cw721_msg: Option<Binary>,
+    },
     Lock {
         value: bool,
     },
+    /// Agrees to refund every member before activation, which happens once all who paid in agree
+    MutualCancel {},
+    /// Pays the whole balance of a one member escrow to the member or back to the house
+    SettleSolo {
+        member_wins: bool,
+    },
+    /// Locks and activates the escrow once its scheduled lock has passed
+    LockScheduled {},
+    /// Merges another escrow into this one, summing the shared members' balances
+    /// The other escrow must expect this one to absorb it, and this escrow must not be locked or activated
+    AbsorbEscrow {
+        other: String,
+    },
+    /// Records the escrow which may absorb this one
+    ExpectAbsorb {
+        by: String,
+    },
+    /// Sent by the expected absorbing escrow, or the owner, to take this escrow's whole balance
+    /// This escrow is closed afterwards
+    TransferToEscrow {},
+    /// Swaps every other native denom of the locked pool into the target denom through the router
+    /// Each holder's balance is converted pro rata, and the whole consolidation reverts below the minimum output
+    ConsolidatePool {
+        router: String,
+        target_denom: String,
+        min_output: Uint128,
+    },
+    /// Refunds every member their balance less the fee, which is sent to the treasury
+    /// The fee cannot exceed 10%
+    /// If the escrow holds less of an asset than it owes, the shortfall is shared by the refund priority
+    NoContest {
+        fee: Decimal,
+        /// Defaults to pro rata
+        refund_priority: Option<RefundPriority>,
+    },
+    UpdateListeners {
+        to_add: Vec<String>,
+        to_remove: Vec<String>,
+    },
+    /// Replaces the escrow's display metadata, or removes it if none is given
+    UpdateMetadata {
+        metadata: Option<EscrowMetadata>,
+    },
+    /// Sets who besides the owner may distribute or lock the escrow
+    UpdateResolvers {
+        to_add: Vec<String>,
+        to_remove: Vec<String>,
+    },
+    /// Pulls cw20 tokens the owner has approved for the escrow and credits them to the owner's due
+    PullCw20 {
+        token: String,
+        owner: String,
+        amount: Uint128,
+    },
+    /// Fills in the denom of native dues which omit it, such as a ruleset's required currency
+    SetDueDenom {
+        denom: String,
+    },
+    /// Tags the escrow's events with the competition which most recently adopted it
+    SetCompetitionId {
+        competition_id: Uint128,
+    },
 }
 
 #[cw_ownable_query]
@@ -48,28 +186,119 @@ pub enum QueryMsg {
     Balance { addr: String },
     #[returns(Option<BalanceVerified>)]
     Due { addr: String },
+    #[returns(Option<BalanceVerified>)]
+    Deposit { addr: String },
+    /// The member's funding less any refunds, which is unaffected by distributed winnings
+    #[returns(Option<BalanceVerified>)]
+    NetPosition { addr: String },
     #[returns(Vec<MemberBalanceChecked>)]
     Dues {
         start_after: Option<String>,
         limit: Option<u32>,
     },
+    /// The combined remaining dues needed to fully fund the escrow
+    #[returns(BalanceVerified)]
+    OutstandingFunding {},
     #[returns(Vec<MemberBalanceChecked>)]
     InitialDues {
         start_after: Option<String>,
         limit: Option<u32>,
     },
+    /// Each member's initial due, remaining due, balance, and funded status
+    #[returns(Vec<RosterEntry>)]
+    Roster {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// The member's initial due, funding height, balance, net funding, and preset distribution
+    #[returns(MemberTimelineResponse)]
+    MemberTimeline { addr: String },
     #[returns(bool)]
     IsFunded { addr: String },
     #[returns(bool)]
     IsFullyFunded {},
+    /// Whether crediting the balance to the member would fully fund and activate the escrow
+    /// Always false with an auto lock delay, since funding then only schedules the lock
+    #[returns(bool)]
+    WillActivateOnFunding {
+        addr: String,
+        balance: BalanceUnchecked,
+    },
+    #[returns(Option<u64>)]
+    FundedAt { addr: String },
     #[returns(Option<BalanceVerified>)]
     TotalBalance {},
     #[returns(bool)]
     IsLocked {},
+    #[returns(Option<EscrowMetadata>)]
+    Metadata {},
+    /// When the escrow will lock, if full funding scheduled a delayed lock
+    #[returns(Option<Expiration>)]
+    ScheduledLock {},
+    /// The part of the total balance the distribution would leave for its remainder address
+    #[returns(BalanceVerified)]
+    DistributionRemainder { distribution: Distribution<String> },
+    /// The balance each address would be credited if the distribution were applied now
+    /// The options match distribute's, and the tax is the rate taken up front
+    #[returns(Vec<MemberBalanceChecked>)]
+    EffectiveDistribution {
+        distribution: Vec<MemberPercentage<String>>,
+        remainder_addr: String,
+        bonus_distribution: Option<Distribution<String>>,
+        require_full_funding: Option<bool>,
+        rounding: Option<RoundingMode>,
+        tax: Option<Decimal>,
+        fixed_amounts: Option<Vec<(String, BalanceVerified)>>,
+        nft_mode: Option<NftMode>,
+    },
+    /// The total balance as of the start of the given height
+    #[returns(Option<BalanceVerified>)]
+    TotalBalanceAt { height: u64 },
+    /// Whether the escrow was locked as of the start of the given height
+    #[returns(bool)]
+    IsLockedAt { height: u64 },
     #[returns(Option<Distribution<String>>)]
     Distribution { addr: String },
     #[returns(DumpStateResponse)]
     DumpState { addr: Option<String> },
+    /// The most recent distributions, oldest first
+    #[returns(Vec<DistributionRecord>)]
+    DistributionHistory {},
+    /// The deduplicated native denoms, cw20 tokens, and cw721 collections in the total balance
+    #[returns(HeldAssetsResponse)]
+    HeldAssets {},
+    /// The cw2 contract name and version, with the message schema version
+    #[returns(ContractInfoResponse)]
+    ContractInfo {},
+    #[returns(Vec<cosmwasm_std::Addr>)]
+    Listeners {},
+    #[returns(Vec<cosmwasm_std::Addr>)]
+    Resolvers {},
+    /// The funds seeded at instantiation and who sent them
+    #[returns(Option<MemberBalanceChecked>)]
+    House {},
+}
+
+/// The query interface expected of a price oracle
+/// Prices are quoted in a common unit, so cw20 tokens are queried by their address
+#[cw_serde]
+#[derive(QueryResponses)]
+pub enum OracleQueryMsg {
+    #[returns(Decimal)]
+    Price { denom: String },
+}
+
+/// The swap interface of the router used to consolidate the prize pool
+#[cw_serde]
+pub enum RouterExecuteMsg {
+    /// Swaps the sent funds into the ask denom and returns them to the sender
+    Swap { ask_denom: String },
+}
+
+/// Wraps a treasury's share of a distribution so the transfer carries its reference
+#[cw_serde]
+pub enum TreasuryExecuteMsg {
+    ReceiveDistribution { reference: String },
 }
 
 #[cw_serde]
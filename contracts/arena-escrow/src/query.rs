@@ -1,11 +1,23 @@
+use std::collections::{BTreeMap, BTreeSet};
+
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{Addr, Deps, StdError, StdResult};
-use cw_balance::{BalanceVerified, Distribution, MemberBalanceChecked};
+use cosmwasm_std::{Addr, Decimal, Deps, StdError, StdResult};
+use cw_balance::{
+    BalanceUnchecked, BalanceVerified, Distribution, MemberBalanceChecked, MemberPercentage,
+    NftMode, RoundingMode,
+};
 use cw_storage_plus::Bound;
 use cw_utils::maybe_addr;
 
-use crate::state::{
-    BALANCE, DUE, INITIAL_DUE, IS_LOCKED, PRESET_DISTRIBUTION, TAX_AT_WITHDRAWAL, TOTAL_BALANCE,
+use crate::{
+    contract::SCHEMA_VERSION,
+    execute::{cover_with_oracle, split_balance, validate_fixed_amounts},
+    state::{
+        funded_count, is_activated, is_activated_with, DistributionRecord, AUTHORIZED_RESOLVERS,
+        AUTO_LOCK_DELAY, BALANCE, DEPOSIT, DISTRIBUTION_HISTORY, DUE, FUNDED_AT, HAS_DISTRIBUTED,
+        INITIAL_DUE, IS_LOCKED, NET_FUNDED, ORACLE, PRESET_DISTRIBUTION, TAX_AT_WITHDRAWAL,
+        TOTAL_BALANCE,
+    },
 };
 
 #[cw_serde]
@@ -16,6 +28,41 @@ pub struct DumpStateResponse {
     pub due: Option<BalanceVerified>,
 }
 
+#[cw_serde]
+pub struct ContractInfoResponse {
+    pub contract: String,
+    pub version: String,
+    /// Incremented whenever the messages change in a way clients must branch on
+    pub schema_version: u32,
+}
+
+#[cw_serde]
+pub struct HeldAssetsResponse {
+    pub native: Vec<String>,
+    pub cw20: Vec<Addr>,
+    pub cw721: Vec<Addr>,
+}
+
+#[cw_serde]
+pub struct RosterEntry {
+    pub addr: Addr,
+    pub initial_due: BalanceVerified,
+    pub remaining_due: Option<BalanceVerified>,
+    pub balance: Option<BalanceVerified>,
+    pub is_funded: bool,
+}
+
+/// Everything recorded about a single member's funding
+#[cw_serde]
+pub struct MemberTimelineResponse {
+    /// Empty if the address is not a participant
+    pub initial_due: Option<BalanceVerified>,
+    pub funded_at: Option<u64>,
+    pub current_balance: Option<BalanceVerified>,
+    pub net_funded: Option<BalanceVerified>,
+    pub preset_distribution: Option<Distribution<Addr>>,
+}
+
 pub fn balance(deps: Deps, addr: String) -> StdResult<Option<BalanceVerified>> {
     let addr = deps.api.addr_validate(&addr)?;
 
@@ -43,14 +90,111 @@ pub fn due(deps: Deps, addr: String) -> StdResult<Option<BalanceVerified>> {
     DUE.may_load(deps.storage, &addr)
 }
 
+pub fn deposit(deps: Deps, addr: String) -> StdResult<Option<BalanceVerified>> {
+    let addr = deps.api.addr_validate(&addr)?;
+    DEPOSIT.may_load(deps.storage, &addr)
+}
+
+pub fn net_position(deps: Deps, addr: String) -> StdResult<Option<BalanceVerified>> {
+    let addr = deps.api.addr_validate(&addr)?;
+    NET_FUNDED.may_load(deps.storage, &addr)
+}
+
 pub fn total_balance(deps: Deps) -> StdResult<Option<BalanceVerified>> {
     TOTAL_BALANCE.may_load(deps.storage)
 }
 
+pub fn distribution_remainder(
+    deps: Deps,
+    distribution: Distribution<String>,
+) -> StdResult<BalanceVerified> {
+    distribution
+        .total_weight()
+        .map_err(|e| StdError::generic_err(e.to_string()))?;
+    let distribution = distribution.into_checked(deps)?;
+
+    TOTAL_BALANCE
+        .may_load(deps.storage)?
+        .unwrap_or_default()
+        .remainder(&distribution)
+        .map_err(|e| StdError::generic_err(e.to_string()))
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn effective_distribution(
+    deps: Deps,
+    distribution: Vec<MemberPercentage<String>>,
+    remainder_addr: String,
+    bonus_distribution: Option<Distribution<String>>,
+    require_full_funding: Option<bool>,
+    rounding: Option<RoundingMode>,
+    tax: Option<Decimal>,
+    fixed_amounts: Option<Vec<(String, BalanceVerified)>>,
+    nft_mode: Option<NftMode>,
+) -> StdResult<Vec<MemberBalanceChecked>> {
+    let distribution = Distribution {
+        member_percentages: distribution,
+        remainder_addr,
+    };
+    for distribution in [Some(&distribution), bonus_distribution.as_ref()]
+        .into_iter()
+        .flatten()
+    {
+        distribution
+            .total_weight()
+            .map_err(|e| StdError::generic_err(e.to_string()))?;
+    }
+    let distribution = distribution.into_checked(deps)?;
+    let bonus_distribution = bonus_distribution
+        .map(|bonus_distribution| bonus_distribution.into_checked(deps))
+        .transpose()?;
+
+    let split = validate_fixed_amounts(deps, fixed_amounts.unwrap_or_default())
+        .and_then(|fixed_amounts| {
+            split_balance(
+                deps,
+                Some(&distribution),
+                bonus_distribution.as_ref(),
+                require_full_funding,
+                &rounding.unwrap_or_default(),
+                tax,
+                fixed_amounts,
+                &nft_mode.unwrap_or_default(),
+            )
+        })
+        .map_err(|e| StdError::generic_err(e.to_string()))?;
+
+    // Combine the credits per address as they would be stored
+    let mut balances: BTreeMap<Addr, BalanceVerified> = BTreeMap::new();
+    for payout in split.credits {
+        let balance = match balances.remove(&payout.addr) {
+            Some(balance) => balance.checked_add(&payout.balance)?,
+            None => payout.balance,
+        };
+        balances.insert(payout.addr, balance);
+    }
+
+    Ok(balances
+        .into_iter()
+        .map(|(addr, balance)| MemberBalanceChecked { addr, balance })
+        .collect())
+}
+
 pub fn is_locked(deps: Deps) -> bool {
     IS_LOCKED.load(deps.storage).unwrap_or_default()
 }
 
+// History is only recorded for changes made after the snapshots were introduced
+pub fn total_balance_at(deps: Deps, height: u64) -> StdResult<Option<BalanceVerified>> {
+    TOTAL_BALANCE.may_load_at_height(deps.storage, height)
+}
+
+pub fn is_locked_at(deps: Deps, height: u64) -> StdResult<bool> {
+    Ok(IS_LOCKED
+        .may_load_at_height(deps.storage, height)?
+        .unwrap_or_default())
+}
+
 pub fn distribution(deps: Deps, addr: String) -> StdResult<Option<Distribution<Addr>>> {
     let addr = deps.api.addr_validate(&addr)?;
     PRESET_DISTRIBUTION.may_load(deps.storage, &addr)
@@ -61,6 +205,56 @@ pub fn is_funded(deps: Deps, addr: String) -> StdResult<bool> {
     Ok(crate::state::is_funded(deps, &addr))
 }
 
+/// Simulates crediting the balance to the member, as a deposit would
+pub fn will_activate_on_funding(
+    deps: Deps,
+    addr: String,
+    balance: BalanceUnchecked,
+) -> StdResult<bool> {
+    let addr = deps.api.addr_validate(&addr)?;
+    let balance = balance.into_checked(deps)?;
+
+    // Only an unfunded member of an undistributed escrow can be credited
+    if HAS_DISTRIBUTED.may_load(deps.storage)?.unwrap_or_default() {
+        return Ok(false);
+    }
+    // A delayed lock only schedules activation, which `LockScheduled` applies later
+    if AUTO_LOCK_DELAY.may_load(deps.storage)?.is_some() {
+        return Ok(false);
+    }
+    let due = match DUE.may_load(deps.storage, &addr)? {
+        Some(due) => due,
+        None => return Ok(false),
+    };
+
+    let updated_balance = BALANCE
+        .may_load(deps.storage, &addr)?
+        .unwrap_or_default()
+        .checked_add(&balance)?;
+    let mut remaining_due = updated_balance.difference(&due)?;
+    if !remaining_due.is_empty() {
+        if let Some(oracle) = ORACLE.may_load(deps.storage)? {
+            remaining_due = cover_with_oracle(deps, &oracle, &updated_balance, &due, remaining_due)
+                .map_err(|e| StdError::generic_err(e.to_string()))?;
+        }
+    }
+    if !remaining_due.is_empty() {
+        return Ok(false);
+    }
+
+    // The member would be funded, so check whether that newly activates the escrow
+    Ok(
+        !is_locked(deps)
+            && !is_activated(deps)?
+            && is_activated_with(deps, funded_count(deps) + 1)?,
+    )
+}
+
+pub fn funded_at(deps: Deps, addr: String) -> StdResult<Option<u64>> {
+    let addr = deps.api.addr_validate(&addr)?;
+    FUNDED_AT.may_load(deps.storage, &addr)
+}
+
 pub fn balances(
     deps: Deps,
     start_after: Option<String>,
@@ -103,6 +297,14 @@ pub fn dues(
     })
 }
 
+/// Sums the remaining dues, naming each native denom, cw20 token, and cw721 collection owed
+pub fn outstanding_funding(deps: Deps) -> StdResult<BalanceVerified> {
+    DUE.range(deps.storage, None, None, cosmwasm_std::Order::Ascending)
+        .try_fold(BalanceVerified::default(), |total, due| {
+            total.checked_add(&due?.1)
+        })
+}
+
 pub fn initial_dues(
     deps: Deps,
     start_after: Option<String>,
@@ -118,6 +320,38 @@ pub fn initial_dues(
     })
 }
 
+pub fn roster(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<Vec<RosterEntry>> {
+    let binding = maybe_addr(deps.api, start_after)?;
+    let start = binding.as_ref().map(Bound::exclusive);
+    cw_paginate::paginate_map(&INITIAL_DUE, deps.storage, start, limit, |k, v| {
+        let remaining_due = DUE.may_load(deps.storage, &k)?;
+
+        Ok(RosterEntry {
+            balance: balance(deps, k.to_string())?,
+            is_funded: remaining_due.is_none(),
+            remaining_due,
+            initial_due: v,
+            addr: k,
+        })
+    })
+}
+
+pub fn member_timeline(deps: Deps, addr: String) -> StdResult<MemberTimelineResponse> {
+    let addr = deps.api.addr_validate(&addr)?;
+
+    Ok(MemberTimelineResponse {
+        initial_due: INITIAL_DUE.may_load(deps.storage, &addr)?,
+        funded_at: FUNDED_AT.may_load(deps.storage, &addr)?,
+        current_balance: balance(deps, addr.to_string())?,
+        net_funded: NET_FUNDED.may_load(deps.storage, &addr)?,
+        preset_distribution: PRESET_DISTRIBUTION.may_load(deps.storage, &addr)?,
+    })
+}
+
 pub fn dump_state(deps: Deps, addr: Option<String>) -> StdResult<DumpStateResponse> {
     let maybe_addr = maybe_addr(deps.api, addr)?;
     let balance = maybe_addr
@@ -137,3 +371,53 @@ pub fn dump_state(deps: Deps, addr: Option<String>) -> StdResult<DumpStateRespon
         balance,
     })
 }
+
+pub fn distribution_history(deps: Deps) -> StdResult<Vec<DistributionRecord>> {
+    Ok(DISTRIBUTION_HISTORY
+        .may_load(deps.storage)?
+        .unwrap_or_default())
+}
+
+pub fn held_assets(deps: Deps) -> StdResult<HeldAssetsResponse> {
+    let total_balance = TOTAL_BALANCE.may_load(deps.storage)?.unwrap_or_default();
+
+    Ok(HeldAssetsResponse {
+        native: total_balance
+            .native
+            .into_iter()
+            .map(|x| x.denom)
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect(),
+        cw20: total_balance
+            .cw20
+            .into_iter()
+            .map(|x| x.address)
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect(),
+        cw721: total_balance
+            .cw721
+            .into_iter()
+            .map(|x| x.address)
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect(),
+    })
+}
+
+pub fn resolvers(deps: Deps) -> StdResult<Vec<Addr>> {
+    AUTHORIZED_RESOLVERS
+        .keys(deps.storage, None, None, cosmwasm_std::Order::Ascending)
+        .collect()
+}
+
+pub fn contract_info(deps: Deps) -> StdResult<ContractInfoResponse> {
+    let version = cw2::get_contract_version(deps.storage)?;
+
+    Ok(ContractInfoResponse {
+        contract: version.contract,
+        version: version.version,
+        schema_version: SCHEMA_VERSION,
+    })
+}
@@ -1,15 +1,106 @@
-use cosmwasm_std::{Addr, Decimal, Deps};
-use cw_balance::{BalanceVerified, Distribution};
-use cw_storage_plus::{Item, Map};
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Addr, Binary, Coin, Decimal, Deps, StdError, StdResult, Timestamp, Uint128};
+use cw_balance::{BalanceVerified, Distribution, MemberBalanceChecked};
+use cw_storage_plus::{Item, Map, SnapshotItem, Strategy};
+use cw_utils::{Duration, Expiration};
 
-pub const TOTAL_BALANCE: Item<BalanceVerified> = Item::new("total");
+use crate::{
+    msg::{EscrowMetadata, Quorum},
+    ContractError,
+};
+
+/// Snapshotted to reconstruct the funding state at past heights
+pub const TOTAL_BALANCE: SnapshotItem<BalanceVerified> = SnapshotItem::new(
+    "total",
+    "total__check",
+    "total__change",
+    Strategy::EveryBlock,
+);
 pub const BALANCE: Map<&Addr, BalanceVerified> = Map::new("balance");
+/// Refundable deposits held apart from the prize pool, which are never locked or distributed
+pub const DEPOSIT: Map<&Addr, BalanceVerified> = Map::new("deposit");
+/// Each member's cumulative funding less what they withdrew before distribution
+pub const NET_FUNDED: Map<&Addr, BalanceVerified> = Map::new("net_funded");
 pub const INITIAL_DUE: Map<&Addr, BalanceVerified> = Map::new("initial_due");
 pub const DUE: Map<&Addr, BalanceVerified> = Map::new("due");
-pub const IS_LOCKED: Item<bool> = Item::new("is_locked");
+pub const IS_LOCKED: SnapshotItem<bool> = SnapshotItem::new(
+    "is_locked",
+    "is_locked__check",
+    "is_locked__change",
+    Strategy::EveryBlock,
+);
 pub const HAS_DISTRIBUTED: Item<bool> = Item::new("has_distributed");
 pub const PRESET_DISTRIBUTION: Map<&Addr, Distribution<Addr>> = Map::new("distribution");
 pub const TAX_AT_WITHDRAWAL: Item<Decimal> = Item::new("tax_at_withdrawal");
+/// The block height at which a member's due was fully paid
+pub const FUNDED_AT: Map<&Addr, u64> = Map::new("funded_at");
+/// Funds seeded by the instantiator, which owe no due and are distributed with the prize pool
+pub const HOUSE: Item<MemberBalanceChecked> = Item::new("house");
+/// Contracts notified when a member is funded and when the escrow locks or distributes
+pub const LISTENERS: Item<Vec<Addr>> = Item::new("listeners");
+/// Judges who may distribute or lock the escrow alongside the owner
+pub const AUTHORIZED_RESOLVERS: Map<&Addr, ()> = Map::new("authorized_resolvers");
+/// A cw20 pull awaiting its reply, with the escrow's token balance before the transfer
+pub const PENDING_PULL: Item<PendingPull> = Item::new("pending_pull");
+pub const PENDING_CONSOLIDATION: Item<PendingConsolidation> = Item::new("pending_consolidation");
+pub const ORACLE: Item<Addr> = Item::new("oracle");
+pub const ACTIVATION_QUORUM: Item<Quorum> = Item::new("activation_quorum");
+pub const MIN_PARTICIPANTS: Item<u32> = Item::new("min_participants");
+/// Whether the owner is asked to cancel the competition once every member has withdrawn
+pub const CANCEL_ON_CLOSE: Item<bool> = Item::new("cancel_on_close");
+/// How long after full funding the escrow waits before locking
+pub const AUTO_LOCK_DELAY: Item<Duration> = Item::new("auto_lock_delay");
+/// When a lock scheduled by full funding can be applied
+pub const SCHEDULED_LOCK: Item<Expiration> = Item::new("scheduled_lock");
+/// The owner-set title, description, and URL shown by frontends
+pub const METADATA: Item<EscrowMetadata> = Item::new("metadata");
+/// Receives the fee of a no contest resolution
+pub const TREASURY: Item<Addr> = Item::new("treasury");
+pub const COMPETITION_ID: Item<Uint128> = Item::new("competition_id");
+/// The escrow the owner expects to absorb this one, which may take its whole balance
+pub const EXPECTED_ABSORBER: Item<Addr> = Item::new("expected_absorber");
+/// The serialized distribution each member has approved for an early settlement
+pub const APPROVALS: Map<&Addr, Binary> = Map::new("approvals");
+/// Members with a balance who have agreed to cancel the escrow before it activates
+pub const MUTUAL_CANCELS: Map<&Addr, ()> = Map::new("mutual_cancels");
+/// The most recent distributions, kept for auditing after balances are withdrawn
+pub const DISTRIBUTION_HISTORY: Item<Vec<DistributionRecord>> = Item::new("distribution_history");
+pub const MAX_DISTRIBUTION_HISTORY: usize = 10;
+
+#[cw_serde]
+pub struct PendingPull {
+    pub owner: Addr,
+    pub token: Addr,
+    pub balance_before: Uint128,
+}
+
+/// A pool consolidation awaiting the replies of its swaps
+#[cw_serde]
+pub struct PendingConsolidation {
+    pub target_denom: String,
+    pub min_output: Uint128,
+    /// The swapped coins whose replies are still to come, in submessage order
+    pub swaps: Vec<Coin>,
+    /// The escrow's target denom balance before the next swap
+    pub balance_before: Uint128,
+    pub received: Uint128,
+}
+
+#[cw_serde]
+pub struct DistributionRecord {
+    pub timestamp: Timestamp,
+    /// The amounts each recipient can withdraw after any tax
+    pub recipients: Vec<MemberBalanceChecked>,
+}
+
+/// Allows the owner or any authorized resolver
+pub fn assert_resolver(deps: Deps, sender: &Addr) -> Result<(), ContractError> {
+    if AUTHORIZED_RESOLVERS.has(deps.storage, sender) {
+        return Ok(());
+    }
+
+    Ok(cw_ownable::assert_owner(deps.storage, sender)?)
+}
 
 pub fn is_fully_funded(deps: Deps) -> bool {
     DUE.is_empty(deps.storage)
@@ -18,3 +109,54 @@ pub fn is_fully_funded(deps: Deps) -> bool {
 pub fn is_funded(deps: Deps, addr: &Addr) -> bool {
     !DUE.has(deps.storage, addr)
 }
+
+/// Whether the escrow has met the funding which activates the competition
+pub fn is_activated(deps: Deps) -> StdResult<bool> {
+    match ACTIVATION_QUORUM.may_load(deps.storage)? {
+        Some(quorum) => is_quorum_met(deps, &quorum),
+        None => Ok(is_fully_funded(deps)),
+    }
+}
+
+/// Checks the activating funding against a given number of funded members
+pub fn is_activated_with(deps: Deps, funded: usize) -> StdResult<bool> {
+    match ACTIVATION_QUORUM.may_load(deps.storage)? {
+        Some(quorum) => quorum_met_with(deps, &quorum, funded),
+        None => Ok(funded
+            >= INITIAL_DUE
+                .keys(deps.storage, None, None, cosmwasm_std::Order::Ascending)
+                .count()),
+    }
+}
+
+/// The number of members who have paid their full due
+pub fn funded_count(deps: Deps) -> usize {
+    let members = INITIAL_DUE
+        .keys(deps.storage, None, None, cosmwasm_std::Order::Ascending)
+        .count();
+    let unfunded = DUE
+        .keys(deps.storage, None, None, cosmwasm_std::Order::Ascending)
+        .count();
+
+    members - unfunded
+}
+
+pub fn is_quorum_met(deps: Deps, quorum: &Quorum) -> StdResult<bool> {
+    quorum_met_with(deps, quorum, funded_count(deps))
+}
+
+/// Checks the quorum against a given number of funded members
+pub fn quorum_met_with(deps: Deps, quorum: &Quorum, funded: usize) -> StdResult<bool> {
+    let members = INITIAL_DUE
+        .keys(deps.storage, None, None, cosmwasm_std::Order::Ascending)
+        .count();
+
+    Ok(match quorum {
+        Quorum::Members { count } => funded >= *count as usize,
+        Quorum::Percentage { value } => {
+            Decimal::checked_from_ratio(funded as u128, members as u128)
+                .map_err(|e| StdError::generic_err(e.to_string()))?
+                >= *value
+        }
+    })
+}
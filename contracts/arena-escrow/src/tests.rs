@@ -1,13 +1,26 @@
-use cosmwasm_std::{Addr, Binary, Coin, Decimal, Empty, Uint128};
+use cosmwasm_std::{
+    to_json_binary, Addr, Binary, Coin, CosmosMsg, Decimal, Deps, DepsMut, Empty, Env, Event,
+    MessageInfo, Response, StdResult, Uint128, WasmMsg,
+};
 use cw20::{Cw20Coin, Cw20CoinVerified};
 use cw_balance::{
-    BalanceUnchecked, BalanceVerified, Cw721Collection, Distribution, MemberBalanceUnchecked,
-    MemberPercentage,
+    BalanceUnchecked, BalanceVerified, Cw721Collection, Cw721CollectionVerified, Distribution,
+    MemberBalanceChecked, MemberBalanceUnchecked, MemberPercentage,
+};
+use cw_competition::escrow::{
+    CompetitionEscrowDistributeMsgBuilder, CompetitionEscrowEvent, CompetitionEscrowListenerMsg,
 };
-use cw_multi_test::{App, Executor};
+use cw_multi_test::{App, Contract, ContractWrapper, Executor};
+use cw_storage_plus::{Item, Map};
+use cw_utils::{Duration, Expiration};
 
 use crate::{
-    msg::{ExecuteMsg, InstantiateMsg, QueryMsg},
+    msg::{
+        EscrowMetadata, ExecuteMsg, InstantiateMsg, OracleQueryMsg, QueryMsg, Quorum,
+        RefundPriority, RouterExecuteMsg, TreasuryExecuteMsg,
+    },
+    query::{ContractInfoResponse, HeldAssetsResponse, MemberTimelineResponse, RosterEntry},
+    state::DistributionRecord,
     ContractError,
 };
 
@@ -15,10 +28,283 @@ const CREATOR: &str = "creator";
 const ADDR1: &str = "addr1";
 const ADDR2: &str = "addr2";
 
+const PRICES: Map<&str, Decimal> = Map::new("prices");
+const EVENTS: Item<Vec<CompetitionEscrowEvent>> = Item::new("events");
+
+fn mock_oracle_contract() -> Box<dyn Contract<Empty>> {
+    fn instantiate(
+        deps: DepsMut,
+        _env: Env,
+        _info: MessageInfo,
+        msg: Vec<(String, Decimal)>,
+    ) -> StdResult<Response> {
+        for (denom, price) in msg {
+            PRICES.save(deps.storage, &denom, &price)?;
+        }
+        Ok(Response::default())
+    }
+
+    fn execute(_deps: DepsMut, _env: Env, _info: MessageInfo, _msg: Empty) -> StdResult<Response> {
+        Ok(Response::default())
+    }
+
+    fn query(deps: Deps, _env: Env, msg: OracleQueryMsg) -> StdResult<Binary> {
+        match msg {
+            OracleQueryMsg::Price { denom } => to_json_binary(&PRICES.load(deps.storage, &denom)?),
+        }
+    }
+
+    Box::new(ContractWrapper::new(execute, instantiate, query))
+}
+
+fn mock_competition_module_contract() -> Box<dyn Contract<Empty>> {
+    fn instantiate(
+        _deps: DepsMut,
+        _env: Env,
+        _info: MessageInfo,
+        _msg: Empty,
+    ) -> StdResult<Response> {
+        Ok(Response::default())
+    }
+
+    fn execute(
+        _deps: DepsMut,
+        _env: Env,
+        _info: MessageInfo,
+        _msg: cw_competition::msg::ExecuteBase<Empty, Empty>,
+    ) -> StdResult<Response> {
+        Ok(Response::default())
+    }
+
+    fn query(_deps: Deps, _env: Env, _msg: Empty) -> StdResult<Binary> {
+        Ok(Binary::default())
+    }
+
+    Box::new(ContractWrapper::new(execute, instantiate, query))
+}
+
+// Records the escrow events it receives, or rejects them all if instantiated with true
+// Rejects every message, such as an activation from its escrow
+fn mock_rejecting_competition_module_contract() -> Box<dyn Contract<Empty>> {
+    fn instantiate(
+        _deps: DepsMut,
+        _env: Env,
+        _info: MessageInfo,
+        _msg: Empty,
+    ) -> StdResult<Response> {
+        Ok(Response::default())
+    }
+
+    fn execute(
+        _deps: DepsMut,
+        _env: Env,
+        _info: MessageInfo,
+        _msg: cw_competition::msg::ExecuteBase<Empty, Empty>,
+    ) -> StdResult<Response> {
+        Err(cosmwasm_std::StdError::generic_err("Activation rejected"))
+    }
+
+    fn query(_deps: Deps, _env: Env, _msg: Empty) -> StdResult<Binary> {
+        Ok(Binary::default())
+    }
+
+    Box::new(ContractWrapper::new(execute, instantiate, query))
+}
+
+fn mock_listener_contract() -> Box<dyn Contract<Empty>> {
+    const FAILS: Item<bool> = Item::new("fails");
+
+    fn instantiate(deps: DepsMut, _env: Env, _info: MessageInfo, msg: bool) -> StdResult<Response> {
+        FAILS.save(deps.storage, &msg)?;
+        EVENTS.save(deps.storage, &vec![])?;
+        Ok(Response::default())
+    }
+
+    fn execute(
+        deps: DepsMut,
+        _env: Env,
+        _info: MessageInfo,
+        msg: CompetitionEscrowListenerMsg,
+    ) -> StdResult<Response> {
+        if FAILS.load(deps.storage)? {
+            return Err(cosmwasm_std::StdError::generic_err("Listener failed"));
+        }
+        match msg {
+            CompetitionEscrowListenerMsg::EscrowEvent(event) => {
+                EVENTS.update(deps.storage, |mut events| -> StdResult<_> {
+                    events.push(event);
+                    Ok(events)
+                })?;
+            }
+        }
+        Ok(Response::default())
+    }
+
+    fn query(deps: Deps, _env: Env, _msg: Empty) -> StdResult<Binary> {
+        to_json_binary(&EVENTS.load(deps.storage)?)
+    }
+
+    Box::new(ContractWrapper::new(execute, instantiate, query))
+}
+
+// Records the reference of each distribution it receives
+fn mock_treasury_contract() -> Box<dyn Contract<Empty>> {
+    const REFERENCES: Item<Vec<String>> = Item::new("references");
+
+    fn instantiate(
+        deps: DepsMut,
+        _env: Env,
+        _info: MessageInfo,
+        _msg: Empty,
+    ) -> StdResult<Response> {
+        REFERENCES.save(deps.storage, &vec![])?;
+        Ok(Response::default())
+    }
+
+    fn execute(
+        deps: DepsMut,
+        _env: Env,
+        _info: MessageInfo,
+        msg: TreasuryExecuteMsg,
+    ) -> StdResult<Response> {
+        match msg {
+            TreasuryExecuteMsg::ReceiveDistribution { reference } => {
+                REFERENCES.update(deps.storage, |mut references| -> StdResult<_> {
+                    references.push(reference);
+                    Ok(references)
+                })?;
+            }
+        }
+        Ok(Response::default())
+    }
+
+    fn query(deps: Deps, _env: Env, _msg: Empty) -> StdResult<Binary> {
+        to_json_binary(&REFERENCES.load(deps.storage)?)
+    }
+
+    Box::new(ContractWrapper::new(execute, instantiate, query))
+}
+
+fn mock_router_contract() -> Box<dyn Contract<Empty>> {
+    const RATE: Item<Decimal> = Item::new("rate");
+
+    fn instantiate(
+        deps: DepsMut,
+        _env: Env,
+        _info: MessageInfo,
+        msg: Decimal,
+    ) -> StdResult<Response> {
+        RATE.save(deps.storage, &msg)?;
+        Ok(Response::default())
+    }
+
+    fn execute(
+        deps: DepsMut,
+        _env: Env,
+        info: MessageInfo,
+        msg: RouterExecuteMsg,
+    ) -> StdResult<Response> {
+        let rate = RATE.load(deps.storage)?;
+        match msg {
+            RouterExecuteMsg::Swap { ask_denom } => {
+                let offered: Uint128 = info.funds.iter().map(|x| x.amount).sum();
+                Ok(Response::new().add_message(cosmwasm_std::BankMsg::Send {
+                    to_address: info.sender.to_string(),
+                    amount: vec![Coin {
+                        denom: ask_denom,
+                        amount: offered.mul_floor(rate),
+                    }],
+                }))
+            }
+        }
+    }
+
+    fn query(_deps: Deps, _env: Env, _msg: Empty) -> StdResult<Binary> {
+        Ok(Binary::default())
+    }
+
+    Box::new(ContractWrapper::new(execute, instantiate, query))
+}
+
+// Reports the arena core it is given as its owner, like a competition module owned by a core
+fn mock_owned_competition_module_contract() -> Box<dyn Contract<Empty>> {
+    const OWNER: Item<Addr> = Item::new("owner");
+
+    fn instantiate(
+        _deps: DepsMut,
+        _env: Env,
+        _info: MessageInfo,
+        _msg: Empty,
+    ) -> StdResult<Response> {
+        Ok(Response::default())
+    }
+
+    fn execute(deps: DepsMut, _env: Env, _info: MessageInfo, msg: String) -> StdResult<Response> {
+        OWNER.save(deps.storage, &Addr::unchecked(msg))?;
+        Ok(Response::default())
+    }
+
+    fn query(
+        deps: Deps,
+        _env: Env,
+        msg: cw_competition::msg::QueryBase<Empty, Empty, Empty>,
+    ) -> StdResult<Binary> {
+        match msg {
+            cw_competition::msg::QueryBase::ArenaCore {} => {
+                to_json_binary(&OWNER.load(deps.storage)?)
+            }
+            _ => to_json_binary(&cw_ownable::Ownership {
+                owner: OWNER.may_load(deps.storage)?,
+                pending_owner: None,
+                pending_expiry: None,
+            }),
+        }
+    }
+
+    Box::new(ContractWrapper::new(execute, instantiate, query))
+}
+
+// Reports the owner it is given, like an ownable contract which is not a competition module
+fn mock_ownable_contract() -> Box<dyn Contract<Empty>> {
+    const OWNER: Item<Addr> = Item::new("owner");
+
+    #[cosmwasm_schema::cw_serde]
+    enum OwnableQueryMsg {
+        Ownership {},
+    }
+
+    fn instantiate(
+        deps: DepsMut,
+        _env: Env,
+        _info: MessageInfo,
+        msg: String,
+    ) -> StdResult<Response> {
+        OWNER.save(deps.storage, &Addr::unchecked(msg))?;
+        Ok(Response::default())
+    }
+
+    fn execute(_deps: DepsMut, _env: Env, _info: MessageInfo, _msg: Empty) -> StdResult<Response> {
+        Ok(Response::default())
+    }
+
+    fn query(deps: Deps, _env: Env, msg: OwnableQueryMsg) -> StdResult<Binary> {
+        match msg {
+            OwnableQueryMsg::Ownership {} => to_json_binary(&cw_ownable::Ownership {
+                owner: OWNER.may_load(deps.storage)?,
+                pending_owner: None,
+                pending_expiry: None,
+            }),
+        }
+    }
+
+    Box::new(ContractWrapper::new(execute, instantiate, query))
+}
+
 struct Context {
     pub app: App,
     pub escrow_addr: Addr,
     pub cw20_addr: Addr,
+    pub cw721_addr: Addr,
 }
 
 fn setup() -> Context {
@@ -178,6 +464,14 @@ fn setup() -> Context {
                         },
                     },
                 ],
+                oracle: None,
+                competition_id: Some(Uint128::one()),
+                activation_quorum: None,
+                min_participants: None,
+                cancel_on_close: None,
+                treasury: None,
+                auto_lock_delay: None,
+                metadata: None,
             },
             &[],
             "Arena Escrow",
@@ -189,6 +483,7 @@ fn setup() -> Context {
         app,
         escrow_addr,
         cw20_addr,
+        cw721_addr,
     }
 }
 
@@ -415,3 +710,5451 @@ fn test_deposit_withdraw_and_check_balances() {
     assert!(balance_addr1.is_none());
     assert!(balance_total.is_none());
 }
+
+#[test]
+fn test_funded_at() {
+    let mut context = setup();
+    let addr1 = Addr::unchecked(ADDR1);
+
+    // Fund the native portion of the due
+    context
+        .app
+        .execute_contract(
+            addr1.clone(),
+            context.escrow_addr.clone(),
+            &ExecuteMsg::ReceiveNative {},
+            &[
+                Coin {
+                    denom: "native1".to_string(),
+                    amount: Uint128::from(100u128),
+                },
+                Coin {
+                    denom: "native2".to_string(),
+                    amount: Uint128::from(50u128),
+                },
+            ],
+        )
+        .unwrap();
+    context
+        .app
+        .execute_contract(
+            addr1.clone(),
+            context.cw20_addr.clone(),
+            &cw20::Cw20ExecuteMsg::Send {
+                contract: context.escrow_addr.to_string(),
+                amount: Uint128::from(150u128),
+                msg: Binary::default(),
+            },
+            &[],
+        )
+        .unwrap();
+
+    // Not yet fully funded
+    let funded_at: Option<u64> = context
+        .app
+        .wrap()
+        .query_wasm_smart(
+            context.escrow_addr.clone(),
+            &QueryMsg::FundedAt {
+                addr: ADDR1.to_string(),
+            },
+        )
+        .unwrap();
+    assert!(funded_at.is_none());
+
+    // Send the final nft at a known height
+    context.app.update_block(|block| block.height += 5);
+    let funding_height = context.app.block_info().height;
+    context
+        .app
+        .execute_contract(
+            addr1.clone(),
+            context.cw721_addr.clone(),
+            &cw721_base::ExecuteMsg::<Option<Empty>, Empty>::SendNft {
+                contract: context.escrow_addr.to_string(),
+                token_id: 1.to_string(),
+                msg: Binary::default(),
+            },
+            &[],
+        )
+        .unwrap();
+
+    let funded_at: Option<u64> = context
+        .app
+        .wrap()
+        .query_wasm_smart(
+            context.escrow_addr.clone(),
+            &QueryMsg::FundedAt {
+                addr: ADDR1.to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(funded_at, Some(funding_height));
+
+    // Withdrawing clears the funded height
+    context
+        .app
+        .execute_contract(
+            addr1,
+            context.escrow_addr.clone(),
+            &ExecuteMsg::Withdraw {
+                cw20_msg: None,
+                cw721_msg: None,
+            },
+            &[],
+        )
+        .unwrap();
+
+    let funded_at: Option<u64> = context
+        .app
+        .wrap()
+        .query_wasm_smart(
+            context.escrow_addr.clone(),
+            &QueryMsg::FundedAt {
+                addr: ADDR1.to_string(),
+            },
+        )
+        .unwrap();
+    assert!(funded_at.is_none());
+}
+
+#[test]
+fn test_oracle_funding() {
+    let mut context = setup();
+    let escrow_code_id = context
+        .app
+        .store_code(arena_testing::contracts::arena_dao_escrow_contract());
+    let oracle_code_id = context.app.store_code(mock_oracle_contract());
+
+    // native2 is worth twice as much as native1
+    let oracle_addr = context
+        .app
+        .instantiate_contract(
+            oracle_code_id,
+            Addr::unchecked(CREATOR),
+            &vec![
+                ("native1".to_string(), Decimal::one()),
+                ("native2".to_string(), Decimal::from_ratio(2u128, 1u128)),
+            ],
+            &[],
+            "Oracle",
+            None,
+        )
+        .unwrap();
+
+    let escrow_addr = context
+        .app
+        .instantiate_contract(
+            escrow_code_id,
+            Addr::unchecked(CREATOR),
+            &InstantiateMsg {
+                dues: vec![
+                    MemberBalanceUnchecked {
+                        addr: ADDR1.to_string(),
+                        balance: BalanceUnchecked {
+                            native: vec![Coin {
+                                denom: "native1".to_string(),
+                                amount: Uint128::from(100u128),
+                            }],
+                            cw20: vec![],
+                            cw721: vec![],
+                        },
+                    },
+                    MemberBalanceUnchecked {
+                        addr: ADDR2.to_string(),
+                        balance: BalanceUnchecked {
+                            native: vec![Coin {
+                                denom: "native1".to_string(),
+                                amount: Uint128::from(100u128),
+                            }],
+                            cw20: vec![],
+                            cw721: vec![],
+                        },
+                    },
+                ],
+                oracle: Some(oracle_addr.to_string()),
+                competition_id: None,
+                activation_quorum: None,
+                min_participants: None,
+                cancel_on_close: None,
+                treasury: None,
+                auto_lock_delay: None,
+                metadata: None,
+            },
+            &[],
+            "Arena Escrow",
+            None,
+        )
+        .unwrap();
+
+    // Paying less than the due's value in native2 does not fund
+    context
+        .app
+        .execute_contract(
+            Addr::unchecked(ADDR1),
+            escrow_addr.clone(),
+            &ExecuteMsg::ReceiveNative {},
+            &[Coin {
+                denom: "native2".to_string(),
+                amount: Uint128::from(40u128),
+            }],
+        )
+        .unwrap();
+    let is_funded: bool = context
+        .app
+        .wrap()
+        .query_wasm_smart(
+            escrow_addr.clone(),
+            &QueryMsg::IsFunded {
+                addr: ADDR1.to_string(),
+            },
+        )
+        .unwrap();
+    assert!(!is_funded);
+
+    // Topping up to 50 native2 covers the 100 native1 due
+    context
+        .app
+        .execute_contract(
+            Addr::unchecked(ADDR1),
+            escrow_addr.clone(),
+            &ExecuteMsg::ReceiveNative {},
+            &[Coin {
+                denom: "native2".to_string(),
+                amount: Uint128::from(10u128),
+            }],
+        )
+        .unwrap();
+    let is_funded: bool = context
+        .app
+        .wrap()
+        .query_wasm_smart(
+            escrow_addr,
+            &QueryMsg::IsFunded {
+                addr: ADDR1.to_string(),
+            },
+        )
+        .unwrap();
+    assert!(is_funded);
+}
+
+#[test]
+fn test_competition_id_attributes() {
+    let mut context = setup();
+
+    let has_competition_id = |res: &cw_multi_test::AppResponse| {
+        res.events
+            .iter()
+            .flat_map(|e| e.attributes.iter())
+            .any(|attr| attr.key == "competition_id" && attr.value == "1")
+    };
+
+    // Funding includes the competition id
+    let res = context
+        .app
+        .execute_contract(
+            Addr::unchecked(ADDR1),
+            context.cw20_addr.clone(),
+            &cw20::Cw20ExecuteMsg::Send {
+                contract: context.escrow_addr.to_string(),
+                amount: Uint128::from(150u128),
+                msg: Binary::default(),
+            },
+            &[],
+        )
+        .unwrap();
+    assert!(has_competition_id(&res));
+
+    // Distribution includes the competition id
+    let res = context
+        .app
+        .execute_contract(
+            Addr::unchecked(CREATOR),
+            context.escrow_addr.clone(),
+            &ExecuteMsg::Distribute(cw_competition::escrow::CompetitionEscrowDistributeMsg {
+                distribution: None,
+                bonus_distribution: None,
+                require_full_funding: None,
+                rounding: None,
+                tax_info: None,
+                fixed_amounts: None,
+                treasury: None,
+                nft_mode: None,
+            }),
+            &[],
+        )
+        .unwrap();
+    assert!(has_competition_id(&res));
+}
+
+#[test]
+fn test_payout_batch() {
+    let mut context = setup();
+    let escrow_code_id = context
+        .app
+        .store_code(arena_testing::contracts::arena_dao_escrow_contract());
+
+    let escrow_addr = context
+        .app
+        .instantiate_contract(
+            escrow_code_id,
+            Addr::unchecked(CREATOR),
+            &InstantiateMsg {
+                dues: [ADDR1, ADDR2]
+                    .iter()
+                    .map(|addr| MemberBalanceUnchecked {
+                        addr: addr.to_string(),
+                        balance: BalanceUnchecked {
+                            native: vec![Coin {
+                                denom: "native1".to_string(),
+                                amount: Uint128::from(500u128),
+                            }],
+                            cw20: vec![],
+                            cw721: vec![],
+                        },
+                    })
+                    .collect(),
+                oracle: None,
+                competition_id: None,
+                activation_quorum: None,
+                min_participants: None,
+                cancel_on_close: None,
+                treasury: None,
+                auto_lock_delay: None,
+                metadata: None,
+            },
+            &[],
+            "Arena Escrow",
+            None,
+        )
+        .unwrap();
+
+    context
+        .app
+        .execute_contract(
+            Addr::unchecked(ADDR1),
+            escrow_addr.clone(),
+            &ExecuteMsg::ReceiveNative {},
+            &[Coin {
+                denom: "native1".to_string(),
+                amount: Uint128::from(500u128),
+            }],
+        )
+        .unwrap();
+
+    // Payouts are only available after distribution
+    let err: ContractError = context
+        .app
+        .execute_contract(
+            Addr::unchecked(ADDR1),
+            escrow_addr.clone(),
+            &ExecuteMsg::PayoutBatch { limit: 20 },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::NotDistributed {});
+
+    // Split the pool between 50 members
+    let members: Vec<String> = (0..50).map(|i| format!("member{}", i)).collect();
+    context
+        .app
+        .execute_contract(
+            Addr::unchecked(CREATOR),
+            escrow_addr.clone(),
+            &ExecuteMsg::Distribute(cw_competition::escrow::CompetitionEscrowDistributeMsg {
+                distribution: Some(Distribution::<String> {
+                    member_percentages: members
+                        .iter()
+                        .map(|addr| MemberPercentage {
+                            addr: addr.clone(),
+                            percentage: Decimal::percent(2),
+                        })
+                        .collect(),
+                    remainder_addr: members[0].clone(),
+                }),
+                bonus_distribution: None,
+                require_full_funding: None,
+                rounding: None,
+                tax_info: None,
+                fixed_amounts: None,
+                treasury: None,
+                nft_mode: None,
+            }),
+            &[],
+        )
+        .unwrap();
+
+    // Settle the members in batches of 20
+    let mut is_complete = vec![];
+    for _ in 0..3 {
+        let res = context
+            .app
+            .execute_contract(
+                Addr::unchecked(CREATOR),
+                escrow_addr.clone(),
+                &ExecuteMsg::PayoutBatch { limit: 20 },
+                &[],
+            )
+            .unwrap();
+        is_complete.push(
+            res.events
+                .iter()
+                .flat_map(|e| e.attributes.iter())
+                .find(|attr| attr.key == "is_complete")
+                .map(|attr| attr.value.clone())
+                .unwrap(),
+        );
+    }
+    assert_eq!(is_complete, vec!["false", "false", "true"]);
+
+    for member in &members {
+        let balance = context.app.wrap().query_balance(member, "native1").unwrap();
+        assert_eq!(balance.amount, Uint128::from(10u128));
+    }
+
+    let total_balance: Option<BalanceVerified> = context
+        .app
+        .wrap()
+        .query_wasm_smart(escrow_addr, &QueryMsg::TotalBalance {})
+        .unwrap();
+    assert!(total_balance.is_none());
+}
+
+#[test]
+fn test_held_assets() {
+    let mut context = setup();
+
+    // Nothing is held before funding
+    let held_assets: HeldAssetsResponse = context
+        .app
+        .wrap()
+        .query_wasm_smart(context.escrow_addr.clone(), &QueryMsg::HeldAssets {})
+        .unwrap();
+    assert!(held_assets.native.is_empty());
+    assert!(held_assets.cw20.is_empty());
+    assert!(held_assets.cw721.is_empty());
+
+    // Both members fund native1, while addr1 also funds native2, cw20, and cw721
+    for (addr, amount) in [(ADDR1, 100u128), (ADDR2, 200u128)] {
+        context
+            .app
+            .execute_contract(
+                Addr::unchecked(addr),
+                context.escrow_addr.clone(),
+                &ExecuteMsg::ReceiveNative {},
+                &[Coin {
+                    denom: "native1".to_string(),
+                    amount: Uint128::from(amount),
+                }],
+            )
+            .unwrap();
+    }
+    context
+        .app
+        .execute_contract(
+            Addr::unchecked(ADDR1),
+            context.escrow_addr.clone(),
+            &ExecuteMsg::ReceiveNative {},
+            &[Coin {
+                denom: "native2".to_string(),
+                amount: Uint128::from(50u128),
+            }],
+        )
+        .unwrap();
+    context
+        .app
+        .execute_contract(
+            Addr::unchecked(ADDR1),
+            context.cw20_addr.clone(),
+            &cw20::Cw20ExecuteMsg::Send {
+                contract: context.escrow_addr.to_string(),
+                amount: Uint128::from(150u128),
+                msg: Binary::default(),
+            },
+            &[],
+        )
+        .unwrap();
+    context
+        .app
+        .execute_contract(
+            Addr::unchecked(ADDR1),
+            context.cw721_addr.clone(),
+            &cw721_base::ExecuteMsg::<Option<Empty>, Empty>::SendNft {
+                contract: context.escrow_addr.to_string(),
+                token_id: 1.to_string(),
+                msg: Binary::default(),
+            },
+            &[],
+        )
+        .unwrap();
+
+    let held_assets: HeldAssetsResponse = context
+        .app
+        .wrap()
+        .query_wasm_smart(context.escrow_addr.clone(), &QueryMsg::HeldAssets {})
+        .unwrap();
+    assert_eq!(
+        held_assets,
+        HeldAssetsResponse {
+            native: vec!["native1".to_string(), "native2".to_string()],
+            cw20: vec![context.cw20_addr.clone()],
+            cw721: vec![context.cw721_addr.clone()],
+        }
+    );
+}
+
+#[test]
+fn test_deposit_while_locked() {
+    let mut context = setup();
+    let addr1 = Addr::unchecked(ADDR1);
+
+    // Fund the prize pool and the refundable deposit
+    context
+        .app
+        .execute_contract(
+            addr1.clone(),
+            context.escrow_addr.clone(),
+            &ExecuteMsg::ReceiveNative {},
+            &[Coin {
+                denom: "native1".to_string(),
+                amount: Uint128::from(100u128),
+            }],
+        )
+        .unwrap();
+    context
+        .app
+        .execute_contract(
+            addr1.clone(),
+            context.escrow_addr.clone(),
+            &ExecuteMsg::ReceiveDeposit {},
+            &[Coin {
+                denom: "native2".to_string(),
+                amount: Uint128::from(20u128),
+            }],
+        )
+        .unwrap();
+
+    // Non-members cannot deposit
+    let res = context.app.execute_contract(
+        Addr::unchecked(CREATOR),
+        context.escrow_addr.clone(),
+        &ExecuteMsg::ReceiveDeposit {},
+        &[],
+    );
+    assert!(res.is_err());
+
+    // The deposit does not count toward the prize pool
+    let total_balance: BalanceVerified = context
+        .app
+        .wrap()
+        .query_wasm_smart(context.escrow_addr.clone(), &QueryMsg::TotalBalance {})
+        .unwrap();
+    assert_eq!(
+        total_balance.native,
+        vec![Coin {
+            denom: "native1".to_string(),
+            amount: Uint128::from(100u128),
+        }]
+    );
+
+    // Lock the prize pool
+    context
+        .app
+        .execute_contract(
+            Addr::unchecked(CREATOR),
+            context.escrow_addr.clone(),
+            &ExecuteMsg::Lock { value: true },
+            &[],
+        )
+        .unwrap();
+
+    // Only the deposit is withdrawn while locked
+    let withdraw_msg = ExecuteMsg::Withdraw {
+        cw20_msg: None,
+        cw721_msg: None,
+    };
+    context
+        .app
+        .execute_contract(
+            addr1.clone(),
+            context.escrow_addr.clone(),
+            &withdraw_msg,
+            &[],
+        )
+        .unwrap();
+
+    let native1 = context.app.wrap().query_balance(ADDR1, "native1").unwrap();
+    let native2 = context.app.wrap().query_balance(ADDR1, "native2").unwrap();
+    assert_eq!(native1.amount, Uint128::from(900u128));
+    assert_eq!(native2.amount, Uint128::from(1000u128));
+
+    let deposit: Option<BalanceVerified> = context
+        .app
+        .wrap()
+        .query_wasm_smart(
+            context.escrow_addr.clone(),
+            &QueryMsg::Deposit {
+                addr: ADDR1.to_string(),
+            },
+        )
+        .unwrap();
+    assert!(deposit.is_none());
+
+    // The prize remains locked
+    let res = context.app.execute_contract(
+        addr1.clone(),
+        context.escrow_addr.clone(),
+        &withdraw_msg,
+        &[],
+    );
+    assert_eq!(
+        res.unwrap_err().root_cause().to_string(),
+        ContractError::Locked {}.to_string()
+    );
+}
+
+#[test]
+fn test_distribution_history() {
+    let mut context = setup();
+
+    context
+        .app
+        .execute_contract(
+            Addr::unchecked(ADDR1),
+            context.escrow_addr.clone(),
+            &ExecuteMsg::ReceiveNative {},
+            &[Coin {
+                denom: "native1".to_string(),
+                amount: Uint128::from(100u128),
+            }],
+        )
+        .unwrap();
+
+    // No history before distribution
+    let history: Vec<DistributionRecord> = context
+        .app
+        .wrap()
+        .query_wasm_smart(
+            context.escrow_addr.clone(),
+            &QueryMsg::DistributionHistory {},
+        )
+        .unwrap();
+    assert!(history.is_empty());
+
+    // Send everything to addr2
+    context
+        .app
+        .execute_contract(
+            Addr::unchecked(CREATOR),
+            context.escrow_addr.clone(),
+            &ExecuteMsg::Distribute(cw_competition::escrow::CompetitionEscrowDistributeMsg {
+                distribution: Some(Distribution::<String> {
+                    member_percentages: vec![MemberPercentage {
+                        addr: ADDR2.to_string(),
+                        percentage: Decimal::one(),
+                    }],
+                    remainder_addr: ADDR2.to_string(),
+                }),
+                bonus_distribution: None,
+                require_full_funding: None,
+                rounding: None,
+                tax_info: None,
+                fixed_amounts: None,
+                treasury: None,
+                nft_mode: None,
+            }),
+            &[],
+        )
+        .unwrap();
+
+    // The record remains after the balance is withdrawn
+    context
+        .app
+        .execute_contract(
+            Addr::unchecked(ADDR2),
+            context.escrow_addr.clone(),
+            &ExecuteMsg::Withdraw {
+                cw20_msg: None,
+                cw721_msg: None,
+            },
+            &[],
+        )
+        .unwrap();
+
+    let history: Vec<DistributionRecord> = context
+        .app
+        .wrap()
+        .query_wasm_smart(
+            context.escrow_addr.clone(),
+            &QueryMsg::DistributionHistory {},
+        )
+        .unwrap();
+    assert_eq!(
+        history,
+        vec![DistributionRecord {
+            timestamp: context.app.block_info().time,
+            recipients: vec![MemberBalanceChecked {
+                addr: Addr::unchecked(ADDR2),
+                balance: BalanceVerified {
+                    native: vec![Coin {
+                        denom: "native1".to_string(),
+                        amount: Uint128::from(100u128),
+                    }],
+                    cw20: vec![],
+                    cw721: vec![],
+                },
+            }],
+        }]
+    );
+}
+
+#[test]
+fn test_clear_distribution() {
+    let mut context = setup();
+
+    // addr1 presets their winnings to go to addr2, then clears it
+    context
+        .app
+        .execute_contract(
+            Addr::unchecked(ADDR1),
+            context.escrow_addr.clone(),
+            &ExecuteMsg::SetDistribution {
+                distribution: Some(Distribution::<String> {
+                    member_percentages: vec![MemberPercentage {
+                        addr: ADDR2.to_string(),
+                        percentage: Decimal::one(),
+                    }],
+                    remainder_addr: ADDR2.to_string(),
+                }),
+            },
+            &[],
+        )
+        .unwrap();
+    context
+        .app
+        .execute_contract(
+            Addr::unchecked(ADDR1),
+            context.escrow_addr.clone(),
+            &ExecuteMsg::ClearDistribution {},
+            &[],
+        )
+        .unwrap();
+
+    let contract_distribution: Option<Distribution<String>> = context
+        .app
+        .wrap()
+        .query_wasm_smart(
+            &context.escrow_addr,
+            &QueryMsg::Distribution {
+                addr: ADDR1.to_string(),
+            },
+        )
+        .unwrap();
+    assert!(contract_distribution.is_none());
+
+    // Fund and distribute everything to addr1
+    context
+        .app
+        .execute_contract(
+            Addr::unchecked(ADDR1),
+            context.escrow_addr.clone(),
+            &ExecuteMsg::ReceiveNative {},
+            &[Coin {
+                denom: "native1".to_string(),
+                amount: Uint128::from(100u128),
+            }],
+        )
+        .unwrap();
+    context
+        .app
+        .execute_contract(
+            Addr::unchecked(CREATOR),
+            context.escrow_addr.clone(),
+            &ExecuteMsg::Distribute(cw_competition::escrow::CompetitionEscrowDistributeMsg {
+                distribution: Some(Distribution::<String> {
+                    member_percentages: vec![MemberPercentage {
+                        addr: ADDR1.to_string(),
+                        percentage: Decimal::one(),
+                    }],
+                    remainder_addr: ADDR1.to_string(),
+                }),
+                bonus_distribution: None,
+                require_full_funding: None,
+                rounding: None,
+                tax_info: None,
+                fixed_amounts: None,
+                treasury: None,
+                nft_mode: None,
+            }),
+            &[],
+        )
+        .unwrap();
+
+    // The cleared preset is ignored
+    let balance: Option<BalanceVerified> = context
+        .app
+        .wrap()
+        .query_wasm_smart(
+            &context.escrow_addr,
+            &QueryMsg::Balance {
+                addr: ADDR1.to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        balance.unwrap().native,
+        vec![Coin {
+            denom: "native1".to_string(),
+            amount: Uint128::from(100u128),
+        }]
+    );
+    let balance: Option<BalanceVerified> = context
+        .app
+        .wrap()
+        .query_wasm_smart(
+            &context.escrow_addr,
+            &QueryMsg::Balance {
+                addr: ADDR2.to_string(),
+            },
+        )
+        .unwrap();
+    assert!(balance.is_none());
+}
+
+#[test]
+fn test_balance_math_errors() {
+    let native1 = |amount: u128| BalanceVerified {
+        native: vec![Coin {
+            denom: "native1".to_string(),
+            amount: Uint128::from(amount),
+        }],
+        cw20: vec![],
+        cw721: vec![],
+    };
+
+    // Adding past the maximum amount overflows
+    let err = native1(u128::MAX)
+        .checked_add(&native1(1))
+        .map_err(ContractError::from_balance_math)
+        .unwrap_err();
+    assert_eq!(err, ContractError::BalanceOverflow {});
+
+    // Subtracting more than is held is insufficient
+    let err = native1(1)
+        .checked_sub(&native1(2))
+        .map_err(ContractError::from_balance_math)
+        .unwrap_err();
+    assert_eq!(err, ContractError::InsufficientBalance {});
+
+    // Subtracting an asset that is not held is insufficient
+    let err = BalanceVerified::default()
+        .checked_sub(&native1(1))
+        .map_err(ContractError::from_balance_math)
+        .unwrap_err();
+    assert_eq!(err, ContractError::InsufficientBalance {});
+}
+
+#[test]
+fn test_roster() {
+    let mut context = setup();
+
+    // addr1 pays their native due in full while addr2 pays part of theirs
+    context
+        .app
+        .execute_contract(
+            Addr::unchecked(ADDR1),
+            context.escrow_addr.clone(),
+            &ExecuteMsg::ReceiveNative {},
+            &[
+                Coin {
+                    denom: "native1".to_string(),
+                    amount: Uint128::from(100u128),
+                },
+                Coin {
+                    denom: "native2".to_string(),
+                    amount: Uint128::from(50u128),
+                },
+            ],
+        )
+        .unwrap();
+    context
+        .app
+        .execute_contract(
+            Addr::unchecked(ADDR1),
+            context.cw20_addr.clone(),
+            &cw20::Cw20ExecuteMsg::Send {
+                contract: context.escrow_addr.to_string(),
+                amount: Uint128::from(150u128),
+                msg: Binary::default(),
+            },
+            &[],
+        )
+        .unwrap();
+    context
+        .app
+        .execute_contract(
+            Addr::unchecked(ADDR1),
+            context.cw721_addr.clone(),
+            &cw721_base::ExecuteMsg::<Option<Empty>, Empty>::SendNft {
+                contract: context.escrow_addr.to_string(),
+                token_id: 1.to_string(),
+                msg: Binary::default(),
+            },
+            &[],
+        )
+        .unwrap();
+    context
+        .app
+        .execute_contract(
+            Addr::unchecked(ADDR2),
+            context.escrow_addr.clone(),
+            &ExecuteMsg::ReceiveNative {},
+            &[Coin {
+                denom: "native1".to_string(),
+                amount: Uint128::from(200u128),
+            }],
+        )
+        .unwrap();
+
+    let roster: Vec<RosterEntry> = context
+        .app
+        .wrap()
+        .query_wasm_smart(
+            context.escrow_addr.clone(),
+            &QueryMsg::Roster {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(roster.len(), 2);
+
+    // addr1 is funded with no remaining due
+    assert_eq!(roster[0].addr, Addr::unchecked(ADDR1));
+    assert!(roster[0].is_funded);
+    assert!(roster[0].remaining_due.is_none());
+    assert_eq!(
+        roster[0].balance.as_ref().unwrap().cw721[0].token_ids,
+        vec!["1".to_string()]
+    );
+
+    // addr2 still owes everything but native1
+    assert_eq!(roster[1].addr, Addr::unchecked(ADDR2));
+    assert!(!roster[1].is_funded);
+    let remaining_due = roster[1].remaining_due.as_ref().unwrap();
+    assert!(remaining_due
+        .native
+        .iter()
+        .all(|coin| coin.denom != "native1"));
+    assert_eq!(remaining_due.cw20[0].amount, Uint128::from(300u128));
+    assert_eq!(
+        roster[1].balance.as_ref().unwrap().native,
+        vec![Coin {
+            denom: "native1".to_string(),
+            amount: Uint128::from(200u128),
+        }]
+    );
+    assert_eq!(
+        roster[1].initial_due.native[0].amount,
+        Uint128::from(200u128)
+    );
+
+    // Pagination starts after the given member
+    let roster: Vec<RosterEntry> = context
+        .app
+        .wrap()
+        .query_wasm_smart(
+            context.escrow_addr.clone(),
+            &QueryMsg::Roster {
+                start_after: Some(ADDR1.to_string()),
+                limit: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(roster.len(), 1);
+    assert_eq!(roster[0].addr, Addr::unchecked(ADDR2));
+}
+
+#[test]
+fn test_receive_native_no_funds() {
+    let mut context = setup();
+
+    let err: ContractError = context
+        .app
+        .execute_contract(
+            Addr::unchecked(ADDR1),
+            context.escrow_addr.clone(),
+            &ExecuteMsg::ReceiveNative {},
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::NoFunds {});
+
+    // No balance was recorded
+    let balance: Option<BalanceVerified> = context
+        .app
+        .wrap()
+        .query_wasm_smart(
+            context.escrow_addr.clone(),
+            &QueryMsg::Balance {
+                addr: ADDR1.to_string(),
+            },
+        )
+        .unwrap();
+    assert!(balance.is_none());
+}
+
+#[test]
+fn test_receive_errors() {
+    let mut context = setup();
+    let addr1 = Addr::unchecked(ADDR1);
+    let outsider = Addr::unchecked("outsider");
+
+    // Addresses outside of the dues cannot fund the escrow
+    context
+        .app
+        .send_tokens(
+            addr1.clone(),
+            outsider.clone(),
+            &[Coin {
+                denom: "native1".to_string(),
+                amount: Uint128::from(100u128),
+            }],
+        )
+        .unwrap();
+    let err: ContractError = context
+        .app
+        .execute_contract(
+            outsider,
+            context.escrow_addr.clone(),
+            &ExecuteMsg::ReceiveNative {},
+            &[Coin {
+                denom: "native1".to_string(),
+                amount: Uint128::from(100u128),
+            }],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::NotAParticipant {});
+
+    // Fully fund addr1's due
+    context
+        .app
+        .execute_contract(
+            addr1.clone(),
+            context.escrow_addr.clone(),
+            &ExecuteMsg::ReceiveNative {},
+            &[
+                Coin {
+                    denom: "native1".to_string(),
+                    amount: Uint128::from(100u128),
+                },
+                Coin {
+                    denom: "native2".to_string(),
+                    amount: Uint128::from(50u128),
+                },
+            ],
+        )
+        .unwrap();
+    context
+        .app
+        .execute_contract(
+            addr1.clone(),
+            context.cw20_addr.clone(),
+            &cw20::Cw20ExecuteMsg::Send {
+                contract: context.escrow_addr.to_string(),
+                amount: Uint128::from(150u128),
+                msg: Binary::default(),
+            },
+            &[],
+        )
+        .unwrap();
+    context
+        .app
+        .execute_contract(
+            addr1.clone(),
+            context.cw721_addr.clone(),
+            &cw721_base::ExecuteMsg::<Option<Empty>, Empty>::SendNft {
+                contract: context.escrow_addr.to_string(),
+                token_id: 1.to_string(),
+                msg: Binary::default(),
+            },
+            &[],
+        )
+        .unwrap();
+
+    // Funding again after the due is paid is rejected
+    let err: ContractError = context
+        .app
+        .execute_contract(
+            addr1,
+            context.escrow_addr.clone(),
+            &ExecuteMsg::ReceiveNative {},
+            &[Coin {
+                denom: "native1".to_string(),
+                amount: Uint128::from(100u128),
+            }],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::AlreadyFunded {});
+}
+
+#[test]
+fn test_house_funds() {
+    let mut context = setup();
+    let escrow_code_id = context
+        .app
+        .store_code(arena_testing::contracts::arena_dao_escrow_contract());
+
+    // addr1 sponsors the pool at instantiation
+    let dues: Vec<MemberBalanceUnchecked> = [ADDR1, ADDR2]
+        .iter()
+        .map(|addr| MemberBalanceUnchecked {
+            addr: addr.to_string(),
+            balance: BalanceUnchecked {
+                native: vec![Coin {
+                    denom: "native1".to_string(),
+                    amount: Uint128::from(100u128),
+                }],
+                cw20: vec![],
+                cw721: vec![],
+            },
+        })
+        .collect();
+    let escrow_addr = context
+        .app
+        .instantiate_contract(
+            escrow_code_id,
+            Addr::unchecked(ADDR1),
+            &InstantiateMsg {
+                dues,
+                oracle: None,
+                competition_id: None,
+                activation_quorum: None,
+                min_participants: None,
+                cancel_on_close: None,
+                treasury: None,
+                auto_lock_delay: None,
+                metadata: None,
+            },
+            &[Coin {
+                denom: "native1".to_string(),
+                amount: Uint128::from(500u128),
+            }],
+            "Arena Escrow",
+            None,
+        )
+        .unwrap();
+
+    let house: Option<MemberBalanceChecked> = context
+        .app
+        .wrap()
+        .query_wasm_smart(escrow_addr.clone(), &QueryMsg::House {})
+        .unwrap();
+    assert_eq!(house.unwrap().addr, Addr::unchecked(ADDR1));
+
+    // The seeded funds don't count towards any due
+    let is_funded: bool = context
+        .app
+        .wrap()
+        .query_wasm_smart(
+            escrow_addr.clone(),
+            &QueryMsg::IsFunded {
+                addr: ADDR1.to_string(),
+            },
+        )
+        .unwrap();
+    assert!(!is_funded);
+
+    context
+        .app
+        .execute_contract(
+            Addr::unchecked(ADDR2),
+            escrow_addr.clone(),
+            &ExecuteMsg::ReceiveNative {},
+            &[Coin {
+                denom: "native1".to_string(),
+                amount: Uint128::from(100u128),
+            }],
+        )
+        .unwrap();
+
+    // The pool includes the seeded funds and the entry fee
+    let total: Option<BalanceVerified> = context
+        .app
+        .wrap()
+        .query_wasm_smart(escrow_addr.clone(), &QueryMsg::TotalBalance {})
+        .unwrap();
+    assert_eq!(
+        total.unwrap().native,
+        vec![Coin {
+            denom: "native1".to_string(),
+            amount: Uint128::from(600u128),
+        }]
+    );
+
+    // The owner distributes the whole pool to addr2
+    context
+        .app
+        .execute_contract(
+            Addr::unchecked(ADDR1),
+            escrow_addr.clone(),
+            &ExecuteMsg::Distribute(cw_competition::escrow::CompetitionEscrowDistributeMsg {
+                distribution: Some(Distribution::<String> {
+                    member_percentages: vec![MemberPercentage {
+                        addr: ADDR2.to_string(),
+                        percentage: Decimal::one(),
+                    }],
+                    remainder_addr: ADDR2.to_string(),
+                }),
+                bonus_distribution: None,
+                require_full_funding: None,
+                rounding: None,
+                tax_info: None,
+                fixed_amounts: None,
+                treasury: None,
+                nft_mode: None,
+            }),
+            &[],
+        )
+        .unwrap();
+
+    let balance: Option<BalanceVerified> = context
+        .app
+        .wrap()
+        .query_wasm_smart(
+            escrow_addr,
+            &QueryMsg::Balance {
+                addr: ADDR2.to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        balance.unwrap().native,
+        vec![Coin {
+            denom: "native1".to_string(),
+            amount: Uint128::from(600u128),
+        }]
+    );
+}
+
+#[test]
+fn test_withdraw_surplus_nfts() {
+    let mut context = setup();
+    let addr1 = Addr::unchecked(ADDR1);
+
+    // Use a competition module owner, so the escrow can activate when fully funded
+    let module_code_id = context.app.store_code(mock_competition_module_contract());
+    let module_addr = context
+        .app
+        .instantiate_contract(
+            module_code_id,
+            Addr::unchecked(CREATOR),
+            &Empty {},
+            &[],
+            "Competition Module",
+            None,
+        )
+        .unwrap();
+    let escrow_code_id = context
+        .app
+        .store_code(arena_testing::contracts::arena_dao_escrow_contract());
+    let escrow_addr = context
+        .app
+        .instantiate_contract(
+            escrow_code_id,
+            module_addr,
+            &InstantiateMsg {
+                dues: vec![
+                    MemberBalanceUnchecked {
+                        addr: ADDR1.to_string(),
+                        balance: BalanceUnchecked {
+                            native: vec![],
+                            cw20: vec![],
+                            cw721: vec![Cw721Collection {
+                                address: context.cw721_addr.to_string(),
+                                token_ids: vec![1.to_string()],
+                            }],
+                        },
+                    },
+                    MemberBalanceUnchecked {
+                        addr: ADDR2.to_string(),
+                        balance: BalanceUnchecked {
+                            native: vec![Coin {
+                                denom: "native1".to_string(),
+                                amount: Uint128::from(100u128),
+                            }],
+                            cw20: vec![],
+                            cw721: vec![],
+                        },
+                    },
+                ],
+                oracle: None,
+                competition_id: None,
+                activation_quorum: None,
+                min_participants: None,
+                cancel_on_close: None,
+                treasury: None,
+                auto_lock_delay: None,
+                metadata: None,
+            },
+            &[],
+            "Arena Escrow",
+            None,
+        )
+        .unwrap();
+
+    // addr1 sends a surplus nft before its due
+    for token_id in [2, 1] {
+        context
+            .app
+            .execute_contract(
+                addr1.clone(),
+                context.cw721_addr.clone(),
+                &cw721_base::ExecuteMsg::<Option<Empty>, Empty>::SendNft {
+                    contract: escrow_addr.to_string(),
+                    token_id: token_id.to_string(),
+                    msg: Binary::default(),
+                },
+                &[],
+            )
+            .unwrap();
+    }
+    context
+        .app
+        .execute_contract(
+            Addr::unchecked(ADDR2),
+            escrow_addr.clone(),
+            &ExecuteMsg::ReceiveNative {},
+            &[Coin {
+                denom: "native1".to_string(),
+                amount: Uint128::from(100u128),
+            }],
+        )
+        .unwrap();
+
+    let is_locked: bool = context
+        .app
+        .wrap()
+        .query_wasm_smart(escrow_addr.clone(), &QueryMsg::IsLocked {})
+        .unwrap();
+    assert!(is_locked);
+
+    // The nft in the due stays locked
+    let err: ContractError = context
+        .app
+        .execute_contract(
+            addr1.clone(),
+            escrow_addr.clone(),
+            &ExecuteMsg::WithdrawNfts {
+                collection: context.cw721_addr.to_string(),
+                token_ids: vec![1.to_string()],
+                cw721_msg: None,
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(
+        err,
+        ContractError::RequiredNft {
+            token_id: 1.to_string()
+        }
+    );
+
+    // The surplus nft can be withdrawn
+    context
+        .app
+        .execute_contract(
+            addr1.clone(),
+            escrow_addr.clone(),
+            &ExecuteMsg::WithdrawNfts {
+                collection: context.cw721_addr.to_string(),
+                token_ids: vec![2.to_string()],
+                cw721_msg: None,
+            },
+            &[],
+        )
+        .unwrap();
+
+    let owner: cw721::OwnerOfResponse = context
+        .app
+        .wrap()
+        .query_wasm_smart(
+            context.cw721_addr.clone(),
+            &cw721_base::QueryMsg::<Empty>::OwnerOf {
+                token_id: 2.to_string(),
+                include_expired: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(owner.owner, ADDR1);
+
+    let balance: Option<BalanceVerified> = context
+        .app
+        .wrap()
+        .query_wasm_smart(
+            escrow_addr.clone(),
+            &QueryMsg::Balance {
+                addr: ADDR1.to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(balance.unwrap().cw721[0].token_ids, vec![1.to_string()]);
+
+    // A withdrawn nft cannot be withdrawn again
+    let result = context.app.execute_contract(
+        addr1,
+        escrow_addr,
+        &ExecuteMsg::WithdrawNfts {
+            collection: context.cw721_addr.to_string(),
+            token_ids: vec![2.to_string()],
+            cw721_msg: None,
+        },
+        &[],
+    );
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_house_bonus_distribution() {
+    let mut context = setup();
+    let escrow_code_id = context
+        .app
+        .store_code(arena_testing::contracts::arena_dao_escrow_contract());
+
+    // addr1 sponsors the pool at instantiation
+    let dues: Vec<MemberBalanceUnchecked> = [ADDR1, ADDR2]
+        .iter()
+        .map(|addr| MemberBalanceUnchecked {
+            addr: addr.to_string(),
+            balance: BalanceUnchecked {
+                native: vec![Coin {
+                    denom: "native1".to_string(),
+                    amount: Uint128::from(200u128),
+                }],
+                cw20: vec![],
+                cw721: vec![],
+            },
+        })
+        .collect();
+    let escrow_addr = context
+        .app
+        .instantiate_contract(
+            escrow_code_id,
+            Addr::unchecked(ADDR1),
+            &InstantiateMsg {
+                dues,
+                oracle: None,
+                competition_id: None,
+                activation_quorum: None,
+                min_participants: None,
+                cancel_on_close: None,
+                treasury: None,
+                auto_lock_delay: None,
+                metadata: None,
+            },
+            &[Coin {
+                denom: "native1".to_string(),
+                amount: Uint128::from(500u128),
+            }],
+            "Arena Escrow",
+            None,
+        )
+        .unwrap();
+
+    for addr in [ADDR1, ADDR2] {
+        context
+            .app
+            .execute_contract(
+                Addr::unchecked(addr),
+                escrow_addr.clone(),
+                &ExecuteMsg::ReceiveNative {},
+                &[Coin {
+                    denom: "native1".to_string(),
+                    amount: Uint128::from(100u128),
+                }],
+            )
+            .unwrap();
+    }
+
+    // The entry fees are split evenly, while the house bonus goes to addr2
+    context
+        .app
+        .execute_contract(
+            Addr::unchecked(ADDR1),
+            escrow_addr.clone(),
+            &ExecuteMsg::Distribute(cw_competition::escrow::CompetitionEscrowDistributeMsg {
+                distribution: Some(Distribution::<String> {
+                    member_percentages: vec![
+                        MemberPercentage {
+                            addr: ADDR1.to_string(),
+                            percentage: Decimal::percent(50),
+                        },
+                        MemberPercentage {
+                            addr: ADDR2.to_string(),
+                            percentage: Decimal::percent(50),
+                        },
+                    ],
+                    remainder_addr: ADDR1.to_string(),
+                }),
+                bonus_distribution: Some(Distribution::<String> {
+                    member_percentages: vec![MemberPercentage {
+                        addr: ADDR2.to_string(),
+                        percentage: Decimal::one(),
+                    }],
+                    remainder_addr: ADDR2.to_string(),
+                }),
+                require_full_funding: None,
+                rounding: None,
+                tax_info: Some(cw_competition::escrow::TaxInformation {
+                    tax: Decimal::percent(10),
+                    receiver: CREATOR.to_string(),
+                    cw20_msg: None,
+                    cw721_msg: None,
+                }),
+                fixed_amounts: None,
+                treasury: None,
+                nft_mode: None,
+            }),
+            &[],
+        )
+        .unwrap();
+
+    // Fees: (200 - 20) / 2 each, bonus: 500 - 50
+    for (addr, amount) in [(ADDR1, 90u128), (ADDR2, 540u128)] {
+        let balance: Option<BalanceVerified> = context
+            .app
+            .wrap()
+            .query_wasm_smart(
+                escrow_addr.clone(),
+                &QueryMsg::Balance {
+                    addr: addr.to_string(),
+                },
+            )
+            .unwrap();
+        assert_eq!(
+            balance.unwrap().native,
+            vec![Coin {
+                denom: "native1".to_string(),
+                amount: Uint128::from(amount),
+            }]
+        );
+    }
+}
+
+#[test]
+fn test_listeners() {
+    let mut context = setup();
+    let escrow_code_id = context
+        .app
+        .store_code(arena_testing::contracts::arena_dao_escrow_contract());
+    let listener_code_id = context.app.store_code(mock_listener_contract());
+
+    let dues: Vec<MemberBalanceUnchecked> = [ADDR1, ADDR2]
+        .iter()
+        .map(|addr| MemberBalanceUnchecked {
+            addr: addr.to_string(),
+            balance: BalanceUnchecked {
+                native: vec![Coin {
+                    denom: "native1".to_string(),
+                    amount: Uint128::from(100u128),
+                }],
+                cw20: vec![],
+                cw721: vec![],
+            },
+        })
+        .collect();
+    let escrow_addr = context
+        .app
+        .instantiate_contract(
+            escrow_code_id,
+            Addr::unchecked(CREATOR),
+            &InstantiateMsg {
+                dues,
+                oracle: None,
+                competition_id: None,
+                activation_quorum: None,
+                min_participants: None,
+                cancel_on_close: None,
+                treasury: None,
+                auto_lock_delay: None,
+                metadata: None,
+            },
+            &[],
+            "Arena Escrow",
+            None,
+        )
+        .unwrap();
+    let listener_addr = context
+        .app
+        .instantiate_contract(
+            listener_code_id,
+            Addr::unchecked(CREATOR),
+            &false,
+            &[],
+            "Listener",
+            None,
+        )
+        .unwrap();
+    let failing_listener_addr = context
+        .app
+        .instantiate_contract(
+            listener_code_id,
+            Addr::unchecked(CREATOR),
+            &true,
+            &[],
+            "Failing Listener",
+            None,
+        )
+        .unwrap();
+
+    // Only the owner can configure listeners
+    let err: ContractError = context
+        .app
+        .execute_contract(
+            Addr::unchecked(ADDR1),
+            escrow_addr.clone(),
+            &ExecuteMsg::UpdateListeners {
+                to_add: vec![listener_addr.to_string()],
+                to_remove: vec![],
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::OwnershipError(_)));
+
+    context
+        .app
+        .execute_contract(
+            Addr::unchecked(CREATOR),
+            escrow_addr.clone(),
+            &ExecuteMsg::UpdateListeners {
+                to_add: vec![listener_addr.to_string(), failing_listener_addr.to_string()],
+                to_remove: vec![],
+            },
+            &[],
+        )
+        .unwrap();
+    let listeners: Vec<Addr> = context
+        .app
+        .wrap()
+        .query_wasm_smart(escrow_addr.clone(), &QueryMsg::Listeners {})
+        .unwrap();
+    assert_eq!(
+        listeners,
+        vec![listener_addr.clone(), failing_listener_addr]
+    );
+
+    // A failing listener does not block funding
+    context
+        .app
+        .execute_contract(
+            Addr::unchecked(ADDR1),
+            escrow_addr.clone(),
+            &ExecuteMsg::ReceiveNative {},
+            &[Coin {
+                denom: "native1".to_string(),
+                amount: Uint128::from(100u128),
+            }],
+        )
+        .unwrap();
+
+    context
+        .app
+        .execute_contract(
+            Addr::unchecked(CREATOR),
+            escrow_addr.clone(),
+            &ExecuteMsg::Lock { value: true },
+            &[],
+        )
+        .unwrap();
+
+    let events: Vec<CompetitionEscrowEvent> = context
+        .app
+        .wrap()
+        .query_wasm_smart(listener_addr, &Empty {})
+        .unwrap();
+    assert_eq!(
+        events,
+        vec![
+            CompetitionEscrowEvent::Funded {
+                addr: ADDR1.to_string()
+            },
+            CompetitionEscrowEvent::Locked {},
+        ]
+    );
+}
+
+#[test]
+fn test_pull_cw20() {
+    let mut context = setup();
+    let addr1 = Addr::unchecked(ADDR1);
+
+    // Pulling without an allowance fails
+    let result = context.app.execute_contract(
+        addr1.clone(),
+        context.escrow_addr.clone(),
+        &ExecuteMsg::PullCw20 {
+            token: context.cw20_addr.to_string(),
+            owner: ADDR1.to_string(),
+            amount: Uint128::from(150u128),
+        },
+        &[],
+    );
+    assert!(result.is_err());
+
+    context
+        .app
+        .execute_contract(
+            addr1.clone(),
+            context.cw20_addr.clone(),
+            &cw20::Cw20ExecuteMsg::IncreaseAllowance {
+                spender: context.escrow_addr.to_string(),
+                amount: Uint128::from(150u128),
+                expires: None,
+            },
+            &[],
+        )
+        .unwrap();
+
+    // Anyone can trigger the pull, which credits the owner
+    context
+        .app
+        .execute_contract(
+            Addr::unchecked(ADDR2),
+            context.escrow_addr.clone(),
+            &ExecuteMsg::PullCw20 {
+                token: context.cw20_addr.to_string(),
+                owner: ADDR1.to_string(),
+                amount: Uint128::from(150u128),
+            },
+            &[],
+        )
+        .unwrap();
+
+    let balance_addr1: BalanceVerified = context
+        .app
+        .wrap()
+        .query_wasm_smart(
+            context.escrow_addr.clone(),
+            &QueryMsg::Balance {
+                addr: ADDR1.to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        balance_addr1.cw20,
+        vec![Cw20CoinVerified {
+            address: context.cw20_addr.clone(),
+            amount: Uint128::from(150u128),
+        }]
+    );
+
+    // The cw20 portion of the due is paid
+    let due_addr1: BalanceVerified = context
+        .app
+        .wrap()
+        .query_wasm_smart(
+            context.escrow_addr.clone(),
+            &QueryMsg::Due {
+                addr: ADDR1.to_string(),
+            },
+        )
+        .unwrap();
+    assert!(due_addr1.cw20.is_empty());
+
+    let cw20_balance: cw20::BalanceResponse = context
+        .app
+        .wrap()
+        .query_wasm_smart(
+            context.cw20_addr.clone(),
+            &cw20::Cw20QueryMsg::Balance {
+                address: context.escrow_addr.to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(cw20_balance.balance, Uint128::from(150u128));
+
+    // Non-members cannot be pulled from
+    let err: ContractError = context
+        .app
+        .execute_contract(
+            addr1,
+            context.escrow_addr.clone(),
+            &ExecuteMsg::PullCw20 {
+                token: context.cw20_addr.to_string(),
+                owner: CREATOR.to_string(),
+                amount: Uint128::from(150u128),
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::NotAParticipant {});
+}
+
+#[test]
+fn test_distribute_invalid_recipient() {
+    let mut context = setup();
+
+    context
+        .app
+        .execute_contract(
+            Addr::unchecked(ADDR1),
+            context.escrow_addr.clone(),
+            &ExecuteMsg::ReceiveNative {},
+            &[Coin {
+                denom: "native1".to_string(),
+                amount: Uint128::from(100u128),
+            }],
+        )
+        .unwrap();
+    let total_before: BalanceVerified = context
+        .app
+        .wrap()
+        .query_wasm_smart(context.escrow_addr.clone(), &QueryMsg::TotalBalance {})
+        .unwrap();
+
+    let err: ContractError = context
+        .app
+        .execute_contract(
+            Addr::unchecked(CREATOR),
+            context.escrow_addr.clone(),
+            &ExecuteMsg::Distribute(cw_competition::escrow::CompetitionEscrowDistributeMsg {
+                distribution: Some(Distribution::<String> {
+                    member_percentages: vec![
+                        MemberPercentage {
+                            addr: ADDR1.to_string(),
+                            percentage: Decimal::percent(50),
+                        },
+                        MemberPercentage {
+                            addr: "NotAnAddress".to_string(),
+                            percentage: Decimal::percent(50),
+                        },
+                    ],
+                    remainder_addr: ADDR1.to_string(),
+                }),
+                bonus_distribution: None,
+                require_full_funding: None,
+                rounding: None,
+                tax_info: Some(cw_competition::escrow::TaxInformation {
+                    tax: Decimal::percent(10),
+                    receiver: CREATOR.to_string(),
+                    cw20_msg: None,
+                    cw721_msg: None,
+                }),
+                fixed_amounts: None,
+                treasury: None,
+                nft_mode: None,
+            }),
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(err.to_string().contains("NotAnAddress"));
+
+    // Nothing was taxed or distributed
+    let total_after: BalanceVerified = context
+        .app
+        .wrap()
+        .query_wasm_smart(context.escrow_addr.clone(), &QueryMsg::TotalBalance {})
+        .unwrap();
+    assert_eq!(total_before, total_after);
+    let history: Vec<DistributionRecord> = context
+        .app
+        .wrap()
+        .query_wasm_smart(
+            context.escrow_addr.clone(),
+            &QueryMsg::DistributionHistory {},
+        )
+        .unwrap();
+    assert!(history.is_empty());
+}
+
+#[test]
+fn test_contract_info() {
+    let context = setup();
+
+    let info: ContractInfoResponse = context
+        .app
+        .wrap()
+        .query_wasm_smart(context.escrow_addr.clone(), &QueryMsg::ContractInfo {})
+        .unwrap();
+    assert_eq!(
+        info,
+        ContractInfoResponse {
+            contract: crate::contract::CONTRACT_NAME.to_string(),
+            version: crate::contract::CONTRACT_VERSION.to_string(),
+            schema_version: crate::contract::SCHEMA_VERSION,
+        }
+    );
+}
+
+#[test]
+fn test_distribute_funded_members_only() {
+    let mut context = setup();
+    let escrow_code_id = context
+        .app
+        .store_code(arena_testing::contracts::arena_dao_escrow_contract());
+
+    let dues: Vec<MemberBalanceUnchecked> = [ADDR1, ADDR2]
+        .iter()
+        .map(|addr| MemberBalanceUnchecked {
+            addr: addr.to_string(),
+            balance: BalanceUnchecked {
+                native: vec![Coin {
+                    denom: "native1".to_string(),
+                    amount: Uint128::from(100u128),
+                }],
+                cw20: vec![],
+                cw721: vec![],
+            },
+        })
+        .collect();
+    let escrow_addr = context
+        .app
+        .instantiate_contract(
+            escrow_code_id,
+            Addr::unchecked(CREATOR),
+            &InstantiateMsg {
+                dues,
+                oracle: None,
+                competition_id: None,
+                activation_quorum: None,
+                min_participants: None,
+                cancel_on_close: None,
+                treasury: None,
+                auto_lock_delay: None,
+                metadata: None,
+            },
+            &[],
+            "Arena Escrow",
+            None,
+        )
+        .unwrap();
+
+    // addr1 funds fully while addr2 only pays part of its due
+    for (addr, amount) in [(ADDR1, 100u128), (ADDR2, 40u128)] {
+        context
+            .app
+            .execute_contract(
+                Addr::unchecked(addr),
+                escrow_addr.clone(),
+                &ExecuteMsg::ReceiveNative {},
+                &[Coin {
+                    denom: "native1".to_string(),
+                    amount: Uint128::from(amount),
+                }],
+            )
+            .unwrap();
+    }
+
+    let distribute = |context: &mut Context, addr: &str, require_full_funding: bool| {
+        context.app.execute_contract(
+            Addr::unchecked(CREATOR),
+            escrow_addr.clone(),
+            &ExecuteMsg::Distribute(cw_competition::escrow::CompetitionEscrowDistributeMsg {
+                distribution: Some(Distribution::<String> {
+                    member_percentages: vec![MemberPercentage {
+                        addr: addr.to_string(),
+                        percentage: Decimal::one(),
+                    }],
+                    remainder_addr: addr.to_string(),
+                }),
+                bonus_distribution: None,
+                require_full_funding: Some(require_full_funding),
+                rounding: None,
+                tax_info: None,
+                fixed_amounts: None,
+                treasury: None,
+                nft_mode: None,
+            }),
+            &[],
+        )
+    };
+
+    let err: ContractError = distribute(&mut context, ADDR1, true)
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::NotFullyFunded {});
+
+    // Unfunded members cannot receive a share
+    let err: ContractError = distribute(&mut context, ADDR2, false)
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::InvalidDistribution { .. }));
+
+    distribute(&mut context, ADDR1, false).unwrap();
+
+    // addr1 takes the funded pool and addr2's partial payment is refunded
+    for (addr, amount) in [(ADDR1, 100u128), (ADDR2, 40u128)] {
+        let balance: BalanceVerified = context
+            .app
+            .wrap()
+            .query_wasm_smart(
+                escrow_addr.clone(),
+                &QueryMsg::Balance {
+                    addr: addr.to_string(),
+                },
+            )
+            .unwrap();
+        assert_eq!(
+            balance.native,
+            vec![Coin {
+                denom: "native1".to_string(),
+                amount: Uint128::from(amount),
+            }]
+        );
+    }
+}
+
+#[test]
+fn test_outstanding_funding() {
+    let mut context = setup();
+
+    // addr1 pays its native dues, leaving its cw20 and nft outstanding
+    context
+        .app
+        .execute_contract(
+            Addr::unchecked(ADDR1),
+            context.escrow_addr.clone(),
+            &ExecuteMsg::ReceiveNative {},
+            &[
+                Coin {
+                    denom: "native1".to_string(),
+                    amount: Uint128::from(100u128),
+                },
+                Coin {
+                    denom: "native2".to_string(),
+                    amount: Uint128::from(50u128),
+                },
+            ],
+        )
+        .unwrap();
+
+    let outstanding: BalanceVerified = context
+        .app
+        .wrap()
+        .query_wasm_smart(
+            context.escrow_addr.clone(),
+            &QueryMsg::OutstandingFunding {},
+        )
+        .unwrap();
+    assert_eq!(
+        outstanding.native,
+        vec![
+            Coin {
+                denom: "native1".to_string(),
+                amount: Uint128::from(200u128),
+            },
+            Coin {
+                denom: "native2".to_string(),
+                amount: Uint128::from(100u128),
+            },
+        ]
+    );
+    assert_eq!(
+        outstanding.cw20,
+        vec![Cw20CoinVerified {
+            address: context.cw20_addr.clone(),
+            amount: Uint128::from(450u128),
+        }]
+    );
+    assert_eq!(outstanding.cw721.len(), 1);
+    assert_eq!(outstanding.cw721[0].address, context.cw721_addr);
+    assert_eq!(outstanding.cw721[0].token_ids.len(), 2);
+}
+
+#[test]
+fn test_withdraw_collection() {
+    let mut context = setup();
+    let addr1 = Addr::unchecked(ADDR1);
+
+    // A second collection, which is not part of any due
+    let cw721_code_id = context
+        .app
+        .store_code(arena_testing::contracts::cw721_base_contract());
+    let other_cw721_addr = context
+        .app
+        .instantiate_contract(
+            cw721_code_id,
+            Addr::unchecked(CREATOR),
+            &cw721_base::msg::InstantiateMsg {
+                name: "OtherNFTCollection".to_string(),
+                symbol: "OTHERNFT".to_string(),
+                minter: CREATOR.to_string(),
+            },
+            &[],
+            "OtherToken",
+            None,
+        )
+        .unwrap();
+    context
+        .app
+        .execute_contract(
+            Addr::unchecked(CREATOR),
+            other_cw721_addr.clone(),
+            &cw721_base::ExecuteMsg::<Option<Empty>, Empty>::Mint {
+                token_id: 1.to_string(),
+                owner: ADDR1.to_string(),
+                token_uri: None,
+                extension: None,
+            },
+            &[],
+        )
+        .unwrap();
+
+    // addr1 sends an nft from each collection
+    for collection in [&context.cw721_addr, &other_cw721_addr] {
+        context
+            .app
+            .execute_contract(
+                addr1.clone(),
+                collection.clone(),
+                &cw721_base::ExecuteMsg::<Option<Empty>, Empty>::SendNft {
+                    contract: context.escrow_addr.to_string(),
+                    token_id: 1.to_string(),
+                    msg: Binary::default(),
+                },
+                &[],
+            )
+            .unwrap();
+    }
+
+    // Only the other collection is refunded
+    context
+        .app
+        .execute_contract(
+            addr1.clone(),
+            context.escrow_addr.clone(),
+            &ExecuteMsg::WithdrawCollection {
+                collection: other_cw721_addr.to_string(),
+                cw721_msg: None,
+            },
+            &[],
+        )
+        .unwrap();
+
+    let owner: cw721::OwnerOfResponse = context
+        .app
+        .wrap()
+        .query_wasm_smart(
+            other_cw721_addr.clone(),
+            &cw721_base::QueryMsg::<Empty>::OwnerOf {
+                token_id: 1.to_string(),
+                include_expired: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(owner.owner, ADDR1);
+
+    let balance: Option<BalanceVerified> = context
+        .app
+        .wrap()
+        .query_wasm_smart(
+            context.escrow_addr.clone(),
+            &QueryMsg::Balance {
+                addr: ADDR1.to_string(),
+            },
+        )
+        .unwrap();
+    let balance = balance.unwrap();
+    assert_eq!(balance.cw721.len(), 1);
+    assert_eq!(balance.cw721[0].address, context.cw721_addr);
+
+    let total_balance: Option<BalanceVerified> = context
+        .app
+        .wrap()
+        .query_wasm_smart(context.escrow_addr.clone(), &QueryMsg::TotalBalance {})
+        .unwrap();
+    assert_eq!(total_balance.unwrap(), balance);
+
+    // Nothing is left to refund from the other collection
+    let err: ContractError = context
+        .app
+        .execute_contract(
+            addr1.clone(),
+            context.escrow_addr.clone(),
+            &ExecuteMsg::WithdrawCollection {
+                collection: other_cw721_addr.to_string(),
+                cw721_msg: None,
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::EmptyBalance {});
+
+    // Refunding the collection in the due reopens it
+    context
+        .app
+        .execute_contract(
+            addr1.clone(),
+            context.escrow_addr.clone(),
+            &ExecuteMsg::WithdrawCollection {
+                collection: context.cw721_addr.to_string(),
+                cw721_msg: None,
+            },
+            &[],
+        )
+        .unwrap();
+
+    let due: Option<BalanceVerified> = context
+        .app
+        .wrap()
+        .query_wasm_smart(
+            context.escrow_addr.clone(),
+            &QueryMsg::Due {
+                addr: ADDR1.to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        due.unwrap().cw721,
+        vec![Cw721CollectionVerified {
+            address: context.cw721_addr.clone(),
+            token_ids: vec![1.to_string()],
+        }]
+    );
+}
+
+#[test]
+fn test_activation_quorum() {
+    let mut context = setup();
+
+    // Use a competition module owner, so the escrow can activate
+    let module_code_id = context.app.store_code(mock_competition_module_contract());
+    let module_addr = context
+        .app
+        .instantiate_contract(
+            module_code_id,
+            Addr::unchecked(CREATOR),
+            &Empty {},
+            &[],
+            "Competition Module",
+            None,
+        )
+        .unwrap();
+    let escrow_code_id = context
+        .app
+        .store_code(arena_testing::contracts::arena_dao_escrow_contract());
+    let instantiate_msg = |activation_quorum: Option<Quorum>| InstantiateMsg {
+        dues: [ADDR1, ADDR2, "addr3"]
+            .iter()
+            .map(|addr| MemberBalanceUnchecked {
+                addr: addr.to_string(),
+                balance: BalanceUnchecked {
+                    native: vec![Coin {
+                        denom: "native1".to_string(),
+                        amount: Uint128::from(100u128),
+                    }],
+                    cw20: vec![],
+                    cw721: vec![],
+                },
+            })
+            .collect(),
+        oracle: None,
+        competition_id: None,
+        activation_quorum,
+        min_participants: None,
+        cancel_on_close: None,
+        treasury: None,
+        auto_lock_delay: None,
+        metadata: None,
+    };
+
+    // The quorum must be reachable
+    let result = context.app.instantiate_contract(
+        escrow_code_id,
+        module_addr.clone(),
+        &instantiate_msg(Some(Quorum::Members { count: 4 })),
+        &[],
+        "Arena Escrow",
+        None,
+    );
+    assert!(result.is_err());
+
+    let escrow_addr = context
+        .app
+        .instantiate_contract(
+            escrow_code_id,
+            module_addr,
+            &instantiate_msg(Some(Quorum::Members { count: 2 })),
+            &[],
+            "Arena Escrow",
+            None,
+        )
+        .unwrap();
+
+    let fund = |context: &mut Context, addr: &str| {
+        context
+            .app
+            .execute_contract(
+                Addr::unchecked(addr),
+                escrow_addr.clone(),
+                &ExecuteMsg::ReceiveNative {},
+                &[Coin {
+                    denom: "native1".to_string(),
+                    amount: Uint128::from(100u128),
+                }],
+            )
+            .unwrap()
+    };
+    let will_activate = |context: &Context, addr: &str, amount: u128| -> bool {
+        context
+            .app
+            .wrap()
+            .query_wasm_smart(
+                escrow_addr.clone(),
+                &QueryMsg::WillActivateOnFunding {
+                    addr: addr.to_string(),
+                    balance: BalanceUnchecked {
+                        native: vec![Coin {
+                            denom: "native1".to_string(),
+                            amount: Uint128::from(amount),
+                        }],
+                        cw20: vec![],
+                        cw721: vec![],
+                    },
+                },
+            )
+            .unwrap()
+    };
+
+    // The first deposit falls short of the quorum
+    assert!(!will_activate(&context, ADDR1, 100));
+
+    // One of three funded members does not meet the quorum
+    let response = fund(&mut context, ADDR1);
+    assert_eq!(
+        response
+            .events
+            .iter()
+            .flat_map(|x| x.attributes.iter())
+            .find(|x| x.key == "activated")
+            .unwrap()
+            .value,
+        "false"
+    );
+
+    // Only a full deposit from a second member meets the quorum
+    assert!(!will_activate(&context, ADDR2, 50));
+    assert!(will_activate(&context, "addr3", 100));
+    assert!(will_activate(&context, ADDR2, 100));
+
+    // The second funded member activates the escrow while addr3 is still unfunded
+    let response = fund(&mut context, ADDR2);
+    assert_eq!(
+        response
+            .events
+            .iter()
+            .flat_map(|x| x.attributes.iter())
+            .find(|x| x.key == "activated")
+            .unwrap()
+            .value,
+        "true"
+    );
+
+    let is_locked: bool = context
+        .app
+        .wrap()
+        .query_wasm_smart(escrow_addr.clone(), &QueryMsg::IsLocked {})
+        .unwrap();
+    assert!(is_locked);
+
+    // The escrow is already active, so addr3's deposit does not activate it again
+    assert!(!will_activate(&context, "addr3", 100));
+
+    let is_fully_funded: bool = context
+        .app
+        .wrap()
+        .query_wasm_smart(escrow_addr, &QueryMsg::IsFullyFunded {})
+        .unwrap();
+    assert!(!is_fully_funded);
+}
+
+#[test]
+fn test_distribute_msg_builder() {
+    let distribution = Distribution::<String> {
+        member_percentages: vec![
+            MemberPercentage {
+                addr: ADDR1.to_string(),
+                percentage: Decimal::percent(60),
+            },
+            MemberPercentage {
+                addr: ADDR2.to_string(),
+                percentage: Decimal::percent(40),
+            },
+        ],
+        remainder_addr: ADDR1.to_string(),
+    };
+
+    // The builder encodes the escrow's distribute message
+    let msg = CompetitionEscrowDistributeMsgBuilder::new()
+        .distribution(distribution.clone())
+        .require_full_funding(true)
+        .into_cosmos_msg("escrow")
+        .unwrap();
+    assert_eq!(
+        msg,
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "escrow".to_string(),
+            msg: to_json_binary(&ExecuteMsg::Distribute(
+                cw_competition::escrow::CompetitionEscrowDistributeMsg {
+                    distribution: Some(distribution.clone()),
+                    bonus_distribution: None,
+                    require_full_funding: Some(true),
+                    rounding: None,
+                    tax_info: None,
+                    fixed_amounts: None,
+                    treasury: None,
+                    nft_mode: None,
+                }
+            ))
+            .unwrap(),
+            funds: vec![],
+        })
+    );
+
+    // Shares that do not sum to one are rejected before sending
+    let mut invalid = distribution;
+    invalid.member_percentages[1].percentage = Decimal::percent(30);
+    let result = CompetitionEscrowDistributeMsgBuilder::new()
+        .distribution(invalid)
+        .build();
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_state_at_height() {
+    let mut context = setup();
+
+    // Each member pays part of its native dues in a different block
+    let first_height = context.app.block_info().height;
+    context
+        .app
+        .execute_contract(
+            Addr::unchecked(ADDR1),
+            context.escrow_addr.clone(),
+            &ExecuteMsg::ReceiveNative {},
+            &[Coin {
+                denom: "native1".to_string(),
+                amount: Uint128::from(100u128),
+            }],
+        )
+        .unwrap();
+    context.app.update_block(|x| x.height += 1);
+    let second_height = context.app.block_info().height;
+    context
+        .app
+        .execute_contract(
+            Addr::unchecked(ADDR2),
+            context.escrow_addr.clone(),
+            &ExecuteMsg::ReceiveNative {},
+            &[Coin {
+                denom: "native2".to_string(),
+                amount: Uint128::from(100u128),
+            }],
+        )
+        .unwrap();
+    context.app.update_block(|x| x.height += 1);
+    let lock_height = context.app.block_info().height;
+    context
+        .app
+        .execute_contract(
+            Addr::unchecked(CREATOR),
+            context.escrow_addr.clone(),
+            &ExecuteMsg::Lock { value: true },
+            &[],
+        )
+        .unwrap();
+    context.app.update_block(|x| x.height += 1);
+
+    let total_balance_at = |context: &Context, height: u64| -> Option<BalanceVerified> {
+        context
+            .app
+            .wrap()
+            .query_wasm_smart(
+                context.escrow_addr.clone(),
+                &QueryMsg::TotalBalanceAt { height },
+            )
+            .unwrap()
+    };
+    let is_locked_at = |context: &Context, height: u64| -> bool {
+        context
+            .app
+            .wrap()
+            .query_wasm_smart(
+                context.escrow_addr.clone(),
+                &QueryMsg::IsLockedAt { height },
+            )
+            .unwrap()
+    };
+
+    // Each height reflects the state before its own changes
+    assert_eq!(total_balance_at(&context, first_height), None);
+    assert_eq!(
+        total_balance_at(&context, second_height).unwrap().native,
+        vec![Coin {
+            denom: "native1".to_string(),
+            amount: Uint128::from(100u128),
+        }]
+    );
+    assert_eq!(
+        total_balance_at(&context, lock_height).unwrap().native,
+        vec![
+            Coin {
+                denom: "native1".to_string(),
+                amount: Uint128::from(100u128),
+            },
+            Coin {
+                denom: "native2".to_string(),
+                amount: Uint128::from(100u128),
+            },
+        ]
+    );
+
+    assert!(!is_locked_at(&context, lock_height));
+    assert!(is_locked_at(&context, lock_height + 1));
+}
+
+#[test]
+fn test_min_participants() {
+    let mut context = setup();
+    let escrow_code_id = context
+        .app
+        .store_code(arena_testing::contracts::arena_dao_escrow_contract());
+    let instantiate_msg = |min_participants: Option<u32>| InstantiateMsg {
+        dues: [ADDR1, ADDR2]
+            .iter()
+            .map(|addr| MemberBalanceUnchecked {
+                addr: addr.to_string(),
+                balance: BalanceUnchecked {
+                    native: vec![Coin {
+                        denom: "native1".to_string(),
+                        amount: Uint128::from(100u128),
+                    }],
+                    cw20: vec![],
+                    cw721: vec![],
+                },
+            })
+            .collect(),
+        oracle: None,
+        competition_id: None,
+        activation_quorum: None,
+        min_participants,
+        cancel_on_close: None,
+        treasury: None,
+        auto_lock_delay: None,
+        metadata: None,
+    };
+
+    // The minimum cannot exceed the members
+    let result = context.app.instantiate_contract(
+        escrow_code_id,
+        Addr::unchecked(CREATOR),
+        &instantiate_msg(Some(3)),
+        &[],
+        "Arena Escrow",
+        None,
+    );
+    assert!(result.is_err());
+
+    let escrow_addr = context
+        .app
+        .instantiate_contract(
+            escrow_code_id,
+            Addr::unchecked(CREATOR),
+            &instantiate_msg(Some(2)),
+            &[],
+            "Arena Escrow",
+            None,
+        )
+        .unwrap();
+
+    // Only one side of the wager is funded
+    context
+        .app
+        .execute_contract(
+            Addr::unchecked(ADDR1),
+            escrow_addr.clone(),
+            &ExecuteMsg::ReceiveNative {},
+            &[Coin {
+                denom: "native1".to_string(),
+                amount: Uint128::from(100u128),
+            }],
+        )
+        .unwrap();
+
+    let distribute_msg = |distribution: Option<Distribution<String>>| {
+        ExecuteMsg::Distribute(cw_competition::escrow::CompetitionEscrowDistributeMsg {
+            distribution,
+            ..Default::default()
+        })
+    };
+
+    // The escrow cannot be resolved in favor of the funded member
+    let result = context.app.execute_contract(
+        Addr::unchecked(CREATOR),
+        escrow_addr.clone(),
+        &distribute_msg(Some(Distribution::<String> {
+            member_percentages: vec![MemberPercentage {
+                addr: ADDR1.to_string(),
+                percentage: Decimal::one(),
+            }],
+            remainder_addr: ADDR1.to_string(),
+        })),
+        &[],
+    );
+    assert_eq!(
+        result.unwrap_err().root_cause().to_string(),
+        "NotEnoughParticipants"
+    );
+
+    // The funded member can still be refunded
+    let result = context.app.execute_contract(
+        Addr::unchecked(CREATOR),
+        escrow_addr.clone(),
+        &distribute_msg(None),
+        &[],
+    );
+    assert!(result.is_ok());
+
+    let balance: Option<BalanceVerified> = context
+        .app
+        .wrap()
+        .query_wasm_smart(
+            escrow_addr,
+            &QueryMsg::Balance {
+                addr: ADDR1.to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        balance.unwrap().native,
+        vec![Coin {
+            denom: "native1".to_string(),
+            amount: Uint128::from(100u128),
+        }]
+    );
+}
+
+#[test]
+fn test_share_total_overflow() {
+    let mut context = setup();
+
+    context
+        .app
+        .execute_contract(
+            Addr::unchecked(ADDR1),
+            context.escrow_addr.clone(),
+            &ExecuteMsg::ReceiveNative {},
+            &[Coin {
+                denom: "native1".to_string(),
+                amount: Uint128::from(100u128),
+            }],
+        )
+        .unwrap();
+
+    // Share weights near the top of the decimal range cannot be summed
+    let result = context.app.execute_contract(
+        Addr::unchecked(CREATOR),
+        context.escrow_addr.clone(),
+        &ExecuteMsg::Distribute(cw_competition::escrow::CompetitionEscrowDistributeMsg {
+            distribution: Some(Distribution::<String> {
+                member_percentages: vec![
+                    MemberPercentage {
+                        addr: ADDR1.to_string(),
+                        percentage: Decimal::MAX,
+                    },
+                    MemberPercentage {
+                        addr: ADDR2.to_string(),
+                        percentage: Decimal::MAX,
+                    },
+                ],
+                remainder_addr: ADDR1.to_string(),
+            }),
+            ..Default::default()
+        }),
+        &[],
+    );
+    assert_eq!(
+        result.unwrap_err().root_cause().to_string(),
+        "ShareTotalOverflow"
+    );
+}
+
+#[test]
+fn test_net_position() {
+    let mut context = setup();
+    let addr1 = Addr::unchecked(ADDR1);
+
+    let query_net_position = |context: &Context| -> Option<BalanceVerified> {
+        context
+            .app
+            .wrap()
+            .query_wasm_smart(
+                context.escrow_addr.clone(),
+                &QueryMsg::NetPosition {
+                    addr: ADDR1.to_string(),
+                },
+            )
+            .unwrap()
+    };
+    let native1 = Coin {
+        denom: "native1".to_string(),
+        amount: Uint128::from(100u128),
+    };
+
+    // addr1 funds native tokens and its nft
+    context
+        .app
+        .execute_contract(
+            addr1.clone(),
+            context.escrow_addr.clone(),
+            &ExecuteMsg::ReceiveNative {},
+            &[native1.clone()],
+        )
+        .unwrap();
+    context
+        .app
+        .execute_contract(
+            addr1.clone(),
+            context.cw721_addr.clone(),
+            &cw721_base::ExecuteMsg::<Option<Empty>, Empty>::SendNft {
+                contract: context.escrow_addr.to_string(),
+                token_id: 1.to_string(),
+                msg: Binary::default(),
+            },
+            &[],
+        )
+        .unwrap();
+
+    let net_position = query_net_position(&context).unwrap();
+    assert_eq!(net_position.native, vec![native1.clone()]);
+    assert_eq!(
+        net_position.cw721,
+        vec![Cw721CollectionVerified {
+            address: context.cw721_addr.clone(),
+            token_ids: vec![1.to_string()],
+        }]
+    );
+
+    // Partially withdrawing the nft leaves the native funding
+    context
+        .app
+        .execute_contract(
+            addr1.clone(),
+            context.escrow_addr.clone(),
+            &ExecuteMsg::WithdrawCollection {
+                collection: context.cw721_addr.to_string(),
+                cw721_msg: None,
+            },
+            &[],
+        )
+        .unwrap();
+    let net_position = query_net_position(&context).unwrap();
+    assert_eq!(net_position.native, vec![native1.clone()]);
+    assert!(net_position.cw721.is_empty());
+
+    // Further funding adds to the running total
+    let native2 = Coin {
+        denom: "native2".to_string(),
+        amount: Uint128::from(50u128),
+    };
+    context
+        .app
+        .execute_contract(
+            addr1.clone(),
+            context.escrow_addr.clone(),
+            &ExecuteMsg::ReceiveNative {},
+            &[native2.clone()],
+        )
+        .unwrap();
+    assert_eq!(
+        query_net_position(&context).unwrap().native,
+        vec![native1, native2]
+    );
+
+    // Withdrawing everything clears the net position
+    context
+        .app
+        .execute_contract(
+            addr1,
+            context.escrow_addr.clone(),
+            &ExecuteMsg::Withdraw {
+                cw20_msg: None,
+                cw721_msg: None,
+            },
+            &[],
+        )
+        .unwrap();
+    assert!(query_net_position(&context).is_none());
+}
+
+#[test]
+fn test_distribution_remainder() {
+    let mut context = setup();
+
+    context
+        .app
+        .execute_contract(
+            Addr::unchecked(ADDR1),
+            context.escrow_addr.clone(),
+            &ExecuteMsg::ReceiveNative {},
+            &[Coin {
+                denom: "native1".to_string(),
+                amount: Uint128::from(100u128),
+            }],
+        )
+        .unwrap();
+
+    let query_remainder = |context: &Context, percentages: [Decimal; 2]| -> BalanceVerified {
+        context
+            .app
+            .wrap()
+            .query_wasm_smart(
+                context.escrow_addr.clone(),
+                &QueryMsg::DistributionRemainder {
+                    distribution: Distribution::<String> {
+                        member_percentages: vec![
+                            MemberPercentage {
+                                addr: ADDR1.to_string(),
+                                percentage: percentages[0],
+                            },
+                            MemberPercentage {
+                                addr: ADDR2.to_string(),
+                                percentage: percentages[1],
+                            },
+                        ],
+                        remainder_addr: ADDR1.to_string(),
+                    },
+                },
+            )
+            .unwrap()
+    };
+
+    // An even split consumes the whole pool
+    let remainder = query_remainder(&context, [Decimal::percent(50), Decimal::percent(50)]);
+    assert!(remainder.is_empty());
+
+    // Flooring 33.5 and 66.5 leaves a unit of dust
+    let remainder = query_remainder(&context, [Decimal::permille(335), Decimal::permille(665)]);
+    assert_eq!(
+        remainder.native,
+        vec![Coin {
+            denom: "native1".to_string(),
+            amount: Uint128::one(),
+        }]
+    );
+    assert!(remainder.cw20.is_empty());
+}
+
+#[test]
+fn test_authorized_resolvers() {
+    let mut context = setup();
+    let judge = Addr::unchecked("judge");
+    let native1 = Coin {
+        denom: "native1".to_string(),
+        amount: Uint128::from(100u128),
+    };
+
+    context
+        .app
+        .execute_contract(
+            Addr::unchecked(ADDR1),
+            context.escrow_addr.clone(),
+            &ExecuteMsg::ReceiveNative {},
+            &[native1.clone()],
+        )
+        .unwrap();
+
+    let distribute_msg =
+        ExecuteMsg::Distribute(cw_competition::escrow::CompetitionEscrowDistributeMsg {
+            distribution: Some(Distribution::<String> {
+                member_percentages: vec![MemberPercentage {
+                    addr: ADDR2.to_string(),
+                    percentage: Decimal::one(),
+                }],
+                remainder_addr: ADDR2.to_string(),
+            }),
+            bonus_distribution: None,
+            require_full_funding: None,
+            rounding: None,
+            tax_info: None,
+            fixed_amounts: None,
+            treasury: None,
+            nft_mode: None,
+        });
+
+    // The judge cannot resolve the escrow until authorized
+    let result = context.app.execute_contract(
+        judge.clone(),
+        context.escrow_addr.clone(),
+        &distribute_msg,
+        &[],
+    );
+    assert!(result.is_err());
+
+    // Only the owner manages the resolvers
+    let update_msg = ExecuteMsg::UpdateResolvers {
+        to_add: vec![judge.to_string()],
+        to_remove: vec![],
+    };
+    let result = context.app.execute_contract(
+        Addr::unchecked(ADDR1),
+        context.escrow_addr.clone(),
+        &update_msg,
+        &[],
+    );
+    assert!(result.is_err());
+    context
+        .app
+        .execute_contract(
+            Addr::unchecked(CREATOR),
+            context.escrow_addr.clone(),
+            &update_msg,
+            &[],
+        )
+        .unwrap();
+    let resolvers: Vec<Addr> = context
+        .app
+        .wrap()
+        .query_wasm_smart(context.escrow_addr.clone(), &QueryMsg::Resolvers {})
+        .unwrap();
+    assert_eq!(resolvers, vec![judge.clone()]);
+
+    // The authorized judge distributes the escrow
+    context
+        .app
+        .execute_contract(
+            judge.clone(),
+            context.escrow_addr.clone(),
+            &distribute_msg,
+            &[],
+        )
+        .unwrap();
+    let balance: Option<BalanceVerified> = context
+        .app
+        .wrap()
+        .query_wasm_smart(
+            context.escrow_addr.clone(),
+            &QueryMsg::Balance {
+                addr: ADDR2.to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(balance.unwrap().native, vec![native1]);
+}
+
+#[test]
+fn test_fixed_amounts() {
+    let mut context = setup();
+    let native1 = |amount: u128| Coin {
+        denom: "native1".to_string(),
+        amount: Uint128::from(amount),
+    };
+
+    for (addr, amount) in [(ADDR1, 100u128), (ADDR2, 200u128)] {
+        context
+            .app
+            .execute_contract(
+                Addr::unchecked(addr),
+                context.escrow_addr.clone(),
+                &ExecuteMsg::ReceiveNative {},
+                &[native1(amount)],
+            )
+            .unwrap();
+    }
+
+    let distribute_msg = |second_place: u128| {
+        ExecuteMsg::Distribute(cw_competition::escrow::CompetitionEscrowDistributeMsg {
+            distribution: Some(Distribution::<String> {
+                member_percentages: vec![MemberPercentage {
+                    addr: ADDR1.to_string(),
+                    percentage: Decimal::one(),
+                }],
+                remainder_addr: ADDR1.to_string(),
+            }),
+            fixed_amounts: Some(vec![(
+                ADDR2.to_string(),
+                BalanceVerified {
+                    native: vec![native1(second_place)],
+                    cw20: vec![],
+                    cw721: vec![],
+                },
+            )]),
+            ..Default::default()
+        })
+    };
+
+    // The fixed amounts cannot exceed the pool
+    let err: ContractError = context
+        .app
+        .execute_contract(
+            Addr::unchecked(CREATOR),
+            context.escrow_addr.clone(),
+            &distribute_msg(400),
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(
+        err,
+        ContractError::InvalidDistribution {
+            msg: "The fixed amounts exceed the total balance".to_string()
+        }
+    );
+
+    // The second place is paid exactly and the surplus goes to the remainder
+    context
+        .app
+        .execute_contract(
+            Addr::unchecked(CREATOR),
+            context.escrow_addr.clone(),
+            &distribute_msg(120),
+            &[],
+        )
+        .unwrap();
+    let query_balance = |addr: &str| -> Option<BalanceVerified> {
+        context
+            .app
+            .wrap()
+            .query_wasm_smart(
+                context.escrow_addr.clone(),
+                &QueryMsg::Balance {
+                    addr: addr.to_string(),
+                },
+            )
+            .unwrap()
+    };
+    assert_eq!(query_balance(ADDR1).unwrap().native, vec![native1(180)]);
+    assert_eq!(query_balance(ADDR2).unwrap().native, vec![native1(120)]);
+}
+
+#[test]
+fn test_mutual_cancel() {
+    let mut context = setup();
+    let native1 = |amount: u128| Coin {
+        denom: "native1".to_string(),
+        amount: Uint128::from(amount),
+    };
+
+    // Both members partially fund, so the escrow has not activated
+    for (addr, amount) in [(ADDR1, 100u128), (ADDR2, 200u128)] {
+        context
+            .app
+            .execute_contract(
+                Addr::unchecked(addr),
+                context.escrow_addr.clone(),
+                &ExecuteMsg::ReceiveNative {},
+                &[native1(amount)],
+            )
+            .unwrap();
+    }
+
+    // The owner locks the escrow, which blocks unilateral withdrawals
+    context
+        .app
+        .execute_contract(
+            Addr::unchecked(CREATOR),
+            context.escrow_addr.clone(),
+            &ExecuteMsg::Lock { value: true },
+            &[],
+        )
+        .unwrap();
+    let err: ContractError = context
+        .app
+        .execute_contract(
+            Addr::unchecked(ADDR1),
+            context.escrow_addr.clone(),
+            &ExecuteMsg::Withdraw {
+                cw20_msg: None,
+                cw721_msg: None,
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::Locked {});
+
+    // A single agreement does not cancel
+    let res = context
+        .app
+        .execute_contract(
+            Addr::unchecked(ADDR1),
+            context.escrow_addr.clone(),
+            &ExecuteMsg::MutualCancel {},
+            &[],
+        )
+        .unwrap();
+    assert!(res.events.iter().any(|e| e
+        .attributes
+        .iter()
+        .any(|a| a.key == "is_cancelled" && a.value == "false")));
+    let balance = context.app.wrap().query_balance(ADDR1, "native1").unwrap();
+    assert_eq!(balance.amount, Uint128::from(900u128));
+
+    // Once both agree, everyone is refunded and the escrow resets
+    context
+        .app
+        .execute_contract(
+            Addr::unchecked(ADDR2),
+            context.escrow_addr.clone(),
+            &ExecuteMsg::MutualCancel {},
+            &[],
+        )
+        .unwrap();
+    for addr in [ADDR1, ADDR2] {
+        let balance = context.app.wrap().query_balance(addr, "native1").unwrap();
+        assert_eq!(balance.amount, Uint128::from(1000u128));
+    }
+    let is_locked: bool = context
+        .app
+        .wrap()
+        .query_wasm_smart(context.escrow_addr.clone(), &QueryMsg::IsLocked {})
+        .unwrap();
+    assert!(!is_locked);
+    let total_balance: Option<BalanceVerified> = context
+        .app
+        .wrap()
+        .query_wasm_smart(context.escrow_addr.clone(), &QueryMsg::TotalBalance {})
+        .unwrap();
+    assert!(total_balance.is_none());
+    let due: Option<BalanceVerified> = context
+        .app
+        .wrap()
+        .query_wasm_smart(
+            context.escrow_addr.clone(),
+            &QueryMsg::Due {
+                addr: ADDR1.to_string(),
+            },
+        )
+        .unwrap();
+    assert!(due.unwrap().native.contains(&native1(100)));
+}
+
+#[test]
+fn test_treasury_payout() {
+    let mut context = setup();
+    let treasury_code_id = context.app.store_code(mock_treasury_contract());
+    let treasury_addr = context
+        .app
+        .instantiate_contract(
+            treasury_code_id,
+            Addr::unchecked(CREATOR),
+            &Empty {},
+            &[],
+            "Treasury",
+            None,
+        )
+        .unwrap();
+    let native1 = |amount: u128| Coin {
+        denom: "native1".to_string(),
+        amount: Uint128::from(amount),
+    };
+
+    context
+        .app
+        .execute_contract(
+            Addr::unchecked(ADDR1),
+            context.escrow_addr.clone(),
+            &ExecuteMsg::ReceiveNative {},
+            &[native1(100)],
+        )
+        .unwrap();
+
+    // Half of the pool goes to the treasury with an accounting reference
+    context
+        .app
+        .execute_contract(
+            Addr::unchecked(CREATOR),
+            context.escrow_addr.clone(),
+            &ExecuteMsg::Distribute(cw_competition::escrow::CompetitionEscrowDistributeMsg {
+                distribution: Some(Distribution::<String> {
+                    member_percentages: vec![
+                        MemberPercentage {
+                            addr: ADDR1.to_string(),
+                            percentage: Decimal::percent(50),
+                        },
+                        MemberPercentage {
+                            addr: treasury_addr.to_string(),
+                            percentage: Decimal::percent(50),
+                        },
+                    ],
+                    remainder_addr: ADDR1.to_string(),
+                }),
+                treasury: Some(cw_competition::escrow::TreasuryPayout {
+                    addr: treasury_addr.to_string(),
+                    reference: "INV-42".to_string(),
+                }),
+                ..Default::default()
+            }),
+            &[],
+        )
+        .unwrap();
+
+    // The treasury received its share through the wrapped message
+    let references: Vec<String> = context
+        .app
+        .wrap()
+        .query_wasm_smart(treasury_addr.clone(), &Empty {})
+        .unwrap();
+    assert_eq!(references, vec!["INV-42".to_string()]);
+    let balance = context
+        .app
+        .wrap()
+        .query_balance(&treasury_addr, "native1")
+        .unwrap();
+    assert_eq!(balance, native1(50));
+
+    // Only the other winnings are held for withdrawal
+    let balance: Option<BalanceVerified> = context
+        .app
+        .wrap()
+        .query_wasm_smart(
+            context.escrow_addr.clone(),
+            &QueryMsg::Balance {
+                addr: treasury_addr.to_string(),
+            },
+        )
+        .unwrap();
+    assert!(balance.is_none());
+    let total_balance: Option<BalanceVerified> = context
+        .app
+        .wrap()
+        .query_wasm_smart(context.escrow_addr.clone(), &QueryMsg::TotalBalance {})
+        .unwrap();
+    assert_eq!(total_balance.unwrap().native, vec![native1(50)]);
+}
+
+#[test]
+fn test_will_activate_on_funding() {
+    let mut context = setup();
+    let escrow_code_id = context
+        .app
+        .store_code(arena_testing::contracts::arena_dao_escrow_contract());
+    let native1 = |amount: u128| Coin {
+        denom: "native1".to_string(),
+        amount: Uint128::from(amount),
+    };
+
+    let dues: Vec<MemberBalanceUnchecked> = [(ADDR1, 100u128), (ADDR2, 200u128)]
+        .iter()
+        .map(|(addr, amount)| MemberBalanceUnchecked {
+            addr: addr.to_string(),
+            balance: BalanceUnchecked {
+                native: vec![native1(*amount)],
+                cw20: vec![],
+                cw721: vec![],
+            },
+        })
+        .collect();
+    let escrow_addr = context
+        .app
+        .instantiate_contract(
+            escrow_code_id,
+            Addr::unchecked(CREATOR),
+            &InstantiateMsg {
+                dues,
+                oracle: None,
+                competition_id: None,
+                activation_quorum: None,
+                min_participants: None,
+                cancel_on_close: None,
+                treasury: None,
+                auto_lock_delay: None,
+                metadata: None,
+            },
+            &[],
+            "Arena Escrow",
+            None,
+        )
+        .unwrap();
+
+    let will_activate = |context: &Context, addr: &str, amount: u128| -> bool {
+        context
+            .app
+            .wrap()
+            .query_wasm_smart(
+                escrow_addr.clone(),
+                &QueryMsg::WillActivateOnFunding {
+                    addr: addr.to_string(),
+                    balance: BalanceUnchecked {
+                        native: vec![native1(amount)],
+                        cw20: vec![],
+                        cw721: vec![],
+                    },
+                },
+            )
+            .unwrap()
+    };
+
+    // Neither deposit activates while the other member is unfunded
+    assert!(!will_activate(&context, ADDR1, 100));
+    assert!(!will_activate(&context, ADDR2, 200));
+
+    context
+        .app
+        .execute_contract(
+            Addr::unchecked(ADDR1),
+            escrow_addr.clone(),
+            &ExecuteMsg::ReceiveNative {},
+            &[native1(100)],
+        )
+        .unwrap();
+
+    // Only the final member's full deposit starts the competition
+    assert!(!will_activate(&context, ADDR1, 100));
+    assert!(!will_activate(&context, ADDR2, 150));
+    assert!(will_activate(&context, ADDR2, 200));
+}
+
+#[test]
+fn test_close_on_withdrawal() {
+    let mut context = setup();
+    let module_code_id = context.app.store_code(mock_competition_module_contract());
+    let module_addr = context
+        .app
+        .instantiate_contract(
+            module_code_id,
+            Addr::unchecked(CREATOR),
+            &Empty {},
+            &[],
+            "Competition Module",
+            None,
+        )
+        .unwrap();
+    let listener_code_id = context.app.store_code(mock_listener_contract());
+    let listener_addr = context
+        .app
+        .instantiate_contract(
+            listener_code_id,
+            Addr::unchecked(CREATOR),
+            &false,
+            &[],
+            "Listener",
+            None,
+        )
+        .unwrap();
+    let escrow_code_id = context
+        .app
+        .store_code(arena_testing::contracts::arena_dao_escrow_contract());
+
+    let dues: Vec<MemberBalanceUnchecked> = [ADDR1, ADDR2]
+        .iter()
+        .map(|addr| MemberBalanceUnchecked {
+            addr: addr.to_string(),
+            balance: BalanceUnchecked {
+                native: vec![Coin {
+                    denom: "native1".to_string(),
+                    amount: Uint128::from(100u128),
+                }],
+                cw20: vec![],
+                cw721: vec![],
+            },
+        })
+        .collect();
+    let escrow_addr = context
+        .app
+        .instantiate_contract(
+            escrow_code_id,
+            module_addr.clone(),
+            &InstantiateMsg {
+                dues,
+                oracle: None,
+                competition_id: None,
+                activation_quorum: None,
+                min_participants: None,
+                cancel_on_close: Some(true),
+                treasury: None,
+                auto_lock_delay: None,
+                metadata: None,
+            },
+            &[],
+            "Arena Escrow",
+            None,
+        )
+        .unwrap();
+    context
+        .app
+        .execute_contract(
+            module_addr.clone(),
+            escrow_addr.clone(),
+            &ExecuteMsg::UpdateListeners {
+                to_add: vec![listener_addr.to_string()],
+                to_remove: vec![],
+            },
+            &[],
+        )
+        .unwrap();
+
+    // One member funds and the other pays part of their due
+    context
+        .app
+        .execute_contract(
+            Addr::unchecked(ADDR1),
+            escrow_addr.clone(),
+            &ExecuteMsg::ReceiveNative {},
+            &[Coin {
+                denom: "native1".to_string(),
+                amount: Uint128::from(100u128),
+            }],
+        )
+        .unwrap();
+    context
+        .app
+        .execute_contract(
+            Addr::unchecked(ADDR2),
+            escrow_addr.clone(),
+            &ExecuteMsg::ReceiveNative {},
+            &[Coin {
+                denom: "native1".to_string(),
+                amount: Uint128::from(50u128),
+            }],
+        )
+        .unwrap();
+
+    // The escrow stays open while another member holds a balance
+    let result = context
+        .app
+        .execute_contract(
+            Addr::unchecked(ADDR1),
+            escrow_addr.clone(),
+            &ExecuteMsg::Withdraw {
+                cw20_msg: None,
+                cw721_msg: None,
+            },
+            &[],
+        )
+        .unwrap();
+    assert!(result.has_event(&Event::new("wasm").add_attribute("closed", "false")));
+    assert!(!result.has_event(&Event::new("wasm-closed")));
+
+    // The last withdrawal closes the escrow and asks the module to cancel
+    let result = context
+        .app
+        .execute_contract(
+            Addr::unchecked(ADDR2),
+            escrow_addr.clone(),
+            &ExecuteMsg::Withdraw {
+                cw20_msg: None,
+                cw721_msg: None,
+            },
+            &[],
+        )
+        .unwrap();
+    assert!(result.has_event(&Event::new("wasm").add_attribute("closed", "true")));
+    assert!(result.has_event(&Event::new("wasm-closed")));
+    assert!(
+        result.has_event(&Event::new("execute").add_attribute("_contract_address", module_addr))
+    );
+
+    let events: Vec<CompetitionEscrowEvent> = context
+        .app
+        .wrap()
+        .query_wasm_smart(listener_addr, &Empty {})
+        .unwrap();
+    assert_eq!(
+        events,
+        vec![
+            CompetitionEscrowEvent::Funded {
+                addr: ADDR1.to_string()
+            },
+            CompetitionEscrowEvent::Closed {},
+        ]
+    );
+}
+
+#[test]
+fn test_effective_distribution() {
+    let mut context = setup();
+    let native1 = |amount: u128| Coin {
+        denom: "native1".to_string(),
+        amount: Uint128::from(amount),
+    };
+
+    // addr1 passes most of their winnings on to addr2
+    context
+        .app
+        .execute_contract(
+            Addr::unchecked(ADDR1),
+            context.escrow_addr.clone(),
+            &ExecuteMsg::SetDistribution {
+                distribution: Some(Distribution::<String> {
+                    member_percentages: vec![MemberPercentage {
+                        addr: ADDR2.to_string(),
+                        percentage: Decimal::percent(60),
+                    }],
+                    remainder_addr: ADDR1.to_string(),
+                }),
+            },
+            &[],
+        )
+        .unwrap();
+    for (addr, amount) in [(ADDR1, 100), (ADDR2, 200)] {
+        context
+            .app
+            .execute_contract(
+                Addr::unchecked(addr),
+                context.escrow_addr.clone(),
+                &ExecuteMsg::ReceiveNative {},
+                &[native1(amount)],
+            )
+            .unwrap();
+    }
+
+    let member_percentages = vec![
+        MemberPercentage {
+            addr: ADDR1.to_string(),
+            percentage: Decimal::percent(75),
+        },
+        MemberPercentage {
+            addr: ADDR2.to_string(),
+            percentage: Decimal::percent(25),
+        },
+    ];
+    let effective: Vec<MemberBalanceChecked> = context
+        .app
+        .wrap()
+        .query_wasm_smart(
+            context.escrow_addr.clone(),
+            &QueryMsg::EffectiveDistribution {
+                distribution: member_percentages.clone(),
+                remainder_addr: ADDR2.to_string(),
+                bonus_distribution: None,
+                require_full_funding: None,
+                rounding: None,
+                tax: None,
+                fixed_amounts: None,
+                nft_mode: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        effective,
+        vec![
+            MemberBalanceChecked {
+                addr: Addr::unchecked(ADDR1),
+                balance: BalanceVerified {
+                    native: vec![native1(90)],
+                    cw20: vec![],
+                    cw721: vec![],
+                },
+            },
+            MemberBalanceChecked {
+                addr: Addr::unchecked(ADDR2),
+                balance: BalanceVerified {
+                    native: vec![native1(210)],
+                    cw20: vec![],
+                    cw721: vec![],
+                },
+            },
+        ]
+    );
+
+    // The query takes the same options as distribute
+    let fixed_amounts = vec![(
+        ADDR1.to_string(),
+        BalanceVerified {
+            native: vec![native1(20)],
+            cw20: vec![],
+            cw721: vec![],
+        },
+    )];
+    let effective: Vec<MemberBalanceChecked> = context
+        .app
+        .wrap()
+        .query_wasm_smart(
+            context.escrow_addr.clone(),
+            &QueryMsg::EffectiveDistribution {
+                distribution: member_percentages.clone(),
+                remainder_addr: ADDR2.to_string(),
+                bonus_distribution: None,
+                require_full_funding: Some(true),
+                rounding: Some(cw_balance::RoundingMode::LargestRemainder),
+                tax: Some(Decimal::percent(10)),
+                fixed_amounts: Some(fixed_amounts.clone()),
+                nft_mode: None,
+            },
+        )
+        .unwrap();
+
+    // The 10% tax leaves 270 to be credited
+    let total = effective
+        .iter()
+        .try_fold(BalanceVerified::default(), |total, x| {
+            total.checked_add(&x.balance)
+        })
+        .unwrap();
+    assert_eq!(total.native, vec![native1(270)]);
+
+    // Distributing credits exactly what the query reported
+    context
+        .app
+        .execute_contract(
+            Addr::unchecked(CREATOR),
+            context.escrow_addr.clone(),
+            &ExecuteMsg::Distribute(
+                CompetitionEscrowDistributeMsgBuilder::new()
+                    .distribution(Distribution::<String> {
+                        member_percentages,
+                        remainder_addr: ADDR2.to_string(),
+                    })
+                    .require_full_funding(true)
+                    .rounding(cw_balance::RoundingMode::LargestRemainder)
+                    .tax_info(cw_competition::escrow::TaxInformation {
+                        tax: Decimal::percent(10),
+                        receiver: CREATOR.to_string(),
+                        cw20_msg: None,
+                        cw721_msg: None,
+                    })
+                    .fixed_amounts(fixed_amounts)
+                    .build()
+                    .unwrap(),
+            ),
+            &[],
+        )
+        .unwrap();
+    let balances: Vec<MemberBalanceChecked> = context
+        .app
+        .wrap()
+        .query_wasm_smart(
+            context.escrow_addr.clone(),
+            &QueryMsg::Balances {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(balances, effective);
+}
+
+#[test]
+fn test_activation_failed() {
+    let mut context = setup();
+    let module_code_id = context
+        .app
+        .store_code(mock_rejecting_competition_module_contract());
+    let module_addr = context
+        .app
+        .instantiate_contract(
+            module_code_id,
+            Addr::unchecked(CREATOR),
+            &Empty {},
+            &[],
+            "Competition Module",
+            None,
+        )
+        .unwrap();
+    let escrow_code_id = context
+        .app
+        .store_code(arena_testing::contracts::arena_dao_escrow_contract());
+
+    let dues: Vec<MemberBalanceUnchecked> = [ADDR1, ADDR2]
+        .iter()
+        .map(|addr| MemberBalanceUnchecked {
+            addr: addr.to_string(),
+            balance: BalanceUnchecked {
+                native: vec![Coin {
+                    denom: "native1".to_string(),
+                    amount: Uint128::from(100u128),
+                }],
+                cw20: vec![],
+                cw721: vec![],
+            },
+        })
+        .collect();
+    let escrow_addr = context
+        .app
+        .instantiate_contract(
+            escrow_code_id,
+            module_addr,
+            &InstantiateMsg {
+                dues,
+                oracle: None,
+                competition_id: None,
+                activation_quorum: None,
+                min_participants: None,
+                cancel_on_close: None,
+                treasury: None,
+                auto_lock_delay: None,
+                metadata: None,
+            },
+            &[],
+            "Arena Escrow",
+            None,
+        )
+        .unwrap();
+
+    // The final payment still succeeds when the module rejects the activation
+    let mut results = vec![];
+    for addr in [ADDR1, ADDR2] {
+        results.push(
+            context
+                .app
+                .execute_contract(
+                    Addr::unchecked(addr),
+                    escrow_addr.clone(),
+                    &ExecuteMsg::ReceiveNative {},
+                    &[Coin {
+                        denom: "native1".to_string(),
+                        amount: Uint128::from(100u128),
+                    }],
+                )
+                .unwrap(),
+        );
+    }
+    assert!(!results[0].has_event(&Event::new("wasm-activation_failed")));
+    assert!(results[1].has_event(&Event::new("wasm-activation_failed")));
+
+    // The escrow is unlocked so the funds are not trapped
+    let is_locked: bool = context
+        .app
+        .wrap()
+        .query_wasm_smart(escrow_addr.clone(), &QueryMsg::IsLocked {})
+        .unwrap();
+    assert!(!is_locked);
+
+    context
+        .app
+        .execute_contract(
+            Addr::unchecked(ADDR1),
+            escrow_addr,
+            &ExecuteMsg::Withdraw {
+                cw20_msg: None,
+                cw721_msg: None,
+            },
+            &[],
+        )
+        .unwrap();
+    let balance = context.app.wrap().query_balance(ADDR1, "native1").unwrap();
+    assert_eq!(balance.amount, Uint128::from(1000u128));
+}
+
+#[test]
+fn test_no_contest() {
+    let mut context = setup();
+    let module_code_id = context.app.store_code(mock_competition_module_contract());
+    let module_addr = context
+        .app
+        .instantiate_contract(
+            module_code_id,
+            Addr::unchecked(CREATOR),
+            &Empty {},
+            &[],
+            "Competition Module",
+            None,
+        )
+        .unwrap();
+    let escrow_code_id = context
+        .app
+        .store_code(arena_testing::contracts::arena_dao_escrow_contract());
+
+    let dues: Vec<MemberBalanceUnchecked> = [ADDR1, ADDR2]
+        .iter()
+        .map(|addr| MemberBalanceUnchecked {
+            addr: addr.to_string(),
+            balance: BalanceUnchecked {
+                native: vec![Coin {
+                    denom: "native1".to_string(),
+                    amount: Uint128::from(100u128),
+                }],
+                cw20: vec![],
+                cw721: vec![],
+            },
+        })
+        .collect();
+    let escrow_addr = context
+        .app
+        .instantiate_contract(
+            escrow_code_id,
+            module_addr.clone(),
+            &InstantiateMsg {
+                dues,
+                oracle: None,
+                competition_id: None,
+                activation_quorum: None,
+                min_participants: None,
+                cancel_on_close: None,
+                treasury: Some("treasury".to_string()),
+                auto_lock_delay: None,
+                metadata: None,
+            },
+            &[],
+            "Arena Escrow",
+            None,
+        )
+        .unwrap();
+
+    for addr in [ADDR1, ADDR2] {
+        context
+            .app
+            .execute_contract(
+                Addr::unchecked(addr),
+                escrow_addr.clone(),
+                &ExecuteMsg::ReceiveNative {},
+                &[Coin {
+                    denom: "native1".to_string(),
+                    amount: Uint128::from(100u128),
+                }],
+            )
+            .unwrap();
+    }
+
+    // Only the owner can resolve the escrow as no contest
+    let res = context.app.execute_contract(
+        Addr::unchecked(ADDR1),
+        escrow_addr.clone(),
+        &ExecuteMsg::NoContest {
+            fee: Decimal::percent(2),
+            refund_priority: None,
+        },
+        &[],
+    );
+    assert!(res.is_err());
+
+    // The fee is bounded
+    let err: ContractError = context
+        .app
+        .execute_contract(
+            module_addr.clone(),
+            escrow_addr.clone(),
+            &ExecuteMsg::NoContest {
+                fee: Decimal::percent(11),
+                refund_priority: None,
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(
+        err,
+        ContractError::FeeTooHigh {
+            max: Decimal::percent(10)
+        }
+    );
+
+    context
+        .app
+        .execute_contract(
+            module_addr,
+            escrow_addr.clone(),
+            &ExecuteMsg::NoContest {
+                fee: Decimal::percent(2),
+                refund_priority: None,
+            },
+            &[],
+        )
+        .unwrap();
+
+    // Each member is refunded 98 and the treasury receives the 2% taken from both
+    for addr in [ADDR1, ADDR2] {
+        let balance = context.app.wrap().query_balance(addr, "native1").unwrap();
+        assert_eq!(balance.amount, Uint128::from(998u128));
+    }
+    let balance = context
+        .app
+        .wrap()
+        .query_balance("treasury", "native1")
+        .unwrap();
+    assert_eq!(balance.amount, Uint128::from(4u128));
+    let balance = context
+        .app
+        .wrap()
+        .query_balance(escrow_addr.to_string(), "native1")
+        .unwrap();
+    assert!(balance.amount.is_zero());
+}
+
+#[test]
+fn test_no_contest_shortfall() {
+    // ADDR2 is funded first, and the escrow only holds half of the 400 it owes
+    for (refund_priority, expected) in [
+        (None, [(ADDR1, 950u128), (ADDR2, 850u128)]),
+        (
+            Some(RefundPriority::FundingOrder),
+            [(ADDR1, 900u128), (ADDR2, 900u128)],
+        ),
+    ] {
+        let mut context = setup();
+        let module_code_id = context.app.store_code(mock_competition_module_contract());
+        let module_addr = context
+            .app
+            .instantiate_contract(
+                module_code_id,
+                Addr::unchecked(CREATOR),
+                &Empty {},
+                &[],
+                "Competition Module",
+                None,
+            )
+            .unwrap();
+        let escrow_code_id = context
+            .app
+            .store_code(arena_testing::contracts::arena_dao_escrow_contract());
+        let escrow_addr = context
+            .app
+            .instantiate_contract(
+                escrow_code_id,
+                module_addr.clone(),
+                &InstantiateMsg {
+                    dues: [(ADDR1, 100u128), (ADDR2, 300u128)]
+                        .iter()
+                        .map(|(addr, amount)| MemberBalanceUnchecked {
+                            addr: addr.to_string(),
+                            balance: BalanceUnchecked {
+                                native: vec![Coin {
+                                    denom: "native1".to_string(),
+                                    amount: Uint128::from(*amount),
+                                }],
+                                cw20: vec![],
+                                cw721: vec![],
+                            },
+                        })
+                        .collect(),
+                    oracle: None,
+                    competition_id: None,
+                    activation_quorum: None,
+                    min_participants: None,
+                    cancel_on_close: None,
+                    treasury: None,
+                    auto_lock_delay: None,
+                    metadata: None,
+                },
+                &[],
+                "Arena Escrow",
+                None,
+            )
+            .unwrap();
+
+        for (addr, amount) in [(ADDR2, 300u128), (ADDR1, 100u128)] {
+            context
+                .app
+                .execute_contract(
+                    Addr::unchecked(addr),
+                    escrow_addr.clone(),
+                    &ExecuteMsg::ReceiveNative {},
+                    &[Coin {
+                        denom: "native1".to_string(),
+                        amount: Uint128::from(amount),
+                    }],
+                )
+                .unwrap();
+            context.app.update_block(|x| x.height += 1);
+        }
+        context.app.init_modules(|router, _, storage| {
+            router
+                .bank
+                .init_balance(
+                    storage,
+                    &escrow_addr,
+                    vec![Coin {
+                        denom: "native1".to_string(),
+                        amount: Uint128::from(200u128),
+                    }],
+                )
+                .unwrap();
+        });
+
+        context
+            .app
+            .execute_contract(
+                module_addr,
+                escrow_addr.clone(),
+                &ExecuteMsg::NoContest {
+                    fee: Decimal::zero(),
+                    refund_priority,
+                },
+                &[],
+            )
+            .unwrap();
+
+        // Pro rata refunds each member half, while the funding order refunds ADDR2 first
+        for (addr, amount) in expected {
+            let balance = context.app.wrap().query_balance(addr, "native1").unwrap();
+            assert_eq!(balance.amount, Uint128::from(amount));
+        }
+        let balance = context
+            .app
+            .wrap()
+            .query_balance(escrow_addr.to_string(), "native1")
+            .unwrap();
+        assert!(balance.amount.is_zero());
+    }
+}
+
+#[test]
+fn test_absorb_escrow() {
+    let mut context = setup();
+    let escrow_code_id = context
+        .app
+        .store_code(arena_testing::contracts::arena_dao_escrow_contract());
+
+    // The other escrow shares ADDR2 and adds addr3
+    let other_addr = context
+        .app
+        .instantiate_contract(
+            escrow_code_id,
+            Addr::unchecked(CREATOR),
+            &InstantiateMsg {
+                dues: [ADDR2, "addr3"]
+                    .iter()
+                    .map(|addr| MemberBalanceUnchecked {
+                        addr: addr.to_string(),
+                        balance: BalanceUnchecked {
+                            native: vec![Coin {
+                                denom: "native1".to_string(),
+                                amount: Uint128::from(100u128),
+                            }],
+                            cw20: vec![],
+                            cw721: vec![],
+                        },
+                    })
+                    .collect(),
+                oracle: None,
+                competition_id: None,
+                activation_quorum: None,
+                min_participants: None,
+                cancel_on_close: None,
+                treasury: None,
+                auto_lock_delay: None,
+                metadata: None,
+            },
+            &[],
+            "Arena Escrow",
+            None,
+        )
+        .unwrap();
+
+    for (escrow, amount) in [
+        (context.escrow_addr.clone(), 200u128),
+        (other_addr.clone(), 100u128),
+    ] {
+        context
+            .app
+            .execute_contract(
+                Addr::unchecked(ADDR2),
+                escrow,
+                &ExecuteMsg::ReceiveNative {},
+                &[Coin {
+                    denom: "native1".to_string(),
+                    amount: Uint128::from(amount),
+                }],
+            )
+            .unwrap();
+    }
+
+    // ADDR2 presets a distribution in the other escrow only
+    context
+        .app
+        .execute_contract(
+            Addr::unchecked(ADDR2),
+            other_addr.clone(),
+            &ExecuteMsg::SetDistribution {
+                distribution: Some(Distribution::<String> {
+                    member_percentages: vec![MemberPercentage {
+                        addr: ADDR1.to_string(),
+                        percentage: Decimal::one(),
+                    }],
+                    remainder_addr: ADDR2.to_string(),
+                }),
+            },
+            &[],
+        )
+        .unwrap();
+
+    // Only the owner can absorb another escrow
+    let result = context.app.execute_contract(
+        Addr::unchecked(ADDR1),
+        context.escrow_addr.clone(),
+        &ExecuteMsg::AbsorbEscrow {
+            other: other_addr.to_string(),
+        },
+        &[],
+    );
+    assert!(result.is_err());
+
+    // The other escrow does not release its balance before it expects the absorbing escrow
+    let result = context.app.execute_contract(
+        Addr::unchecked(CREATOR),
+        context.escrow_addr.clone(),
+        &ExecuteMsg::AbsorbEscrow {
+            other: other_addr.to_string(),
+        },
+        &[],
+    );
+    assert!(result.is_err());
+
+    // Only the owner can name the absorbing escrow
+    let result = context.app.execute_contract(
+        Addr::unchecked(ADDR1),
+        other_addr.clone(),
+        &ExecuteMsg::ExpectAbsorb {
+            by: ADDR1.to_string(),
+        },
+        &[],
+    );
+    assert!(result.is_err());
+    context
+        .app
+        .execute_contract(
+            Addr::unchecked(CREATOR),
+            other_addr.clone(),
+            &ExecuteMsg::ExpectAbsorb {
+                by: context.escrow_addr.to_string(),
+            },
+            &[],
+        )
+        .unwrap();
+
+    // The other escrow only releases its balance to the expected escrow
+    let result = context.app.execute_contract(
+        Addr::unchecked(ADDR1),
+        other_addr.clone(),
+        &ExecuteMsg::TransferToEscrow {},
+        &[],
+    );
+    assert!(result.is_err());
+
+    context
+        .app
+        .execute_contract(
+            Addr::unchecked(CREATOR),
+            context.escrow_addr.clone(),
+            &ExecuteMsg::AbsorbEscrow {
+                other: other_addr.to_string(),
+            },
+            &[],
+        )
+        .unwrap();
+
+    // The shared member's balances are summed
+    let balance: Option<BalanceVerified> = context
+        .app
+        .wrap()
+        .query_wasm_smart(
+            context.escrow_addr.clone(),
+            &QueryMsg::Balance {
+                addr: ADDR2.to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        balance.unwrap().native,
+        vec![Coin {
+            denom: "native1".to_string(),
+            amount: Uint128::from(300u128),
+        }]
+    );
+
+    // The shared member's funding is summed, but they are not funded until both dues are paid
+    let timeline: MemberTimelineResponse = context
+        .app
+        .wrap()
+        .query_wasm_smart(
+            context.escrow_addr.clone(),
+            &QueryMsg::MemberTimeline {
+                addr: ADDR2.to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        timeline.net_funded.unwrap().native,
+        vec![Coin {
+            denom: "native1".to_string(),
+            amount: Uint128::from(300u128),
+        }]
+    );
+    assert_eq!(timeline.funded_at, None);
+    assert_eq!(
+        timeline.preset_distribution.unwrap().remainder_addr,
+        Addr::unchecked(ADDR2)
+    );
+
+    // The new member joins with their remaining due
+    let due: Option<BalanceVerified> = context
+        .app
+        .wrap()
+        .query_wasm_smart(
+            context.escrow_addr.clone(),
+            &QueryMsg::Due {
+                addr: "addr3".to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        due.unwrap().native,
+        vec![Coin {
+            denom: "native1".to_string(),
+            amount: Uint128::from(100u128),
+        }]
+    );
+
+    // The funds moved with the merged balances
+    let total_balance: Option<BalanceVerified> = context
+        .app
+        .wrap()
+        .query_wasm_smart(context.escrow_addr.clone(), &QueryMsg::TotalBalance {})
+        .unwrap();
+    assert_eq!(
+        total_balance.unwrap().native,
+        vec![Coin {
+            denom: "native1".to_string(),
+            amount: Uint128::from(300u128),
+        }]
+    );
+    let balance = context
+        .app
+        .wrap()
+        .query_balance(context.escrow_addr.to_string(), "native1")
+        .unwrap();
+    assert_eq!(balance.amount, Uint128::from(300u128));
+    let balance = context
+        .app
+        .wrap()
+        .query_balance(other_addr.to_string(), "native1")
+        .unwrap();
+    assert!(balance.amount.is_zero());
+}
+
+#[test]
+fn test_member_timeline() {
+    let mut context = setup();
+    let escrow_code_id = context
+        .app
+        .store_code(arena_testing::contracts::arena_dao_escrow_contract());
+    let due = BalanceUnchecked {
+        native: vec![Coin {
+            denom: "native1".to_string(),
+            amount: Uint128::from(100u128),
+        }],
+        cw20: vec![],
+        cw721: vec![],
+    };
+    let escrow_addr = context
+        .app
+        .instantiate_contract(
+            escrow_code_id,
+            Addr::unchecked(CREATOR),
+            &InstantiateMsg {
+                dues: [ADDR1, ADDR2]
+                    .iter()
+                    .map(|addr| MemberBalanceUnchecked {
+                        addr: addr.to_string(),
+                        balance: due.clone(),
+                    })
+                    .collect(),
+                oracle: None,
+                competition_id: None,
+                activation_quorum: None,
+                min_participants: None,
+                cancel_on_close: None,
+                treasury: None,
+                auto_lock_delay: None,
+                metadata: None,
+            },
+            &[],
+            "Arena Escrow",
+            None,
+        )
+        .unwrap();
+
+    // addr1 funds their due and presets their winnings to go to addr2
+    context
+        .app
+        .execute_contract(
+            Addr::unchecked(ADDR1),
+            escrow_addr.clone(),
+            &ExecuteMsg::ReceiveNative {},
+            &[Coin {
+                denom: "native1".to_string(),
+                amount: Uint128::from(100u128),
+            }],
+        )
+        .unwrap();
+    let funded_height = context.app.block_info().height;
+    context
+        .app
+        .execute_contract(
+            Addr::unchecked(ADDR1),
+            escrow_addr.clone(),
+            &ExecuteMsg::SetDistribution {
+                distribution: Some(Distribution::<String> {
+                    member_percentages: vec![MemberPercentage {
+                        addr: ADDR2.to_string(),
+                        percentage: Decimal::one(),
+                    }],
+                    remainder_addr: ADDR2.to_string(),
+                }),
+            },
+            &[],
+        )
+        .unwrap();
+
+    let funded = BalanceVerified {
+        native: vec![Coin {
+            denom: "native1".to_string(),
+            amount: Uint128::from(100u128),
+        }],
+        cw20: vec![],
+        cw721: vec![],
+    };
+    let timeline: MemberTimelineResponse = context
+        .app
+        .wrap()
+        .query_wasm_smart(
+            escrow_addr.clone(),
+            &QueryMsg::MemberTimeline {
+                addr: ADDR1.to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        timeline,
+        MemberTimelineResponse {
+            initial_due: Some(funded.clone()),
+            funded_at: Some(funded_height),
+            current_balance: Some(funded.clone()),
+            net_funded: Some(funded),
+            preset_distribution: Some(Distribution::<Addr> {
+                member_percentages: vec![MemberPercentage {
+                    addr: Addr::unchecked(ADDR2),
+                    percentage: Decimal::one(),
+                }],
+                remainder_addr: Addr::unchecked(ADDR2),
+            }),
+        }
+    );
+
+    // An unfunded member only has their initial due
+    let timeline: MemberTimelineResponse = context
+        .app
+        .wrap()
+        .query_wasm_smart(
+            escrow_addr,
+            &QueryMsg::MemberTimeline {
+                addr: ADDR2.to_string(),
+            },
+        )
+        .unwrap();
+    assert!(timeline.funded_at.is_none());
+    assert!(timeline.current_balance.is_none());
+    assert!(timeline.preset_distribution.is_none());
+}
+
+#[test]
+fn test_auto_lock_delay() {
+    let mut context = setup();
+    let module_code_id = context.app.store_code(mock_competition_module_contract());
+    let module_addr = context
+        .app
+        .instantiate_contract(
+            module_code_id,
+            Addr::unchecked(CREATOR),
+            &Empty {},
+            &[],
+            "Competition Module",
+            None,
+        )
+        .unwrap();
+    let escrow_code_id = context
+        .app
+        .store_code(arena_testing::contracts::arena_dao_escrow_contract());
+    let escrow_addr = context
+        .app
+        .instantiate_contract(
+            escrow_code_id,
+            module_addr,
+            &InstantiateMsg {
+                dues: [ADDR1, ADDR2]
+                    .iter()
+                    .map(|addr| MemberBalanceUnchecked {
+                        addr: addr.to_string(),
+                        balance: BalanceUnchecked {
+                            native: vec![Coin {
+                                denom: "native1".to_string(),
+                                amount: Uint128::from(100u128),
+                            }],
+                            cw20: vec![],
+                            cw721: vec![],
+                        },
+                    })
+                    .collect(),
+                oracle: None,
+                competition_id: None,
+                activation_quorum: None,
+                min_participants: None,
+                cancel_on_close: None,
+                treasury: None,
+                auto_lock_delay: Some(Duration::Height(10)),
+                metadata: None,
+            },
+            &[],
+            "Arena Escrow",
+            None,
+        )
+        .unwrap();
+
+    let fund = |context: &mut Context, addr: &str| {
+        context
+            .app
+            .execute_contract(
+                Addr::unchecked(addr),
+                escrow_addr.clone(),
+                &ExecuteMsg::ReceiveNative {},
+                &[Coin {
+                    denom: "native1".to_string(),
+                    amount: Uint128::from(100u128),
+                }],
+            )
+            .unwrap()
+    };
+    let query_scheduled_lock = |context: &Context| -> Option<Expiration> {
+        context
+            .app
+            .wrap()
+            .query_wasm_smart(escrow_addr.clone(), &QueryMsg::ScheduledLock {})
+            .unwrap()
+    };
+
+    // Full funding schedules the lock instead of locking, so it doesn't activate
+    fund(&mut context, ADDR1);
+    let will_activate: bool = context
+        .app
+        .wrap()
+        .query_wasm_smart(
+            escrow_addr.clone(),
+            &QueryMsg::WillActivateOnFunding {
+                addr: ADDR2.to_string(),
+                balance: BalanceUnchecked {
+                    native: vec![Coin {
+                        denom: "native1".to_string(),
+                        amount: Uint128::from(100u128),
+                    }],
+                    cw20: vec![],
+                    cw721: vec![],
+                },
+            },
+        )
+        .unwrap();
+    assert!(!will_activate);
+    let result = fund(&mut context, ADDR2);
+    assert!(result.has_event(&Event::new("wasm").add_attribute("activated", "false")));
+    let height = context.app.block_info().height;
+    assert_eq!(
+        query_scheduled_lock(&context),
+        Some(Expiration::AtHeight(height + 10))
+    );
+
+    // A member can still withdraw during the delay, which cancels the lock
+    context
+        .app
+        .execute_contract(
+            Addr::unchecked(ADDR1),
+            escrow_addr.clone(),
+            &ExecuteMsg::Withdraw {
+                cw20_msg: None,
+                cw721_msg: None,
+            },
+            &[],
+        )
+        .unwrap();
+    let balance = context.app.wrap().query_balance(ADDR1, "native1").unwrap();
+    assert_eq!(balance.amount, Uint128::from(1000u128));
+    assert_eq!(query_scheduled_lock(&context), None);
+
+    // Members can mutually cancel while the lock is only scheduled, which cancels it
+    fund(&mut context, ADDR1);
+    assert!(query_scheduled_lock(&context).is_some());
+    for addr in [ADDR1, ADDR2] {
+        context
+            .app
+            .execute_contract(
+                Addr::unchecked(addr),
+                escrow_addr.clone(),
+                &ExecuteMsg::MutualCancel {},
+                &[],
+            )
+            .unwrap();
+    }
+    assert_eq!(query_scheduled_lock(&context), None);
+    context.app.update_block(|block| block.height += 10);
+    let result = context.app.execute_contract(
+        Addr::unchecked(ADDR2),
+        escrow_addr.clone(),
+        &ExecuteMsg::LockScheduled {},
+        &[],
+    );
+    assert!(result.is_err());
+
+    // Funding again reschedules the lock, which applies after the delay
+    fund(&mut context, ADDR1);
+    fund(&mut context, ADDR2);
+    let result = context.app.execute_contract(
+        Addr::unchecked(ADDR2),
+        escrow_addr.clone(),
+        &ExecuteMsg::LockScheduled {},
+        &[],
+    );
+    assert!(result.is_err());
+
+    context.app.update_block(|block| block.height += 10);
+    let result = context
+        .app
+        .execute_contract(
+            Addr::unchecked(ADDR2),
+            escrow_addr.clone(),
+            &ExecuteMsg::LockScheduled {},
+            &[],
+        )
+        .unwrap();
+    assert!(result.has_event(&Event::new("wasm").add_attribute("activated", "true")));
+
+    let is_locked: bool = context
+        .app
+        .wrap()
+        .query_wasm_smart(escrow_addr.clone(), &QueryMsg::IsLocked {})
+        .unwrap();
+    assert!(is_locked);
+    assert_eq!(query_scheduled_lock(&context), None);
+}
+
+#[test]
+fn test_settle_solo() {
+    let mut context = setup();
+
+    // A solo challenge can't be settled on an escrow with two members
+    let result = context.app.execute_contract(
+        Addr::unchecked(CREATOR),
+        context.escrow_addr.clone(),
+        &ExecuteMsg::SettleSolo { member_wins: true },
+        &[],
+    );
+    assert!(result.is_err());
+
+    let module_code_id = context.app.store_code(mock_competition_module_contract());
+    let module_addr = context
+        .app
+        .instantiate_contract(
+            module_code_id,
+            Addr::unchecked(CREATOR),
+            &Empty {},
+            &[],
+            "Competition Module",
+            None,
+        )
+        .unwrap();
+    context
+        .app
+        .send_tokens(
+            Addr::unchecked(ADDR2),
+            module_addr.clone(),
+            &[Coin {
+                denom: "native1".to_string(),
+                amount: Uint128::from(500u128),
+            }],
+        )
+        .unwrap();
+
+    // The module stakes the house against a single member
+    let escrow_code_id = context
+        .app
+        .store_code(arena_testing::contracts::arena_dao_escrow_contract());
+    let escrow_addr = context
+        .app
+        .instantiate_contract(
+            escrow_code_id,
+            module_addr.clone(),
+            &InstantiateMsg {
+                dues: vec![MemberBalanceUnchecked {
+                    addr: ADDR1.to_string(),
+                    balance: BalanceUnchecked {
+                        native: vec![Coin {
+                            denom: "native1".to_string(),
+                            amount: Uint128::from(100u128),
+                        }],
+                        cw20: vec![],
+                        cw721: vec![],
+                    },
+                }],
+                oracle: None,
+                competition_id: None,
+                activation_quorum: None,
+                min_participants: None,
+                cancel_on_close: None,
+                treasury: None,
+                auto_lock_delay: None,
+                metadata: None,
+            },
+            &[Coin {
+                denom: "native1".to_string(),
+                amount: Uint128::from(500u128),
+            }],
+            "Arena Escrow",
+            None,
+        )
+        .unwrap();
+
+    // Funding the only due locks and activates the escrow
+    let result = context
+        .app
+        .execute_contract(
+            Addr::unchecked(ADDR1),
+            escrow_addr.clone(),
+            &ExecuteMsg::ReceiveNative {},
+            &[Coin {
+                denom: "native1".to_string(),
+                amount: Uint128::from(100u128),
+            }],
+        )
+        .unwrap();
+    assert!(result.has_event(&Event::new("wasm").add_attribute("activated", "true")));
+
+    // Only the owner or a resolver can settle
+    let result = context.app.execute_contract(
+        Addr::unchecked(ADDR1),
+        escrow_addr.clone(),
+        &ExecuteMsg::SettleSolo { member_wins: true },
+        &[],
+    );
+    assert!(result.is_err());
+
+    let result = context
+        .app
+        .execute_contract(
+            module_addr,
+            escrow_addr.clone(),
+            &ExecuteMsg::SettleSolo { member_wins: true },
+            &[],
+        )
+        .unwrap();
+    assert!(result.has_event(&Event::new("wasm").add_attribute("solo_winner", ADDR1)));
+
+    // The member takes both their entry and the house stake
+    context
+        .app
+        .execute_contract(
+            Addr::unchecked(ADDR1),
+            escrow_addr,
+            &ExecuteMsg::Withdraw {
+                cw20_msg: None,
+                cw721_msg: None,
+            },
+            &[],
+        )
+        .unwrap();
+    let balance = context.app.wrap().query_balance(ADDR1, "native1").unwrap();
+    assert_eq!(balance.amount, Uint128::from(1500u128));
+}
+
+#[test]
+fn test_metadata() {
+    let mut context = setup();
+    let escrow_code_id = context
+        .app
+        .store_code(arena_testing::contracts::arena_dao_escrow_contract());
+    let metadata = EscrowMetadata {
+        title: "Weekly Cup".to_string(),
+        description: Some("A weekly wager between two teams".to_string()),
+        url: Some("https://example.com/weekly-cup".to_string()),
+    };
+    let escrow_addr = context
+        .app
+        .instantiate_contract(
+            escrow_code_id,
+            Addr::unchecked(CREATOR),
+            &InstantiateMsg {
+                dues: vec![MemberBalanceUnchecked {
+                    addr: ADDR1.to_string(),
+                    balance: BalanceUnchecked {
+                        native: vec![Coin {
+                            denom: "native1".to_string(),
+                            amount: Uint128::from(100u128),
+                        }],
+                        cw20: vec![],
+                        cw721: vec![],
+                    },
+                }],
+                oracle: None,
+                competition_id: None,
+                activation_quorum: None,
+                min_participants: None,
+                cancel_on_close: None,
+                treasury: None,
+                auto_lock_delay: None,
+                metadata: Some(metadata.clone()),
+            },
+            &[],
+            "Arena Escrow",
+            None,
+        )
+        .unwrap();
+    let query_metadata = |context: &Context| -> Option<EscrowMetadata> {
+        context
+            .app
+            .wrap()
+            .query_wasm_smart(escrow_addr.clone(), &QueryMsg::Metadata {})
+            .unwrap()
+    };
+    assert_eq!(query_metadata(&context), Some(metadata));
+
+    // Only the owner can update the metadata
+    let updated = EscrowMetadata {
+        title: "Weekly Cup Final".to_string(),
+        description: None,
+        url: None,
+    };
+    let result = context.app.execute_contract(
+        Addr::unchecked(ADDR1),
+        escrow_addr.clone(),
+        &ExecuteMsg::UpdateMetadata {
+            metadata: Some(updated.clone()),
+        },
+        &[],
+    );
+    assert!(result.is_err());
+
+    context
+        .app
+        .execute_contract(
+            Addr::unchecked(CREATOR),
+            escrow_addr.clone(),
+            &ExecuteMsg::UpdateMetadata {
+                metadata: Some(updated.clone()),
+            },
+            &[],
+        )
+        .unwrap();
+    assert_eq!(query_metadata(&context), Some(updated));
+
+    // The owner can also remove it
+    context
+        .app
+        .execute_contract(
+            Addr::unchecked(CREATOR),
+            escrow_addr.clone(),
+            &ExecuteMsg::UpdateMetadata { metadata: None },
+            &[],
+        )
+        .unwrap();
+    assert_eq!(query_metadata(&context), None);
+
+    // Escrows created without metadata have none
+    let metadata: Option<EscrowMetadata> = context
+        .app
+        .wrap()
+        .query_wasm_smart(context.escrow_addr.clone(), &QueryMsg::Metadata {})
+        .unwrap();
+    assert!(metadata.is_none());
+}
+
+#[test]
+fn test_consolidate_pool() {
+    let mut context = setup();
+    let module_code_id = context.app.store_code(mock_competition_module_contract());
+    let module_addr = context
+        .app
+        .instantiate_contract(
+            module_code_id,
+            Addr::unchecked(CREATOR),
+            &Empty {},
+            &[],
+            "Competition Module",
+            None,
+        )
+        .unwrap();
+
+    // The router pays 2 native1 for every native2 from its own liquidity
+    let router_code_id = context.app.store_code(mock_router_contract());
+    let router_addr = context
+        .app
+        .instantiate_contract(
+            router_code_id,
+            Addr::unchecked(CREATOR),
+            &Decimal::percent(200),
+            &[],
+            "Router",
+            None,
+        )
+        .unwrap();
+    context
+        .app
+        .send_tokens(
+            Addr::unchecked(ADDR1),
+            router_addr.clone(),
+            &[Coin {
+                denom: "native1".to_string(),
+                amount: Uint128::from(500u128),
+            }],
+        )
+        .unwrap();
+
+    let dues = |native1: u128, native2: u128| BalanceUnchecked {
+        native: vec![
+            Coin {
+                denom: "native1".to_string(),
+                amount: Uint128::from(native1),
+            },
+            Coin {
+                denom: "native2".to_string(),
+                amount: Uint128::from(native2),
+            },
+        ],
+        cw20: vec![],
+        cw721: vec![],
+    };
+    let escrow_code_id = context
+        .app
+        .store_code(arena_testing::contracts::arena_dao_escrow_contract());
+    let escrow_addr = context
+        .app
+        .instantiate_contract(
+            escrow_code_id,
+            module_addr.clone(),
+            &InstantiateMsg {
+                dues: vec![
+                    MemberBalanceUnchecked {
+                        addr: ADDR1.to_string(),
+                        balance: dues(100, 50),
+                    },
+                    MemberBalanceUnchecked {
+                        addr: ADDR2.to_string(),
+                        balance: dues(200, 100),
+                    },
+                ],
+                oracle: None,
+                competition_id: None,
+                activation_quorum: None,
+                min_participants: None,
+                cancel_on_close: None,
+                treasury: None,
+                auto_lock_delay: None,
+                metadata: None,
+            },
+            &[],
+            "Arena Escrow",
+            None,
+        )
+        .unwrap();
+    for (addr, native1, native2) in [(ADDR1, 100u128, 50u128), (ADDR2, 200, 100)] {
+        context
+            .app
+            .execute_contract(
+                Addr::unchecked(addr),
+                escrow_addr.clone(),
+                &ExecuteMsg::ReceiveNative {},
+                &[
+                    Coin {
+                        denom: "native1".to_string(),
+                        amount: Uint128::from(native1),
+                    },
+                    Coin {
+                        denom: "native2".to_string(),
+                        amount: Uint128::from(native2),
+                    },
+                ],
+            )
+            .unwrap();
+    }
+
+    let consolidate_msg = |min_output: u128| ExecuteMsg::ConsolidatePool {
+        router: router_addr.to_string(),
+        target_denom: "native1".to_string(),
+        min_output: Uint128::from(min_output),
+    };
+    let query_total = |context: &Context| -> Option<BalanceVerified> {
+        context
+            .app
+            .wrap()
+            .query_wasm_smart(escrow_addr.clone(), &QueryMsg::TotalBalance {})
+            .unwrap()
+    };
+
+    // A swap paying out less than the minimum reverts the whole consolidation
+    let result = context.app.execute_contract(
+        module_addr.clone(),
+        escrow_addr.clone(),
+        &consolidate_msg(301),
+        &[],
+    );
+    assert_eq!(
+        result.unwrap_err().root_cause().to_string(),
+        ContractError::Slippage {
+            min_output: Uint128::from(301u128),
+            received: Uint128::from(300u128),
+        }
+        .to_string()
+    );
+    assert_eq!(query_total(&context).unwrap().native.len(), 2);
+
+    // The 150 native2 are swapped for 300 native1, converting each member's share
+    context
+        .app
+        .execute_contract(
+            module_addr.clone(),
+            escrow_addr.clone(),
+            &consolidate_msg(300),
+            &[],
+        )
+        .unwrap();
+    assert_eq!(
+        query_total(&context).unwrap().native,
+        vec![Coin {
+            denom: "native1".to_string(),
+            amount: Uint128::from(600u128),
+        }]
+    );
+    for (addr, amount) in [(ADDR1, 200u128), (ADDR2, 400)] {
+        let balance: Option<BalanceVerified> = context
+            .app
+            .wrap()
+            .query_wasm_smart(
+                escrow_addr.clone(),
+                &QueryMsg::Balance {
+                    addr: addr.to_string(),
+                },
+            )
+            .unwrap();
+        assert_eq!(
+            balance.unwrap().native,
+            vec![Coin {
+                denom: "native1".to_string(),
+                amount: Uint128::from(amount),
+            }]
+        );
+    }
+
+    // The winner is paid out in the single denom
+    context
+        .app
+        .execute_contract(
+            module_addr,
+            escrow_addr.clone(),
+            &ExecuteMsg::Distribute(cw_competition::escrow::CompetitionEscrowDistributeMsg {
+                distribution: Some(Distribution::<String> {
+                    member_percentages: vec![MemberPercentage {
+                        addr: ADDR1.to_string(),
+                        percentage: Decimal::one(),
+                    }],
+                    remainder_addr: ADDR1.to_string(),
+                }),
+                bonus_distribution: None,
+                require_full_funding: None,
+                rounding: None,
+                tax_info: None,
+                fixed_amounts: None,
+                treasury: None,
+                nft_mode: None,
+            }),
+            &[],
+        )
+        .unwrap();
+    context
+        .app
+        .execute_contract(
+            Addr::unchecked(ADDR1),
+            escrow_addr,
+            &ExecuteMsg::Withdraw {
+                cw20_msg: None,
+                cw721_msg: None,
+            },
+            &[],
+        )
+        .unwrap();
+    let native1 = context.app.wrap().query_balance(ADDR1, "native1").unwrap();
+    let native2 = context.app.wrap().query_balance(ADDR1, "native2").unwrap();
+    assert_eq!(native1.amount, Uint128::from(1000u128));
+    assert_eq!(native2.amount, Uint128::from(950u128));
+}
+
+#[test]
+fn test_partial_funding_total_balance() {
+    let mut context = setup();
+
+    // Two partial deposits toward the same due
+    for amount in [40u128, 30u128] {
+        context
+            .app
+            .execute_contract(
+                Addr::unchecked(ADDR1),
+                context.escrow_addr.clone(),
+                &ExecuteMsg::ReceiveNative {},
+                &[Coin {
+                    denom: "native1".to_string(),
+                    amount: Uint128::from(amount),
+                }],
+            )
+            .unwrap();
+    }
+
+    // The total balance counts each deposit once
+    let total_balance: Option<BalanceVerified> = context
+        .app
+        .wrap()
+        .query_wasm_smart(context.escrow_addr.clone(), &QueryMsg::TotalBalance {})
+        .unwrap();
+    assert_eq!(
+        total_balance.unwrap().native,
+        vec![Coin {
+            denom: "native1".to_string(),
+            amount: Uint128::from(70u128),
+        }]
+    );
+    let held = context
+        .app
+        .wrap()
+        .query_balance(context.escrow_addr.clone(), "native1")
+        .unwrap();
+    assert_eq!(held.amount, Uint128::from(70u128));
+
+    // The full amount can be withdrawn, leaving nothing behind
+    context
+        .app
+        .execute_contract(
+            Addr::unchecked(ADDR1),
+            context.escrow_addr.clone(),
+            &ExecuteMsg::Withdraw {
+                cw20_msg: None,
+                cw721_msg: None,
+            },
+            &[],
+        )
+        .unwrap();
+    let held = context
+        .app
+        .wrap()
+        .query_balance(context.escrow_addr.clone(), "native1")
+        .unwrap();
+    assert!(held.amount.is_zero());
+    let total_balance: Option<BalanceVerified> = context
+        .app
+        .wrap()
+        .query_wasm_smart(context.escrow_addr.clone(), &QueryMsg::TotalBalance {})
+        .unwrap();
+    assert!(total_balance.map_or(true, |x| x.is_empty()));
+}
+
+#[test]
+fn test_distribute_after_approval() {
+    let mut context = setup();
+
+    let module_code_id = context.app.store_code(mock_competition_module_contract());
+    let module_addr = context
+        .app
+        .instantiate_contract(
+            module_code_id,
+            Addr::unchecked(CREATOR),
+            &Empty {},
+            &[],
+            "Competition Module",
+            None,
+        )
+        .unwrap();
+    let escrow_code_id = context
+        .app
+        .store_code(arena_testing::contracts::arena_dao_escrow_contract());
+    let escrow_addr = context
+        .app
+        .instantiate_contract(
+            escrow_code_id,
+            module_addr.clone(),
+            &InstantiateMsg {
+                dues: [ADDR1, ADDR2]
+                    .iter()
+                    .map(|addr| MemberBalanceUnchecked {
+                        addr: addr.to_string(),
+                        balance: BalanceUnchecked {
+                            native: vec![Coin {
+                                denom: "native1".to_string(),
+                                amount: Uint128::from(100u128),
+                            }],
+                            cw20: vec![],
+                            cw721: vec![],
+                        },
+                    })
+                    .collect(),
+                oracle: None,
+                competition_id: None,
+                activation_quorum: None,
+                min_participants: None,
+                cancel_on_close: None,
+                treasury: None,
+                auto_lock_delay: None,
+                metadata: None,
+            },
+            &[],
+            "Arena Escrow",
+            None,
+        )
+        .unwrap();
+
+    let distribution = Distribution::<String> {
+        member_percentages: vec![MemberPercentage {
+            addr: ADDR1.to_string(),
+            percentage: Decimal::one(),
+        }],
+        remainder_addr: ADDR1.to_string(),
+    };
+    for addr in [ADDR1, ADDR2] {
+        context
+            .app
+            .execute_contract(
+                Addr::unchecked(addr),
+                escrow_addr.clone(),
+                &ExecuteMsg::ReceiveNative {},
+                &[Coin {
+                    denom: "native1".to_string(),
+                    amount: Uint128::from(100u128),
+                }],
+            )
+            .unwrap();
+    }
+    for addr in [ADDR1, ADDR2] {
+        context
+            .app
+            .execute_contract(
+                Addr::unchecked(addr),
+                escrow_addr.clone(),
+                &ExecuteMsg::ApproveDistribution {
+                    distribution: distribution.clone(),
+                },
+                &[],
+            )
+            .unwrap();
+    }
+
+    // The owner cannot split the already distributed balance again
+    let result = context.app.execute_contract(
+        module_addr,
+        escrow_addr.clone(),
+        &ExecuteMsg::Distribute(cw_competition::escrow::CompetitionEscrowDistributeMsg {
+            distribution: Some(Distribution::<String> {
+                member_percentages: vec![MemberPercentage {
+                    addr: ADDR2.to_string(),
+                    percentage: Decimal::one(),
+                }],
+                remainder_addr: ADDR2.to_string(),
+            }),
+            bonus_distribution: None,
+            require_full_funding: None,
+            rounding: None,
+            tax_info: None,
+            fixed_amounts: None,
+            treasury: None,
+            nft_mode: None,
+        }),
+        &[],
+    );
+    assert_eq!(
+        result.unwrap_err().root_cause().to_string(),
+        ContractError::AlreadyDistributed {}.to_string()
+    );
+
+    let balance: Option<BalanceVerified> = context
+        .app
+        .wrap()
+        .query_wasm_smart(
+            escrow_addr,
+            &QueryMsg::Balance {
+                addr: ADDR1.to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        balance.unwrap().native,
+        vec![Coin {
+            denom: "native1".to_string(),
+            amount: Uint128::from(200u128),
+        }]
+    );
+}
+
+#[test]
+fn test_unresponsive_arena_core() {
+    let mut context = setup();
+    let module_code_id = context
+        .app
+        .store_code(mock_owned_competition_module_contract());
+    let module_addr = context
+        .app
+        .instantiate_contract(
+            module_code_id,
+            Addr::unchecked(CREATOR),
+            &Empty {},
+            &[],
+            "Competition Module",
+            None,
+        )
+        .unwrap();
+    let escrow_code_id = context
+        .app
+        .store_code(arena_testing::contracts::arena_dao_escrow_contract());
+    let escrow_addr = context
+        .app
+        .instantiate_contract(
+            escrow_code_id,
+            module_addr.clone(),
+            &InstantiateMsg {
+                dues: vec![MemberBalanceUnchecked {
+                    addr: ADDR1.to_string(),
+                    balance: BalanceUnchecked {
+                        native: vec![Coin {
+                            denom: "native1".to_string(),
+                            amount: Uint128::from(100u128),
+                        }],
+                        cw20: vec![],
+                        cw721: vec![],
+                    },
+                }],
+                oracle: None,
+                competition_id: None,
+                activation_quorum: None,
+                min_participants: None,
+                cancel_on_close: None,
+                treasury: None,
+                auto_lock_delay: None,
+                metadata: None,
+            },
+            &[],
+            "Arena Escrow",
+            None,
+        )
+        .unwrap();
+
+    // The module is now owned by a core which can't answer whether funding is paused
+    let core_code_id = context.app.store_code(mock_competition_module_contract());
+    let core_addr = context
+        .app
+        .instantiate_contract(
+            core_code_id,
+            Addr::unchecked(CREATOR),
+            &Empty {},
+            &[],
+            "Arena Core",
+            None,
+        )
+        .unwrap();
+    context
+        .app
+        .execute_contract(
+            Addr::unchecked(CREATOR),
+            module_addr.clone(),
+            &core_addr.to_string(),
+            &[],
+        )
+        .unwrap();
+
+    // Funding is rejected rather than assuming the core is unpaused
+    let result = context.app.execute_contract(
+        Addr::unchecked(ADDR1),
+        escrow_addr,
+        &ExecuteMsg::ReceiveNative {},
+        &[Coin {
+            denom: "native1".to_string(),
+            amount: Uint128::from(100u128),
+        }],
+    );
+    assert!(result.is_err());
+
+    // Dues are rejected rather than assuming the core blocks none of their assets
+    let result = context.app.instantiate_contract(
+        escrow_code_id,
+        module_addr,
+        &InstantiateMsg {
+            dues: vec![MemberBalanceUnchecked {
+                addr: ADDR1.to_string(),
+                balance: BalanceUnchecked {
+                    native: vec![Coin {
+                        denom: "native1".to_string(),
+                        amount: Uint128::from(100u128),
+                    }],
+                    cw20: vec![],
+                    cw721: vec![],
+                },
+            }],
+            oracle: None,
+            competition_id: None,
+            activation_quorum: None,
+            min_participants: None,
+            cancel_on_close: None,
+            treasury: None,
+            auto_lock_delay: None,
+            metadata: None,
+        },
+        &[],
+        "Arena Escrow",
+        None,
+    );
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_non_arena_owner_funding() {
+    let mut context = setup();
+
+    // The owner's own owner does not answer arena queries, so it is not taken for a core
+    let unresponsive_code_id = context.app.store_code(mock_competition_module_contract());
+    let unresponsive_addr = context
+        .app
+        .instantiate_contract(
+            unresponsive_code_id,
+            Addr::unchecked(CREATOR),
+            &Empty {},
+            &[],
+            "Unresponsive",
+            None,
+        )
+        .unwrap();
+    let owner_code_id = context.app.store_code(mock_ownable_contract());
+    let owner_addr = context
+        .app
+        .instantiate_contract(
+            owner_code_id,
+            Addr::unchecked(CREATOR),
+            &unresponsive_addr.to_string(),
+            &[],
+            "Owner",
+            None,
+        )
+        .unwrap();
+    let escrow_code_id = context
+        .app
+        .store_code(arena_testing::contracts::arena_dao_escrow_contract());
+
+    // The dues are not rejected as possibly blocked by a contract which is not a core
+    let escrow_addr = context
+        .app
+        .instantiate_contract(
+            escrow_code_id,
+            owner_addr,
+            &InstantiateMsg {
+                dues: vec![MemberBalanceUnchecked {
+                    addr: ADDR1.to_string(),
+                    balance: BalanceUnchecked {
+                        native: vec![Coin {
+                            denom: "native1".to_string(),
+                            amount: Uint128::from(100u128),
+                        }],
+                        cw20: vec![],
+                        cw721: vec![],
+                    },
+                }],
+                oracle: None,
+                competition_id: None,
+                activation_quorum: None,
+                min_participants: None,
+                cancel_on_close: None,
+                treasury: None,
+                auto_lock_delay: None,
+                metadata: None,
+            },
+            &[],
+            "Arena Escrow",
+            None,
+        )
+        .unwrap();
+
+    // Funding is not blocked by a pause check against a contract which is not a core
+    context
+        .app
+        .execute_contract(
+            Addr::unchecked(ADDR1),
+            escrow_addr,
+            &ExecuteMsg::ReceiveNative {},
+            &[Coin {
+                denom: "native1".to_string(),
+                amount: Uint128::from(100u128),
+            }],
+        )
+        .unwrap();
+}
+
+#[test]
+fn test_absorb_escrow_locked() {
+    let mut context = setup();
+    let escrow_code_id = context
+        .app
+        .store_code(arena_testing::contracts::arena_dao_escrow_contract());
+    let other_addr = context
+        .app
+        .instantiate_contract(
+            escrow_code_id,
+            Addr::unchecked(CREATOR),
+            &InstantiateMsg {
+                dues: vec![MemberBalanceUnchecked {
+                    addr: ADDR2.to_string(),
+                    balance: BalanceUnchecked {
+                        native: vec![Coin {
+                            denom: "native1".to_string(),
+                            amount: Uint128::from(100u128),
+                        }],
+                        cw20: vec![],
+                        cw721: vec![],
+                    },
+                }],
+                oracle: None,
+                competition_id: None,
+                activation_quorum: None,
+                min_participants: None,
+                cancel_on_close: None,
+                treasury: None,
+                auto_lock_delay: None,
+                metadata: None,
+            },
+            &[],
+            "Arena Escrow",
+            None,
+        )
+        .unwrap();
+    context
+        .app
+        .execute_contract(
+            Addr::unchecked(CREATOR),
+            other_addr.clone(),
+            &ExecuteMsg::ExpectAbsorb {
+                by: context.escrow_addr.to_string(),
+            },
+            &[],
+        )
+        .unwrap();
+
+    // A locked escrow cannot take on new members or funds
+    context
+        .app
+        .execute_contract(
+            Addr::unchecked(CREATOR),
+            context.escrow_addr.clone(),
+            &ExecuteMsg::Lock { value: true },
+            &[],
+        )
+        .unwrap();
+    let result = context.app.execute_contract(
+        Addr::unchecked(CREATOR),
+        context.escrow_addr.clone(),
+        &ExecuteMsg::AbsorbEscrow {
+            other: other_addr.to_string(),
+        },
+        &[],
+    );
+    assert!(result.is_err());
+}
@@ -14,7 +14,7 @@ use crate::{
         MigrateMsg, QueryExt, QueryMsg,
     },
     query,
-    state::TournamentExt,
+    state::{TournamentExt, LEAGUE_TEAMS, MATCH_EVIDENCE},
     ContractError,
 };
 
@@ -58,6 +58,7 @@ pub fn execute(
             expiration,
             rules,
             rulesets,
+            result_authority,
             instantiate_extension,
         } => {
             let response = CompetitionModule::default().execute_create_competition(
@@ -71,6 +72,7 @@ pub fn execute(
                 expiration,
                 rules,
                 rulesets,
+                result_authority,
                 instantiate_extension.clone(),
             )?;
 
@@ -81,6 +83,9 @@ pub fn execute(
                 instantiate_extension.teams,
                 instantiate_extension.distribution,
                 instantiate_extension.round_duration,
+                instantiate_extension.schedule_algorithm.unwrap_or_default(),
+                instantiate_extension.pivot,
+                instantiate_extension.shuffle_seed.map(|x| x.u64()),
             )
         }
         ExecuteBase::Extension { msg } => match msg {
@@ -88,18 +93,75 @@ pub fn execute(
                 league_id,
                 round_number,
                 match_results,
-            } => execute::process_matches(deps, env, info, league_id, round_number, match_results),
+                evidence,
+            } => execute::process_matches(
+                deps,
+                env,
+                info,
+                league_id,
+                round_number,
+                match_results,
+                evidence,
+            ),
+            ExecuteExt::ProcessRound {
+                league_id,
+                round_number,
+                results,
+                default_result,
+                evidence,
+            } => execute::process_round(
+                deps,
+                env,
+                info,
+                league_id,
+                round_number,
+                results,
+                default_result,
+                evidence,
+            ),
             ExecuteExt::UpdateDistribution {
                 league_id,
                 distribution,
             } => execute::update_distribution(deps, info, league_id, distribution),
+            ExecuteExt::AddRounds {
+                league_id,
+                teams_subset,
+                round_duration,
+                count,
+            } => execute::add_rounds(
+                deps,
+                env,
+                info,
+                league_id,
+                teams_subset,
+                round_duration,
+                count,
+            ),
+            ExecuteExt::CreateTiebreak {
+                league_id,
+                round_duration,
+            } => execute::create_tiebreak(deps, env, info, league_id, round_duration),
+            ExecuteExt::SplitTopTie { league_id } => execute::split_top_tie(deps, info, league_id),
+            ExecuteExt::VoidMatch {
+                league_id,
+                round_number,
+                match_number,
+            } => execute::void_match(deps, info, league_id, round_number, match_number),
+            ExecuteExt::SettleLeague {
+                league_id,
+                payout_curve,
+            } => execute::settle_league(deps, info, league_id, payout_curve),
         },
         ExecuteBase::ProcessCompetition {
             competition_id: _,
             distribution: _,
             tax_cw20_msg: _,
             tax_cw721_msg: _,
-        } => Err(ContractError::InvalidExecute),
+            evidence: _,
+        }
+        | ExecuteBase::CommitResult { .. }
+        | ExecuteBase::RevealResult { .. }
+        | ExecuteBase::ProcessApproved { .. } => Err(ContractError::InvalidExecute),
         _ => Ok(CompetitionModule::default().execute(deps, env, info, msg)?),
     }
 }
@@ -116,10 +178,46 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
             QueryExt::Leaderboard { league_id, round } => {
                 to_json_binary(&query::leaderboard(deps, league_id, round)?)
             }
+            QueryExt::Standings { league_id, round } => {
+                to_json_binary(&query::standings(deps, league_id, round)?)
+            }
+            QueryExt::StandingsAtRound {
+                league_id,
+                round_number,
+            } => to_json_binary(&query::standings_at_round(deps, league_id, round_number)?),
+            QueryExt::ProjectedPayout {
+                league_id,
+                payout_curve,
+            } => to_json_binary(&query::projected_payout(deps, league_id, payout_curve)?),
+            QueryExt::LeagueProgress { league_id } => {
+                to_json_binary(&query::league_progress(deps, league_id)?)
+            }
+            QueryExt::IsLeagueComplete { league_id } => {
+                to_json_binary(&query::is_league_complete(deps, league_id)?)
+            }
             QueryExt::Round {
                 league_id,
                 round_number,
             } => to_json_binary(&query::round(deps, league_id, round_number)?),
+            QueryExt::RemainingFixtures { league_id } => {
+                to_json_binary(&query::remaining_fixtures(deps, league_id)?)
+            }
+            QueryExt::MatchEvidence {
+                league_id,
+                round_number,
+                match_number,
+            } => to_json_binary(&MATCH_EVIDENCE.may_load(
+                deps.storage,
+                (league_id.u128(), round_number.u64(), match_number.u128()),
+            )?),
+            QueryExt::Teams { league_id } => to_json_binary(
+                &LEAGUE_TEAMS
+                    .may_load(deps.storage, league_id.u128())?
+                    .unwrap_or_default(),
+            ),
+            QueryExt::NextMatch { league_id, team } => {
+                to_json_binary(&query::next_match(deps, env, league_id, team)?)
+            }
         },
         _ => CompetitionModule::default().query(deps, env, msg),
     }
@@ -23,4 +23,7 @@ pub enum ContractError {
 
     #[error("NotExpired")]
     NotExpired { expiration: Expiration },
+
+    #[error("ExtensionLimitReached")]
+    ExtensionLimitReached { max_extensions: u32 },
 }
@@ -1,17 +1,26 @@
 use cosmwasm_std::{
-    Addr, Decimal, DepsMut, Env, MessageInfo, OverflowError, OverflowOperation, Response, StdError,
-    StdResult, Uint128, Uint64,
+    Addr, Decimal, Deps, DepsMut, Env, MessageInfo, OverflowError, OverflowOperation, Response,
+    StdError, StdResult, Uint128, Uint64,
 };
 use cw_balance::{Distribution, MemberPercentage};
+use cw_competition::state::CompetitionStatus;
 use cw_utils::Duration;
 use itertools::Itertools;
-use std::{ops::Add, vec};
+use std::{
+    cmp::Ordering,
+    collections::{BTreeMap, BTreeSet},
+    ops::Add,
+    vec,
+};
 
 use crate::{
     contract::CompetitionModule,
-    msg::MatchResult,
+    msg::{League, MatchResult, ScheduleAlgorithm},
     query,
-    state::{Match, Round, MATCHES, ROUNDS},
+    state::{
+        Match, Result as MatchOutcome, Round, EXPIRATION_EXTENSIONS, LEAGUE_TEAMS, MATCHES,
+        MATCH_EVIDENCE, ROUNDS,
+    },
     ContractError,
 };
 
@@ -23,6 +32,9 @@ pub fn instantiate_rounds(
     teams: Vec<String>,
     distribution: Vec<Decimal>,
     round_duration: Duration,
+    schedule_algorithm: ScheduleAlgorithm,
+    pivot: Option<Uint64>,
+    shuffle_seed: Option<u64>,
 ) -> Result<Response, ContractError> {
     let team_count = teams.len();
     if team_count < 2 {
@@ -40,6 +52,19 @@ pub fn instantiate_rounds(
             msg: "Cannot have a distribution size bigger than the teams size".to_string(),
         }));
     }
+    validate_round_duration(&round_duration)?;
+    if let Some(pivot) = pivot {
+        if schedule_algorithm != ScheduleAlgorithm::CircleMethod {
+            return Err(ContractError::StdError(StdError::GenericErr {
+                msg: "A pivot is only supported by the circle method".to_string(),
+            }));
+        }
+        if pivot.u64() as usize >= team_count {
+            return Err(ContractError::StdError(StdError::GenericErr {
+                msg: "The pivot must be the index of a team".to_string(),
+            }));
+        }
+    }
 
     // Convert teams to addresses
     let team_addresses: Vec<Addr> = teams
@@ -47,31 +72,17 @@ pub fn instantiate_rounds(
         .map(|x| deps.api.addr_validate(x))
         .collect::<StdResult<_>>()?;
 
-    // Determine the number of rounds and matches per round
-    let rounds = if team_count % 2 == 1 {
-        team_count
-    } else {
-        team_count - 1
-    };
-    let matches_per_round = (rounds + 1) / 2;
-
-    // Table of teams, starting from 1 to n
-    let mut table: Vec<usize> = (1..=(rounds + 1)).collect();
-
     // Stores the rounds with the corresponding matches
-    let mut matches: Vec<Vec<(usize, usize)>> = Vec::new();
-    for r in 0..rounds {
-        matches.push(vec![]);
-        for m in 0..matches_per_round {
-            // Ignore the dummy team
-            if table[table.len() - 1 - m] != rounds + 1 && table[m] != rounds + 1 {
-                // Pair the teams based on the circle method
-                matches[r].push((table[m], table[table.len() - 1 - m]));
-            }
-        }
-
-        if let Some(last) = table.pop() {
-            table.insert(1, last);
+    let mut matches = match schedule_algorithm {
+        ScheduleAlgorithm::CircleMethod => round_robin(
+            team_count,
+            pivot.map(|x| x.u64() as usize).unwrap_or_default(),
+        ),
+        ScheduleAlgorithm::BergerTables => berger_tables(team_count),
+    };
+    if let Some(mut state) = shuffle_seed {
+        for round_pairings in matches.iter_mut() {
+            shuffle(round_pairings, &mut state);
         }
     }
 
@@ -79,6 +90,7 @@ pub fn instantiate_rounds(
     let league_id = CompetitionModule::default()
         .competition_count
         .load(deps.storage)?;
+    LEAGUE_TEAMS.save(deps.storage, league_id.u128(), &team_addresses)?;
 
     // Save rounds and matches to storage
     let mut duration = round_duration;
@@ -97,6 +109,7 @@ pub fn instantiate_rounds(
                     team_1: team_addresses[idx1 - 1].clone(),
                     team_2: team_addresses[idx2 - 1].clone(),
                     result: None,
+                    bonus: None,
                     match_number: Uint128::from(match_number),
                 },
             )?;
@@ -150,6 +163,425 @@ pub fn instantiate_rounds(
         .add_attribute("rounds", rounds_count.to_string()))
 }
 
+// A zero duration would create rounds that are already expired
+fn validate_round_duration(round_duration: &Duration) -> Result<(), ContractError> {
+    match round_duration {
+        Duration::Height(0) | Duration::Time(0) => Err(ContractError::CompetitionError(
+            cw_competition_base::error::CompetitionError::InvalidExpiration {},
+        )),
+        _ => Ok(()),
+    }
+}
+
+/// Generates the round robin pairings of team indexes (starting from 1) using the circle method
+/// The team at the pivot index (starting from 0) stays fixed while the others rotate
+fn round_robin(team_count: usize, pivot: usize) -> Vec<Vec<(usize, usize)>> {
+    // Determine the number of rounds and matches per round
+    let rounds = if team_count % 2 == 1 {
+        team_count
+    } else {
+        team_count - 1
+    };
+    let matches_per_round = (rounds + 1) / 2;
+
+    // Table of teams, starting from 1 to n, with the pivot moved to the fixed first position
+    let mut table: Vec<usize> = (1..=(rounds + 1)).collect();
+    let fixed = table.remove(pivot);
+    table.insert(0, fixed);
+
+    let mut matches: Vec<Vec<(usize, usize)>> = Vec::new();
+    for r in 0..rounds {
+        matches.push(vec![]);
+        for m in 0..matches_per_round {
+            // Ignore the dummy team
+            if table[table.len() - 1 - m] != rounds + 1 && table[m] != rounds + 1 {
+                // Pair the teams based on the circle method
+                matches[r].push((table[m], table[table.len() - 1 - m]));
+            }
+        }
+
+        if let Some(last) = table.pop() {
+            table.insert(1, last);
+        }
+    }
+
+    matches
+}
+
+/// Permutes the items with a Fisher-Yates shuffle driven by a SplitMix64 generator
+/// The same starting state always produces the same order
+fn shuffle<T>(items: &mut [T], state: &mut u64) {
+    for i in (1..items.len()).rev() {
+        let j = (next_random(state) % (i as u64 + 1)) as usize;
+        items.swap(i, j);
+    }
+}
+
+fn next_random(state: &mut u64) -> u64 {
+    *state = state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    let mut z = *state;
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+fn berger_tables(team_count: usize) -> Vec<Vec<(usize, usize)>> {
+    // Add a dummy team for an odd number of teams
+    let n = team_count + team_count % 2;
+    let rounds = n - 1;
+    let shift = n / 2;
+
+    // The first round pairs 1 against n, 2 against n - 1, and so on
+    let mut pairings: Vec<(usize, usize)> = (1..=n / 2).map(|i| (i, n + 1 - i)).collect();
+
+    let mut matches: Vec<Vec<(usize, usize)>> = Vec::new();
+    for _ in 0..rounds {
+        // Ignore the dummy team
+        matches.push(
+            pairings
+                .iter()
+                .filter(|(a, b)| *a <= team_count && *b <= team_count)
+                .copied()
+                .collect(),
+        );
+
+        // Advance every team except n by half the table
+        let advance = |x: usize| {
+            if x == n {
+                n
+            } else {
+                (x - 1 + shift) % rounds + 1
+            }
+        };
+        pairings = pairings
+            .iter()
+            .map(|&(a, b)| (advance(a), advance(b)))
+            .collect();
+
+        // Team n switches sides every round
+        pairings[0] = (pairings[0].1, pairings[0].0);
+    }
+
+    matches
+}
+
+pub fn add_rounds(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    league_id: Uint128,
+    teams_subset: Vec<String>,
+    round_duration: Duration,
+    count: Uint64,
+) -> Result<Response, ContractError> {
+    let mut league = CompetitionModule::default()
+        .competitions
+        .load(deps.storage, league_id.u128())?;
+
+    if league.host != info.sender && league.admin_dao != info.sender {
+        return Err(ContractError::CompetitionError(
+            cw_competition_base::error::CompetitionError::Unauthorized {},
+        ));
+    }
+    if league.status != CompetitionStatus::Active && league.status != CompetitionStatus::Pending {
+        return Err(ContractError::CompetitionError(
+            cw_competition_base::error::CompetitionError::InvalidCompetitionStatus {
+                current_status: league.status,
+            },
+        ));
+    }
+    if count.is_zero() {
+        return Err(ContractError::StdError(StdError::GenericErr {
+            msg: "At least 1 round should be added".to_string(),
+        }));
+    }
+    validate_round_duration(&round_duration)?;
+
+    let team_count = teams_subset.len();
+    if team_count < 2 {
+        return Err(ContractError::StdError(StdError::GenericErr {
+            msg: "At least 2 teams should be provided".to_string(),
+        }));
+    }
+    if teams_subset.iter().unique().count() != team_count {
+        return Err(ContractError::StdError(StdError::GenericErr {
+            msg: "Teams should not contain duplicates".to_string(),
+        }));
+    }
+
+    // Teams must already be in the league
+    let league_teams = MATCHES
+        .sub_prefix(league_id.u128())
+        .range(deps.storage, None, None, cosmwasm_std::Order::Ascending)
+        .map(|x| x.map(|y| [y.1.team_1, y.1.team_2]))
+        .collect::<StdResult<Vec<_>>>()?
+        .into_iter()
+        .flatten()
+        .collect::<BTreeSet<Addr>>();
+    let team_addresses: Vec<Addr> = teams_subset
+        .iter()
+        .map(|x| deps.api.addr_validate(x))
+        .collect::<StdResult<_>>()?;
+    if let Some(team) = team_addresses.iter().find(|x| !league_teams.contains(*x)) {
+        return Err(ContractError::StdError(StdError::GenericErr {
+            msg: format!("{} is not a team in the league", team),
+        }));
+    }
+
+    schedule_rounds(
+        deps,
+        &env,
+        league_id,
+        &mut league,
+        &team_addresses,
+        round_duration,
+        count.u64(),
+        false,
+    )?;
+
+    Ok(Response::new()
+        .add_attribute("action", "add_rounds")
+        .add_attribute("league_id", league_id)
+        .add_attribute("rounds", league.extension.rounds)
+        .add_attribute("expiration", league.expiration.to_string()))
+}
+
+// Appends round robin rounds between the given teams after the league's last round
+// Tiebreak rounds extend the league without counting against its extension allowance
+#[allow(clippy::too_many_arguments)]
+fn schedule_rounds(
+    deps: DepsMut,
+    env: &Env,
+    league_id: Uint128,
+    league: &mut League,
+    team_addresses: &[Addr],
+    round_duration: Duration,
+    count: u64,
+    is_tiebreak: bool,
+) -> Result<(), ContractError> {
+    // Continue the round and match numbering after the last round
+    let pairings = round_robin(team_addresses.len(), 0);
+    let mut round_number = league.extension.rounds.u64();
+    let mut match_number = league.extension.matches.u128();
+    // Start after the last round if it has not expired yet
+    let last_expiration = ROUNDS
+        .may_load(deps.storage, (league_id.u128(), round_number))?
+        .map(|x| x.expiration)
+        .filter(|x| !x.is_expired(&env.block));
+    let mut expiration = match last_expiration {
+        Some(last_expiration) => (last_expiration + round_duration)?,
+        None => round_duration.after(&env.block),
+    };
+    for i in 0..count as usize {
+        round_number += 1;
+        let mut matches = vec![];
+
+        for &(idx1, idx2) in &pairings[i % pairings.len()] {
+            match_number += 1;
+            MATCHES.save(
+                deps.storage,
+                (league_id.u128(), round_number, match_number),
+                &Match {
+                    team_1: team_addresses[idx1 - 1].clone(),
+                    team_2: team_addresses[idx2 - 1].clone(),
+                    result: None,
+                    bonus: None,
+                    match_number: Uint128::from(match_number),
+                },
+            )?;
+            matches.push(Uint128::from(match_number));
+        }
+
+        ROUNDS.save(
+            deps.storage,
+            (league_id.u128(), round_number),
+            &Round {
+                round_number: Uint64::from(round_number),
+                matches,
+                expiration,
+            },
+        )?;
+        expiration = (expiration + round_duration)?;
+    }
+
+    // Extend the league to cover the last round + 1 round duration
+    if matches!(
+        league.expiration.partial_cmp(&expiration),
+        Some(Ordering::Less) | None
+    ) {
+        // Each extension counts against the league's allowance so it cannot be stalled indefinitely
+        if !is_tiebreak {
+            let extensions = EXPIRATION_EXTENSIONS
+                .may_load(deps.storage, league_id.u128())?
+                .unwrap_or_default();
+            if let Some(max_extensions) = league.extension.max_extensions {
+                if extensions >= max_extensions {
+                    return Err(ContractError::ExtensionLimitReached { max_extensions });
+                }
+            }
+            EXPIRATION_EXTENSIONS.save(deps.storage, league_id.u128(), &(extensions + 1))?;
+        }
+        league.expiration = expiration;
+    }
+    league.extension.rounds = Uint64::from(round_number);
+    league.extension.matches = Uint128::from(match_number);
+
+    CompetitionModule::default()
+        .competitions
+        .save(deps.storage, league_id.u128(), league)?;
+
+    Ok(())
+}
+
+pub fn create_tiebreak(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    league_id: Uint128,
+    round_duration: Duration,
+) -> Result<Response, ContractError> {
+    let mut league = CompetitionModule::default()
+        .competitions
+        .load(deps.storage, league_id.u128())?;
+
+    if league.host != info.sender && league.admin_dao != info.sender {
+        return Err(ContractError::CompetitionError(
+            cw_competition_base::error::CompetitionError::Unauthorized {},
+        ));
+    }
+    if league.status != CompetitionStatus::Active && league.status != CompetitionStatus::Pending {
+        return Err(ContractError::CompetitionError(
+            cw_competition_base::error::CompetitionError::InvalidCompetitionStatus {
+                current_status: league.status,
+            },
+        ));
+    }
+    if !query::is_league_complete(deps.as_ref(), league_id)? {
+        return Err(ContractError::StdError(StdError::GenericErr {
+            msg: "Cannot create a tiebreak before all matches are processed".to_string(),
+        }));
+    }
+    validate_round_duration(&round_duration)?;
+
+    let (team_1, team_2) = top_tie(deps.as_ref(), league_id)?.ok_or_else(|| {
+        ContractError::StdError(StdError::GenericErr {
+            msg: "There is no tie at the top of the league".to_string(),
+        })
+    })?;
+
+    schedule_rounds(
+        deps,
+        &env,
+        league_id,
+        &mut league,
+        &[team_1.clone(), team_2.clone()],
+        round_duration,
+        1,
+        true,
+    )?;
+
+    Ok(Response::new()
+        .add_attribute("action", "create_tiebreak")
+        .add_attribute("league_id", league_id)
+        .add_attribute("round_number", league.extension.rounds)
+        .add_attribute("team_1", team_1)
+        .add_attribute("team_2", team_2))
+}
+
+/// The top two teams if they are level on points and on their head-to-head results
+fn top_tie(deps: Deps, league_id: Uint128) -> StdResult<Option<(Addr, Addr)>> {
+    let mut leaderboard = query::leaderboard(deps, league_id, None)?;
+    leaderboard.sort_by(|x, y| y.points.cmp(&x.points));
+
+    if leaderboard.len() < 2 || leaderboard[0].points != leaderboard[1].points {
+        return Ok(None);
+    }
+    let team_1 = leaderboard[0].member.clone();
+    let team_2 = leaderboard[1].member.clone();
+
+    let mut head_to_head = 0i64;
+    for m in MATCHES.sub_prefix(league_id.u128()).range(
+        deps.storage,
+        None,
+        None,
+        cosmwasm_std::Order::Ascending,
+    ) {
+        let m = m?.1;
+        let sign = if m.team_1 == team_1 && m.team_2 == team_2 {
+            1
+        } else if m.team_1 == team_2 && m.team_2 == team_1 {
+            -1
+        } else {
+            continue;
+        };
+        match m.result {
+            Some(MatchOutcome::Team1) => head_to_head += sign,
+            Some(MatchOutcome::Team2) => head_to_head -= sign,
+            _ => {}
+        }
+    }
+
+    Ok((head_to_head == 0).then_some((team_1, team_2)))
+}
+
+/// Distributes a league whose top tie was never broken, with the tied teams sharing their positions
+pub fn split_top_tie(
+    deps: DepsMut,
+    info: MessageInfo,
+    league_id: Uint128,
+) -> Result<Response, ContractError> {
+    let league = CompetitionModule::default()
+        .competitions
+        .load(deps.storage, league_id.u128())?;
+
+    if league.admin_dao != info.sender {
+        return Err(ContractError::CompetitionError(
+            cw_competition_base::error::CompetitionError::Unauthorized {},
+        ));
+    }
+    if league.escrow.is_none() || league.extension.distribution.is_empty() {
+        return Err(ContractError::StdError(StdError::GenericErr {
+            msg: "The league is not distributed by its standings".to_string(),
+        }));
+    }
+    if !query::is_league_complete(deps.as_ref(), league_id)? {
+        return Err(ContractError::StdError(StdError::GenericErr {
+            msg: "Every match must be resolved before distributing the league".to_string(),
+        }));
+    }
+    if top_tie(deps.as_ref(), league_id)?.is_none() {
+        return Err(ContractError::StdError(StdError::GenericErr {
+            msg: "There is no tie at the top of the league".to_string(),
+        }));
+    }
+
+    let mut leaderboard = query::leaderboard(deps.as_ref(), league_id, None)?;
+    leaderboard.sort_by(|x, y| y.points.cmp(&x.points));
+    let ranked = leaderboard
+        .iter()
+        .map(|x| (x.member.clone(), x.points))
+        .collect::<Vec<_>>();
+    let member_percentages = query::split_tied_positions(&ranked, &league.extension.distribution)?;
+
+    let config = CompetitionModule::default().config.load(deps.storage)?;
+    let response = CompetitionModule::default().execute_process_competition(
+        deps,
+        info,
+        league_id,
+        Some(Distribution::<String> {
+            member_percentages,
+            remainder_addr: leaderboard[0].member.to_string(),
+        }),
+        config.extension.tax_cw20_msg,
+        config.extension.tax_cw721_msg,
+        None,
+    )?;
+
+    Ok(response
+        .add_attribute("action", "split_top_tie")
+        .add_attribute("league_id", league_id))
+}
+
 pub fn process_matches(
     deps: DepsMut,
     env: Env,
@@ -157,12 +589,16 @@ pub fn process_matches(
     league_id: Uint128,
     round_number: Uint64,
     match_results: Vec<MatchResult>,
+    evidence: Option<String>,
 ) -> Result<Response, ContractError> {
     let mut league = CompetitionModule::default()
         .competitions
         .load(deps.storage, league_id.u128())?;
 
-    if league.host != info.sender && league.admin_dao != info.sender {
+    if league.host != info.sender
+        && league.admin_dao != info.sender
+        && league.result_authority.as_ref() != Some(&info.sender)
+    {
         return Err(ContractError::CompetitionError(
             cw_competition_base::error::CompetitionError::OwnershipError(
                 cw_ownable::OwnershipError::NotOwner,
@@ -179,11 +615,20 @@ pub fn process_matches(
     }
 
     for match_result in match_results {
+        if match_result.result.is_none() && match_result.bonus.is_some() {
+            return Err(ContractError::StdError(StdError::generic_err(
+                "Bonus points cannot be applied to an unresolved match",
+            )));
+        }
+
         let key = (
             league_id.u128(),
             round_number.u64(),
             match_result.match_number.u128(),
         );
+        if let Some(evidence) = &evidence {
+            MATCH_EVIDENCE.save(deps.storage, key, evidence)?;
+        }
         MATCHES.update(deps.storage, key, |x| -> Result<_, ContractError> {
             match x {
                 Some(mut m) => {
@@ -201,6 +646,7 @@ pub fn process_matches(
                             .checked_add(Uint128::one())?;
                     }
                     m.result = match_result.result;
+                    m.bonus = match_result.bonus;
 
                     Ok(m)
                 }
@@ -211,10 +657,26 @@ pub fn process_matches(
         })?;
     }
 
+    CompetitionModule::default()
+        .competitions
+        .save(deps.storage, league_id.u128(), &league)?;
+
     let mut response = Response::new();
 
+    // A league without a distribution is settled by a payout curve instead,
+    // and only an active league's escrow is funded for distribution
     if let Some(_escrow) = league.escrow {
-        if league.extension.processed_matches >= league.extension.matches {
+        if !league.extension.distribution.is_empty()
+            && league.status == CompetitionStatus::Active
+            && league.extension.processed_matches >= league.extension.matches
+        {
+            // A tie at the top is settled by a tiebreak match before distributing
+            if top_tie(deps.as_ref(), league_id)?.is_some() {
+                return Ok(response
+                    .add_attribute("action", "process_matches")
+                    .add_attribute("tiebreak_required", "true"));
+            }
+
             // Distribute funds if we have processed all of the matches
             let mut leaderboard = query::leaderboard(deps.as_ref(), league_id, None)?;
 
@@ -241,6 +703,7 @@ pub fn process_matches(
                 }),
                 config.extension.tax_cw20_msg,
                 config.extension.tax_cw721_msg,
+                None,
             )?;
         }
     }
@@ -248,6 +711,69 @@ pub fn process_matches(
     Ok(response.add_attribute("action", "process_matches"))
 }
 
+#[allow(clippy::too_many_arguments)]
+pub fn process_round(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    league_id: Uint128,
+    round_number: Uint64,
+    results: Vec<(Uint128, MatchOutcome)>,
+    default_result: Option<MatchOutcome>,
+    evidence: Option<String>,
+) -> Result<Response, ContractError> {
+    let round = ROUNDS.load(deps.storage, (league_id.u128(), round_number.u64()))?;
+
+    let mut results_by_match = BTreeMap::new();
+    for (match_number, result) in results {
+        if !round.matches.contains(&match_number) {
+            return Err(ContractError::StdError(StdError::NotFound {
+                kind: "Match".to_string(),
+            }));
+        }
+        if results_by_match.insert(match_number, result).is_some() {
+            return Err(ContractError::StdError(StdError::generic_err(format!(
+                "Match {} is reported more than once",
+                match_number
+            ))));
+        }
+    }
+
+    // Every match of the round must be accounted for
+    let match_results = round
+        .matches
+        .iter()
+        .map(|match_number| {
+            match results_by_match
+                .remove(match_number)
+                .or_else(|| default_result.clone())
+            {
+                Some(result) => Ok(MatchResult {
+                    match_number: *match_number,
+                    result: Some(result),
+                    bonus: None,
+                }),
+                None => Err(ContractError::StdError(StdError::generic_err(format!(
+                    "Match {} has no result",
+                    match_number
+                )))),
+            }
+        })
+        .collect::<Result<Vec<_>, ContractError>>()?;
+
+    let response = process_matches(
+        deps,
+        env,
+        info,
+        league_id,
+        round_number,
+        match_results,
+        evidence,
+    )?;
+
+    Ok(response.add_attribute("round_number", round_number))
+}
+
 pub fn update_distribution(
     deps: DepsMut,
     info: MessageInfo,
@@ -279,3 +805,113 @@ pub fn update_distribution(
         .add_attribute("action", "update_distribution")
         .add_attribute("distribution", format!("{:#?}", distribution)))
 }
+
+/// Standings are derived from the match results, so clearing the result reverses its impact
+pub fn void_match(
+    deps: DepsMut,
+    info: MessageInfo,
+    league_id: Uint128,
+    round_number: Uint64,
+    match_number: Uint128,
+) -> Result<Response, ContractError> {
+    let competition_module = CompetitionModule::default();
+    let mut league = competition_module
+        .competitions
+        .load(deps.storage, league_id.u128())?;
+
+    if info.sender != league.admin_dao {
+        return Err(ContractError::CompetitionError(
+            cw_competition_base::error::CompetitionError::Unauthorized {},
+        ));
+    }
+    if league.status != CompetitionStatus::Active && league.status != CompetitionStatus::Pending {
+        return Err(ContractError::CompetitionError(
+            cw_competition_base::error::CompetitionError::InvalidCompetitionStatus {
+                current_status: league.status,
+            },
+        ));
+    }
+
+    let key = (league_id.u128(), round_number.u64(), match_number.u128());
+    MATCHES.update(deps.storage, key, |x| -> Result<_, ContractError> {
+        match x {
+            Some(mut m) => {
+                if m.result.is_none() {
+                    return Err(ContractError::StdError(StdError::GenericErr {
+                        msg: "The match has no result to void".to_string(),
+                    }));
+                }
+                m.result = None;
+                m.bonus = None;
+
+                Ok(m)
+            }
+            None => Err(ContractError::StdError(StdError::NotFound {
+                kind: "Match".to_string(),
+            })),
+        }
+    })?;
+    MATCH_EVIDENCE.remove(deps.storage, key);
+
+    // The voided match must be reported again before the league can settle
+    league.extension.processed_matches = league
+        .extension
+        .processed_matches
+        .checked_sub(Uint128::one())?;
+    competition_module
+        .competitions
+        .save(deps.storage, league_id.u128(), &league)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "void_match")
+        .add_attribute("league_id", league_id)
+        .add_attribute("round_number", round_number)
+        .add_attribute("match_number", match_number))
+}
+
+pub fn settle_league(
+    deps: DepsMut,
+    info: MessageInfo,
+    league_id: Uint128,
+    payout_curve: Vec<Decimal>,
+) -> Result<Response, ContractError> {
+    let league = CompetitionModule::default()
+        .competitions
+        .load(deps.storage, league_id.u128())?;
+
+    if league.escrow.is_none() {
+        return Err(ContractError::StdError(StdError::GenericErr {
+            msg: "The league has no escrow to settle".to_string(),
+        }));
+    }
+    if !league.extension.distribution.is_empty() {
+        return Err(ContractError::StdError(StdError::GenericErr {
+            msg: "The league is distributed when its last match is processed".to_string(),
+        }));
+    }
+    if !query::league_progress(deps.as_ref(), league_id)?
+        .remaining_matches
+        .is_zero()
+    {
+        return Err(ContractError::StdError(StdError::GenericErr {
+            msg: "Every match must be resolved before settling the league".to_string(),
+        }));
+    }
+
+    let distribution = query::projected_payout(deps.as_ref(), league_id, payout_curve)?;
+    let config = CompetitionModule::default().config.load(deps.storage)?;
+
+    let response = CompetitionModule::default().execute_process_competition(
+        deps,
+        info,
+        league_id,
+        Some(distribution),
+        config.extension.tax_cw20_msg,
+        config.extension.tax_cw721_msg,
+        None,
+    )?;
+
+    Ok(response
+        .add_attribute("action", "settle_league")
+        .add_attribute("league_id", league_id))
+}
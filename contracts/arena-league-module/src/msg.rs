@@ -1,13 +1,14 @@
 #[allow(unused_imports)]
 use crate::state::RoundResponse;
-use crate::state::{Result, TournamentExt};
+use crate::state::{MatchBonus, Result, TournamentExt};
 use cosmwasm_schema::{cw_serde, QueryResponses};
 use cosmwasm_std::{Addr, Decimal, StdResult, Uint128, Uint64};
+use cw_balance::Distribution;
 use cw_competition::{
     msg::{ExecuteBase, InstantiateBase, IntoCompetitionExt, QueryBase},
     state::{Competition, CompetitionResponse},
 };
-use cw_utils::Duration;
+use cw_utils::{Duration, Expiration};
 
 #[cw_serde]
 pub enum ExecuteExt {
@@ -15,17 +16,60 @@ pub enum ExecuteExt {
         league_id: Uint128,
         round_number: Uint64,
         match_results: Vec<MatchResult>,
+        /// An off-chain reference backing the results, such as an IPFS hash or signed attestation
+        evidence: Option<String>,
+    },
+    /// Reports every match of a round at once
+    /// Matches missing from the results take the default result, and are rejected without one
+    ProcessRound {
+        league_id: Uint128,
+        round_number: Uint64,
+        results: Vec<(Uint128, Result)>,
+        default_result: Option<Result>,
+        evidence: Option<String>,
     },
     UpdateDistribution {
         league_id: Uint128,
         distribution: Vec<Decimal>,
     },
+    /// Appends rounds played between a subset of the league's teams after the last round
+    AddRounds {
+        league_id: Uint128,
+        teams_subset: Vec<String>,
+        round_duration: Duration,
+        count: Uint64,
+    },
+    /// Schedules a single decisive match between the top two teams when they are tied
+    /// Tiebreaks don't count against the league's max extensions
+    CreateTiebreak {
+        league_id: Uint128,
+        round_duration: Duration,
+    },
+    /// Distributes a league still tied at the top, splitting the tied positions' shares equally
+    /// Only the admin dao can fall back to this when tiebreaks fail to break the tie
+    SplitTopTie { league_id: Uint128 },
+    /// Clears a match's result so it can be replayed and reported again
+    VoidMatch {
+        league_id: Uint128,
+        round_number: Uint64,
+        match_number: Uint128,
+    },
+    /// Distributes the league's escrow by the final standings once every match is resolved
+    /// Each entry of the payout curve is the share of the team finishing in that position
+    /// Only leagues without a distribution are settled this way, as the others are distributed
+    /// when their last match is processed
+    SettleLeague {
+        league_id: Uint128,
+        payout_curve: Vec<Decimal>,
+    },
 }
 
 #[cw_serde]
 pub struct MatchResult {
     pub match_number: Uint128,
     pub result: Option<Result>,
+    /// Only applies to a resolved match
+    pub bonus: Option<MatchBonus>,
 }
 
 #[cw_serde]
@@ -36,11 +80,51 @@ pub enum QueryExt {
         league_id: Uint128,
         round: Option<Uint64>,
     },
+    /// Includes every scheduled team with its record and win rate
+    #[returns(Vec<TeamStanding>)]
+    Standings {
+        league_id: Uint128,
+        round: Option<Uint64>,
+    },
+    /// The standings as they were once the given round was complete
+    #[returns(Vec<TeamStanding>)]
+    StandingsAtRound {
+        league_id: Uint128,
+        round_number: Uint64,
+    },
+    /// The distribution settling the league with the payout curve would use at the current standings
+    /// Unlike settling, matches may still be unresolved
+    #[returns(Distribution<String>)]
+    ProjectedPayout {
+        league_id: Uint128,
+        payout_curve: Vec<Decimal>,
+    },
+    #[returns(LeagueProgress)]
+    LeagueProgress { league_id: Uint128 },
+    /// Whether every match has a result, so the league can be settled
+    #[returns(bool)]
+    IsLeagueComplete { league_id: Uint128 },
     #[returns(RoundResponse)]
     Round {
         league_id: Uint128,
         round_number: Uint64,
     },
+    /// The unplayed matches of each round, in round order
+    #[returns(Vec<RoundResponse>)]
+    RemainingFixtures { league_id: Uint128 },
+    /// The off-chain reference attached when the match result was reported
+    #[returns(Option<String>)]
+    MatchEvidence {
+        league_id: Uint128,
+        round_number: Uint64,
+        match_number: Uint128,
+    },
+    /// The teams the league was created with
+    #[returns(Vec<Addr>)]
+    Teams { league_id: Uint128 },
+    /// The team's first unplayed match in a round that has not expired yet
+    #[returns(Option<ScheduledMatch>)]
+    NextMatch { league_id: Uint128, team: String },
 }
 
 #[cw_serde]
@@ -66,6 +150,8 @@ pub struct CompetitionExt {
     pub teams: Uint64,
     pub processed_matches: Uint128,
     pub distribution: Vec<Decimal>,
+    /// The number of times added rounds may push out the league's expiration
+    pub max_extensions: Option<u32>,
 }
 
 #[cw_serde]
@@ -76,6 +162,24 @@ pub struct CompetitionInstantiateExt {
     pub teams: Vec<String>,
     pub round_duration: Duration,
     pub distribution: Vec<Decimal>,
+    /// Defaults to the circle method
+    pub schedule_algorithm: Option<ScheduleAlgorithm>,
+    /// The index of the team the circle method keeps fixed, which decides who rests each round
+    /// Defaults to the first team
+    pub pivot: Option<Uint64>,
+    /// Reproducibly permutes the order of the matches within each round
+    pub shuffle_seed: Option<Uint64>,
+    /// Caps how many times added rounds can push out the league's expiration, with no cap by default
+    pub max_extensions: Option<u32>,
+}
+
+#[cw_serde]
+#[derive(Default)]
+pub enum ScheduleAlgorithm {
+    #[default]
+    CircleMethod,
+    /// The standard chess pairing tables, alternating the fixed team's side each round
+    BergerTables,
 }
 
 impl IntoCompetitionExt<CompetitionExt> for CompetitionInstantiateExt {
@@ -89,6 +193,7 @@ impl IntoCompetitionExt<CompetitionExt> for CompetitionInstantiateExt {
             matches: Uint128::zero(),
             processed_matches: Uint128::zero(),
             distribution: self.distribution,
+            max_extensions: self.max_extensions,
         })
     }
 }
@@ -100,6 +205,50 @@ pub struct MemberPoints {
     pub matches_played: Uint64,
 }
 
+#[cw_serde]
+pub struct TeamStanding {
+    pub member: Addr,
+    pub points: Uint128,
+    pub matches_played: Uint64,
+    pub wins: Uint64,
+    pub draws: Uint64,
+    pub losses: Uint64,
+    /// Zero if no matches have been played
+    pub win_rate: Decimal,
+}
+
+impl TeamStanding {
+    pub fn new(member: Addr) -> Self {
+        TeamStanding {
+            member,
+            points: Uint128::zero(),
+            matches_played: Uint64::zero(),
+            wins: Uint64::zero(),
+            draws: Uint64::zero(),
+            losses: Uint64::zero(),
+            win_rate: Decimal::zero(),
+        }
+    }
+}
+
+#[cw_serde]
+pub struct LeagueProgress {
+    pub total_matches: Uint128,
+    pub played_matches: Uint128,
+    pub remaining_matches: Uint128,
+    /// The earliest round with an unplayed match, or the last round if every match is played
+    pub current_round: Uint64,
+    pub total_rounds: Uint64,
+}
+
+#[cw_serde]
+pub struct ScheduledMatch {
+    pub round_number: Uint64,
+    pub match_number: Uint128,
+    pub opponent: Addr,
+    pub expiration: Expiration,
+}
+
 pub type InstantiateMsg = InstantiateBase<TournamentExt>;
 pub type ExecuteMsg = ExecuteBase<ExecuteExt, CompetitionInstantiateExt>;
 pub type QueryMsg = QueryBase<TournamentExt, QueryExt, CompetitionExt>;
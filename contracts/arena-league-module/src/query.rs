@@ -2,10 +2,11 @@ use std::collections::BTreeMap;
 
 use crate::{
     contract::CompetitionModule,
-    msg::MemberPoints,
+    msg::{LeagueProgress, MemberPoints, ScheduledMatch, TeamStanding},
     state::{Match, Result, Round, RoundResponse, MATCHES, ROUNDS},
 };
-use cosmwasm_std::{Addr, Deps, StdResult, Uint128, Uint64};
+use cosmwasm_std::{Addr, Decimal, Deps, Env, StdError, StdResult, Uint128, Uint64};
+use cw_balance::{Distribution, MemberPercentage};
 use cw_storage_plus::Bound;
 
 pub fn leaderboard(
@@ -13,6 +14,23 @@ pub fn leaderboard(
     league_id: Uint128,
     round: Option<Uint64>,
 ) -> StdResult<Vec<MemberPoints>> {
+    Ok(standings(deps, league_id, round)?
+        .into_iter()
+        .filter(|x| !x.matches_played.is_zero())
+        .map(|x| MemberPoints {
+            member: x.member,
+            points: x.points,
+            matches_played: x.matches_played,
+        })
+        .collect())
+}
+
+/// Includes every team scheduled in the rounds, even if it has not played yet
+pub fn standings(
+    deps: Deps,
+    league_id: Uint128,
+    round: Option<Uint64>,
+) -> StdResult<Vec<TeamStanding>> {
     let league = CompetitionModule::default()
         .competitions
         .load(deps.storage, league_id.u128())?;
@@ -28,7 +46,7 @@ pub fn leaderboard(
         .map(|x| x.map(|y| y.1))
         .collect::<StdResult<Vec<Round>>>()?;
 
-    let mut leaderboard: BTreeMap<Addr, (Uint128, Uint64)> = BTreeMap::new();
+    let mut standings: BTreeMap<Addr, TeamStanding> = BTreeMap::new();
     for round in rounds {
         let matches: Vec<Match> = MATCHES
             .prefix((league_id.u128(), round.round_number.u64()))
@@ -37,7 +55,21 @@ pub fn leaderboard(
             .collect::<StdResult<_>>()?;
 
         for m in matches {
+            for team in [&m.team_1, &m.team_2] {
+                standings
+                    .entry(team.clone())
+                    .or_insert_with(|| TeamStanding::new(team.clone()));
+            }
+
             if let Some(match_result) = m.result {
+                if let Some(bonus) = m.bonus {
+                    for (team, points) in [(&m.team_1, bonus.team_1), (&m.team_2, bonus.team_2)] {
+                        if let Some(standing) = standings.get_mut(team) {
+                            standing.points = standing.points.checked_add(points)?;
+                        }
+                    }
+                }
+
                 match match_result {
                     Result::Team1 | Result::Team2 => {
                         let (team_1, team_2) = if match_result == Result::Team1 {
@@ -46,44 +78,249 @@ pub fn leaderboard(
                             (m.team_2, m.team_1)
                         };
 
-                        let record_1 = leaderboard.entry(team_1).or_default();
-                        *record_1 = (
-                            record_1.0.checked_add(league.extension.match_win_points)?,
-                            record_1.1.checked_add(Uint64::one())?,
-                        );
-
-                        let record_2 = leaderboard.entry(team_2).or_default();
-                        *record_2 = (
-                            record_2.0.checked_add(league.extension.match_lose_points)?,
-                            record_2.1.checked_add(Uint64::one())?,
-                        );
+                        if let Some(winner) = standings.get_mut(&team_1) {
+                            winner.points = winner
+                                .points
+                                .checked_add(league.extension.match_win_points)?;
+                            winner.wins = winner.wins.checked_add(Uint64::one())?;
+                        }
+                        if let Some(loser) = standings.get_mut(&team_2) {
+                            loser.points = loser
+                                .points
+                                .checked_add(league.extension.match_lose_points)?;
+                            loser.losses = loser.losses.checked_add(Uint64::one())?;
+                        }
                     }
                     Result::Draw => {
-                        let record_1 = leaderboard.entry(m.team_1).or_default();
-                        *record_1 = (
-                            record_1.0.checked_add(league.extension.match_draw_points)?,
-                            record_1.1.checked_add(Uint64::one())?,
-                        );
-
-                        let record_2 = leaderboard.entry(m.team_2).or_default();
-                        *record_2 = (
-                            record_2.0.checked_add(league.extension.match_draw_points)?,
-                            record_2.1.checked_add(Uint64::one())?,
-                        );
+                        for team in [&m.team_1, &m.team_2] {
+                            if let Some(standing) = standings.get_mut(team) {
+                                standing.points = standing
+                                    .points
+                                    .checked_add(league.extension.match_draw_points)?;
+                                standing.draws = standing.draws.checked_add(Uint64::one())?;
+                            }
+                        }
                     }
                 }
             }
         }
     }
 
-    Ok(leaderboard
-        .into_iter()
-        .map(|(member, record)| MemberPoints {
-            member,
-            points: record.0,
-            matches_played: record.1,
+    standings
+        .into_values()
+        .map(|mut standing| -> StdResult<TeamStanding> {
+            standing.matches_played = standing
+                .wins
+                .checked_add(standing.draws)?
+                .checked_add(standing.losses)?;
+            standing.win_rate = if standing.matches_played.is_zero() {
+                Decimal::zero()
+            } else {
+                Decimal::from_ratio(standing.wins, standing.matches_played)
+            };
+
+            Ok(standing)
         })
-        .collect())
+        .collect()
+}
+
+/// Only counts the matches of the rounds up to and including the given round
+pub fn standings_at_round(
+    deps: Deps,
+    league_id: Uint128,
+    round_number: Uint64,
+) -> StdResult<Vec<TeamStanding>> {
+    // The round must exist, unlike the optional bound of the standings query
+    ROUNDS.load(deps.storage, (league_id.u128(), round_number.u64()))?;
+
+    standings(deps, league_id, Some(round_number))
+}
+
+/// Rounds are scheduled one after another, so the first open round holds the earliest match
+pub fn next_match(
+    deps: Deps,
+    env: Env,
+    league_id: Uint128,
+    team: String,
+) -> StdResult<Option<ScheduledMatch>> {
+    let team = deps.api.addr_validate(&team)?;
+
+    for item in ROUNDS.prefix(league_id.u128()).range(
+        deps.storage,
+        None,
+        None,
+        cosmwasm_std::Order::Ascending,
+    ) {
+        let (round_number, round) = item?;
+        if round.expiration.is_expired(&env.block) {
+            continue;
+        }
+
+        for item in MATCHES.prefix((league_id.u128(), round_number)).range(
+            deps.storage,
+            None,
+            None,
+            cosmwasm_std::Order::Ascending,
+        ) {
+            let (_, m) = item?;
+            if m.result.is_some() {
+                continue;
+            }
+
+            let opponent = if m.team_1 == team {
+                m.team_2
+            } else if m.team_2 == team {
+                m.team_1
+            } else {
+                continue;
+            };
+
+            return Ok(Some(ScheduledMatch {
+                round_number: round.round_number,
+                match_number: m.match_number,
+                opponent,
+                expiration: round.expiration,
+            }));
+        }
+    }
+
+    Ok(None)
+}
+
+/// Rounds without an unplayed match are left out
+pub fn remaining_fixtures(deps: Deps, league_id: Uint128) -> StdResult<Vec<RoundResponse>> {
+    let mut fixtures = vec![];
+    for item in ROUNDS.prefix(league_id.u128()).range(
+        deps.storage,
+        None,
+        None,
+        cosmwasm_std::Order::Ascending,
+    ) {
+        let (_, round) = item?;
+        let mut response = round.into_response(deps, league_id)?;
+        response.matches.retain(|m| m.result.is_none());
+
+        if !response.matches.is_empty() {
+            fixtures.push(response);
+        }
+    }
+
+    Ok(fixtures)
+}
+
+/// Maps the current standings onto the payout curve, as settling the league would
+/// Teams on equal points split the combined share of the positions they span
+pub fn projected_payout(
+    deps: Deps,
+    league_id: Uint128,
+    payout_curve: Vec<Decimal>,
+) -> StdResult<Distribution<String>> {
+    let mut standings = standings(deps, league_id, None)?;
+    if payout_curve.is_empty() || payout_curve.len() > standings.len() {
+        return Err(StdError::generic_err(
+            "The payout curve must pay between one and every team",
+        ));
+    }
+
+    standings.sort_by(|x, y| y.points.cmp(&x.points));
+
+    let ranked: Vec<(Addr, Uint128)> = standings
+        .iter()
+        .map(|x| (x.member.clone(), x.points))
+        .collect();
+    let member_percentages = split_tied_positions(&ranked, &payout_curve)?;
+
+    Ok(Distribution::<String> {
+        member_percentages,
+        remainder_addr: standings[0].member.to_string(),
+    })
+}
+
+/// Pays each ranked team its position's share of the curve, with teams level on points splitting
+/// the shares of the positions they span equally
+/// The last team of a tie absorbs the rounding, so the shares still sum to the curve's total
+pub fn split_tied_positions(
+    ranked: &[(Addr, Uint128)],
+    curve: &[Decimal],
+) -> StdResult<Vec<MemberPercentage<String>>> {
+    let mut member_percentages = vec![];
+    let mut start = 0;
+    while start < ranked.len() && start < curve.len() {
+        let points = ranked[start].1;
+        let end = ranked[start..]
+            .iter()
+            .position(|x| x.1 != points)
+            .map_or(ranked.len(), |i| start + i);
+
+        let total = curve[start..end.min(curve.len())]
+            .iter()
+            .try_fold(Decimal::zero(), |total, x| total.checked_add(*x))?;
+        let share = total * Decimal::from_ratio(1u128, (end - start) as u128);
+
+        let mut remaining = total;
+        for (i, (addr, _)) in ranked[start..end].iter().enumerate() {
+            let percentage = if start + i == end - 1 {
+                remaining
+            } else {
+                share
+            };
+            remaining = remaining.checked_sub(percentage)?;
+
+            if !percentage.is_zero() {
+                member_percentages.push(MemberPercentage::<String> {
+                    addr: addr.to_string(),
+                    percentage,
+                });
+            }
+        }
+
+        start = end;
+    }
+
+    Ok(member_percentages)
+}
+
+/// Byes are never scheduled as matches, so the league is complete once every match has a result
+pub fn is_league_complete(deps: Deps, league_id: Uint128) -> StdResult<bool> {
+    Ok(league_progress(deps, league_id)?
+        .remaining_matches
+        .is_zero())
+}
+
+/// Byes are never scheduled as matches, so only real matches are counted
+pub fn league_progress(deps: Deps, league_id: Uint128) -> StdResult<LeagueProgress> {
+    let league = CompetitionModule::default()
+        .competitions
+        .load(deps.storage, league_id.u128())?;
+    let total_rounds = league.extension.rounds;
+
+    let mut total_matches = Uint128::zero();
+    let mut played_matches = Uint128::zero();
+    let mut current_round: Option<u64> = None;
+
+    for item in MATCHES.sub_prefix(league_id.u128()).range(
+        deps.storage,
+        None,
+        None,
+        cosmwasm_std::Order::Ascending,
+    ) {
+        let ((round_number, _), m) = item?;
+
+        total_matches = total_matches.checked_add(Uint128::one())?;
+        if m.result.is_some() {
+            played_matches = played_matches.checked_add(Uint128::one())?;
+        } else if current_round.is_none() {
+            current_round = Some(round_number);
+        }
+    }
+
+    Ok(LeagueProgress {
+        total_matches,
+        played_matches,
+        remaining_matches: total_matches.checked_sub(played_matches)?,
+        current_round: current_round.map(Uint64::from).unwrap_or(total_rounds),
+        total_rounds,
+    })
 }
 
 pub fn round(deps: Deps, league_id: Uint128, round_number: Uint64) -> StdResult<RoundResponse> {
@@ -16,6 +16,7 @@ pub struct Match {
     pub team_1: Addr,
     pub team_2: Addr,
     pub result: Option<Result>,
+    pub bonus: Option<MatchBonus>,
 }
 
 #[cw_serde]
@@ -25,6 +26,13 @@ pub enum Result {
     Draw,
 }
 
+/// Extra points awarded on top of the match result, such as a bonus point for a close loss
+#[cw_serde]
+pub struct MatchBonus {
+    pub team_1: Uint128,
+    pub team_2: Uint128,
+}
+
 #[cw_serde]
 pub struct Round {
     pub round_number: Uint64,
@@ -55,7 +63,13 @@ pub struct RoundResponse {
     pub expiration: Expiration,
 }
 
+/// The validated teams of each league, in the order they were provided
+pub const LEAGUE_TEAMS: Map<u128, Vec<Addr>> = Map::new("league_teams");
+/// The number of times added rounds have pushed out each league's expiration
+pub const EXPIRATION_EXTENSIONS: Map<u128, u32> = Map::new("expiration_extensions");
 /// (League Id, Round Number)
 pub const ROUNDS: Map<(u128, u64), Round> = Map::new("rounds");
 /// (League Id, Round Number, Match Number)
 pub const MATCHES: Map<(u128, u64, u128), Match> = Map::new("matches");
+/// (League Id, Round Number, Match Number)
+pub const MATCH_EVIDENCE: Map<(u128, u64, u128), String> = Map::new("match_evidence");
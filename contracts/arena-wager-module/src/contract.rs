@@ -2,13 +2,21 @@
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{Binary, Deps, DepsMut, Empty, Env, MessageInfo, Reply, Response, StdResult};
 use cw2::set_contract_version;
+use cw_competition::msg::ExecuteBase;
 use cw_competition_base::{contract::CompetitionModuleContract, error::CompetitionError};
 
-use crate::msg::{EmptyWrapper, ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg};
+use crate::{
+    execute,
+    msg::{
+        CompetitionExt, CompetitionInstantiateExt, ExecuteExt, ExecuteMsg, InstantiateMsg,
+        MigrateMsg, QueryMsg,
+    },
+};
 
 pub(crate) const CONTRACT_NAME: &str = "crates.io:arena-wager-module";
 pub(crate) const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
-pub type CompetitionModule = CompetitionModuleContract<Empty, Empty, Empty, Empty, EmptyWrapper>;
+pub type CompetitionModule =
+    CompetitionModuleContract<Empty, ExecuteExt, Empty, CompetitionExt, CompetitionInstantiateExt>;
 
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
@@ -29,7 +37,16 @@ pub fn execute(
     info: MessageInfo,
     msg: ExecuteMsg,
 ) -> Result<Response, CompetitionError> {
-    CompetitionModule::default().execute(deps, env, info, msg)
+    match msg {
+        ExecuteBase::Extension { msg } => match msg {
+            ExecuteExt::ProcessCoWinners {
+                competition_id,
+                winners,
+                evidence,
+            } => execute::process_co_winners(deps, info, competition_id, winners, evidence),
+        },
+        _ => CompetitionModule::default().execute(deps, env, info, msg),
+    }
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
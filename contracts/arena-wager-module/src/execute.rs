@@ -0,0 +1,60 @@
+use cosmwasm_std::{Decimal, DepsMut, MessageInfo, Response, StdError, Uint128};
+use cw_balance::{Distribution, MemberPercentage};
+use cw_competition_base::error::CompetitionError;
+
+use crate::contract::CompetitionModule;
+
+pub fn process_co_winners(
+    deps: DepsMut,
+    info: MessageInfo,
+    competition_id: Uint128,
+    winners: Vec<String>,
+    evidence: Option<String>,
+) -> Result<Response, CompetitionError> {
+    if winners.len() < 2 {
+        return Err(CompetitionError::StdError(StdError::GenericErr {
+            msg: "At least 2 co-winners should be provided".to_string(),
+        }));
+    }
+
+    // Each co-winner receives an equal share, with the last absorbing the rounding
+    let share = Decimal::from_ratio(1u128, winners.len() as u128);
+    let last_share = Decimal::one()
+        .checked_sub(share.checked_mul(Decimal::from_ratio(winners.len() as u128 - 1, 1u128))?)?;
+    let member_percentages = winners
+        .iter()
+        .enumerate()
+        .map(|(i, addr)| MemberPercentage {
+            addr: addr.clone(),
+            percentage: if i == winners.len() - 1 {
+                last_share
+            } else {
+                share
+            },
+        })
+        .collect();
+
+    // The dust goes to a neutral address, so no co-winner is paid more than the others
+    let wager = CompetitionModule::default()
+        .competitions
+        .load(deps.storage, competition_id.u128())?;
+    let remainder_addr = wager.extension.remainder_addr.unwrap_or(wager.admin_dao);
+
+    // Uniqueness and addresses are validated when the distribution is checked
+    let distribution = Distribution {
+        member_percentages,
+        remainder_addr: remainder_addr.to_string(),
+    };
+
+    let response = CompetitionModule::default().execute_process_competition(
+        deps,
+        info,
+        competition_id,
+        Some(distribution),
+        None,
+        None,
+        evidence,
+    )?;
+
+    Ok(response.add_attribute("co_winners", winners.len().to_string()))
+}
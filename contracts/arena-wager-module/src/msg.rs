@@ -1,5 +1,5 @@
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::Empty;
+use cosmwasm_std::{Addr, Empty, StdResult, Uint128};
 use cw_competition::{
     msg::{ExecuteBase, InstantiateBase, IntoCompetitionExt, QueryBase},
     state::{Competition, CompetitionResponse},
@@ -11,26 +11,42 @@ pub enum MigrateMsg {
 }
 
 pub type InstantiateMsg = InstantiateBase<Empty>;
-pub type ExecuteMsg = ExecuteBase<Empty, EmptyWrapper>;
-pub type QueryMsg = QueryBase<Empty, Empty, Empty>;
-pub type Wager = Competition<Empty>;
-pub type WagerResponse = CompetitionResponse<Empty>;
+pub type ExecuteMsg = ExecuteBase<ExecuteExt, CompetitionInstantiateExt>;
+pub type QueryMsg = QueryBase<Empty, Empty, CompetitionExt>;
+pub type Wager = Competition<CompetitionExt>;
+pub type WagerResponse = CompetitionResponse<CompetitionExt>;
 
 #[cw_serde]
-pub struct EmptyWrapper(Empty);
-impl EmptyWrapper {
-    pub fn new() -> Self {
-        EmptyWrapper(Empty {})
-    }
+pub enum ExecuteExt {
+    /// Processes the wager with the pool split equally between the co-winners
+    /// Unlike a draw, the split is a result and is taxed like any other distribution
+    /// The rounding dust goes to the wager's remainder address rather than a co-winner
+    ProcessCoWinners {
+        competition_id: Uint128,
+        winners: Vec<String>,
+        evidence: Option<String>,
+    },
 }
-impl Default for EmptyWrapper {
-    fn default() -> Self {
-        Self::new()
-    }
+
+#[cw_serde]
+#[derive(Default)]
+pub struct CompetitionInstantiateExt {
+    /// Receives the rounding dust of a co-winner split, defaulting to the admin DAO
+    pub remainder_addr: Option<String>,
+}
+
+#[cw_serde]
+pub struct CompetitionExt {
+    pub remainder_addr: Option<Addr>,
 }
 
-impl IntoCompetitionExt<Empty> for EmptyWrapper {
-    fn into_competition_ext(self, _deps: cosmwasm_std::Deps) -> cosmwasm_std::StdResult<Empty> {
-        Ok(Empty {})
+impl IntoCompetitionExt<CompetitionExt> for CompetitionInstantiateExt {
+    fn into_competition_ext(self, deps: cosmwasm_std::Deps) -> StdResult<CompetitionExt> {
+        Ok(CompetitionExt {
+            remainder_addr: self
+                .remainder_addr
+                .map(|x| deps.api.addr_validate(&x))
+                .transpose()?,
+        })
     }
 }
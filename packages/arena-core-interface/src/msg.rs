@@ -1,7 +1,7 @@
 use cosmwasm_schema::{cw_serde, QueryResponses};
-use cosmwasm_std::{Binary, Decimal, Uint128};
+use cosmwasm_std::{Addr, Binary, Coin, Decimal, HexBinary, Uint128};
 use cw_address_like::AddressLike;
-use cw_balance::Distribution;
+use cw_balance::{Distribution, MemberShare};
 use dao_interface::state::ModuleInstantiateInfo;
 use dao_pre_propose_base::{
     msg::{ExecuteMsg as ExecuteBase, InstantiateMsg as InstantiateBase, QueryMsg as QueryBase},
@@ -23,17 +23,49 @@ pub enum ExecuteExt {
         to_add: Vec<ModuleInstantiateInfo>,
         to_disable: Vec<String>,
     },
+    /// Updates the tax from the next block
+    /// An optional schedule of (height, tax) steps takes effect at each height, replacing any pending steps
     UpdateTax {
         tax: Decimal,
+        schedule: Option<Vec<(u64, Decimal)>>,
     },
     UpdateRulesets {
         to_add: Vec<NewRuleset>,
         to_disable: Vec<Uint128>,
+        /// Rejects new rulesets whose rules and description match an existing ruleset
+        reject_duplicates: Option<bool>,
     },
     UpdateCategories {
         to_add: Vec<NewCompetitionCategory>,
         to_edit: Vec<EditCompetitionCategory>,
     },
+    UpdateTaxExemptions {
+        to_add: Vec<String>,
+        to_remove: Vec<String>,
+    },
+    /// Replaces the asset's tiers of (minimum amount, tax) used to tax competitions by size
+    /// The asset is a native denom or a cw20 address, as amounts of different assets aren't comparable
+    /// Tiers take precedence over the tax schedule and the base tax for competitions reaching their minimum
+    UpdateTaxTiers {
+        asset: String,
+        tiers: Vec<(Uint128, Decimal)>,
+    },
+    /// Distributes each escrow by its members' shares, leaving rounding to the remainder address
+    /// Each escrow's competition is processed by its module, so the arena tax still applies
+    /// Every escrow is processed in its own submessage, so one failure does not revert the others
+    BatchDistribute {
+        distributions: Vec<(String, Vec<MemberShare<String>>, String)>,
+    },
+    /// Sets the address the sender's escrow winnings are routed to
+    /// Setting the sender's own address clears the preference
+    SetPayoutAddress { addr: String },
+    /// Pauses or resumes competition creation and escrow funding across the arena
+    SetPaused { value: bool },
+    /// Updates the native denoms and cw20 addresses which new escrow dues may not use
+    UpdateBlockedAssets {
+        to_add: Vec<String>,
+        to_remove: Vec<String>,
+    },
 }
 
 #[cw_serde]
@@ -47,6 +79,9 @@ pub enum QueryExt {
     },
     #[returns(Ruleset)]
     Ruleset { id: Uint128 },
+    /// The first ruleset with the given sha256 hash of its rules and description
+    #[returns(Option<Ruleset>)]
+    RulesetByHash { hash: HexBinary },
     #[returns(Vec<Ruleset>)]
     Rulesets {
         category_id: Option<Uint128>,
@@ -54,9 +89,20 @@ pub enum QueryExt {
         limit: Option<u32>,
         include_disabled: Option<bool>,
     },
+    /// The tax is zero if the optional addr is tax exempt
+    /// If an escrow value is provided, its asset's tier with the highest minimum at or below the
+    /// amount sets the tax, with the denom holding the native denom or cw20 address
+    /// A matching tier takes precedence over the tax scheduled for the height
     #[returns(Decimal)]
-    Tax { height: Option<u64> },
-    #[returns(CompetitionModuleResponse<String>)]
+    Tax {
+        height: Option<u64>,
+        addr: Option<String>,
+        escrow_value: Option<Coin>,
+    },
+    #[returns(bool)]
+    IsTaxExempt { addr: String },
+    /// The full module, including its status and competition count, looked up by key or address
+    #[returns(Option<CompetitionModuleResponse<String>>)]
     CompetitionModule { query: CompetitionModuleQuery },
     #[returns(CompetitionCategory)]
     Category { id: Uint128 },
@@ -71,8 +117,31 @@ pub enum QueryExt {
         category_id: Option<Uint128>,
         rulesets: Vec<Uint128>,
     },
+    /// Dumps the tax, enabled competition modules, and enabled rulesets
+    /// If a height is provided, the state is resolved as of that height
     #[returns(DumpStateResponse)]
-    DumpState {},
+    DumpState { height: Option<u64> },
+    /// Sums the balances held by the active escrows of every competition module, or only the given one
+    /// Each module sums a page of its competitions by id and reports where its next page starts
+    #[returns(TotalValueLockedResponse)]
+    TotalValueLocked {
+        module: Option<String>,
+        start_after: Option<Uint128>,
+        limit: Option<u32>,
+    },
+    /// The address a member's escrow winnings are routed to, if they have set one
+    #[returns(Option<Addr>)]
+    PayoutAddress { addr: String },
+    #[returns(bool)]
+    IsPaused {},
+    #[returns(Vec<String>)]
+    BlockedAssets {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Whether the native denom or cw20 address is blocked from new escrow dues
+    #[returns(bool)]
+    IsAssetBlocked { asset: String },
 }
 
 #[cw_serde]
@@ -94,10 +163,25 @@ pub type ExecuteMsg = ExecuteBase<ProposeMessage, ExecuteExt>;
 pub type QueryMsg = QueryBase<QueryExt>;
 pub type PrePropose = PreProposeContract<InstantiateExt, ExecuteExt, QueryExt, ProposeMessage>;
 
+#[cw_serde]
+pub struct TotalValueLockedResponse {
+    pub balance: cw_balance::BalanceVerified,
+    pub modules: Vec<ModuleTotalValueLocked>,
+}
+
+#[cw_serde]
+pub struct ModuleTotalValueLocked {
+    pub module: Addr,
+    pub balance: cw_balance::BalanceVerified,
+    /// Continues the module's pagination when passed with its address, if competitions remain
+    pub next_start_after: Option<Uint128>,
+}
+
 #[cw_serde]
 pub struct DumpStateResponse {
     pub tax: Decimal,
     pub competition_modules: Vec<CompetitionModuleResponse<String>>,
+    pub rulesets: Vec<Ruleset>,
 }
 
 #[cw_serde]
@@ -113,6 +197,8 @@ pub struct NewRuleset {
     pub category_id: Option<Uint128>,
     pub rules: Vec<String>,
     pub description: String,
+    /// The native denom of the dues for competitions under this ruleset
+    pub currency: Option<String>,
 }
 
 #[cw_serde]
@@ -133,6 +219,8 @@ pub struct Ruleset {
     pub rules: Vec<String>,
     pub description: String,
     pub is_enabled: bool,
+    /// Fills in the denom of escrow dues which omit it
+    pub currency: Option<String>,
 }
 
 #[cw_serde]
@@ -147,6 +235,7 @@ pub struct ProposeMessage {
     pub id: Uint128,
     pub title: String,
     pub description: String,
+    /// The escrow is refunded to its members if no distribution is given
     pub distribution: Option<Distribution<String>>,
     pub tax_cw20_msg: Option<Binary>,
     pub tax_cw721_msg: Option<Binary>,
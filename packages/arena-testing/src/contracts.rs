@@ -13,11 +13,14 @@ pub fn arena_dao_core_contract() -> Box<dyn Contract<Empty>> {
 }
 
 pub fn arena_dao_escrow_contract() -> Box<dyn Contract<Empty>> {
-    Box::new(ContractWrapper::new(
-        arena_escrow::contract::execute,
-        arena_escrow::contract::instantiate,
-        arena_escrow::contract::query,
-    ))
+    Box::new(
+        ContractWrapper::new(
+            arena_escrow::contract::execute,
+            arena_escrow::contract::instantiate,
+            arena_escrow::contract::query,
+        )
+        .with_reply(arena_escrow::contract::reply),
+    )
 }
 
 pub fn arena_wager_module_contract() -> Box<dyn Contract<Empty>> {
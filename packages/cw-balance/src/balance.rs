@@ -10,7 +10,9 @@ use std::collections::btree_map::Entry;
 use std::collections::{BTreeMap, BTreeSet, HashMap};
 use std::fmt::{Display, Formatter, Result as FmtResult};
 
-use crate::{is_contract, BalanceError, Cw721Collection, Cw721CollectionVerified, Distribution};
+use crate::{
+    is_contract, BalanceError, Cw721Collection, Cw721CollectionVerified, Distribution, RoundingMode,
+};
 
 // Struct to hold the verified member balance
 #[cw_serde]
@@ -199,6 +201,20 @@ impl BalanceVerified {
         Ok(diff)
     }
 
+    // Method to take only the tokens of a single cw721 collection
+    pub fn cw721_collection(&self, address: &Addr) -> BalanceVerified {
+        BalanceVerified {
+            native: vec![],
+            cw20: vec![],
+            cw721: self
+                .cw721
+                .iter()
+                .filter(|x| &x.address == address)
+                .cloned()
+                .collect(),
+        }
+    }
+
     // Method to check if BalanceVerified is empty
     pub fn is_empty(&self) -> bool {
         self.native.is_empty() && self.cw20.is_empty() && self.cw721.is_empty()
@@ -427,6 +443,7 @@ impl BalanceVerified {
     }
 
     // Method to transmit all types of tokens (native, CW20, CW721) to a recipient
+    // Messages are ordered native, then CW20 by contract address, then CW721 by collection
     pub fn transmit_all(
         &self,
         deps: Deps,
@@ -442,16 +459,17 @@ impl BalanceVerified {
 
     // Method to transfer all types of tokens (native, CW20, CW721) to a recipient
     pub fn transfer_all(&self, recipient: &Addr) -> StdResult<Vec<CosmosMsg>> {
+        let balance = self.sorted();
         let mut messages: Vec<CosmosMsg> = Vec::new();
 
         // Transfer native tokens
-        messages.extend(self.send_native(recipient));
+        messages.extend(balance.send_native(recipient));
 
         // Transfer CW20 tokens
-        messages.extend(self.transfer_cw20(recipient)?);
+        messages.extend(balance.transfer_cw20(recipient)?);
 
         // Transfer CW721 tokens
-        messages.extend(self.transfer_cw721(recipient)?);
+        messages.extend(balance.transfer_cw721(recipient)?);
 
         Ok(messages)
     }
@@ -463,20 +481,31 @@ impl BalanceVerified {
         cw20_msg: Option<Binary>,
         cw721_msg: Option<Binary>,
     ) -> StdResult<Vec<CosmosMsg>> {
+        let balance = self.sorted();
         let mut messages: Vec<CosmosMsg> = Vec::new();
 
         // Send native tokens to contract
-        messages.extend(self.send_native(contract_addr));
+        messages.extend(balance.send_native(contract_addr));
 
         // Send CW20 tokens to contract
-        messages.extend(self.send_cw20(contract_addr, cw20_msg.unwrap_or_default())?);
+        messages.extend(balance.send_cw20(contract_addr, cw20_msg.unwrap_or_default())?);
 
         // Send CW721 tokens to contract
-        messages.extend(self.send_cw721(contract_addr, cw721_msg.unwrap_or_default())?);
+        messages.extend(balance.send_cw721(contract_addr, cw721_msg.unwrap_or_default())?);
 
         Ok(messages)
     }
 
+    // Orders the tokens so the emitted messages are stable regardless of how the balance was built
+    fn sorted(&self) -> BalanceVerified {
+        let mut balance = self.clone();
+        balance.native.sort_by(|x, y| x.denom.cmp(&y.denom));
+        balance.cw20.sort_by(|x, y| x.address.cmp(&y.address));
+        balance.cw721.sort_by(|x, y| x.address.cmp(&y.address));
+
+        balance
+    }
+
     // Method to send native tokens to an address
     pub fn send_native(&self, address: &Addr) -> Vec<CosmosMsg> {
         if self.native.is_empty() {
@@ -574,9 +603,34 @@ impl BalanceVerified {
     pub fn split(
         &self,
         distribution: &Distribution<Addr>,
+    ) -> Result<Vec<MemberBalanceChecked>, BalanceError> {
+        self.split_with_rounding(distribution, &RoundingMode::Truncate)
+    }
+
+    // Method to split the balance, resolving fractional amounts with the given rounding mode
+    pub fn split_with_rounding(
+        &self,
+        distribution: &Distribution<Addr>,
+        rounding: &RoundingMode,
     ) -> Result<Vec<MemberBalanceChecked>, BalanceError> {
         let mut split_balances: Vec<MemberBalanceChecked> = Vec::new();
 
+        let percentages: Vec<Decimal> = distribution
+            .member_percentages
+            .iter()
+            .map(|x| x.percentage)
+            .collect();
+        let native_amounts = self
+            .native
+            .iter()
+            .map(|x| apportion(x.amount, &percentages, rounding))
+            .collect::<Result<Vec<_>, _>>()?;
+        let cw20_amounts = self
+            .cw20
+            .iter()
+            .map(|x| apportion(x.amount, &percentages, rounding))
+            .collect::<Result<Vec<_>, _>>()?;
+
         let mut remainders_native: BTreeMap<String, Uint128> = self
             .native
             .iter()
@@ -588,14 +642,10 @@ impl BalanceVerified {
             .map(|x| (x.address.clone(), x.amount))
             .collect();
 
-        for member_percentage in &distribution.member_percentages {
+        for (i, member_percentage) in distribution.member_percentages.iter().enumerate() {
             let mut split_native = BTreeMap::new();
-            for coin in &self.native {
-                let decimal_amount = Decimal::from_atomics(coin.amount, 0u32)?;
-                let split_amount = member_percentage
-                    .percentage
-                    .checked_mul(decimal_amount)?
-                    .to_uint_floor();
+            for (j, coin) in self.native.iter().enumerate() {
+                let split_amount = native_amounts[j][i];
 
                 // Deduct the split amount from the remainder
                 if let Some(remainder) = remainders_native.get_mut(&coin.denom) {
@@ -606,12 +656,8 @@ impl BalanceVerified {
             }
 
             let mut split_cw20 = BTreeMap::new();
-            for cw20_coin in &self.cw20 {
-                let decimal_amount = Decimal::from_atomics(cw20_coin.amount, 0u32)?;
-                let split_amount = member_percentage
-                    .percentage
-                    .checked_mul(decimal_amount)?
-                    .to_uint_floor();
+            for (j, cw20_coin) in self.cw20.iter().enumerate() {
+                let split_amount = cw20_amounts[j][i];
 
                 // Deduct the split amount from the remainder
                 if let Some(remainder) = remainders_cw20.get_mut(&cw20_coin.address) {
@@ -670,4 +716,221 @@ impl BalanceVerified {
 
         Ok(split_balances)
     }
+
+    // Method to assign each NFT to a member drawn from the seed, weighted by their share
+    // NFTs go to the remainder address if no member has a share
+    pub fn assign_cw721(
+        &self,
+        distribution: &Distribution<Addr>,
+        seed: u64,
+    ) -> Result<Vec<MemberBalanceChecked>, BalanceError> {
+        let weights: Vec<u128> = distribution
+            .member_percentages
+            .iter()
+            .map(|x| x.percentage.atomics().u128())
+            .collect();
+        let total_weight = weights
+            .iter()
+            .try_fold(0u128, |accumulator, x| accumulator.checked_add(*x))
+            .ok_or(BalanceError::ShareTotalOverflow {})?;
+
+        // The last slot holds the remainder address
+        let mut assignments: Vec<BTreeMap<Addr, Vec<String>>> =
+            vec![BTreeMap::new(); weights.len() + 1];
+        let mut state = seed;
+        for collection in &self.cw721 {
+            for token_id in &collection.token_ids {
+                let slot = if total_weight == 0 {
+                    weights.len()
+                } else {
+                    let draw = (((next_random(&mut state) as u128) << 64)
+                        | next_random(&mut state) as u128)
+                        % total_weight;
+
+                    let mut cumulative = 0u128;
+                    weights
+                        .iter()
+                        .position(|weight| {
+                            cumulative += weight;
+                            draw < cumulative
+                        })
+                        .unwrap_or(weights.len())
+                };
+
+                assignments[slot]
+                    .entry(collection.address.clone())
+                    .or_default()
+                    .push(token_id.clone());
+            }
+        }
+
+        Ok(collect_cw721_assignments(distribution, assignments))
+    }
+
+    // Method to deal the NFTs out round-robin, capping each member at their truncated share of the count
+    // Members are dealt in the distribution's order, and NFTs left once every member is capped go to the remainder address
+    // For example 5 NFTs across 60% and 40% shares deal 1, 3 and 5 to the first member and 2 and 4 to the second
+    pub fn apportion_cw721(
+        &self,
+        distribution: &Distribution<Addr>,
+    ) -> Result<Vec<MemberBalanceChecked>, BalanceError> {
+        let percentages: Vec<Decimal> = distribution
+            .member_percentages
+            .iter()
+            .map(|x| x.percentage)
+            .collect();
+        let count = self
+            .cw721
+            .iter()
+            .map(|x| x.token_ids.len() as u128)
+            .sum::<u128>();
+        let quotas = apportion(Uint128::new(count), &percentages, &RoundingMode::Truncate)?;
+
+        // Each turn deals one NFT to every member whose quota is not yet filled
+        let max_quota = quotas.iter().max().copied().unwrap_or_default().u128();
+        let turns = (0..max_quota).flat_map(|turn| {
+            quotas
+                .iter()
+                .enumerate()
+                .filter(move |(_, quota)| quota.u128() > turn)
+                .map(|(slot, _)| slot)
+        });
+
+        // The last slot holds the remainder address
+        let mut assignments: Vec<BTreeMap<Addr, Vec<String>>> =
+            vec![BTreeMap::new(); quotas.len() + 1];
+        let tokens = self.cw721.iter().flat_map(|collection| {
+            collection
+                .token_ids
+                .iter()
+                .map(move |token_id| (&collection.address, token_id))
+        });
+        for ((address, token_id), slot) in tokens.zip(turns.chain(std::iter::repeat(quotas.len())))
+        {
+            assignments[slot]
+                .entry(address.clone())
+                .or_default()
+                .push(token_id.clone());
+        }
+
+        Ok(collect_cw721_assignments(distribution, assignments))
+    }
+
+    // Method to calculate what a truncating split leaves for the remainder address
+    pub fn remainder(
+        &self,
+        distribution: &Distribution<Addr>,
+    ) -> Result<BalanceVerified, BalanceError> {
+        let percentages: Vec<Decimal> = distribution
+            .member_percentages
+            .iter()
+            .map(|x| x.percentage)
+            .collect();
+        let unallocated = |amount: Uint128| -> Result<Uint128, BalanceError> {
+            let allocated = apportion(amount, &percentages, &RoundingMode::Truncate)?
+                .into_iter()
+                .try_fold(Uint128::zero(), |accumulator, x| accumulator.checked_add(x))?;
+
+            Ok(amount.checked_sub(allocated)?)
+        };
+
+        let mut native = vec![];
+        for coin in self.native.iter() {
+            let amount = unallocated(coin.amount)?;
+            if !amount.is_zero() {
+                native.push(Coin {
+                    denom: coin.denom.clone(),
+                    amount,
+                });
+            }
+        }
+        let mut cw20 = vec![];
+        for cw20_coin in self.cw20.iter() {
+            let amount = unallocated(cw20_coin.amount)?;
+            if !amount.is_zero() {
+                cw20.push(Cw20CoinVerified {
+                    address: cw20_coin.address.clone(),
+                    amount,
+                });
+            }
+        }
+
+        Ok(BalanceVerified {
+            native,
+            cw20,
+            cw721: self.cw721.clone(),
+        })
+    }
+}
+
+// Advances a SplitMix64 generator, so draws are reproducible from the seed
+fn next_random(state: &mut u64) -> u64 {
+    *state = state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    let mut z = *state;
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+// Pairs each slot of assigned NFTs with its member, the last slot being the remainder address
+fn collect_cw721_assignments(
+    distribution: &Distribution<Addr>,
+    assignments: Vec<BTreeMap<Addr, Vec<String>>>,
+) -> Vec<MemberBalanceChecked> {
+    distribution
+        .member_percentages
+        .iter()
+        .map(|x| &x.addr)
+        .chain(std::iter::once(&distribution.remainder_addr))
+        .zip(assignments)
+        .filter(|(_, collections)| !collections.is_empty())
+        .map(|(addr, collections)| MemberBalanceChecked {
+            addr: addr.clone(),
+            balance: BalanceVerified {
+                native: vec![],
+                cw20: vec![],
+                cw721: collections
+                    .into_iter()
+                    .map(|(address, token_ids)| Cw721CollectionVerified { address, token_ids })
+                    .collect(),
+            },
+        })
+        .collect()
+}
+
+// Apportions an amount by percentages, leaving anything unallocated for the remainder address
+fn apportion(
+    amount: Uint128,
+    percentages: &[Decimal],
+    rounding: &RoundingMode,
+) -> Result<Vec<Uint128>, BalanceError> {
+    let decimal_amount = Decimal::from_atomics(amount, 0u32)?;
+    let exact_amounts = percentages
+        .iter()
+        .map(|x| x.checked_mul(decimal_amount))
+        .collect::<Result<Vec<Decimal>, _>>()?;
+    let mut amounts: Vec<Uint128> = exact_amounts.iter().map(|x| x.to_uint_floor()).collect();
+
+    if *rounding == RoundingMode::LargestRemainder {
+        let total = exact_amounts
+            .iter()
+            .try_fold(Decimal::zero(), |accumulator, x| {
+                accumulator.checked_add(*x)
+            })?
+            .to_uint_floor();
+        let allocated = amounts.iter().try_fold(Uint128::zero(), |accumulator, x| {
+            accumulator.checked_add(*x)
+        })?;
+        // Each share loses less than one unit, so the leftover never exceeds the share count
+        let leftover = total.checked_sub(allocated)?.u128() as usize;
+
+        // Ties keep the distribution's order
+        let mut order: Vec<usize> = (0..amounts.len()).collect();
+        order.sort_by_key(|x| std::cmp::Reverse(exact_amounts[*x] - exact_amounts[*x].floor()));
+        for i in order.into_iter().take(leftover) {
+            amounts[i] = amounts[i].checked_add(Uint128::one())?;
+        }
+    }
+
+    Ok(amounts)
 }
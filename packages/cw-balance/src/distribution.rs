@@ -1,11 +1,13 @@
 use std::fmt::Display;
 
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{to_json_string, Addr, Decimal, Deps, StdError, StdResult};
+use cosmwasm_std::{to_json_string, Addr, Decimal, Deps, StdError, StdResult, Uint128, Uint64};
 use cw_address_like::AddressLike;
 use itertools::Itertools;
 use serde::Serialize;
 
+use crate::BalanceError;
+
 #[cw_serde]
 pub struct MemberPercentage<T: AddressLike> {
     pub addr: T,
@@ -15,26 +17,108 @@ pub struct MemberPercentage<T: AddressLike> {
 impl MemberPercentage<String> {
     pub fn into_checked(&self, deps: Deps) -> StdResult<MemberPercentage<Addr>> {
         Ok(MemberPercentage {
-            addr: deps.api.addr_validate(&self.addr)?,
+            addr: validate_recipient(deps, &self.addr)?,
             percentage: self.percentage,
         })
     }
 }
 
+#[cw_serde]
+pub struct MemberShare<T: AddressLike> {
+    pub addr: T,
+    pub shares: Uint128,
+}
+
+impl<T: AddressLike> MemberShare<T> {
+    /// Normalizes integer shares into percentages summing to exactly 1
+    /// The last member absorbs any rounding remainder
+    pub fn to_percentages(
+        shares: Vec<MemberShare<T>>,
+    ) -> Result<Vec<MemberPercentage<T>>, BalanceError> {
+        let total_shares = shares
+            .iter()
+            .try_fold(Uint128::zero(), |accumulator, x| {
+                accumulator.checked_add(x.shares)
+            })
+            .map_err(|_| BalanceError::ShareTotalOverflow {})?;
+
+        if total_shares.is_zero() {
+            return Err(BalanceError::StdError(StdError::generic_err(
+                "Total shares must be greater than 0",
+            )));
+        }
+
+        let last_index = shares.len() - 1;
+        let mut remaining = Decimal::one();
+        let mut member_percentages = Vec::with_capacity(shares.len());
+        for (i, member_share) in shares.into_iter().enumerate() {
+            let percentage = if i == last_index {
+                remaining
+            } else {
+                Decimal::checked_from_ratio(member_share.shares, total_shares)?
+            };
+            remaining = remaining.checked_sub(percentage)?;
+
+            member_percentages.push(MemberPercentage {
+                addr: member_share.addr,
+                percentage,
+            });
+        }
+
+        Ok(member_percentages)
+    }
+}
+
+/// How fractional token amounts are resolved when splitting a balance
+#[cw_serde]
+#[derive(Default)]
+pub enum RoundingMode {
+    /// Each share is floored and the leftover goes to the remainder address
+    #[default]
+    Truncate,
+    /// The units lost to flooring go to the shares with the largest fractional parts
+    LargestRemainder,
+}
+
+/// How held NFTs are assigned when splitting a balance
+#[cw_serde]
+#[derive(Default)]
+pub enum NftMode {
+    /// Every NFT goes to the remainder address
+    #[default]
+    Remainder,
+    /// Each NFT goes to a member drawn from the seed, weighted by their share
+    /// The same seed always reproduces the same assignment
+    RandomAssign { seed: Uint64 },
+    /// NFTs are dealt round-robin, each member receiving at most their share of the count rounded down
+    /// Any NFTs left over go to the remainder address
+    RoundRobin,
+}
+
 #[cw_serde]
 pub struct Distribution<T: AddressLike> {
     pub member_percentages: Vec<MemberPercentage<T>>,
     pub remainder_addr: T,
 }
 
-impl Distribution<String> {
-    pub fn into_checked(&self, deps: Deps) -> StdResult<Distribution<Addr>> {
-        let total_weight = self
-            .member_percentages
+impl<T: AddressLike> Distribution<T> {
+    /// Sums the member percentages, failing if the total exceeds the decimal range
+    pub fn total_weight(&self) -> Result<Decimal, BalanceError> {
+        self.member_percentages
             .iter()
             .try_fold(Decimal::zero(), |accumulator, x| {
                 accumulator.checked_add(x.percentage)
-            })?;
+            })
+            .map_err(|_| BalanceError::ShareTotalOverflow {})
+    }
+}
+
+impl Distribution<String> {
+    /// Checks the shares without validating the addresses
+    pub fn validate_shares(&self) -> StdResult<()> {
+        let total_weight = self
+            .total_weight()
+            .map_err(|e| StdError::generic_err(e.to_string()))?;
 
         if total_weight != Decimal::one() {
             return Err(StdError::generic_err("Total weight is not equal to 1"));
@@ -54,17 +138,30 @@ impl Distribution<String> {
             return Err(StdError::generic_err("All members must be unique"));
         }
 
+        Ok(())
+    }
+
+    pub fn into_checked(&self, deps: Deps) -> StdResult<Distribution<Addr>> {
+        self.validate_shares()?;
+
         Ok(Distribution::<Addr> {
             member_percentages: self
                 .member_percentages
                 .iter()
                 .map(|x| x.into_checked(deps))
                 .collect::<StdResult<_>>()?,
-            remainder_addr: deps.api.addr_validate(&self.remainder_addr)?,
+            remainder_addr: validate_recipient(deps, &self.remainder_addr)?,
         })
     }
 }
 
+// Names the offending address so a bad recipient is easy to find in a long distribution
+fn validate_recipient(deps: Deps, addr: &str) -> StdResult<Addr> {
+    deps.api.addr_validate(addr).map_err(|e| {
+        StdError::generic_err(format!("Invalid distribution recipient {}: {}", addr, e))
+    })
+}
+
 impl<T: AddressLike + Serialize> Display for Distribution<T> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match to_json_string(self) {
@@ -7,7 +7,7 @@ mod util;
 pub use balance::{
     BalanceUnchecked, BalanceVerified, MemberBalanceChecked, MemberBalanceUnchecked,
 };
-pub use distribution::{Distribution, MemberPercentage};
+pub use distribution::{Distribution, MemberPercentage, MemberShare, NftMode, RoundingMode};
 pub use error::BalanceError;
 pub use tokens::{Cw721Collection, Cw721CollectionVerified};
 pub use util::is_contract;
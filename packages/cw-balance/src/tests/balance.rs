@@ -1,5 +1,6 @@
-use cosmwasm_std::{Addr, Coin, Uint128};
-use cw20::Cw20CoinVerified;
+use cosmwasm_std::{to_json_binary, Addr, BankMsg, Coin, CosmosMsg, Uint128, WasmMsg};
+use cw20::{Cw20CoinVerified, Cw20ExecuteMsg};
+use cw721::Cw721ExecuteMsg;
 
 use crate::{BalanceVerified, Cw721CollectionVerified};
 
@@ -72,3 +73,90 @@ fn test_difference_balances() {
     let diff_balance = balance_a.difference(&balance_b).unwrap();
     assert!(diff_balance.is_empty());
 }
+
+#[test]
+fn test_transfer_all_ordering() {
+    let recipient = Addr::unchecked("recipient");
+
+    // The tokens are out of order within each kind
+    let balance = BalanceVerified {
+        native: vec![
+            Coin {
+                denom: "token2".to_string(),
+                amount: Uint128::from(20u64),
+            },
+            Coin {
+                denom: "token1".to_string(),
+                amount: Uint128::from(10u64),
+            },
+        ],
+        cw20: vec![
+            Cw20CoinVerified {
+                address: Addr::unchecked("cw20b"),
+                amount: Uint128::from(200u64),
+            },
+            Cw20CoinVerified {
+                address: Addr::unchecked("cw20a"),
+                amount: Uint128::from(100u64),
+            },
+        ],
+        cw721: vec![
+            Cw721CollectionVerified {
+                address: Addr::unchecked("cw721b"),
+                token_ids: vec!["1".to_string()],
+            },
+            Cw721CollectionVerified {
+                address: Addr::unchecked("cw721a"),
+                token_ids: vec!["2".to_string(), "3".to_string()],
+            },
+        ],
+    };
+
+    let cw20_transfer = |address: &str, amount: u64| {
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: address.to_string(),
+            msg: to_json_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: recipient.to_string(),
+                amount: Uint128::from(amount),
+            })
+            .unwrap(),
+            funds: vec![],
+        })
+    };
+    let cw721_transfer = |address: &str, token_id: &str| {
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: address.to_string(),
+            msg: to_json_binary(&Cw721ExecuteMsg::TransferNft {
+                recipient: recipient.to_string(),
+                token_id: token_id.to_string(),
+            })
+            .unwrap(),
+            funds: vec![],
+        })
+    };
+
+    // Native first, then cw20 by contract address, then cw721 by collection
+    assert_eq!(
+        balance.transfer_all(&recipient).unwrap(),
+        vec![
+            CosmosMsg::Bank(BankMsg::Send {
+                to_address: recipient.to_string(),
+                amount: vec![
+                    Coin {
+                        denom: "token1".to_string(),
+                        amount: Uint128::from(10u64),
+                    },
+                    Coin {
+                        denom: "token2".to_string(),
+                        amount: Uint128::from(20u64),
+                    },
+                ],
+            }),
+            cw20_transfer("cw20a", 100),
+            cw20_transfer("cw20b", 200),
+            cw721_transfer("cw721a", "2"),
+            cw721_transfer("cw721a", "3"),
+            cw721_transfer("cw721b", "1"),
+        ]
+    );
+}
@@ -2,4 +2,5 @@ mod balance;
 mod cw20;
 mod cw721;
 mod native;
+mod shares;
 mod split;
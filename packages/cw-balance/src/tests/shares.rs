@@ -0,0 +1,101 @@
+use std::str::FromStr;
+
+use cosmwasm_std::{Addr, Decimal, Uint128};
+
+use crate::{BalanceError, Distribution, MemberPercentage, MemberShare};
+
+fn to_member_shares(shares: &[u128]) -> Vec<MemberShare<Addr>> {
+    shares
+        .iter()
+        .enumerate()
+        .map(|(i, x)| MemberShare {
+            addr: Addr::unchecked(format!("addr_{}", i)),
+            shares: Uint128::from(*x),
+        })
+        .collect()
+}
+
+fn total(percentages: &[MemberPercentage<Addr>]) -> Decimal {
+    percentages
+        .iter()
+        .fold(Decimal::zero(), |accumulator, x| accumulator + x.percentage)
+}
+
+#[test]
+fn test_to_percentages() {
+    let percentages = MemberShare::to_percentages(to_member_shares(&[1, 1, 1])).unwrap();
+
+    assert_eq!(
+        percentages.iter().map(|x| x.percentage).collect::<Vec<_>>(),
+        vec![
+            Decimal::from_ratio(1u128, 3u128),
+            Decimal::from_ratio(1u128, 3u128),
+            Decimal::from_str("0.333333333333333334").unwrap(),
+        ]
+    );
+    assert_eq!(percentages[2].addr, Addr::unchecked("addr_2"));
+    assert_eq!(total(&percentages), Decimal::one());
+}
+
+#[test]
+fn test_to_percentages_invalid() {
+    assert!(MemberShare::<Addr>::to_percentages(vec![]).is_err());
+    assert!(MemberShare::to_percentages(to_member_shares(&[0, 0])).is_err());
+    assert!(MemberShare::to_percentages(to_member_shares(&[u128::MAX, 1])).is_err());
+}
+
+#[test]
+fn test_share_total_overflow() {
+    assert_eq!(
+        MemberShare::to_percentages(to_member_shares(&[u128::MAX - 1, 1, 1])).unwrap_err(),
+        BalanceError::ShareTotalOverflow {}
+    );
+
+    let distribution = Distribution::<String> {
+        member_percentages: vec![
+            MemberPercentage {
+                addr: "addr_0".to_string(),
+                percentage: Decimal::MAX,
+            },
+            MemberPercentage {
+                addr: "addr_1".to_string(),
+                percentage: Decimal::one(),
+            },
+        ],
+        remainder_addr: "addr_0".to_string(),
+    };
+    assert_eq!(
+        distribution.total_weight().unwrap_err(),
+        BalanceError::ShareTotalOverflow {}
+    );
+    assert!(distribution.validate_shares().is_err());
+}
+
+#[test]
+fn test_to_percentages_sum_to_one() {
+    // Sweep share sets generated from a simple linear congruential generator
+    let mut seed = 12345u128;
+    let mut next = || {
+        seed = (seed * 1103515245 + 12345) % (1 << 31);
+        seed
+    };
+
+    for _ in 0..500 {
+        let len = (next() % 20 + 1) as usize;
+        let shares: Vec<u128> = (0..len).map(|_| next() % 1_000_000 + 1).collect();
+
+        let percentages = MemberShare::to_percentages(to_member_shares(&shares)).unwrap();
+
+        assert_eq!(percentages.len(), len);
+        assert_eq!(total(&percentages), Decimal::one());
+
+        // Every member except the last receives the floored ratio of their shares
+        let total_shares: u128 = shares.iter().sum();
+        for (percentage, share) in percentages.iter().zip(&shares).take(len - 1) {
+            assert_eq!(
+                percentage.percentage,
+                Decimal::from_ratio(*share, total_shares)
+            );
+        }
+    }
+}
@@ -1,7 +1,10 @@
 use cosmwasm_std::{Addr, Coin, Decimal, Uint128};
 use cw20::Cw20CoinVerified;
 
-use crate::{BalanceVerified, Cw721CollectionVerified, Distribution, MemberPercentage};
+use crate::{
+    BalanceVerified, Cw721CollectionVerified, Distribution, MemberBalanceChecked, MemberPercentage,
+    RoundingMode,
+};
 
 #[test]
 fn test_split_balances() {
@@ -201,3 +204,271 @@ fn test_split_balances_with_remainder_as_member_share() {
     assert_eq!(split_result[2].balance.native[0].amount.u128(), 34u128);
     assert_eq!(split_result[2].balance.cw721[0].token_ids, vec!["1"]);
 }
+
+#[test]
+fn test_split_balances_largest_remainder() {
+    let addr_a = Addr::unchecked("addr_a");
+    let addr_b = Addr::unchecked("addr_b");
+    let addr_c = Addr::unchecked("addr_c");
+    let addr_d = Addr::unchecked("addr_d");
+
+    // Neither pool divides evenly among the members
+    let balance = BalanceVerified {
+        native: vec![Coin {
+            denom: "native1".to_string(),
+            amount: Uint128::from(10u128),
+        }],
+        cw20: vec![Cw20CoinVerified {
+            address: Addr::unchecked("cw20token1"),
+            amount: Uint128::from(7u128),
+        }],
+        cw721: vec![],
+    };
+
+    let distribution = Distribution::<Addr> {
+        member_percentages: vec![
+            MemberPercentage {
+                addr: addr_a.clone(),
+                percentage: Decimal::percent(46),
+            },
+            MemberPercentage {
+                addr: addr_b.clone(),
+                percentage: Decimal::percent(34),
+            },
+            MemberPercentage {
+                addr: addr_c.clone(),
+                percentage: Decimal::percent(20),
+            },
+        ],
+        remainder_addr: addr_d.clone(),
+    };
+
+    let amounts = |split: &Vec<crate::MemberBalanceChecked>, addr: &Addr| {
+        split
+            .iter()
+            .find(|x| &x.addr == addr)
+            .map(|x| (x.balance.native[0].amount, x.balance.cw20[0].amount))
+    };
+
+    // Truncation leaves the lost units to the remainder address
+    let truncated = balance
+        .split_with_rounding(&distribution, &RoundingMode::Truncate)
+        .unwrap();
+    assert_eq!(truncated, balance.split(&distribution).unwrap());
+    assert_eq!(truncated.len(), 4);
+    assert_eq!(
+        amounts(&truncated, &addr_a),
+        Some((Uint128::new(4), Uint128::new(3)))
+    );
+    assert_eq!(
+        amounts(&truncated, &addr_b),
+        Some((Uint128::new(3), Uint128::new(2)))
+    );
+    assert_eq!(
+        amounts(&truncated, &addr_c),
+        Some((Uint128::new(2), Uint128::new(1)))
+    );
+    assert_eq!(
+        amounts(&truncated, &addr_d),
+        Some((Uint128::new(1), Uint128::new(1)))
+    );
+
+    // Largest remainder hands the lost units to the largest fractional shares
+    let apportioned = balance
+        .split_with_rounding(&distribution, &RoundingMode::LargestRemainder)
+        .unwrap();
+    assert_eq!(apportioned.len(), 4);
+    assert_eq!(
+        amounts(&apportioned, &addr_a),
+        Some((Uint128::new(5), Uint128::new(3)))
+    );
+    assert_eq!(
+        amounts(&apportioned, &addr_b),
+        Some((Uint128::new(3), Uint128::new(2)))
+    );
+    assert_eq!(
+        amounts(&apportioned, &addr_c),
+        Some((Uint128::new(2), Uint128::new(2)))
+    );
+    assert_eq!(
+        amounts(&apportioned, &addr_d),
+        Some((Uint128::zero(), Uint128::zero()))
+    );
+}
+
+#[test]
+fn test_assign_cw721() {
+    let addr_a = Addr::unchecked("addr_a");
+    let addr_b = Addr::unchecked("addr_b");
+    let addr_c = Addr::unchecked("addr_c");
+    let addr_d = Addr::unchecked("addr_d");
+
+    let balance = BalanceVerified {
+        native: vec![],
+        cw20: vec![],
+        cw721: vec![
+            Cw721CollectionVerified {
+                address: Addr::unchecked("collection1"),
+                token_ids: (1..=10).map(|x| x.to_string()).collect(),
+            },
+            Cw721CollectionVerified {
+                address: Addr::unchecked("collection2"),
+                token_ids: vec!["1".to_string(), "2".to_string()],
+            },
+        ],
+    };
+
+    let distribution = Distribution::<Addr> {
+        member_percentages: vec![
+            MemberPercentage {
+                addr: addr_a.clone(),
+                percentage: Decimal::percent(60),
+            },
+            MemberPercentage {
+                addr: addr_b.clone(),
+                percentage: Decimal::percent(40),
+            },
+            MemberPercentage {
+                addr: addr_c.clone(),
+                percentage: Decimal::zero(),
+            },
+        ],
+        remainder_addr: addr_d.clone(),
+    };
+
+    // The same seed reproduces the same assignment
+    let assigned = balance.assign_cw721(&distribution, 42).unwrap();
+    assert_eq!(assigned, balance.assign_cw721(&distribution, 42).unwrap());
+
+    // Every NFT is assigned exactly once, and only to members with a share
+    let total = assigned
+        .iter()
+        .try_fold(BalanceVerified::default(), |total, x| {
+            total.checked_add(&x.balance)
+        })
+        .unwrap();
+    assert_eq!(
+        total,
+        BalanceVerified::default().checked_add(&balance).unwrap()
+    );
+    assert!(assigned
+        .iter()
+        .all(|x| x.addr == addr_a || x.addr == addr_b));
+    assert!(assigned.iter().all(|x| x.balance.native.is_empty()));
+
+    // Without any shares the NFTs go to the remainder address
+    let unweighted = Distribution::<Addr> {
+        member_percentages: vec![MemberPercentage {
+            addr: addr_a,
+            percentage: Decimal::zero(),
+        }],
+        remainder_addr: addr_d.clone(),
+    };
+    let assigned = balance.assign_cw721(&unweighted, 42).unwrap();
+    assert_eq!(assigned.len(), 1);
+    assert_eq!(assigned[0].addr, addr_d);
+    assert_eq!(assigned[0].balance, balance);
+}
+
+#[test]
+fn test_apportion_cw721() {
+    let addr_a = Addr::unchecked("addr_a");
+    let addr_b = Addr::unchecked("addr_b");
+    let addr_c = Addr::unchecked("addr_c");
+    let addr_d = Addr::unchecked("addr_d");
+    let collection = Addr::unchecked("collection1");
+
+    let nfts = |count: u32| BalanceVerified {
+        native: vec![],
+        cw20: vec![],
+        cw721: vec![Cw721CollectionVerified {
+            address: collection.clone(),
+            token_ids: (1..=count).map(|x| x.to_string()).collect(),
+        }],
+    };
+    let assigned_to = |addr: &Addr, token_ids: &[&str]| MemberBalanceChecked {
+        addr: addr.clone(),
+        balance: BalanceVerified {
+            native: vec![],
+            cw20: vec![],
+            cw721: vec![Cw721CollectionVerified {
+                address: collection.clone(),
+                token_ids: token_ids.iter().map(|x| x.to_string()).collect(),
+            }],
+        },
+    };
+
+    // 5 NFTs across 2 winners are dealt in turn up to each winner's share
+    let distribution = Distribution::<Addr> {
+        member_percentages: vec![
+            MemberPercentage {
+                addr: addr_a.clone(),
+                percentage: Decimal::percent(60),
+            },
+            MemberPercentage {
+                addr: addr_b.clone(),
+                percentage: Decimal::percent(40),
+            },
+        ],
+        remainder_addr: addr_d.clone(),
+    };
+    assert_eq!(
+        nfts(5).apportion_cw721(&distribution).unwrap(),
+        vec![
+            assigned_to(&addr_a, &["1", "3", "5"]),
+            assigned_to(&addr_b, &["2", "4"]),
+        ]
+    );
+
+    // With even shares the odd NFT out goes to the remainder address
+    let distribution = Distribution::<Addr> {
+        member_percentages: vec![
+            MemberPercentage {
+                addr: addr_a.clone(),
+                percentage: Decimal::percent(50),
+            },
+            MemberPercentage {
+                addr: addr_b.clone(),
+                percentage: Decimal::percent(50),
+            },
+        ],
+        remainder_addr: addr_d.clone(),
+    };
+    assert_eq!(
+        nfts(5).apportion_cw721(&distribution).unwrap(),
+        vec![
+            assigned_to(&addr_a, &["1", "3"]),
+            assigned_to(&addr_b, &["2", "4"]),
+            assigned_to(&addr_d, &["5"]),
+        ]
+    );
+
+    // 1 NFT across 3 winners is less than any winner's share, so the remainder address takes it
+    let distribution = Distribution::<Addr> {
+        member_percentages: vec![
+            MemberPercentage {
+                addr: addr_a,
+                percentage: Decimal::percent(50),
+            },
+            MemberPercentage {
+                addr: addr_b,
+                percentage: Decimal::percent(30),
+            },
+            MemberPercentage {
+                addr: addr_c,
+                percentage: Decimal::percent(20),
+            },
+        ],
+        remainder_addr: addr_d.clone(),
+    };
+    assert_eq!(
+        nfts(1).apportion_cw721(&distribution).unwrap(),
+        vec![assigned_to(&addr_d, &["1"])]
+    );
+
+    // The same inputs always deal the same way
+    assert_eq!(
+        nfts(7).apportion_cw721(&distribution).unwrap(),
+        nfts(7).apportion_cw721(&distribution).unwrap()
+    );
+}
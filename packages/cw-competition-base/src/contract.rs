@@ -3,24 +3,28 @@ use std::marker::PhantomData;
 use arena_core_interface::msg::{CompetitionModuleResponse, ProposeMessage};
 use cosmwasm_schema::schemars::JsonSchema;
 use cosmwasm_std::{
-    instantiate2_address, to_json_binary, Addr, Binary, CosmosMsg, Decimal, Deps, DepsMut, Empty,
-    Env, MessageInfo, Reply, Response, StdError, StdResult, SubMsg, Uint128, WasmMsg,
+    instantiate2_address, to_json_binary, Addr, Binary, Coin, CosmosMsg, Decimal, Deps, DepsMut,
+    Empty, Env, HexBinary, MessageInfo, Reply, Response, StdError, StdResult, SubMsg, Uint128,
+    WasmMsg,
 };
-use cw_balance::Distribution;
+use cw_balance::{BalanceVerified, Distribution};
 use cw_competition::{
-    escrow::{CompetitionEscrowDistributeMsg, TaxInformation},
+    escrow::{
+        accept_ownership_binary, set_competition_id_binary, set_due_denom_binary,
+        CompetitionEscrowDistributeMsg, CompetitionEscrowQueryMsg, CompetitionEscrowRosterEntry,
+        TaxInformation,
+    },
     msg::{
-        CompetitionsFilter, ExecuteBase, HookDirection, InstantiateBase, IntoCompetitionExt,
-        ModuleInfo, QueryBase,
+        result_commitment, CompetitionsFilter, ExecuteBase, HookDirection, InstantiateBase,
+        IntoCompetitionExt, ModuleInfo, QueryBase,
     },
     state::{
         Competition, CompetitionListItemResponse, CompetitionResponse, CompetitionStatus, Config,
-        Evidence,
+        EscrowAttentionReason, EscrowAttentionResponse, Evidence, TotalValueLockedResponse,
     },
 };
 use cw_ownable::{get_ownership, initialize_owner};
 use cw_storage_plus::{Bound, Index, IndexList, IndexedMap, Item, Map, MultiIndex};
-use dao_interface::state::ModuleInstantiateInfo;
 use serde::{de::DeserializeOwned, Serialize};
 
 use crate::error::CompetitionError;
@@ -61,6 +65,9 @@ pub struct CompetitionModuleContract<
     pub competition_evidence: Map<'static, (u128, u128), Evidence>,
     pub competition_evidence_count: Map<'static, u128, Uint128>,
     pub competition_result: Map<'static, u128, Option<Distribution<Addr>>>,
+    pub competition_result_evidence: Map<'static, u128, String>,
+    /// The hash of a result committed but not yet revealed
+    pub competition_result_commits: Map<'static, u128, HexBinary>,
     pub competition_rules: Map<'static, u128, Vec<String>>,
     pub escrows_to_competitions: Map<'static, Addr, u128>,
     pub temp_competition: Item<'static, u128>,
@@ -100,6 +107,8 @@ impl<
         competition_evidence_key: &'static str,
         competition_evidence_count_key: &'static str,
         competition_result_key: &'static str,
+        competition_result_evidence_key: &'static str,
+        competition_result_commits_key: &'static str,
         competition_rules_key: &'static str,
     ) -> Self {
         Self {
@@ -116,6 +125,8 @@ impl<
             competition_evidence: Map::new(competition_evidence_key),
             competition_evidence_count: Map::new(competition_evidence_count_key),
             competition_result: Map::new(competition_result_key),
+            competition_result_evidence: Map::new(competition_result_evidence_key),
+            competition_result_commits: Map::new(competition_result_commits_key),
             competition_rules: Map::new(competition_rules_key),
             instantiate_type: PhantomData,
             execute_type: PhantomData,
@@ -178,6 +189,8 @@ impl<
             "competition_evidence",
             "competition_evidence_count",
             "competition_result",
+            "competition_result_evidence",
+            "competition_result_commits",
             "competition_rules",
         )
     }
@@ -211,6 +224,7 @@ impl<
                 key: msg.key.clone(),
                 description: msg.description.clone(),
                 extension: msg.extension,
+                max_rulesets_per_competition: msg.max_rulesets_per_competition,
             },
         )?;
         let ownership = initialize_owner(deps.storage, deps.api, Some(info.sender.as_str()))?;
@@ -244,6 +258,7 @@ impl<
                 expiration,
                 rules,
                 rulesets,
+                result_authority,
                 instantiate_extension,
             } => self.execute_create_competition(
                 &mut deps,
@@ -256,6 +271,7 @@ impl<
                 expiration,
                 rules,
                 rulesets,
+                result_authority,
                 instantiate_extension,
             ),
             ExecuteBase::ProcessCompetition {
@@ -263,6 +279,7 @@ impl<
                 distribution,
                 tax_cw20_msg,
                 tax_cw721_msg,
+                evidence,
             } => self.execute_process_competition(
                 deps,
                 info,
@@ -270,6 +287,24 @@ impl<
                 distribution,
                 tax_cw20_msg,
                 tax_cw721_msg,
+                evidence,
+            ),
+            ExecuteBase::CommitResult {
+                competition_id,
+                hash,
+            } => self.execute_commit_result(deps, info, competition_id, hash),
+            ExecuteBase::RevealResult {
+                competition_id,
+                distribution,
+                nonce,
+                evidence,
+            } => self.execute_reveal_result(
+                deps,
+                info,
+                competition_id,
+                distribution,
+                nonce,
+                evidence,
             ),
             ExecuteBase::UpdateOwnership(action) => {
                 let ownership =
@@ -277,6 +312,10 @@ impl<
                 Ok(Response::new().add_attributes(ownership.into_attributes()))
             }
             ExecuteBase::Activate {} => self.execute_activate(deps, info),
+            ExecuteBase::Cancel {} => self.execute_cancel(deps, info),
+            ExecuteBase::ProcessApproved { distribution } => {
+                self.execute_process_approved(deps, info, distribution)
+            }
             ExecuteBase::SubmitEvidence {
                 competition_id: id,
                 evidence,
@@ -464,6 +503,38 @@ impl<
             .add_attribute("escrow", info.sender))
     }
 
+    pub fn execute_cancel(
+        &self,
+        deps: DepsMut,
+        info: MessageInfo,
+    ) -> Result<Response, CompetitionError> {
+        // Load competition ID associated with the escrow
+        let id = self
+            .escrows_to_competitions
+            .may_load(deps.storage, info.sender.clone())?
+            .ok_or(CompetitionError::UnknownEscrow {
+                addr: info.sender.to_string(),
+            })?;
+
+        let mut competition = self
+            .competitions
+            .may_load(deps.storage, id)?
+            .ok_or(CompetitionError::UnknownCompetitionId { id })?;
+
+        // Only competitions still waiting on their escrow are cancelled
+        let is_cancelled = competition.status == CompetitionStatus::Pending;
+        if is_cancelled {
+            competition.status = CompetitionStatus::Inactive;
+            self.competitions.save(deps.storage, id, &competition)?;
+        }
+
+        Ok(Response::new()
+            .add_attribute("id", id.to_string())
+            .add_attribute("action", "cancel")
+            .add_attribute("escrow", info.sender)
+            .add_attribute("is_cancelled", is_cancelled.to_string()))
+    }
+
     pub fn execute_jail_competition(
         &self,
         deps: DepsMut,
@@ -515,6 +586,10 @@ impl<
             Ok(competition)
         })?;
 
+        // The dao settles a jailed competition, so a result committed but never revealed is dropped
+        self.competition_result_commits
+            .remove(deps.storage, id.u128());
+
         // Construct message for the DAO owner
         let msg = CosmosMsg::Wasm(WasmMsg::Execute {
             contract_addr: arena_core.to_string(),
@@ -537,18 +612,22 @@ impl<
         env: &Env,
         category_id: Option<Uint128>,
         host: ModuleInfo,
-        escrow: Option<ModuleInstantiateInfo>,
+        escrow: Option<ModuleInfo>,
         name: String,
         description: String,
         expiration: cw_utils::Expiration,
         rules: Vec<String>,
         rulesets: Vec<Uint128>,
+        result_authority: Option<String>,
         extension: CompetitionInstantiateExt,
     ) -> Result<Response, CompetitionError> {
         if expiration.is_expired(&env.block) {
-            return Err(CompetitionError::StdError(StdError::GenericErr {
-                msg: "Cannot create an expired competition".to_string(),
-            }));
+            return Err(CompetitionError::InvalidExpiration {});
+        }
+        if let Some(max) = self.config.load(deps.storage)?.max_rulesets_per_competition {
+            if rulesets.len() > max as usize {
+                return Err(CompetitionError::TooManyRulesets { max });
+            }
         }
 
         // Ensure Module has an owner
@@ -557,6 +636,17 @@ impl<
             cw_ownable::OwnershipError::NoOwner,
         ))?;
 
+        // Ensure the arena is not paused
+        let is_paused: bool = deps.querier.query_wasm_smart(
+            arena_core.to_string(),
+            &arena_core_interface::msg::QueryMsg::QueryExtension {
+                msg: arena_core_interface::msg::QueryExt::IsPaused {},
+            },
+        )?;
+        if is_paused {
+            return Err(CompetitionError::Paused {});
+        }
+
         // Setup
         let competition_id = self
             .competition_count
@@ -566,6 +656,7 @@ impl<
         let admin_dao = self.query_dao(deps.as_ref())?;
         let mut msgs = vec![];
         let mut initial_status = CompetitionStatus::Pending;
+        let mut is_new_escrow = false;
 
         // Declare instantiate2 vars
         let salt = env.block.height.to_ne_bytes();
@@ -593,7 +684,7 @@ impl<
             ModuleInfo::Existing { addr } => deps.api.addr_validate(&addr),
         }?;
         let escrow_addr = match escrow {
-            Some(info) => {
+            Some(ModuleInfo::New { info }) => {
                 let code_info = deps.querier.query_wasm_code_info(info.code_id)?;
                 let canonical_addr =
                     instantiate2_address(&code_info.checksum, &canonical_creator, &salt)?;
@@ -608,6 +699,53 @@ impl<
                 });
 
                 let addr = deps.api.addr_humanize(&canonical_addr)?;
+                is_new_escrow = true;
+
+                self.escrows_to_competitions.save(
+                    deps.storage,
+                    addr.clone(),
+                    &competition_id.u128(),
+                )?;
+
+                Some(addr)
+            }
+            Some(ModuleInfo::Existing { addr }) => {
+                let addr = deps.api.addr_validate(&addr)?;
+
+                if self.escrows_to_competitions.has(deps.storage, addr.clone()) {
+                    return Err(CompetitionError::StdError(StdError::generic_err(
+                        "The escrow is already used by another competition",
+                    )));
+                }
+
+                // The escrow must send its activation and accept distributions from this module
+                let ownership: cw_ownable::Ownership<Addr> = deps
+                    .querier
+                    .query_wasm_smart(&addr, &CompetitionEscrowQueryMsg::Ownership {})?;
+                if ownership.pending_owner.as_ref() == Some(&env.contract.address) {
+                    msgs.push(WasmMsg::Execute {
+                        contract_addr: addr.to_string(),
+                        msg: accept_ownership_binary()?,
+                        funds: vec![],
+                    });
+                } else if ownership.owner.as_ref() != Some(&env.contract.address) {
+                    return Err(CompetitionError::EscrowNotOwned {
+                        addr: addr.to_string(),
+                    });
+                }
+                msgs.push(WasmMsg::Execute {
+                    contract_addr: addr.to_string(),
+                    msg: set_competition_id_binary(competition_id)?,
+                    funds: vec![],
+                });
+
+                // An escrow funded before it was adopted will not send an activation
+                let is_fully_funded: bool = deps
+                    .querier
+                    .query_wasm_smart(&addr, &CompetitionEscrowQueryMsg::IsFullyFunded {})?;
+                if is_fully_funded {
+                    initial_status = CompetitionStatus::Active;
+                }
 
                 self.escrows_to_competitions.save(
                     deps.storage,
@@ -623,6 +761,40 @@ impl<
             }
         };
 
+        // Validate that each ruleset exists and is enabled
+        let mut currency = None;
+        for id in &rulesets {
+            let ruleset: Option<arena_core_interface::msg::Ruleset> =
+                deps.querier.query_wasm_smart(
+                    arena_core.to_string(),
+                    &arena_core_interface::msg::QueryMsg::QueryExtension {
+                        msg: arena_core_interface::msg::QueryExt::Ruleset { id: *id },
+                    },
+                )?;
+            let ruleset = match ruleset {
+                Some(ruleset) if ruleset.is_enabled => ruleset,
+                _ => return Err(CompetitionError::InvalidRuleset { id: *id }),
+            };
+
+            if let Some(ruleset_currency) = ruleset.currency {
+                if currency.as_ref().is_some_and(|x| *x != ruleset_currency) {
+                    return Err(CompetitionError::StdError(StdError::generic_err(
+                        "The rulesets require different currencies",
+                    )));
+                }
+                currency = Some(ruleset_currency);
+            }
+        }
+
+        // A new escrow's dues inherit the ruleset currency where they omit a denom
+        if let (Some(currency), Some(escrow_addr), true) = (currency, &escrow_addr, is_new_escrow) {
+            msgs.push(WasmMsg::Execute {
+                contract_addr: escrow_addr.to_string(),
+                msg: set_due_denom_binary(currency)?,
+                funds: vec![],
+            });
+        }
+
         // Validate that category and rulesets are valid
         let result: bool = deps.querier.query_wasm_smart(
             arena_core,
@@ -646,6 +818,9 @@ impl<
             category_id,
             admin_dao: admin_dao.clone(),
             host: host_addr,
+            result_authority: result_authority
+                .map(|x| deps.api.addr_validate(&x))
+                .transpose()?,
             start_height: env.block.height,
             escrow: escrow_addr,
             name,
@@ -684,6 +859,7 @@ impl<
         distribution: Option<Distribution<String>>,
         tax_cw20_msg: Option<Binary>,
         tax_cw721_msg: Option<Binary>,
+        evidence: Option<String>,
     ) -> Result<Response, CompetitionError> {
         // Load competition
         let competition = self
@@ -694,24 +870,78 @@ impl<
             })?;
 
         // Validate competition status and sender's authorization
-        match competition.status {
-            CompetitionStatus::Active => {
-                if competition.host != info.sender && competition.admin_dao != info.sender {
-                    return Err(CompetitionError::Unauthorized {});
-                }
-            }
-            CompetitionStatus::Jailed => {
-                if competition.admin_dao != info.sender {
-                    return Err(CompetitionError::Unauthorized {});
-                }
-            }
-            _ => {
-                return Err(CompetitionError::InvalidCompetitionStatus {
-                    current_status: competition.status.clone(),
-                })
-            }
+        Self::assert_result_reporter(deps.as_ref(), &competition, &info.sender)?;
+
+        // A committed result must be revealed instead
+        if self
+            .competition_result_commits
+            .has(deps.storage, competition_id.u128())
+        {
+            return Err(CompetitionError::ResultCommitted {});
         }
 
+        self.process_competition(
+            deps,
+            competition_id,
+            competition,
+            distribution,
+            tax_cw20_msg,
+            tax_cw721_msg,
+            evidence,
+        )
+    }
+
+    /// Processes the competition with the distribution its members approved in the escrow
+    /// The escrow is distributed by this module, so the arena tax still applies
+    pub fn execute_process_approved(
+        &self,
+        deps: DepsMut,
+        info: MessageInfo,
+        distribution: Distribution<String>,
+    ) -> Result<Response, CompetitionError> {
+        // Load competition ID associated with the escrow
+        let id = self
+            .escrows_to_competitions
+            .may_load(deps.storage, info.sender.clone())?
+            .ok_or(CompetitionError::UnknownEscrow {
+                addr: info.sender.to_string(),
+            })?;
+
+        let competition = self
+            .competitions
+            .may_load(deps.storage, id)?
+            .ok_or(CompetitionError::UnknownCompetitionId { id })?;
+        if competition.status != CompetitionStatus::Active {
+            return Err(CompetitionError::InvalidCompetitionStatus {
+                current_status: competition.status,
+            });
+        }
+
+        // The members' agreement supersedes a result committed but not yet revealed
+        self.competition_result_commits.remove(deps.storage, id);
+
+        self.process_competition(
+            deps,
+            Uint128::from(id),
+            competition,
+            Some(distribution),
+            None,
+            None,
+            None,
+        )
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn process_competition(
+        &self,
+        deps: DepsMut,
+        competition_id: Uint128,
+        competition: Competition<CompetitionExt>,
+        distribution: Option<Distribution<String>>,
+        tax_cw20_msg: Option<Binary>,
+        tax_cw721_msg: Option<Binary>,
+        evidence: Option<String>,
+    ) -> Result<Response, CompetitionError> {
         // Validate the distribution
         let validated_distribution = distribution
             .as_ref()
@@ -724,6 +954,10 @@ impl<
             competition_id.u128(),
             &validated_distribution,
         )?;
+        if let Some(evidence) = &evidence {
+            self.competition_result_evidence
+                .save(deps.storage, competition_id.u128(), evidence)?;
+        }
 
         // Prepare hooks
         let hooks: Vec<(Addr, HookDirection)> = self
@@ -753,11 +987,34 @@ impl<
                 let arena_core = cw_ownable::get_ownership(deps.storage)?.owner.ok_or(
                     CompetitionError::OwnershipError(cw_ownable::OwnershipError::NoOwner),
                 )?;
+
+                // Tax tiers are set per asset, so only an escrow holding a single fungible asset has a value
+                let balance: Option<BalanceVerified> = deps
+                    .querier
+                    .query_wasm_smart(&escrow, &CompetitionEscrowQueryMsg::TotalBalance {})?;
+                let escrow_value = balance.and_then(|balance| {
+                    let mut assets = balance
+                        .native
+                        .into_iter()
+                        .chain(balance.cw20.into_iter().map(|x| Coin {
+                            denom: x.address.to_string(),
+                            amount: x.amount,
+                        }))
+                        .filter(|x| !x.amount.is_zero());
+
+                    match (assets.next(), assets.next()) {
+                        (Some(asset), None) => Some(asset),
+                        _ => None,
+                    }
+                });
+
                 let tax: Decimal = deps.querier.query_wasm_smart(
                     arena_core,
                     &arena_core_interface::msg::QueryMsg::QueryExtension {
                         msg: arena_core_interface::msg::QueryExt::Tax {
                             height: Some(competition.start_height),
+                            addr: Some(competition.host.to_string()),
+                            escrow_value,
                         },
                     },
                 )?;
@@ -777,7 +1034,13 @@ impl<
             let sub_msg = SubMsg::reply_on_success(
                 CompetitionEscrowDistributeMsg {
                     distribution,
+                    bonus_distribution: None,
+                    require_full_funding: None,
+                    rounding: None,
                     tax_info,
+                    fixed_amounts: None,
+                    treasury: None,
+                    nft_mode: None,
                 }
                 .into_cosmos_msg(escrow.clone())?,
                 PROCESS_REPLY_ID,
@@ -804,6 +1067,105 @@ impl<
             .add_submessages(msgs))
     }
 
+    /// The arena core may also report results, as it settles escrows in batches for the dao
+    fn assert_result_reporter(
+        deps: Deps,
+        competition: &Competition<CompetitionExt>,
+        sender: &Addr,
+    ) -> Result<(), CompetitionError> {
+        match competition.status {
+            CompetitionStatus::Active => {
+                if &competition.host != sender
+                    && &competition.admin_dao != sender
+                    && competition.result_authority.as_ref() != Some(sender)
+                    && cw_ownable::get_ownership(deps.storage)?.owner.as_ref() != Some(sender)
+                {
+                    return Err(CompetitionError::Unauthorized {});
+                }
+            }
+            CompetitionStatus::Jailed => {
+                if &competition.admin_dao != sender {
+                    return Err(CompetitionError::Unauthorized {});
+                }
+            }
+            _ => {
+                return Err(CompetitionError::InvalidCompetitionStatus {
+                    current_status: competition.status.clone(),
+                })
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn execute_commit_result(
+        &self,
+        deps: DepsMut,
+        info: MessageInfo,
+        competition_id: Uint128,
+        hash: HexBinary,
+    ) -> Result<Response, CompetitionError> {
+        let competition = self
+            .competitions
+            .may_load(deps.storage, competition_id.u128())?
+            .ok_or(CompetitionError::UnknownCompetitionId {
+                id: competition_id.u128(),
+            })?;
+        Self::assert_result_reporter(deps.as_ref(), &competition, &info.sender)?;
+
+        // Only the admin dao can replace an unrevealed commitment
+        if self
+            .competition_result_commits
+            .has(deps.storage, competition_id.u128())
+            && competition.admin_dao != info.sender
+        {
+            return Err(CompetitionError::ResultCommitted {});
+        }
+
+        self.competition_result_commits
+            .save(deps.storage, competition_id.u128(), &hash)?;
+
+        Ok(Response::new()
+            .add_attribute("action", "commit_result")
+            .add_attribute("competition_id", competition_id)
+            .add_attribute("hash", hash.to_hex()))
+    }
+
+    pub fn execute_reveal_result(
+        &self,
+        deps: DepsMut,
+        info: MessageInfo,
+        competition_id: Uint128,
+        distribution: Option<Distribution<String>>,
+        nonce: String,
+        evidence: Option<String>,
+    ) -> Result<Response, CompetitionError> {
+        let hash = self
+            .competition_result_commits
+            .may_load(deps.storage, competition_id.u128())?
+            .ok_or(CompetitionError::StdError(StdError::generic_err(
+                "No result has been committed",
+            )))?;
+        if result_commitment(competition_id, &distribution, &nonce)? != hash {
+            return Err(CompetitionError::InvalidReveal {});
+        }
+
+        self.competition_result_commits
+            .remove(deps.storage, competition_id.u128());
+
+        Ok(self
+            .execute_process_competition(
+                deps,
+                info,
+                competition_id,
+                distribution,
+                None,
+                None,
+                evidence,
+            )?
+            .add_attribute("revealed", "true"))
+    }
+
     pub fn query(
         &self,
         deps: Deps,
@@ -823,6 +1185,18 @@ impl<
             QueryBase::Result { competition_id } => {
                 to_json_binary(&self.query_result(deps, competition_id)?)
             }
+            QueryBase::ResultEvidence { competition_id } => to_json_binary(
+                &self
+                    .competition_result_evidence
+                    .may_load(deps.storage, competition_id.u128())?,
+            ),
+            QueryBase::CompetitionRulesets { competition_id } => {
+                to_json_binary(&self.query_competition_rulesets(deps, competition_id)?)
+            }
+            QueryBase::IsCanonicalEscrow {
+                competition_id,
+                addr,
+            } => to_json_binary(&self.query_is_canonical_escrow(deps, competition_id, addr)?),
             QueryBase::Evidence {
                 competition_id,
                 start_after,
@@ -834,9 +1208,32 @@ impl<
                 filter,
             } => to_json_binary(&self.query_competitions(deps, env, start_after, limit, filter)?),
             QueryBase::Ownership {} => to_json_binary(&cw_ownable::get_ownership(deps.storage)?),
+            QueryBase::ArenaCore {} => to_json_binary(
+                &cw_ownable::get_ownership(deps.storage)?
+                    .owner
+                    .ok_or(StdError::generic_err("The arena core is not set"))?,
+            ),
             QueryBase::CompetitionCount {} => {
                 to_json_binary(&self.competition_count.load(deps.storage)?)
             }
+            QueryBase::TotalValueLocked { start_after, limit } => {
+                to_json_binary(&self.query_total_value_locked(deps, start_after, limit)?)
+            }
+            QueryBase::EscrowCompetition { escrow } => {
+                let escrow = deps.api.addr_validate(&escrow)?;
+                let id = self
+                    .escrows_to_competitions
+                    .may_load(deps.storage, escrow)?
+                    .map(Uint128::from);
+
+                to_json_binary(&id)
+            }
+            QueryBase::CompetitionFunding { competition_id } => {
+                to_json_binary(&self.query_competition_funding(deps, competition_id)?)
+            }
+            QueryBase::EscrowsNeedingAttention { competition_ids } => {
+                to_json_binary(&self.query_escrows_needing_attention(deps, env, competition_ids)?)
+            }
             QueryBase::QueryExtension { .. } => Ok(Binary::default()),
             QueryBase::_Phantom(_) => Ok(Binary::default()),
         }
@@ -851,6 +1248,174 @@ impl<
             .load(deps.storage, competition_id.u128())
     }
 
+    pub fn query_competition_rulesets(
+        &self,
+        deps: Deps,
+        competition_id: Uint128,
+    ) -> StdResult<Vec<arena_core_interface::msg::Ruleset>> {
+        let competition = self
+            .competitions
+            .load(deps.storage, competition_id.u128())?;
+        let arena_core = cw_ownable::get_ownership(deps.storage)?
+            .owner
+            .ok_or(StdError::generic_err("The arena core is not set"))?;
+
+        competition
+            .rulesets
+            .iter()
+            .filter_map(|id| {
+                deps.querier
+                    .query_wasm_smart::<Option<arena_core_interface::msg::Ruleset>>(
+                        arena_core.to_string(),
+                        &arena_core_interface::msg::QueryMsg::QueryExtension {
+                            msg: arena_core_interface::msg::QueryExt::Ruleset { id: *id },
+                        },
+                    )
+                    .transpose()
+            })
+            .collect()
+    }
+
+    pub fn query_is_canonical_escrow(
+        &self,
+        deps: Deps,
+        competition_id: Uint128,
+        addr: String,
+    ) -> StdResult<bool> {
+        let addr = deps.api.addr_validate(&addr)?;
+
+        Ok(self
+            .competitions
+            .may_load(deps.storage, competition_id.u128())?
+            .and_then(|competition| competition.escrow)
+            .is_some_and(|escrow| escrow == addr))
+    }
+
+    pub fn query_total_value_locked(
+        &self,
+        deps: Deps,
+        start_after: Option<Uint128>,
+        limit: Option<u32>,
+    ) -> StdResult<TotalValueLockedResponse> {
+        let start_after_bound = start_after.map(|x| Bound::exclusive(x.u128()));
+        let limit = limit.unwrap_or(10).min(30);
+
+        let competitions = self
+            .competitions
+            .range(
+                deps.storage,
+                start_after_bound,
+                None,
+                cosmwasm_std::Order::Ascending,
+            )
+            .take(limit as usize)
+            .collect::<StdResult<Vec<_>>>()?;
+
+        // The page continues from its last competition if any come after it
+        let next_start_after = match competitions.last() {
+            Some((last_id, _)) => self
+                .competitions
+                .keys(
+                    deps.storage,
+                    Some(Bound::exclusive(*last_id)),
+                    None,
+                    cosmwasm_std::Order::Ascending,
+                )
+                .next()
+                .transpose()?
+                .map(|_| Uint128::new(*last_id)),
+            None => None,
+        };
+
+        let balance = competitions.into_iter().try_fold(
+            BalanceVerified::default(),
+            |total, (_, competition)| {
+                // Processed or cancelled competitions no longer lock their escrow's funds
+                let escrow = match competition.escrow {
+                    Some(escrow) if competition.status != CompetitionStatus::Inactive => escrow,
+                    _ => return Ok(total),
+                };
+                let balance: Option<BalanceVerified> = deps
+                    .querier
+                    .query_wasm_smart(escrow, &CompetitionEscrowQueryMsg::TotalBalance {})?;
+
+                match balance {
+                    Some(balance) => total.checked_add(&balance),
+                    None => Ok(total),
+                }
+            },
+        )?;
+
+        Ok(TotalValueLockedResponse {
+            balance,
+            next_start_after,
+        })
+    }
+
+    pub fn query_competition_funding(
+        &self,
+        deps: Deps,
+        competition_id: Uint128,
+    ) -> StdResult<Vec<CompetitionEscrowRosterEntry>> {
+        let competition = self
+            .competitions
+            .load(deps.storage, competition_id.u128())?;
+
+        match competition.escrow {
+            Some(escrow) => deps.querier.query_wasm_smart(
+                escrow,
+                &CompetitionEscrowQueryMsg::Roster {
+                    start_after: None,
+                    limit: None,
+                },
+            ),
+            None => Ok(vec![]),
+        }
+    }
+
+    pub fn query_escrows_needing_attention(
+        &self,
+        deps: Deps,
+        env: Env,
+        competition_ids: Vec<Uint128>,
+    ) -> StdResult<Vec<EscrowAttentionResponse>> {
+        let mut stuck = vec![];
+        for competition_id in competition_ids {
+            let competition = match self
+                .competitions
+                .may_load(deps.storage, competition_id.u128())?
+            {
+                Some(competition) => competition,
+                None => continue,
+            };
+
+            // Processed and cancelled competitions no longer hold anything up
+            if competition.status == CompetitionStatus::Inactive
+                || !competition.expiration.is_expired(&env.block)
+            {
+                continue;
+            }
+
+            if let Some(escrow) = competition.escrow {
+                let is_fully_funded: bool = deps
+                    .querier
+                    .query_wasm_smart(&escrow, &CompetitionEscrowQueryMsg::IsFullyFunded {})?;
+
+                stuck.push(EscrowAttentionResponse {
+                    competition_id,
+                    escrow,
+                    reason: if is_fully_funded {
+                        EscrowAttentionReason::Unresolved
+                    } else {
+                        EscrowAttentionReason::Unfunded
+                    },
+                });
+            }
+        }
+
+        Ok(stuck)
+    }
+
     pub fn query_evidence(
         &self,
         deps: Deps,
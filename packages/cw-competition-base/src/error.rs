@@ -42,12 +42,33 @@ pub enum CompetitionError {
     #[error("UnknownEscrow")]
     UnknownEscrow { addr: String },
 
+    #[error("EscrowNotOwned")]
+    EscrowNotOwned { addr: String },
+
     #[error("UnknownReplyId")]
     UnknownReplyId { id: u64 },
 
     #[error("InvalidCompetitionStatus")]
     InvalidCompetitionStatus { current_status: CompetitionStatus },
 
+    #[error("InvalidExpiration")]
+    InvalidExpiration {},
+
+    #[error("InvalidRuleset")]
+    InvalidRuleset { id: Uint128 },
+
+    #[error("TooManyRulesets")]
+    TooManyRulesets { max: u32 },
+
+    #[error("ResultCommitted")]
+    ResultCommitted {},
+
+    #[error("InvalidReveal")]
+    InvalidReveal {},
+
+    #[error("Paused")]
+    Paused {},
+
     #[error("InvalidCategoryAndRulesets")]
     InvalidCategoryAndRulesets {
         category_id: Option<Uint128>,
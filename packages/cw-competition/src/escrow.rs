@@ -1,7 +1,9 @@
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{to_json_binary, Addr, Binary, CosmosMsg, Decimal, Deps, StdResult, WasmMsg};
+use cosmwasm_std::{
+    to_json_binary, Addr, Binary, CosmosMsg, Decimal, Deps, StdResult, Uint128, WasmMsg,
+};
 use cw_address_like::AddressLike;
-use cw_balance::Distribution;
+use cw_balance::{BalanceVerified, Distribution, NftMode, RoundingMode};
 
 #[cw_serde]
 pub struct TaxInformation<T: AddressLike> {
@@ -22,10 +24,41 @@ impl TaxInformation<String> {
     }
 }
 
+/// A distribution recipient which is paid immediately with an accounting reference
 #[cw_serde]
+pub struct TreasuryPayout<T: AddressLike> {
+    pub addr: T,
+    pub reference: String,
+}
+
+impl TreasuryPayout<String> {
+    pub fn into_checked(&self, deps: Deps) -> StdResult<TreasuryPayout<Addr>> {
+        Ok(TreasuryPayout {
+            addr: deps.api.addr_validate(&self.addr)?,
+            reference: self.reference.clone(),
+        })
+    }
+}
+
+#[cw_serde]
+#[derive(Default)]
 pub struct CompetitionEscrowDistributeMsg {
     pub distribution: Option<Distribution<String>>,
+    /// Splits the escrow's house balance separately from the entry fees
+    pub bonus_distribution: Option<Distribution<String>>,
+    /// Defaults to splitting every balance paid in
+    /// If true, the escrow must be fully funded
+    /// If false, unfunded members are refunded and only funded members can receive a share
+    pub require_full_funding: Option<bool>,
+    /// Defaults to truncating each share and leaving the leftover to the remainder address
+    pub rounding: Option<RoundingMode>,
     pub tax_info: Option<TaxInformation<String>>,
+    /// Exact amounts paid out of the taxed pool before the distribution splits the surplus
+    pub fixed_amounts: Option<Vec<(String, BalanceVerified)>>,
+    /// Sends the treasury's winnings to it with a reference instead of holding them for withdrawal
+    pub treasury: Option<TreasuryPayout<String>>,
+    /// Defaults to sending every NFT to the distribution's remainder address
+    pub nft_mode: Option<NftMode>,
 }
 
 impl CompetitionEscrowDistributeMsg {
@@ -47,7 +80,145 @@ impl CompetitionEscrowDistributeMsg {
     }
 }
 
+/// Builds a distribute message, validating the distribution shares before it is sent
+#[derive(Default)]
+pub struct CompetitionEscrowDistributeMsgBuilder {
+    msg: CompetitionEscrowDistributeMsg,
+}
+
+impl CompetitionEscrowDistributeMsgBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn distribution(mut self, distribution: Distribution<String>) -> Self {
+        self.msg.distribution = Some(distribution);
+        self
+    }
+
+    pub fn bonus_distribution(mut self, bonus_distribution: Distribution<String>) -> Self {
+        self.msg.bonus_distribution = Some(bonus_distribution);
+        self
+    }
+
+    pub fn require_full_funding(mut self, require_full_funding: bool) -> Self {
+        self.msg.require_full_funding = Some(require_full_funding);
+        self
+    }
+
+    pub fn rounding(mut self, rounding: RoundingMode) -> Self {
+        self.msg.rounding = Some(rounding);
+        self
+    }
+
+    pub fn tax_info(mut self, tax_info: TaxInformation<String>) -> Self {
+        self.msg.tax_info = Some(tax_info);
+        self
+    }
+
+    pub fn fixed_amounts(mut self, fixed_amounts: Vec<(String, BalanceVerified)>) -> Self {
+        self.msg.fixed_amounts = Some(fixed_amounts);
+        self
+    }
+
+    pub fn treasury(mut self, treasury: TreasuryPayout<String>) -> Self {
+        self.msg.treasury = Some(treasury);
+        self
+    }
+
+    pub fn nft_mode(mut self, nft_mode: NftMode) -> Self {
+        self.msg.nft_mode = Some(nft_mode);
+        self
+    }
+
+    pub fn build(self) -> StdResult<CompetitionEscrowDistributeMsg> {
+        for distribution in [&self.msg.distribution, &self.msg.bonus_distribution]
+            .into_iter()
+            .flatten()
+        {
+            distribution.validate_shares()?;
+        }
+
+        Ok(self.msg)
+    }
+
+    /// creates a cosmos_msg sending the validated message to the named contract
+    pub fn into_cosmos_msg<T: Into<String>>(self, contract_addr: T) -> StdResult<CosmosMsg> {
+        self.build()?.into_cosmos_msg(contract_addr)
+    }
+}
+
 #[cw_serde]
 enum CompetitionEscrowMsg {
     Distribute(CompetitionEscrowDistributeMsg),
+    UpdateOwnership(cw_ownable::Action),
+    SetDueDenom { denom: String },
+    SetCompetitionId { competition_id: Uint128 },
+}
+
+/// serializes the message accepting a pending ownership transfer of an escrow
+pub fn accept_ownership_binary() -> StdResult<Binary> {
+    to_json_binary(&CompetitionEscrowMsg::UpdateOwnership(
+        cw_ownable::Action::AcceptOwnership,
+    ))
+}
+
+/// serializes the message filling in the denom of dues which omit it
+pub fn set_due_denom_binary(denom: String) -> StdResult<Binary> {
+    to_json_binary(&CompetitionEscrowMsg::SetDueDenom { denom })
+}
+
+/// serializes the message tagging an adopted escrow with its competition
+pub fn set_competition_id_binary(competition_id: Uint128) -> StdResult<Binary> {
+    to_json_binary(&CompetitionEscrowMsg::SetCompetitionId { competition_id })
+}
+
+#[cw_serde]
+pub enum CompetitionEscrowQueryMsg {
+    TotalBalance {},
+    IsFullyFunded {},
+    Ownership {},
+    Roster {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+}
+
+/// A member's funding as reported by the escrow's roster
+#[cw_serde]
+pub struct CompetitionEscrowRosterEntry {
+    pub addr: Addr,
+    pub initial_due: BalanceVerified,
+    pub remaining_due: Option<BalanceVerified>,
+    pub balance: Option<BalanceVerified>,
+    pub is_funded: bool,
+}
+
+/// Sent to an escrow's listeners when its funding state changes
+#[cw_serde]
+pub enum CompetitionEscrowEvent {
+    Funded {
+        addr: String,
+    },
+    Locked {},
+    Distributed {},
+    /// Every member withdrew before funding completed, leaving the escrow empty
+    Closed {},
+}
+
+impl CompetitionEscrowEvent {
+    /// creates a cosmos_msg sending this event to the named listener
+    pub fn into_cosmos_msg<T: Into<String>>(self, contract_addr: T) -> StdResult<CosmosMsg> {
+        let execute = WasmMsg::Execute {
+            contract_addr: contract_addr.into(),
+            msg: to_json_binary(&CompetitionEscrowListenerMsg::EscrowEvent(self))?,
+            funds: vec![],
+        };
+        Ok(execute.into())
+    }
+}
+
+#[cw_serde]
+pub enum CompetitionEscrowListenerMsg {
+    EscrowEvent(CompetitionEscrowEvent),
 }
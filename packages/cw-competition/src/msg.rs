@@ -3,20 +3,35 @@ use std::marker::PhantomData;
 #[allow(unused_imports)]
 use crate::state::{CompetitionResponse, Config};
 #[allow(unused_imports)]
-use crate::state::{CompetitionStatus, Evidence};
+use crate::state::{CompetitionStatus, EscrowAttentionResponse, Evidence};
 use arena_core_interface::msg::ProposeMessage;
 use cosmwasm_schema::{cw_serde, schemars::JsonSchema, QueryResponses};
-use cosmwasm_std::{Binary, Deps, StdResult, Uint128};
+use cosmwasm_std::{to_json_vec, Addr, Binary, Deps, HexBinary, StdResult, Uint128};
 use cw_balance::Distribution;
 use cw_ownable::{cw_ownable_execute, cw_ownable_query};
 use cw_utils::Expiration;
 use dao_interface::state::ModuleInstantiateInfo;
+use sha2::{Digest, Sha256};
+
+/// Hashes the competition id, distribution and nonce of a result, as committed by `CommitResult`
+/// The competition id keeps a commitment from being replayed on another competition
+pub fn result_commitment(
+    competition_id: Uint128,
+    distribution: &Option<Distribution<String>>,
+    nonce: &str,
+) -> StdResult<HexBinary> {
+    let content = to_json_vec(&(competition_id, distribution, nonce))?;
+
+    Ok(HexBinary::from(Sha256::digest(content).to_vec()))
+}
 
 #[cw_serde]
 pub struct InstantiateBase<InstantiateExt> {
     pub key: String, //this is used to map a key (wager, tournament, league) to a module
     pub description: String,
     pub extension: InstantiateExt,
+    /// Caps the rulesets a competition can reference, with no cap by default
+    pub max_rulesets_per_competition: Option<u32>,
 }
 
 #[cw_ownable_execute]
@@ -27,6 +42,12 @@ pub enum ExecuteBase<ExecuteExt, CompetitionInstantiateExt> {
         propose_message: ProposeMessage,
     },
     Activate {},
+    /// Sent by a competition's escrow when it closes before being funded
+    Cancel {},
+    /// Sent by a competition's escrow once every member has approved the same distribution
+    ProcessApproved {
+        distribution: Distribution<String>,
+    },
     AddCompetitionHook {
         competition_id: Uint128,
     },
@@ -40,12 +61,16 @@ pub enum ExecuteBase<ExecuteExt, CompetitionInstantiateExt> {
     CreateCompetition {
         category_id: Option<Uint128>,
         host: ModuleInfo,
-        escrow: Option<ModuleInstantiateInfo>,
+        /// An existing escrow must be owned by the competition module, or pending its acceptance
+        /// It may not already back another competition
+        escrow: Option<ModuleInfo>,
         name: String,
         description: String,
         expiration: Expiration,
         rules: Vec<String>,
         rulesets: Vec<Uint128>,
+        /// An address that may report the result in addition to the host
+        result_authority: Option<String>,
         instantiate_extension: CompetitionInstantiateExt,
     },
     SubmitEvidence {
@@ -57,6 +82,24 @@ pub enum ExecuteBase<ExecuteExt, CompetitionInstantiateExt> {
         distribution: Option<Distribution<String>>,
         tax_cw20_msg: Option<Binary>,
         tax_cw721_msg: Option<Binary>,
+        /// An off-chain reference backing the result, such as an IPFS hash or signed attestation
+        evidence: Option<String>,
+    },
+    /// Commits to a result without revealing it, so it cannot be front-run
+    /// The competition can then only be processed by revealing the result
+    /// Only the admin dao can replace an unrevealed commitment
+    CommitResult {
+        competition_id: Uint128,
+        /// See `result_commitment`
+        hash: HexBinary,
+    },
+    /// Processes the competition with the committed result
+    RevealResult {
+        competition_id: Uint128,
+        distribution: Option<Distribution<String>>,
+        nonce: String,
+        /// An off-chain reference backing the result, such as an IPFS hash or signed attestation
+        evidence: Option<String>,
     },
     Extension {
         msg: ExecuteExt,
@@ -74,6 +117,9 @@ where
     Config {},
     #[returns(String)]
     DAO {},
+    /// The arena core owning the module, which its escrows consult on pauses and blocked assets
+    #[returns(Addr)]
+    ArenaCore {},
     #[returns(Uint128)]
     CompetitionCount {},
     #[returns(CompetitionResponse<CompetitionExt>)]
@@ -92,6 +138,35 @@ where
     },
     #[returns(Option<Distribution<String>>)]
     Result { competition_id: Uint128 },
+    /// The off-chain reference attached when the result was reported
+    #[returns(Option<String>)]
+    ResultEvidence { competition_id: Uint128 },
+    /// Resolves the competition's ruleset ids into the full rulesets stored in the arena core
+    #[returns(Vec<arena_core_interface::msg::Ruleset>)]
+    CompetitionRulesets { competition_id: Uint128 },
+    /// Checks whether the address is the escrow created for the competition
+    #[returns(bool)]
+    IsCanonicalEscrow {
+        competition_id: Uint128,
+        addr: String,
+    },
+    /// The unprocessed competition backed by the escrow
+    #[returns(Option<Uint128>)]
+    EscrowCompetition { escrow: String },
+    /// Sums the balances held by escrows of competitions that have not been processed
+    /// Only a page of competitions is summed, so callers add up the pages by competition id
+    #[returns(crate::state::TotalValueLockedResponse)]
+    TotalValueLocked {
+        start_after: Option<Uint128>,
+        limit: Option<u32>,
+    },
+    /// Each participant's due, balance, and funded status from the competition's escrow
+    /// Competitions without an escrow return an empty list
+    #[returns(Vec<crate::escrow::CompetitionEscrowRosterEntry>)]
+    CompetitionFunding { competition_id: Uint128 },
+    /// Checks each competition's escrow and returns those that expired while unfunded or unresolved
+    #[returns(Vec<EscrowAttentionResponse>)]
+    EscrowsNeedingAttention { competition_ids: Vec<Uint128> },
     #[returns(cosmwasm_std::Binary)]
     QueryExtension { msg: QueryExt },
     #[serde(skip)]
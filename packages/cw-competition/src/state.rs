@@ -1,5 +1,6 @@
 use cosmwasm_schema::cw_serde;
 use cosmwasm_std::{Addr, BlockInfo, Timestamp, Uint128};
+use cw_balance::BalanceVerified;
 use cw_utils::Expiration;
 use std::fmt;
 
@@ -30,6 +31,8 @@ pub struct Competition<CompetitionExt> {
     pub category_id: Option<Uint128>,
     pub admin_dao: Addr,
     pub host: Addr,
+    /// Can report the result alongside the host, such as a referee or oracle
+    pub result_authority: Option<Addr>,
     pub escrow: Option<Addr>,
     pub name: String,
     pub description: String,
@@ -47,6 +50,7 @@ pub struct CompetitionResponse<CompetitionExt> {
     pub id: Uint128,
     pub category_id: Option<Uint128>,
     pub host: Addr,
+    pub result_authority: Option<Addr>,
     pub escrow: Option<Addr>,
     pub name: String,
     pub description: String,
@@ -87,6 +91,7 @@ impl<CompetitionExt> Competition<CompetitionExt> {
             id: self.id,
             category_id: self.category_id,
             host: self.host,
+            result_authority: self.result_authority,
             escrow: self.escrow,
             name: self.name,
             description: self.description,
@@ -128,6 +133,30 @@ pub struct Config<InstantiateExt> {
     pub key: String,
     pub description: String,
     pub extension: InstantiateExt,
+    pub max_rulesets_per_competition: Option<u32>,
+}
+
+/// Why an escrow was flagged by the escrows needing attention query
+#[cw_serde]
+pub enum EscrowAttentionReason {
+    /// The competition expired before every member was funded
+    Unfunded,
+    /// The competition expired funded but was never processed
+    Unresolved,
+}
+
+#[cw_serde]
+pub struct TotalValueLockedResponse {
+    pub balance: BalanceVerified,
+    /// The last competition id summed, if more competitions remain after it
+    pub next_start_after: Option<Uint128>,
+}
+
+#[cw_serde]
+pub struct EscrowAttentionResponse {
+    pub competition_id: Uint128,
+    pub escrow: Addr,
+    pub reason: EscrowAttentionReason,
 }
 
 #[cw_serde]